@@ -0,0 +1,69 @@
+//! Measures the decoding throughput of 16-bit, 32-bit and mixed images, and
+//! compares the lookup tables against the reference decoder.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use disarmv7::{
+    bench::{self, XorShift},
+    buffer::SliceStream,
+    decoder::linear_sweep,
+    operation::Operation,
+    Parse,
+    ParseError,
+};
+
+/// The size of the generated images in bytes.
+const IMAGE_SIZE: usize = 1 << 20;
+
+/// Generates an image where every halfword is invalid or the start of a
+/// 32-bit encoding, i.e. it exercises the error paths.
+fn noise() -> Vec<u8> {
+    XorShift::new(0x9e37_79b9)
+        .take(IMAGE_SIZE / 2)
+        .flat_map(|word| (word as u16).to_le_bytes())
+        .collect()
+}
+
+/// Decodes the image using `parse`, returning the number of operations.
+fn count<F>(image: &[u8], mut parse: F) -> usize
+where
+    F: FnMut(&mut SliceStream<'_>) -> Result<(usize, Operation), ParseError>,
+{
+    let mut stream = SliceStream::new(image);
+    let mut count = 0;
+    while parse(&mut stream).is_ok() {
+        count += 1;
+    }
+    count
+}
+
+fn decode(c: &mut Criterion) {
+    let images = [
+        ("narrow", bench::image(IMAGE_SIZE, &[16])),
+        ("wide", bench::image(IMAGE_SIZE, &[32])),
+        ("mixed", bench::image(IMAGE_SIZE, &[16, 32])),
+    ];
+    let mut group = c.benchmark_group("decode");
+    group.sample_size(20);
+    for (name, image) in &images {
+        group.throughput(Throughput::Bytes(image.len() as u64));
+        group.bench_with_input(BenchmarkId::new("lookup", name), image, |b, image| {
+            b.iter(|| count(image, |stream| Operation::parse(stream)))
+        });
+        group.bench_with_input(BenchmarkId::new("reference", name), image, |b, image| {
+            b.iter(|| count(image, |stream| bench::reference(stream)))
+        });
+    }
+    group.finish();
+
+    let noise = noise();
+    let mut group = c.benchmark_group("linear_sweep");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(noise.len() as u64));
+    group.bench_with_input(BenchmarkId::from_parameter("noise"), &noise, |b, image| {
+        b.iter(|| linear_sweep(&mut SliceStream::new(image), 0x0800_0000).len())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+//! Compares reading and decoding an in-memory image from a [`SliceStream`]
+//! and from a [`PeekableBuffer`].
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use disarmv7::{
+    bench,
+    buffer::{PeekableBuffer, SliceStream},
+    decoder::linear_sweep,
+    Peek,
+    Stream,
+};
+
+/// The size of the generated image in bytes.
+const IMAGE_SIZE: usize = 4 << 20;
+
+fn streams(c: &mut Criterion) {
+    let image = bench::image(IMAGE_SIZE, &[16, 32]);
+    let mut group = c.benchmark_group("linear_sweep");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(image.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("SliceStream", "4MiB"),
+        &image,
+        |b, image| b.iter(|| linear_sweep(&mut SliceStream::new(image), 0x0800_0000).len()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("PeekableBuffer", "4MiB"),
+        &image,
+        |b, image| {
+            b.iter(|| {
+                linear_sweep(
+                    &mut PeekableBuffer::from(image.iter().cloned()),
+                    0x0800_0000,
+                )
+                .len()
+            })
+        },
+    );
+    group.finish();
+
+    // Only the stream, peeking and consuming every halfword.
+    let mut group = c.benchmark_group("consume");
+    group.throughput(Throughput::Bytes(image.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new("SliceStream", "4MiB"),
+        &image,
+        |b, image| b.iter(|| halfwords(&mut SliceStream::new(image))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("PeekableBuffer", "4MiB"),
+        &image,
+        |b, image| b.iter(|| halfwords(&mut PeekableBuffer::from(image.iter().cloned()))),
+    );
+    group.finish();
+}
+
+/// Sums all of the halfwords in the stream.
+fn halfwords<T: Stream>(stream: &mut T) -> u32 {
+    let mut sum: u32 = 0;
+    while let Some(halfword) = Peek::<u16>::peek::<1>(stream) {
+        let _: Option<[u16; 1]> = stream.consume::<1>();
+        sum = sum.wrapping_add(halfword as u32);
+    }
+    sum
+}
+
+criterion_group!(benches, streams);
+criterion_main!(benches);
@@ -0,0 +1,118 @@
+@ Armv6-M fixture for the differential test in `src/asm/differential.rs`,
+@ regenerate the image and the reference listing with
+@
+@   llvm-mc --triple=thumbv6m-none-eabi -filetype=obj cortex_m0.s -o cortex_m0.o
+@   rust-lld -flavor gnu -T link.ld cortex_m0.o -o cortex_m0.elf -n --build-id=none -z norelro
+@   llvm-objcopy --remove-section=.comment cortex_m0.elf
+@   llvm-objdump -d --triple=thumbv6m-none-eabi cortex_m0.elf > cortex_m0.lst
+    .syntax unified
+    .thumb
+    .section .vectors, "ax"
+    .thumb_func
+    .global reset
+reset:
+    b main
+
+    .text
+    .thumb_func
+    .global main
+main:
+    @ A5_2, shift (immediate), add, subtract, move and compare
+    lsls r0, r1, #3
+    lsrs r2, r3, #31
+    asrs r4, r5, #1
+    adds r0, r1, r2
+    subs r3, r4, r5
+    adds r6, r7, #7
+    subs r0, r0, #1
+    movs r1, #255
+    cmp r2, #10
+    adds r3, #200
+    subs r4, #128
+    @ A5_3, data processing
+    ands r0, r1
+    eors r2, r3
+    lsls r4, r5
+    lsrs r6, r7
+    asrs r0, r1
+    adcs r2, r3
+    sbcs r4, r5
+    rors r6, r7
+    tst r0, r1
+    rsbs r2, r3, #0
+    cmp r4, r5
+    cmn r6, r7
+    orrs r0, r1
+    muls r2, r3, r2
+    bics r4, r5
+    mvns r6, r7
+    @ A5_4, special data instructions and branch and exchange
+    add r0, r8
+    add sp, r1
+    cmp r9, r2
+    mov r10, r3
+    mov r4, r11
+    bx lr
+    blx r3
+    @ A5_5, load and store single data item
+    str r0, [r1, r2]
+    strh r3, [r4, r5]
+    strb r6, [r7, r0]
+    ldrsb r1, [r2, r3]
+    ldr r4, [r5, r6]
+    ldrh r7, [r0, r1]
+    ldrb r2, [r3, r4]
+    ldrsh r5, [r6, r7]
+    str r0, [r1, #124]
+    ldr r2, [r3, #4]
+    strb r4, [r5, #31]
+    ldrb r6, [r7]
+    strh r0, [r1, #62]
+    ldrh r2, [r3, #2]
+    str r4, [sp, #1020]
+    ldr r5, [sp, #8]
+    ldr r6, [pc, #12]
+    @ Simply defined encodings
+    adr r0, data
+    add r1, sp, #16
+    stm r2!, {r0, r1, r3}
+    ldm r4!, {r0, r5, r6}
+    ldm r4, {r0, r4}
+    @ A5_6, miscellaneous 16-bit instructions
+    add sp, #16
+    sub sp, #508
+    sxth r0, r1
+    sxtb r2, r3
+    uxth r4, r5
+    uxtb r6, r7
+    push {r4, r5, r6, lr}
+    cpsie i
+    cpsid i
+    rev r0, r1
+    rev16 r2, r3
+    revsh r4, r5
+    pop {r4, r5, r6, pc}
+    bkpt #171
+    nop
+    yield
+    wfe
+    wfi
+    sev
+    @ A5_8, conditional branch and supervisor call
+    beq main
+    bne 1f
+    bgt main
+    svc #42
+1:
+    b main
+    @ 32-bit encodings that exist in Armv6-M
+    bl main
+    dmb sy
+    dsb sy
+    isb sy
+    mrs r0, primask
+    msr control, r1
+    udf #7
+    .p2align 2
+data:
+    .word 0x12345678
@@ -0,0 +1,185 @@
+@ Armv7-M fixture for the differential test in `src/asm/differential.rs`,
+@ regenerate the image and the reference listing with
+@
+@   llvm-mc --triple=thumbv7m-none-eabi -filetype=obj cortex_m3.s -o cortex_m3.o
+@   rust-lld -flavor gnu -T link.ld cortex_m3.o -o cortex_m3.elf -n --build-id=none -z norelro
+@   llvm-objcopy --remove-section=.comment cortex_m3.elf
+@   llvm-objdump -d --triple=thumbv7m-none-eabi cortex_m3.elf > cortex_m3.lst
+    .syntax unified
+    .thumb
+    .section .vectors, "ax"
+    .thumb_func
+    .global reset
+reset:
+    b.w main
+
+    .text
+    .thumb_func
+    .global main
+main:
+    @ 16-bit encodings that are new in Armv7-M
+    cbz r0, 1f
+    cbnz r7, 1f
+    itt eq
+    moveq r0, r1
+    addeq r2, r3
+    ite ne
+    movne r0, #1
+    moveq r0, #2
+    itete gt
+    addgt r0, r0, #1
+    suble r1, r1, #1
+    lslgt r2, r3, #1
+    lsrle r4, r5, r6
+1:
+    @ A5_10, data processing (modified immediate)
+    and r0, r1, #255
+    tst r2, #0xff00
+    bics r3, r4, #0x00ff00ff
+    orr r5, r6, #0xff000000
+    mov.w r7, #0x55555555
+    orn r8, r9, #1
+    mvn r10, #0x3fc
+    eor r11, r12, #0x80000000
+    teq r0, #4
+    add.w r1, r2, #0x100
+    cmn.w r3, #1
+    adc r4, r5, #2
+    sbc r6, r7, #3
+    sub.w r8, r9, #0xab00
+    cmp.w r10, #0xcd
+    rsb r11, r12, #0
+    add.w r0, sp, #8
+    sub.w sp, sp, #16
+    @ A5_12, data processing (plain binary immediate)
+    addw r1, r2, #4095
+    adr.w r3, main
+    movw r4, #0xabcd
+    subw r5, r6, #1
+    movt r7, #0x1234
+    ssat r8, #16, r9, lsl #4
+    sbfx r10, r11, #3, #5
+    bfi r12, r0, #8, #4
+    bfc r1, #0, #16
+    usat r2, #7, r3, asr #2
+    ubfx r4, r5, #1, #31
+    @ A5_13, branches and miscellaneous control
+    b.w main
+    beq.w main
+    bl main
+    msr apsr_nzcvq, r0
+    mrs r1, ipsr
+    mrs r2, basepri
+    msr faultmask, r3
+    nop.w
+    yield.w
+    wfi.w
+    sev.w
+    dbg #5
+    clrex
+    dsb ish
+    dmb ishst
+    isb sy
+    udf.w #1000
+    @ A5_16, load and store multiple
+    stm.w r0, {r1, r2, r8}
+    ldm r1!, {r2, r3, r9}
+    pop.w {r4, r5, r6, r7, r8, pc}
+    stmdb r2!, {r0, r1, lr}
+    push.w {r4, r5, r6, r7, r8, lr}
+    ldmdb r3, {r4, r5, r12}
+    @ A5_17, load and store dual or exclusive, table branch
+    strex r0, r1, [r2, #4]
+    ldrex r3, [r4]
+    strd r5, r6, [r7, #-8]!
+    ldrd r8, r9, [r10], #16
+    strexb r0, r1, [r2]
+    strexh r3, r4, [r5]
+    ldrexb r6, [r7]
+    ldrexh r8, [r9]
+    ldrd r0, r1, [pc, #-32]
+    tbb [r0, r1]
+    tbh [r2, r3, lsl #1]
+    @ A5_18, A5_19 and A5_20, loads
+    ldr.w r0, [r1, #4095]
+    ldr r2, [r3, #-255]
+    ldr r4, [r5], #4
+    ldr r6, [r7, #8]!
+    ldrt r8, [r9, #1]
+    ldr.w r10, [r11, r12, lsl #2]
+    ldr.w r0, main
+    ldrh.w r1, [r2, #2000]
+    ldrh r3, [r4, #-8]
+    ldrh.w r5, [r6, r7, lsl #1]
+    ldrsh.w r8, [r9, #4]
+    ldrsh r10, [r11], #-2
+    ldrht r12, [r0, #3]
+    ldrb.w r1, [r2, #1]
+    ldrb r3, [r4, #-1]!
+    ldrsb.w r5, [r6, r7]
+    ldrsbt r8, [r9]
+    pld [r0, #32]
+    pli [r1, #-4]
+    @ A5_21, store single data item
+    strb.w r0, [r1, #4095]
+    strb r2, [r3, #-4]
+    strh.w r4, [r5, r6, lsl #3]
+    strh r7, [r8], #2
+    str.w r9, [r10, #8]
+    str r11, [sp, #-4]!
+    strt r12, [r0, #255]
+    @ A5_22, data processing (shifted register)
+    and.w r0, r1, r2, lsl #1
+    tst.w r3, r4, lsr #2
+    bic r5, r6, r7, asr #3
+    orr r8, r9, r10, ror #4
+    mov.w r11, r12
+    lsl.w r0, r1, #5
+    lsr.w r2, r3, #6
+    asr.w r4, r5, #7
+    ror r6, r7, #8
+    rrx r8, r9
+    orn r10, r11, r12
+    mvn.w r0, r1, lsl #9
+    eors.w r2, r3, r4
+    teq r5, r6
+    add.w r7, r8, r9, lsl #10
+    cmn.w r10, r11
+    adcs.w r12, r0, r1
+    sbc.w r2, r3, r4
+    sub.w r5, r6, r7, lsr #11
+    cmp.w r8, r9
+    rsb r10, r11, r12
+    @ A5_24, data processing (register)
+    lsl.w r0, r1, r2
+    lsrs.w r3, r4, r5
+    asr.w r6, r7, r8
+    ror.w r9, r10, r11
+    sxth.w r0, r1, ror #8
+    uxth.w r2, r3
+    sxtb.w r4, r5, ror #16
+    uxtb.w r6, r7, ror #24
+    clz r0, r1
+    rbit r2, r3
+    rev.w r4, r5
+    rev16.w r6, r7
+    revsh.w r8, r9
+    @ A5_28 and A5_29, multiply and divide
+    mul r0, r1, r2
+    mla r3, r4, r5, r6
+    mls r7, r8, r9, r10
+    smull r0, r1, r2, r3
+    umull r4, r5, r6, r7
+    smlal r8, r9, r10, r11
+    umlal r0, r1, r2, r3
+    sdiv r4, r5, r6
+    udiv r7, r8, r9
+    @ A5_30, coprocessor instructions
+    mcr p14, #1, r0, c2, c3, #4
+    mrc p15, #0, r1, c0, c0, #0
+    mcrr p12, #3, r2, r3, c4
+    mrrc p9, #2, r4, r5, c6
+    cdp p7, #1, c2, c3, c4, #5
+    ldc p6, c1, [r2, #16]
+    stc p5, c3, [r4], #-8
+    bx lr
@@ -0,0 +1,114 @@
+@ Armv7E-M fixture with the single precision floating point extension for
+@ the differential test in `src/asm/differential.rs`, regenerate the image
+@ and the reference listing with
+@
+@   llvm-mc --triple=thumbv7em-none-eabi --mattr=+vfp4d16sp -filetype=obj cortex_m4f.s -o cortex_m4f.o
+@   rust-lld -flavor gnu -T link.ld cortex_m4f.o -o cortex_m4f.elf -n --build-id=none -z norelro
+@   llvm-objcopy --remove-section=.comment cortex_m4f.elf
+@   llvm-objdump -d --triple=thumbv7em-none-eabi --mattr=+vfp4d16sp cortex_m4f.elf > cortex_m4f.lst
+    .syntax unified
+    .thumb
+    .fpu fpv4-sp-d16
+    .section .vectors, "ax"
+    .thumb_func
+    .global reset
+reset:
+    b.w main
+
+    .text
+    .thumb_func
+    .global main
+main:
+    @ A5_24, extend and add
+    sxtah r0, r1, r2
+    uxtab r3, r4, r5, ror #8
+    sxtb16 r6, r7
+    uxtab16 r8, r9, r10
+    @ A5_25 and A5_26, parallel addition and subtraction
+    sadd16 r0, r1, r2
+    sasx r3, r4, r5
+    qsub8 r6, r7, r8
+    shadd8 r9, r10, r11
+    uadd8 r0, r1, r2
+    usax r3, r4, r5
+    uqadd16 r6, r7, r8
+    uhsub16 r9, r10, r11
+    @ A5_27, miscellaneous operations
+    qadd r0, r1, r2
+    qdadd r3, r4, r5
+    qsub r6, r7, r8
+    qdsub r9, r10, r11
+    sel r0, r1, r2
+    @ A5_28 and A5_29, DSP multiplies
+    smlabb r0, r1, r2, r3
+    smlatb r4, r5, r6, r7
+    smlad r8, r9, r10, r11
+    smladx r0, r1, r2, r3
+    smlawb r4, r5, r6, r7
+    smlsd r8, r9, r10, r11
+    smmla r0, r1, r2, r3
+    smmls r4, r5, r6, r7
+    smmul r8, r9, r10
+    smulbb r0, r1, r2
+    smultt r3, r4, r5
+    smulwb r6, r7, r8
+    smuad r9, r10, r11
+    smusd r0, r1, r2
+    usad8 r3, r4, r5
+    usada8 r6, r7, r8, r9
+    smlalbb r0, r1, r2, r3
+    smlald r4, r5, r6, r7
+    smlsld r8, r9, r10, r11
+    umaal r0, r1, r2, r3
+    ssat16 r4, #8, r5
+    usat16 r6, #4, r7
+    pkhbt r8, r9, r10, lsl #4
+    pkhtb r11, r12, r0, asr #16
+    @ A6_4, floating point data processing
+    vmla.f32 s0, s1, s2
+    vmls.f32 s3, s4, s5
+    vnmla.f32 s6, s7, s8
+    vnmls.f32 s9, s10, s11
+    vnmul.f32 s12, s13, s14
+    vmul.f32 s15, s16, s17
+    vadd.f32 s18, s19, s20
+    vsub.f32 s21, s22, s23
+    vdiv.f32 s24, s25, s26
+    vfma.f32 s27, s28, s29
+    vfms.f32 s30, s31, s0
+    vfnma.f32 s1, s2, s3
+    vfnms.f32 s4, s5, s6
+    vmov.f32 s7, #1.0
+    vmov.f32 s8, s9
+    vabs.f32 s10, s11
+    vneg.f32 s12, s13
+    vsqrt.f32 s14, s15
+    vcmp.f32 s16, s17
+    vcmpe.f32 s18, #0
+    vcvt.s32.f32 s19, s20
+    vcvtr.u32.f32 s21, s22
+    vcvt.f32.s32 s23, s24
+    vcvt.f32.u32 s25, s26
+    vcvtb.f32.f16 s27, s28
+    vcvtt.f16.f32 s29, s30
+    vcvt.s16.f32 s0, s0, #8
+    @ A6_5, A6_6 and A6_7, floating point loads, stores and transfers
+    vldr s0, [r0, #8]
+    vstr s1, [r1, #-4]
+    vldr d2, [r2]
+    vstr d3, [sp, #1020]
+    vpush {s16-s19}
+    vpop {d8-d9}
+    vldmia r0!, {s0-s3}
+    vstmdb r1!, {d0-d1}
+    vmov r0, s1
+    vmov s2, r1
+    vmov r2, r3, d4
+    vmov d5, r4, r5
+    vmov r6, r7, s8, s9
+    vmrs r8, fpscr
+    vmsr fpscr, r9
+    vmrs APSR_nzcv, fpscr
+    vmov.32 d6[1], r10
+    vmov.32 r11, d7[0]
+    bx lr
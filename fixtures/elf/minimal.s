@@ -0,0 +1,37 @@
+@ Fixture for the `elf` feature, regenerate the images with
+@
+@   llvm-mc --triple=thumbv7m-none-eabi -filetype=obj minimal.s -o minimal.o
+@   rust-lld -flavor gnu -T link.ld minimal.o -o minimal.elf -n --build-id=none -z norelro
+@   llvm-objcopy --remove-section=.comment minimal.elf
+@   llvm-objcopy --strip-sections minimal.elf segments.elf
+    .syntax unified
+    .thumb
+    .section .vectors, "ax"
+    .thumb_func
+    .global reset
+reset:
+    b.w main
+
+    .text
+    .thumb_func
+    .global main
+main:
+    push {r4, lr}
+    movs r0, #1
+    ldr r1, =value
+    ldr r1, [r1]
+    adds r0, r0, r1
+    bl helper
+    pop {r4, pc}
+    .thumb_func
+helper:
+    cmp r0, #0
+    beq 1f
+    subs r0, #1
+1:
+    bx lr
+    .ltorg
+
+    .section .rodata
+value:
+    .word 0x12345678
@@ -0,0 +1,27 @@
+//! Parses arbitrary images as a whole, including the IT block tracking.
+
+#![no_main]
+
+use disarmv7::prelude::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|image: &[u8]| {
+    let from_slice = ASM::parse(&mut SliceStream::new(image));
+    let from_buffer = ASM::parse(&mut PeekableBuffer::from(image.iter().cloned()));
+    // The streams must be interchangeable.
+    assert_eq!(format!("{from_slice:?}"), format!("{from_buffer:?}"));
+    match from_slice {
+        Ok(asm) => {
+            for (size, op, conditional) in asm.annotated() {
+                let _ = op
+                    .ual()
+                    .size(size)
+                    .in_it_block(conditional.in_it_block)
+                    .to_string();
+            }
+        }
+        Err(error) => {
+            let _ = error.to_string();
+        }
+    }
+});
@@ -0,0 +1,66 @@
+//! Decodes arbitrary images one operation at a time for an arbitrary
+//! [`Profile`], checking that decoding never panics and that failed parses
+//! do not consume the stream.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use disarmv7::{
+    prelude::*,
+    profile::{Architecture, Extensions, Profile},
+    Consume,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    v6m: bool,
+    extensions: u8,
+    image: &'a [u8],
+}
+
+/// Builds the profile from the fuzzer provided fields.
+fn profile(v6m: bool, bits: u8) -> Profile {
+    let architecture = if v6m {
+        Architecture::V6M
+    } else {
+        Architecture::V7M
+    };
+    let extensions = [
+        Extensions::DSP,
+        Extensions::FP_SINGLE,
+        Extensions::FP_DOUBLE,
+        Extensions::MVE,
+        Extensions::SECURITY,
+    ]
+    .into_iter()
+    .enumerate()
+    .filter(|(idx, _)| bits & (1 << idx) != 0)
+    .fold(Extensions::NONE, |acc, (_, extension)| acc | extension);
+    Profile::new(architecture, extensions)
+}
+
+fuzz_target!(|input: Input<'_>| {
+    let profile = profile(input.v6m, input.extensions);
+    let mut stream = SliceStream::new(input.image);
+    while !stream.is_empty() {
+        let offset = stream.offset();
+        match Operation::parse_for(&mut stream, profile) {
+            Ok((size, op)) => {
+                assert!(size == 16 || size == 32);
+                assert_eq!(stream.offset(), offset.map(|offset| offset + size / 8));
+                assert!(profile.supports(size, &op));
+                let _ = op.ual().size(size).to_string();
+                let _ = op.pc_relative_target(0xffff_fffe);
+            }
+            Err(error) => {
+                assert_eq!(stream.offset(), offset, "{error}");
+                let _ = error.to_string();
+                let skipped: Option<[u16; 1]> = stream.consume::<1>();
+                if skipped.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+});
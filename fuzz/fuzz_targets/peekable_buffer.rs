@@ -0,0 +1,84 @@
+//! Applies arbitrary sequences of peeks and consumes to a [`PeekableBuffer`]
+//! and checks every result against a [`SliceStream`] over the same bytes.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use disarmv7::{prelude::*, Consume};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+enum Width {
+    Byte,
+    Halfword,
+    Word,
+}
+
+#[derive(Debug, Arbitrary)]
+enum Action {
+    Peek(Width, u8),
+    Consume(Width, u8),
+    Parse,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    image: Vec<u8>,
+    actions: Vec<Action>,
+}
+
+/// Dispatches the runtime `count` to the const generic `N` of `$method`.
+macro_rules! apply {
+    ($method:ident, $ty:ty, $count:expr, $buffer:ident, $stream:ident) => {
+        match $count % 5 {
+            0 => assert_eq!(
+                compare!($method, $ty, 0, $buffer),
+                compare!($method, $ty, 0, $stream)
+            ),
+            1 => assert_eq!(
+                compare!($method, $ty, 1, $buffer),
+                compare!($method, $ty, 1, $stream)
+            ),
+            2 => assert_eq!(
+                compare!($method, $ty, 2, $buffer),
+                compare!($method, $ty, 2, $stream)
+            ),
+            3 => assert_eq!(
+                compare!($method, $ty, 3, $buffer),
+                compare!($method, $ty, 3, $stream)
+            ),
+            _ => assert_eq!(
+                compare!($method, $ty, 4, $buffer),
+                compare!($method, $ty, 4, $stream)
+            ),
+        }
+    };
+}
+
+macro_rules! compare {
+    (peek, $ty:ty, $n:literal, $target:ident) => {
+        Peek::<$ty>::peek::<$n>(&mut $target).map(|value| vec![value])
+    };
+    (consume, $ty:ty, $n:literal, $target:ident) => {
+        Consume::<$ty>::consume::<$n>(&mut $target).map(Vec::from)
+    };
+}
+
+fuzz_target!(|input: Input| {
+    let mut buffer = PeekableBuffer::from(input.image.iter().cloned());
+    let mut stream = SliceStream::new(&input.image);
+    for action in input.actions {
+        match action {
+            Action::Peek(Width::Byte, n) => apply!(peek, u8, n, buffer, stream),
+            Action::Peek(Width::Halfword, n) => apply!(peek, u16, n, buffer, stream),
+            Action::Peek(Width::Word, n) => apply!(peek, u32, n, buffer, stream),
+            Action::Consume(Width::Byte, n) => apply!(consume, u8, n, buffer, stream),
+            Action::Consume(Width::Halfword, n) => apply!(consume, u16, n, buffer, stream),
+            Action::Consume(Width::Word, n) => apply!(consume, u32, n, buffer, stream),
+            Action::Parse => {
+                assert_eq!(Operation::parse(&mut buffer), Operation::parse(&mut stream))
+            }
+        }
+        assert_eq!(buffer.offset(), stream.offset());
+    }
+});
@@ -0,0 +1,10 @@
+//! Static analyses over decoded [`Operation`](crate::operation::Operation)s.
+//!
+//! The analyses operate on address tagged instructions, as emitted by
+//! [`Decoder`](crate::decoder::Decoder), rather than on raw bytes.
+
+pub mod cfg;
+pub mod jump_table;
+
+pub use cfg::{BasicBlock, Cfg, Edge, EdgeKind, Instruction};
+pub use jump_table::JumpTable;
@@ -0,0 +1,547 @@
+//! Splits decoded instructions in to [`BasicBlock`]s and connects them in a
+//! control flow graph.
+//!
+//! A block ends at every operation that writes to the PC, i.e. `b`, `bl`,
+//! `bx`, `blx`, `cbz`, `tbb`, `tbh`, `pop` or `ldm` with the PC in the
+//! register list and `ldr` in to the PC. IT blocks are kept in a basic block
+//! of their own, starting at the `it` instruction, such that the predicated
+//! operations form a single unit.
+//!
+//! Calls are assumed to return, `bl` and `blx` fall through to the next
+//! instruction. Branches to registers have no statically known target and
+//! therefore no outgoing edges, apart from the fall through edge if they are
+//! conditional. Table branches get an edge to every case once their
+//! [`JumpTable`] is recovered, see [`Cfg::with_jump_tables`].
+//!
+//! ```
+//! use disarmv7::{
+//!     analysis::{Cfg, EdgeKind},
+//!     decoder::Decoder,
+//!     prelude::*,
+//! };
+//!
+//! // cmp r0, #0 ; beq #2 ; movs r1, #1 ; movs r1, #2 ; bx lr
+//! let bin = [0x00, 0x28, 0x00, 0xd0, 0x01, 0x21, 0x02, 0x21, 0x70, 0x47];
+//! let mut stream = PeekableBuffer::from(bin.into_iter());
+//! let cfg: Cfg = Decoder::new(&mut stream, 0x100)
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap()
+//!     .into_iter()
+//!     .collect();
+//!
+//! let entry = cfg.block(0x100).unwrap();
+//! assert_eq!(entry.successors.len(), 2);
+//! assert_eq!(entry.successors[0].target, 0x106);
+//! assert_eq!(entry.successors[0].kind, EdgeKind::Conditional);
+//! assert_eq!(cfg.block(0x106).unwrap().predecessors, vec![0x100, 0x104]);
+//! println!("{}", cfg.to_dot());
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use crate::{
+    analysis::JumpTable,
+    arch::Condition,
+    it_block::{Conditional, ItState},
+    operation::Operation,
+};
+
+/// A decoded operation placed at a known address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    /// The address of the first byte of the encoding.
+    pub address: u32,
+
+    /// The size of the encoding in bits.
+    pub size: usize,
+
+    /// The decoded operation.
+    pub operation: Operation,
+
+    /// The IT block state that the operation executes in.
+    pub conditional: Conditional,
+}
+
+impl Instruction {
+    /// Returns the address directly after the encoding.
+    pub fn next(&self) -> u32 {
+        self.address.wrapping_add(self.size as u32 / 8)
+    }
+}
+
+/// Enumerates the kinds of edges between [`BasicBlock`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// Execution continues with the next instruction, either because the
+    /// block ended without branching or because a conditional branch was
+    /// not taken.
+    FallThrough,
+
+    /// An unconditional branch.
+    Unconditional,
+
+    /// A conditional branch that is taken.
+    Conditional,
+
+    /// A case of a table branch.
+    Switch,
+}
+
+/// A directed edge to the block starting at `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    /// The address of the successor, this may lie outside of the graph, for
+    /// example for tail calls.
+    pub target: u32,
+
+    /// How control reaches the successor.
+    pub kind: EdgeKind,
+}
+
+/// A sequence of instructions that is only entered at the first and only
+/// left after the last instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+    /// The instructions in address order, never empty.
+    pub instructions: Vec<Instruction>,
+
+    /// The outgoing edges, taken branches precede the fall through edge.
+    pub successors: Vec<Edge>,
+
+    /// The start addresses of the blocks that have an edge to this block.
+    pub predecessors: Vec<u32>,
+}
+
+impl BasicBlock {
+    /// Returns the address of the first instruction.
+    pub fn start(&self) -> u32 {
+        self.instructions[0].address
+    }
+
+    /// Returns the address directly after the last instruction.
+    pub fn end(&self) -> u32 {
+        self.last().next()
+    }
+
+    /// Returns the last instruction, which decides the successors.
+    pub fn last(&self) -> &Instruction {
+        self.instructions
+            .last()
+            .expect("Basic blocks are never empty")
+    }
+
+    /// Returns true if `address` is the address of one of the instructions.
+    pub fn contains(&self, address: u32) -> bool {
+        self.instructions.iter().any(|el| el.address == address)
+    }
+}
+
+/// A control flow graph over decoded instructions, keyed by the start
+/// address of each block.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cfg {
+    blocks: BTreeMap<u32, BasicBlock>,
+}
+
+/// How control leaves an instruction.
+enum Flow {
+    /// Continues with the next instruction.
+    Next,
+    /// Always branches to the target.
+    Jump(u32),
+    /// Branches to the target if the condition passes.
+    Branch(u32),
+    /// Calls a function and returns to the next instruction.
+    Call,
+    /// Branches to one of the cases of a jump table.
+    Switch {
+        targets: Vec<u32>,
+        conditional: bool,
+    },
+    /// Branches to a target that is not statically known.
+    Indirect { conditional: bool },
+}
+
+impl Flow {
+    fn of(instruction: &Instruction, tables: &[JumpTable]) -> Self {
+        let Instruction {
+            address,
+            operation,
+            conditional,
+            ..
+        } = instruction;
+        let conditional = conditional.condition != Condition::None;
+        match (operation, operation.branch_target(*address)) {
+            (Operation::B(_), Some(target)) if conditional => Self::Branch(target),
+            (Operation::B(_), Some(target)) => Self::Jump(target),
+            (Operation::Cbz(_), Some(target)) => Self::Branch(target),
+            (Operation::Bl(_) | Operation::Blx(_), _) => Self::Call,
+            (Operation::Tb(_), _) => match tables.iter().find(|el| el.address == *address) {
+                Some(table) => Self::Switch {
+                    targets: table.targets.clone(),
+                    conditional,
+                },
+                None => Self::Indirect { conditional },
+            },
+            (operation, _) if operation.may_change_pc() => Self::Indirect { conditional },
+            _ => Self::Next,
+        }
+    }
+
+    /// Returns true if the instruction ends a basic block.
+    fn ends_block(&self) -> bool {
+        !matches!(self, Self::Next)
+    }
+
+    /// Returns the statically known branch targets.
+    fn targets(&self) -> &[u32] {
+        match self {
+            Self::Jump(target) | Self::Branch(target) => std::slice::from_ref(target),
+            Self::Switch { targets, .. } => targets,
+            _ => &[],
+        }
+    }
+
+    /// Returns the outgoing edges of an instruction that ends a block and
+    /// is followed by `next`.
+    fn edges(&self, next: u32) -> Vec<Edge> {
+        let fall_through = Edge {
+            target: next,
+            kind: EdgeKind::FallThrough,
+        };
+        match self {
+            Self::Next | Self::Call | Self::Indirect { conditional: true } => vec![fall_through],
+            Self::Jump(target) => vec![Edge {
+                target: *target,
+                kind: EdgeKind::Unconditional,
+            }],
+            Self::Branch(target) => vec![
+                Edge {
+                    target: *target,
+                    kind: EdgeKind::Conditional,
+                },
+                fall_through,
+            ],
+            Self::Switch {
+                targets,
+                conditional,
+            } => {
+                let mut edges: Vec<Edge> = Vec::new();
+                for target in targets {
+                    let edge = Edge {
+                        target: *target,
+                        kind: EdgeKind::Switch,
+                    };
+                    if !edges.contains(&edge) {
+                        edges.push(edge);
+                    }
+                }
+                edges.extend(conditional.then_some(fall_through));
+                edges
+            }
+            Self::Indirect { conditional: false } => vec![],
+        }
+    }
+}
+
+impl Cfg {
+    /// Builds the graph from instructions in ascending address order.
+    ///
+    /// The instructions are expected to be decoded linearly, as the IT block
+    /// state is carried from one instruction to the next. Gaps, such as
+    /// literal pools, are allowed and always end the current block.
+    pub fn new<I: IntoIterator<Item = (u32, usize, Operation)>>(instructions: I) -> Self {
+        Self::with_jump_tables(instructions, &[])
+    }
+
+    /// Builds the graph as [`Cfg::new`] does, adding an edge to every case
+    /// of the recovered `tables`.
+    ///
+    /// The bytes of the tables must not be part of `instructions`, see
+    /// [`sweep`](crate::analysis::jump_table::sweep).
+    pub fn with_jump_tables<I: IntoIterator<Item = (u32, usize, Operation)>>(
+        instructions: I,
+        tables: &[JumpTable],
+    ) -> Self {
+        let mut state = ItState::new();
+        let instructions: Vec<Instruction> = instructions
+            .into_iter()
+            .map(|(address, size, operation)| Instruction {
+                address,
+                size,
+                conditional: state.advance(&operation),
+                operation,
+            })
+            .collect();
+        let flows: Vec<Flow> = instructions.iter().map(|el| Flow::of(el, tables)).collect();
+
+        let addresses: BTreeSet<u32> = instructions.iter().map(|el| el.address).collect();
+        let mut leaders = BTreeSet::new();
+        leaders.extend(instructions.first().map(|el| el.address));
+        for (idx, (instruction, flow)) in instructions.iter().zip(&flows).enumerate() {
+            let next = instructions.get(idx + 1).map(|el| el.address);
+            let gap = next.is_some_and(|next| next != instruction.next());
+            if let Operation::It(_) = instruction.operation {
+                leaders.insert(instruction.address);
+            }
+            if flow.ends_block() || instruction.conditional.last_in_it_block || gap {
+                leaders.extend(next);
+            }
+            leaders.extend(
+                flow.targets()
+                    .iter()
+                    .filter(|target| addresses.contains(target)),
+            );
+        }
+
+        let mut blocks: BTreeMap<u32, BasicBlock> = BTreeMap::new();
+        let mut current: Vec<Instruction> = Vec::new();
+        for (instruction, flow) in instructions.into_iter().zip(flows) {
+            if leaders.contains(&instruction.address) && !current.is_empty() {
+                let block = Self::close(std::mem::take(&mut current), &Flow::Next);
+                blocks.insert(block.start(), block);
+            }
+            let ends_block = flow.ends_block();
+            current.push(instruction);
+            if ends_block {
+                let block = Self::close(std::mem::take(&mut current), &flow);
+                blocks.insert(block.start(), block);
+            }
+        }
+        if !current.is_empty() {
+            let block = Self::close(current, &Flow::Next);
+            blocks.insert(block.start(), block);
+        }
+
+        let edges: Vec<(u32, u32)> = blocks
+            .values()
+            .flat_map(|block| {
+                block
+                    .successors
+                    .iter()
+                    .map(|edge| (block.start(), edge.target))
+            })
+            .collect();
+        for (source, target) in edges {
+            if let Some(block) = blocks.get_mut(&target) {
+                if !block.predecessors.contains(&source) {
+                    block.predecessors.push(source);
+                }
+            }
+        }
+        Self { blocks }
+    }
+
+    /// Creates a block that is left through `flow`.
+    fn close(instructions: Vec<Instruction>, flow: &Flow) -> BasicBlock {
+        let next = instructions
+            .last()
+            .expect("Basic blocks are never empty")
+            .next();
+        BasicBlock {
+            successors: flow.edges(next),
+            instructions,
+            predecessors: Vec::new(),
+        }
+    }
+
+    /// Returns the blocks in ascending address order.
+    pub fn blocks(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.values()
+    }
+
+    /// Returns the number of blocks in the graph.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns true if the graph contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns the block starting at `start`.
+    pub fn block(&self, start: u32) -> Option<&BasicBlock> {
+        self.blocks.get(&start)
+    }
+
+    /// Returns the block that contains the instruction at `address`.
+    pub fn block_containing(&self, address: u32) -> Option<&BasicBlock> {
+        self.blocks
+            .range(..=address)
+            .next_back()
+            .map(|(_, block)| block)
+            .filter(|block| block.contains(address))
+    }
+
+    /// Returns the blocks reached by the edges of the block at `start`,
+    /// edges that leave the graph are skipped.
+    pub fn successors(&self, start: u32) -> impl Iterator<Item = &BasicBlock> {
+        self.block(start)
+            .into_iter()
+            .flat_map(|block| block.successors.iter())
+            .filter_map(|edge| self.block(edge.target))
+    }
+
+    /// Returns the blocks that have an edge to the block at `start`.
+    pub fn predecessors(&self, start: u32) -> impl Iterator<Item = &BasicBlock> {
+        self.block(start)
+            .into_iter()
+            .flat_map(|block| block.predecessors.iter())
+            .filter_map(|source| self.block(*source))
+    }
+
+    /// Renders the graph in the Graphviz DOT language.
+    ///
+    /// Each block is rendered as a box listing its instructions in UAL,
+    /// targets outside of the graph are rendered as ellipses.
+    pub fn to_dot(&self) -> String {
+        let mut dot =
+            String::from("digraph cfg {\n    node [shape=box, fontname=\"monospace\"];\n");
+        let mut external = BTreeSet::new();
+        for block in self.blocks() {
+            let mut label = String::new();
+            for el in &block.instructions {
+                let ual = el
+                    .operation
+                    .ual()
+                    .size(el.size)
+                    .in_it_block(el.conditional.in_it_block);
+                let _ = write!(
+                    label,
+                    "{:#010x}: {}\\l",
+                    el.address,
+                    escape(&ual.to_string())
+                );
+            }
+            let _ = writeln!(dot, "    \"{:#010x}\" [label=\"{label}\"];", block.start());
+            for edge in &block.successors {
+                let style = match edge.kind {
+                    EdgeKind::FallThrough => "style=dashed",
+                    EdgeKind::Unconditional => "color=blue",
+                    EdgeKind::Conditional => "color=green, label=\"taken\"",
+                    EdgeKind::Switch => "color=purple",
+                };
+                let _ = writeln!(
+                    dot,
+                    "    \"{:#010x}\" -> \"{:#010x}\" [{style}];",
+                    block.start(),
+                    edge.target
+                );
+                if !self.blocks.contains_key(&edge.target) {
+                    external.insert(edge.target);
+                }
+            }
+        }
+        for target in external {
+            let _ = writeln!(dot, "    \"{target:#010x}\" [shape=ellipse];");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FromIterator<(u32, usize, Operation)> for Cfg {
+    fn from_iter<T: IntoIterator<Item = (u32, usize, Operation)>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Escapes the characters that are special in quoted DOT strings.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{decoder::Decoder, prelude::*};
+
+    fn cfg(bin: &[u8]) -> Cfg {
+        let mut stream = PeekableBuffer::from(bin.iter().cloned());
+        Decoder::new(&mut stream, 0)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    fn successors(cfg: &Cfg, start: u32) -> Vec<(u32, EdgeKind)> {
+        cfg.block(start)
+            .unwrap()
+            .successors
+            .iter()
+            .map(|edge| (edge.target, edge.kind))
+            .collect()
+    }
+
+    // 0x00: cmp r0, #0
+    // 0x02: beq 0x08
+    // 0x04: movs r1, #1
+    // 0x06: b 0x0a
+    // 0x08: movs r1, #2
+    // 0x0a: cbz r1, 0x14
+    // 0x0c: bl 0x00
+    // 0x10: it ne
+    // 0x12: addne r1, r1, #1
+    // 0x14: pop {r4, pc}
+    const FUNCTION: [u8; 22] = [
+        0x00, 0x28, 0x01, 0xd0, 0x01, 0x21, 0x00, 0xe0, 0x02, 0x21, 0x19, 0xb1, 0xff, 0xf7, 0xf8,
+        0xff, 0x18, 0xbf, 0x49, 0x1c, 0x10, 0xbd,
+    ];
+
+    #[test]
+    fn test_blocks() {
+        let cfg = cfg(&FUNCTION);
+        let starts: Vec<u32> = cfg.blocks().map(BasicBlock::start).collect();
+        assert_eq!(starts, vec![0x00, 0x04, 0x08, 0x0a, 0x0c, 0x10, 0x14]);
+        assert_eq!(cfg.block(0x10).unwrap().end(), 0x14);
+        assert_eq!(cfg.block_containing(0x12).unwrap().start(), 0x10);
+        assert!(cfg.block_containing(0x11).is_none());
+    }
+
+    #[test]
+    fn test_edges() {
+        use EdgeKind::*;
+        let cfg = cfg(&FUNCTION);
+        assert_eq!(successors(&cfg, 0x00), vec![
+            (0x08, Conditional),
+            (0x04, FallThrough)
+        ]);
+        assert_eq!(successors(&cfg, 0x04), vec![(0x0a, Unconditional)]);
+        assert_eq!(successors(&cfg, 0x08), vec![(0x0a, FallThrough)]);
+        assert_eq!(successors(&cfg, 0x0a), vec![
+            (0x14, Conditional),
+            (0x0c, FallThrough)
+        ]);
+        assert_eq!(successors(&cfg, 0x0c), vec![(0x10, FallThrough)]);
+        assert_eq!(successors(&cfg, 0x10), vec![(0x14, FallThrough)]);
+        assert_eq!(successors(&cfg, 0x14), vec![]);
+
+        assert_eq!(cfg.block(0x0a).unwrap().predecessors, vec![0x04, 0x08]);
+        assert_eq!(cfg.block(0x14).unwrap().predecessors, vec![0x0a, 0x10]);
+        assert_eq!(cfg.predecessors(0x00).count(), 0);
+        assert_eq!(cfg.successors(0x00).count(), 2);
+    }
+
+    #[test]
+    fn test_conditional_return() {
+        // it eq ; bxeq lr ; movs r0, #0 ; bx lr
+        let cfg = cfg(&[0x08, 0xbf, 0x70, 0x47, 0x00, 0x20, 0x70, 0x47]);
+        assert_eq!(cfg.len(), 2);
+        assert_eq!(successors(&cfg, 0x00), vec![(0x04, EdgeKind::FallThrough)]);
+        assert_eq!(successors(&cfg, 0x04), vec![]);
+    }
+
+    #[test]
+    fn test_dot() {
+        // b 0x10
+        let dot = cfg(&[0x06, 0xe0]).to_dot();
+        assert!(dot.starts_with("digraph cfg {"));
+        assert!(dot.contains("\"0x00000000\" -> \"0x00000010\" [color=blue];"));
+        assert!(dot.contains("\"0x00000010\" [shape=ellipse];"));
+    }
+}
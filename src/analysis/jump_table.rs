@@ -0,0 +1,250 @@
+//! Recovers the jump tables that follow `tbb` and `tbh` instructions.
+//!
+//! Compilers emit switch statements as a bounds check followed by a table
+//! branch with the table placed directly after it :
+//!
+//! ```text
+//! cmp  r0, #2
+//! bhi  default
+//! tbb  [pc, r0]
+//! .byte (case0 - table) / 2, (case1 - table) / 2, (case2 - table) / 2
+//! ```
+//!
+//! The number of cases is taken from the `cmp` that guards the index
+//! register, the table itself is read from memory. Only tables that are
+//! addressed relative to the PC are recovered.
+//!
+//! ```
+//! use disarmv7::analysis::jump_table::sweep;
+//!
+//! // cmp r0, #1 ; bhi #4 ; tbb [pc, r0] ; .byte 1, 3 ; movs r0, #1 ; bx lr ; bx lr
+//! let bin = [
+//!     0x01, 0x28, 0x04, 0xd8, 0xdf, 0xe8, 0x00, 0xf0, 0x01, 0x03, 0x01, 0x20, 0x70, 0x47, 0x70,
+//!     0x47,
+//! ];
+//! let (records, tables) = sweep(&bin, 0x100);
+//!
+//! assert_eq!(tables[0].table, 0x108..0x10a);
+//! assert_eq!(tables[0].targets, vec![0x10a, 0x10e]);
+//! assert_eq!(records[3].address(), 0x108);
+//! ```
+
+use std::ops::Range;
+
+use crate::{
+    arch::{Condition, Register},
+    buffer::SliceStream,
+    decoder::{MappedDecoder, Record},
+    operation::{pc, Operation},
+};
+
+/// The number of instructions before the table branch that are searched for
+/// the bounds check.
+const WINDOW: usize = 4;
+
+/// A jump table that is indexed by a `tbb` or `tbh`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    /// The address of the table branch.
+    pub address: u32,
+
+    /// True if the table contains halfwords, i.e. it is indexed by `tbh`.
+    pub is_tbh: bool,
+
+    /// The bytes occupied by the table, extended to a halfword boundary.
+    pub table: Range<u32>,
+
+    /// The target of each case, in index order.
+    pub targets: Vec<u32>,
+}
+
+impl JumpTable {
+    /// Recovers the table indexed by the table branch at
+    /// `instructions[index]`, where `memory` is the code placed at `base`.
+    ///
+    /// Returns `None` if the operation is not a PC relative table branch, no
+    /// bounds check precedes it or the table does not fit in `memory`.
+    pub fn recover(
+        instructions: &[(u32, usize, Operation)],
+        index: usize,
+        memory: &[u8],
+        base: u32,
+    ) -> Option<Self> {
+        let (address, _, Operation::Tb(tb)) = instructions.get(index)? else {
+            return None;
+        };
+        if tb.rn != Register::PC {
+            return None;
+        }
+        let cases = bound(&instructions[index.saturating_sub(WINDOW)..index], tb.rm)?;
+        let is_tbh = tb.is_tbh.unwrap_or(false);
+        let width = if is_tbh { 2 } else { 1 };
+
+        let start = pc(*address);
+        let offset = start.checked_sub(base)? as usize;
+        let bytes = memory.get(offset..offset.checked_add(cases * width)?)?;
+        let targets = bytes
+            .chunks(width)
+            .map(|entry| {
+                // The entries are little endian.
+                let entry = entry.iter().rev().fold(0, |acc, el| acc << 8 | *el as u32);
+                start.wrapping_add(2 * entry)
+            })
+            .collect();
+
+        let size = (cases * width).next_multiple_of(2) as u32;
+        Some(Self {
+            address: *address,
+            is_tbh,
+            table: start..start.wrapping_add(size),
+            targets,
+        })
+    }
+}
+
+/// Returns the number of cases admitted by the bounds check on `rm` in
+/// `preceding`, searching backwards from the table branch.
+///
+/// The check is a `cmp rm, #imm` followed by a branch to the default case,
+/// either `bhi` which admits `imm + 1` cases or `bhs` which admits `imm`.
+fn bound(preceding: &[(u32, usize, Operation)], rm: Register) -> Option<usize> {
+    let mut guard = None;
+    for (_, _, operation) in preceding.iter().rev() {
+        match operation {
+            Operation::B(el) if guard.is_none() && el.condition == Condition::Hi => guard = Some(1),
+            Operation::B(el) if guard.is_none() && el.condition == Condition::Cs => guard = Some(0),
+            Operation::CmpImmediate(el) if el.rn == rm => {
+                return Some(el.imm as usize + guard?).filter(|cases| *cases > 0)
+            }
+            operation if operation.may_change_pc() => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Decodes `memory`, placed at `base`, from the address `from` onwards
+/// while marking `data` as data.
+fn decode(memory: &[u8], base: u32, from: u32, data: Vec<Range<u32>>) -> Vec<Record> {
+    let offset = from.wrapping_sub(base) as usize;
+    let mut stream = SliceStream::new(memory.get(offset..).unwrap_or_default());
+    MappedDecoder::new(&mut stream, from, data)
+        .recovering()
+        .filter_map(Result::ok)
+        .collect()
+}
+
+/// Decodes all of `memory`, placed at `base`, while marking the recovered
+/// jump tables as data.
+///
+/// The bytes of a table are first decoded as instructions, so every table
+/// that is found discards the records that follow its table branch and
+/// resumes decoding there with the table marked as data. Everything before
+/// the table branch is decoded once.
+pub fn sweep(memory: &[u8], base: u32) -> (Vec<Record>, Vec<JumpTable>) {
+    let mut tables: Vec<JumpTable> = Vec::new();
+    let mut records = decode(memory, base, base, Vec::new());
+    let mut instructions: Vec<(u32, usize, Operation)> = Vec::new();
+    let mut idx = 0;
+    while let Some(record) = records.get(idx) {
+        idx += 1;
+        let Record::Instruction(address, size, operation) = record else {
+            continue;
+        };
+        let resume = address.wrapping_add(*size as u32 / 8);
+        instructions.push((*address, *size, operation.clone()));
+        let Some(table) = JumpTable::recover(&instructions, instructions.len() - 1, memory, base)
+        else {
+            continue;
+        };
+        records.truncate(idx);
+        records.extend(decode(memory, base, resume, vec![table.table.clone()]));
+        tables.push(table);
+    }
+    (records, tables)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analysis::{Cfg, EdgeKind};
+
+    // 0x00: cmp r0, #2
+    // 0x02: bhi 0x14
+    // 0x04: tbb [pc, r0]
+    // 0x08: .byte 2, 4, 6, 0
+    // 0x0c: movs r0, #1 ; bx lr
+    // 0x10: movs r0, #2 ; bx lr
+    // 0x14: movs r0, #0 ; bx lr
+    // 0x18: cmp r1, #2
+    // 0x1a: bhs 0x24
+    // 0x1c: tbh [pc, r1, lsl #1]
+    // 0x20: .hword 2, 3
+    // 0x24: bx lr
+    // 0x26: movs r0, #3 ; bx lr
+    const SWITCH: [u8; 42] = [
+        0x02, 0x28, 0x07, 0xd8, 0xdf, 0xe8, 0x00, 0xf0, 0x02, 0x04, 0x06, 0x00, 0x01, 0x20, 0x70,
+        0x47, 0x02, 0x20, 0x70, 0x47, 0x00, 0x20, 0x70, 0x47, 0x02, 0x29, 0x03, 0xd2, 0xdf, 0xe8,
+        0x11, 0xf0, 0x02, 0x00, 0x03, 0x00, 0x70, 0x47, 0x03, 0x20, 0x70, 0x47,
+    ];
+
+    #[test]
+    fn test_recover() {
+        let (records, tables) = sweep(&SWITCH, 0);
+        assert_eq!(tables, vec![
+            JumpTable {
+                address: 0x04,
+                is_tbh: false,
+                table: 0x08..0x0c,
+                targets: vec![0x0c, 0x10, 0x14],
+            },
+            JumpTable {
+                address: 0x1c,
+                is_tbh: true,
+                table: 0x20..0x24,
+                targets: vec![0x24, 0x26],
+            },
+        ]);
+        assert!(records
+            .iter()
+            .all(|el| matches!(el, Record::Instruction(..) | Record::Data(..))));
+        assert_eq!(records[3], Record::Data(0x08, vec![0x02, 0x04, 0x06, 0x00]));
+    }
+
+    #[test]
+    fn test_unguarded() {
+        // tbb [pc, r0] without a bounds check.
+        let (records, tables) = sweep(&SWITCH[4..24], 0);
+        assert!(tables.is_empty());
+        assert!(records.iter().all(|el| !matches!(el, Record::Data(..))));
+
+        // cmp r1, #2 ; bhs ; tbh [pc, r0] checks the wrong register.
+        let mut bin = SWITCH;
+        bin[30] = 0x10;
+        let (_, tables) = sweep(&bin[24..], 0);
+        assert!(tables.is_empty());
+    }
+
+    #[test]
+    fn test_switch_edges() {
+        let (records, tables) = sweep(&SWITCH, 0);
+        let instructions = records.into_iter().filter_map(|el| match el {
+            Record::Instruction(address, size, operation) => Some((address, size, operation)),
+            _ => None,
+        });
+        let cfg = Cfg::with_jump_tables(instructions, &tables);
+        let block = cfg.block(0x04).unwrap();
+        let targets: Vec<(u32, EdgeKind)> = block
+            .successors
+            .iter()
+            .map(|el| (el.target, el.kind))
+            .collect();
+        assert_eq!(targets, vec![
+            (0x0c, EdgeKind::Switch),
+            (0x10, EdgeKind::Switch),
+            (0x14, EdgeKind::Switch),
+        ]);
+        assert_eq!(cfg.block(0x14).unwrap().predecessors, vec![0x00, 0x04]);
+        assert_eq!(cfg.block(0x26).unwrap().predecessors, vec![0x1c]);
+    }
+}
@@ -9,21 +9,29 @@
 #![deny(rustdoc::all)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+pub mod barrier;
 pub mod condition;
 pub mod coproc;
+pub mod hint;
 pub mod register;
+pub mod rounding;
 pub mod set_flags;
 pub mod shift;
+pub mod special_register;
 pub mod wrapper_types;
 
+pub use barrier::BarrierOption;
 pub use condition::{Condition, ITCondition};
 pub use coproc::CoProcessor;
-pub use register::{Register, RegisterList};
+pub use hint::DebugHint;
+pub use register::{F32Register, F64Register, Register, RegisterList};
+pub use rounding::RoundingMode;
 pub use set_flags::SetFlags;
 pub use shift::{ImmShift, Shift};
+pub use special_register::{ApsrMask, SpecialRegister};
 pub use wrapper_types::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 /// Enumerates all of the possible errors in this crate.
 pub enum ArchError {
     /// Thrown when trying to parse a [`Condition`] from
@@ -37,6 +45,18 @@ pub enum ArchError {
     InvalidField(String),
 }
 
+impl std::fmt::Display for ArchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCondition => write!(f, "invalid condition"),
+            Self::InvalidRegister(register) => write!(f, "invalid register {register}"),
+            Self::InvalidField(field) => write!(f, "invalid field, {field}"),
+        }
+    }
+}
+
+impl std::error::Error for ArchError {}
+
 /// Masks out a set of bits from the number
 pub(crate) trait Mask {
     /// Masks out bits start -> end from the number
@@ -0,0 +1,182 @@
+//! Defines the [`BarrierOption`]s used by the memory barrier instructions.
+
+use std::fmt::Display;
+
+/// Enumerates the options of `dmb`, `dsb` and `isb`, see section A7.7.
+///
+/// Armv7-M only defines [`Sy`](BarrierOption::Sy), the remaining options
+/// are carried over from the A-profile and Armv8-M. The processor executes
+/// any option it does not implement as `sy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarrierOption {
+    /// Full system, orders all accesses.
+    Sy,
+    /// Full system, orders stores.
+    St,
+    /// Full system, orders loads.
+    Ld,
+    /// Inner shareable, orders all accesses.
+    Ish,
+    /// Inner shareable, orders stores.
+    Ishst,
+    /// Inner shareable, orders loads.
+    Ishld,
+    /// Non shareable, orders all accesses.
+    Nsh,
+    /// Non shareable, orders stores.
+    Nshst,
+    /// Non shareable, orders loads.
+    Nshld,
+    /// Outer shareable, orders all accesses.
+    Osh,
+    /// Outer shareable, orders stores.
+    Oshst,
+    /// Outer shareable, orders loads.
+    Oshld,
+    /// A reserved encoding, contains the raw option.
+    Reserved(u8),
+}
+
+/// The kinds of memory accesses that a barrier can order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+    /// A load from memory.
+    Load,
+    /// A store to memory.
+    Store,
+}
+
+/// The shareability domain that a barrier applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Domain {
+    /// All observers in the system.
+    FullSystem,
+    /// The outer shareable domain.
+    OuterShareable,
+    /// The inner shareable domain.
+    InnerShareable,
+    /// The executing processor only.
+    NonShareable,
+}
+
+impl BarrierOption {
+    /// Returns true if accesses of type `before` that precede the barrier
+    /// are ordered with respect to accesses of type `after` that follow it.
+    ///
+    /// ```
+    /// use disarmv7::arch::{barrier::Access, BarrierOption};
+    ///
+    /// assert!(BarrierOption::St.orders(Access::Store, Access::Store));
+    /// assert!(!BarrierOption::St.orders(Access::Load, Access::Load));
+    /// assert!(BarrierOption::Ld.orders(Access::Load, Access::Store));
+    /// assert!(!BarrierOption::Ld.orders(Access::Store, Access::Load));
+    /// ```
+    pub const fn orders(&self, before: Access, after: Access) -> bool {
+        match self {
+            Self::St | Self::Ishst | Self::Nshst | Self::Oshst => {
+                matches!((before, after), (Access::Store, Access::Store))
+            }
+            Self::Ld | Self::Ishld | Self::Nshld | Self::Oshld => matches!(before, Access::Load),
+            _ => true,
+        }
+    }
+
+    /// Returns the shareability domain that the barrier applies to.
+    pub const fn domain(&self) -> Domain {
+        match self {
+            Self::Ish | Self::Ishst | Self::Ishld => Domain::InnerShareable,
+            Self::Nsh | Self::Nshst | Self::Nshld => Domain::NonShareable,
+            Self::Osh | Self::Oshst | Self::Oshld => Domain::OuterShareable,
+            _ => Domain::FullSystem,
+        }
+    }
+}
+
+/// Parses the 4 bit `option` field, unknown values are
+/// [`Reserved`](BarrierOption::Reserved).
+impl From<u8> for BarrierOption {
+    fn from(value: u8) -> Self {
+        match value {
+            0b1111 => Self::Sy,
+            0b1110 => Self::St,
+            0b1101 => Self::Ld,
+            0b1011 => Self::Ish,
+            0b1010 => Self::Ishst,
+            0b1001 => Self::Ishld,
+            0b0111 => Self::Nsh,
+            0b0110 => Self::Nshst,
+            0b0101 => Self::Nshld,
+            0b0011 => Self::Osh,
+            0b0010 => Self::Oshst,
+            0b0001 => Self::Oshld,
+            value => Self::Reserved(value),
+        }
+    }
+}
+
+impl From<BarrierOption> for u8 {
+    fn from(value: BarrierOption) -> Self {
+        match value {
+            BarrierOption::Sy => 0b1111,
+            BarrierOption::St => 0b1110,
+            BarrierOption::Ld => 0b1101,
+            BarrierOption::Ish => 0b1011,
+            BarrierOption::Ishst => 0b1010,
+            BarrierOption::Ishld => 0b1001,
+            BarrierOption::Nsh => 0b0111,
+            BarrierOption::Nshst => 0b0110,
+            BarrierOption::Nshld => 0b0101,
+            BarrierOption::Osh => 0b0011,
+            BarrierOption::Oshst => 0b0010,
+            BarrierOption::Oshld => 0b0001,
+            BarrierOption::Reserved(value) => value,
+        }
+    }
+}
+
+impl Display for BarrierOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Sy => "sy",
+            Self::St => "st",
+            Self::Ld => "ld",
+            Self::Ish => "ish",
+            Self::Ishst => "ishst",
+            Self::Ishld => "ishld",
+            Self::Nsh => "nsh",
+            Self::Nshst => "nshst",
+            Self::Nshld => "nshld",
+            Self::Osh => "osh",
+            Self::Oshst => "oshst",
+            Self::Oshld => "oshld",
+            Self::Reserved(value) => return write!(f, "#{value}"),
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for option in 0..16u8 {
+            assert_eq!(u8::from(BarrierOption::from(option)), option);
+        }
+        assert_eq!(BarrierOption::from(0), BarrierOption::Reserved(0));
+    }
+
+    #[test]
+    fn test_ordering() {
+        for before in [Access::Load, Access::Store] {
+            for after in [Access::Load, Access::Store] {
+                assert!(BarrierOption::Sy.orders(before, after));
+                assert!(BarrierOption::Reserved(0b1100).orders(before, after));
+            }
+        }
+        assert!(!BarrierOption::Oshst.orders(Access::Store, Access::Load));
+        assert_eq!(BarrierOption::Oshst.domain(), Domain::OuterShareable);
+        assert_eq!(BarrierOption::Sy.domain(), Domain::FullSystem);
+    }
+}
@@ -1,6 +1,8 @@
 //! Defines the [`Condition`] codes that are defined in the Armv7-m instruction
 //! set..
 
+use std::fmt::Display;
+
 use crate::ArchError;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -185,3 +187,30 @@ impl TryFrom<u16> for Condition {
         Self::try_from(value as u8)
     }
 }
+
+/// Renders the condition as a mnemonic suffix.
+///
+/// [`Condition::None`] renders as an empty string since unconditional
+/// instructions carry no suffix.
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suffix = match self {
+            Self::Eq => "eq",
+            Self::Ne => "ne",
+            Self::Cs => "cs",
+            Self::Cc => "cc",
+            Self::Mi => "mi",
+            Self::Pl => "pl",
+            Self::Vs => "vs",
+            Self::Vc => "vc",
+            Self::Hi => "hi",
+            Self::Ls => "ls",
+            Self::Ge => "ge",
+            Self::Lt => "lt",
+            Self::Gt => "gt",
+            Self::Le => "le",
+            Self::None => "",
+        };
+        write!(f, "{suffix}")
+    }
+}
@@ -1,5 +1,7 @@
 //! Defines the standard [`co processor`](CoProcessor) ids.
 
+use std::fmt::Display;
+
 use crate::ArchError;
 
 macro_rules! coproc {
@@ -29,16 +31,9 @@ macro_rules! coproc {
             }
         }
         impl From<CoProcessor> for u8 {
-            #[allow(unused_assignments)]
             fn from(val:CoProcessor) -> u8 {
-                let mut i = 0;
-                $(
-                    if CoProcessor::$coproc == val{
-                        return i;
-                    }
-                    i+=1;
-                )*
-                unreachable!();
+                // The variants are numbered in declaration order.
+                val as u8
             }
         }
     };
@@ -52,3 +47,9 @@ impl TryFrom<u16> for CoProcessor {
         (value as u8).try_into()
     }
 }
+
+impl Display for CoProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p{}", u8::from(*self))
+    }
+}
@@ -0,0 +1,51 @@
+//! Defines the [`DebugHint`] that is passed to the debug system by `dbg`.
+
+use std::fmt::Display;
+
+use crate::ArchError;
+
+/// The 4 bit `option` field of `dbg`, see section A7.7.26.
+///
+/// The architecture leaves the meaning of the hint to the debug system, so it
+/// is kept as a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DebugHint(u8);
+
+/// Parses the 4 bit `option` field, values that do not fit are rejected.
+impl TryFrom<u8> for DebugHint {
+    type Error = ArchError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > 0b1111 {
+            return Err(ArchError::InvalidField(format!(
+                "Invalid debug hint {value}"
+            )));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<DebugHint> for u8 {
+    fn from(value: DebugHint) -> Self {
+        value.0
+    }
+}
+
+impl Display for DebugHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for option in 0..16u8 {
+            assert_eq!(u8::from(DebugHint::try_from(option).unwrap()), option);
+        }
+        assert!(DebugHint::try_from(0b1_0000).is_err());
+    }
+}
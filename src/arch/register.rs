@@ -1,20 +1,21 @@
 //! Defines the [`Register`]s that are available in the system.
 
+use std::fmt::Display;
+
 use crate::ArchError;
 
 macro_rules! reg {
-    ($($reg:ident),*) => {
+    ($(#[doc = $comment:expr])* $name:ident : $($reg:ident),*) => {
         #[repr(u8)]
         #[derive(Debug,Copy,Clone,PartialEq)]
-        /// Enumerates the registers that are available
-        /// to the system
+        $(#[doc = $comment])*
         #[allow(missing_docs)]
-        pub enum Register {
+        pub enum $name {
         $(
             $reg
         ),*
         }
-        impl TryFrom<u8> for Register {
+        impl TryFrom<u8> for $name {
             type Error = ArchError;
             #[allow(unused_assignments)]
             fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -28,22 +29,33 @@ macro_rules! reg {
                 Err(ArchError::InvalidRegister(value))
             }
         }
-        impl From<Register> for u8 {
-            #[allow(unused_assignments)]
-            fn from(val:Register) -> u8 {
-                let mut i = 0;
-                $(
-                    if Register::$reg == val{
-                        return i;
-                    }
-                    i+=1;
-                )*
-                unreachable!();
+        impl From<$name> for u8 {
+            fn from(val:$name) -> u8 {
+                // The variants are numbered in declaration order.
+                val as u8
             }
         }
     };
 }
-reg!(R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, R10, R11, R12, SP, LR, PC);
+reg!(
+    /// Enumerates the registers that are available
+    /// to the system
+    Register: R0, R1, R2, R3, R4, R5, R6, R7, R8, R9, R10, R11, R12, SP, LR, PC
+);
+reg!(
+    /// Enumerates the single precision registers that are available
+    /// in the floating point extension.
+    F32Register: S0, S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12, S13, S14, S15, S16, S17,
+    S18, S19, S20, S21, S22, S23, S24, S25, S26, S27, S28, S29, S30, S31
+);
+reg!(
+    /// Enumerates the double precision registers that are available
+    /// in the floating point extension.
+    ///
+    /// The M-profile floating point extension only provides 16 double
+    /// precision registers, `D<n>` overlaps `S<2n>` and `S<2n+1>`.
+    F64Register: D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15
+);
 
 /// Register lists lifted from a bit vector to allow
 /// type level representations
@@ -91,3 +103,39 @@ impl TryFrom<u16> for RegisterList {
         Ok(Self { registers })
     }
 }
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SP => write!(f, "sp"),
+            Self::LR => write!(f, "lr"),
+            Self::PC => write!(f, "pc"),
+            reg => write!(f, "r{}", u8::from(*reg)),
+        }
+    }
+}
+
+impl Display for F32Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "s{}", u8::from(*self))
+    }
+}
+
+impl Display for F64Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "d{}", u8::from(*self))
+    }
+}
+
+impl Display for RegisterList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (idx, reg) in self.registers.iter().enumerate() {
+            if idx != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{reg}")?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -0,0 +1,56 @@
+//! Defines the [`RoundingMode`]s used by the floating point extension.
+
+use std::fmt::Display;
+
+use crate::ArchError;
+
+/// Enumerates the IEEE 754 rounding modes that an instruction can request
+/// explicitly.
+///
+/// Instructions that round according to the `FPSCR` carry no rounding mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+    /// Round to nearest, ties away from zero.
+    TiesAway,
+    /// Round to nearest, ties to even.
+    TiesEven,
+    /// Round towards plus infinity.
+    PlusInfinity,
+    /// Round towards minus infinity.
+    MinusInfinity,
+    /// Round towards zero.
+    Zero,
+}
+
+/// Parses the `RM` field of the directed rounding instructions.
+impl TryFrom<u8> for RoundingMode {
+    type Error = ArchError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0b00 => Self::TiesAway,
+            0b01 => Self::TiesEven,
+            0b10 => Self::PlusInfinity,
+            0b11 => Self::MinusInfinity,
+            _ => {
+                return Err(ArchError::InvalidField(format!(
+                    "Invalid rounding mode {value}"
+                )))
+            }
+        })
+    }
+}
+
+/// Renders the rounding mode as a mnemonic suffix, i.e. the `a` in `vrinta`.
+impl Display for RoundingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suffix = match self {
+            Self::TiesAway => "a",
+            Self::TiesEven => "n",
+            Self::PlusInfinity => "p",
+            Self::MinusInfinity => "m",
+            Self::Zero => "z",
+        };
+        write!(f, "{suffix}")
+    }
+}
@@ -1,5 +1,7 @@
 //! Enumerates and parses shift operations.
 
+use std::fmt::Display;
+
 use crate::ArchError;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,7 +37,7 @@ impl TryFrom<u8> for Shift {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(Self::Lsl),
-            1 => Ok(Self::Asr),
+            1 => Ok(Self::Lsr),
             2 => Ok(Self::Asr),
             3 => Ok(Self::Ror),
             _ => Err(ArchError::InvalidField(format!(
@@ -53,7 +55,7 @@ impl From<(Shift, u8)> for ImmShift {
                 shift_n: 32,
             },
             (Shift::Asr, 0) => Self {
-                shift_t: Shift::Lsr,
+                shift_t: Shift::Asr,
                 shift_n: 32,
             },
             (Shift::Ror, 0) => Self {
@@ -65,3 +67,24 @@ impl From<(Shift, u8)> for ImmShift {
         }
     }
 }
+
+impl Display for Shift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lsl => write!(f, "lsl"),
+            Self::Lsr => write!(f, "lsr"),
+            Self::Asr => write!(f, "asr"),
+            Self::Rrx => write!(f, "rrx"),
+            Self::Ror => write!(f, "ror"),
+        }
+    }
+}
+
+impl Display for ImmShift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.shift_t {
+            Shift::Rrx => write!(f, "rrx"),
+            _ => write!(f, "{} #{}", self.shift_t, self.shift_n),
+        }
+    }
+}
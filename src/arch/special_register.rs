@@ -0,0 +1,269 @@
+//! Defines the [`SpecialRegister`]s accessed by `mrs` and `msr`.
+
+use std::fmt::Display;
+
+use crate::ArchError;
+
+/// Enumerates the special registers as encoded in the `SYSm` field, see
+/// section B5.1.1.
+///
+/// The `_ns` variants and the stack limit registers are only present in
+/// Armv8-M implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecialRegister {
+    /// The application program status register.
+    Apsr,
+    /// The APSR and IPSR combined.
+    Iapsr,
+    /// The APSR and EPSR combined.
+    Eapsr,
+    /// The APSR, IPSR and EPSR combined.
+    Xpsr,
+    /// The interrupt program status register.
+    Ipsr,
+    /// The execution program status register.
+    Epsr,
+    /// The IPSR and EPSR combined.
+    Iepsr,
+    /// The main stack pointer.
+    Msp,
+    /// The process stack pointer.
+    Psp,
+    /// The main stack pointer limit.
+    Msplim,
+    /// The process stack pointer limit.
+    Psplim,
+    /// Masks all exceptions with configurable priority.
+    Primask,
+    /// The base priority mask.
+    Basepri,
+    /// Writes to the base priority mask only if it raises the priority.
+    BasepriMax,
+    /// Masks all exceptions other than NMI.
+    Faultmask,
+    /// The control register.
+    Control,
+    /// The non secure main stack pointer.
+    MspNs,
+    /// The non secure process stack pointer.
+    PspNs,
+    /// The non secure main stack pointer limit.
+    MsplimNs,
+    /// The non secure process stack pointer limit.
+    PsplimNs,
+    /// The non secure PRIMASK.
+    PrimaskNs,
+    /// The non secure BASEPRI.
+    BasepriNs,
+    /// The non secure FAULTMASK.
+    FaultmaskNs,
+    /// The non secure CONTROL.
+    ControlNs,
+    /// The currently selected non secure stack pointer.
+    SpNs,
+}
+
+impl SpecialRegister {
+    /// Returns true if the register is one of the program status registers.
+    pub const fn is_xpsr(&self) -> bool {
+        matches!(
+            self,
+            Self::Apsr
+                | Self::Iapsr
+                | Self::Eapsr
+                | Self::Xpsr
+                | Self::Ipsr
+                | Self::Epsr
+                | Self::Iepsr
+        )
+    }
+
+    /// Returns true if the register includes the APSR.
+    pub const fn includes_apsr(&self) -> bool {
+        matches!(self, Self::Apsr | Self::Iapsr | Self::Eapsr | Self::Xpsr)
+    }
+
+    /// Returns true if the register only exists in Armv8-M.
+    pub const fn is_v8m(&self) -> bool {
+        matches!(
+            self,
+            Self::Msplim
+                | Self::Psplim
+                | Self::MspNs
+                | Self::PspNs
+                | Self::MsplimNs
+                | Self::PsplimNs
+                | Self::PrimaskNs
+                | Self::BasepriNs
+                | Self::FaultmaskNs
+                | Self::ControlNs
+                | Self::SpNs
+        )
+    }
+}
+
+/// Parses the `SYSm` field, reserved values are rejected.
+impl TryFrom<u8> for SpecialRegister {
+    type Error = ArchError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Apsr,
+            1 => Self::Iapsr,
+            2 => Self::Eapsr,
+            3 => Self::Xpsr,
+            5 => Self::Ipsr,
+            6 => Self::Epsr,
+            7 => Self::Iepsr,
+            8 => Self::Msp,
+            9 => Self::Psp,
+            10 => Self::Msplim,
+            11 => Self::Psplim,
+            16 => Self::Primask,
+            17 => Self::Basepri,
+            18 => Self::BasepriMax,
+            19 => Self::Faultmask,
+            20 => Self::Control,
+            0x88 => Self::MspNs,
+            0x89 => Self::PspNs,
+            0x8a => Self::MsplimNs,
+            0x8b => Self::PsplimNs,
+            0x90 => Self::PrimaskNs,
+            0x91 => Self::BasepriNs,
+            0x93 => Self::FaultmaskNs,
+            0x94 => Self::ControlNs,
+            0x98 => Self::SpNs,
+            _ => {
+                return Err(ArchError::InvalidField(format!(
+                    "Reserved special register {value}"
+                )))
+            }
+        })
+    }
+}
+
+impl From<SpecialRegister> for u8 {
+    fn from(value: SpecialRegister) -> Self {
+        match value {
+            SpecialRegister::Apsr => 0,
+            SpecialRegister::Iapsr => 1,
+            SpecialRegister::Eapsr => 2,
+            SpecialRegister::Xpsr => 3,
+            SpecialRegister::Ipsr => 5,
+            SpecialRegister::Epsr => 6,
+            SpecialRegister::Iepsr => 7,
+            SpecialRegister::Msp => 8,
+            SpecialRegister::Psp => 9,
+            SpecialRegister::Msplim => 10,
+            SpecialRegister::Psplim => 11,
+            SpecialRegister::Primask => 16,
+            SpecialRegister::Basepri => 17,
+            SpecialRegister::BasepriMax => 18,
+            SpecialRegister::Faultmask => 19,
+            SpecialRegister::Control => 20,
+            SpecialRegister::MspNs => 0x88,
+            SpecialRegister::PspNs => 0x89,
+            SpecialRegister::MsplimNs => 0x8a,
+            SpecialRegister::PsplimNs => 0x8b,
+            SpecialRegister::PrimaskNs => 0x90,
+            SpecialRegister::BasepriNs => 0x91,
+            SpecialRegister::FaultmaskNs => 0x93,
+            SpecialRegister::ControlNs => 0x94,
+            SpecialRegister::SpNs => 0x98,
+        }
+    }
+}
+
+impl Display for SpecialRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Apsr => "apsr",
+            Self::Iapsr => "iapsr",
+            Self::Eapsr => "eapsr",
+            Self::Xpsr => "xpsr",
+            Self::Ipsr => "ipsr",
+            Self::Epsr => "epsr",
+            Self::Iepsr => "iepsr",
+            Self::Msp => "msp",
+            Self::Psp => "psp",
+            Self::Msplim => "msplim",
+            Self::Psplim => "psplim",
+            Self::Primask => "primask",
+            Self::Basepri => "basepri",
+            Self::BasepriMax => "basepri_max",
+            Self::Faultmask => "faultmask",
+            Self::Control => "control",
+            Self::MspNs => "msp_ns",
+            Self::PspNs => "psp_ns",
+            Self::MsplimNs => "msplim_ns",
+            Self::PsplimNs => "psplim_ns",
+            Self::PrimaskNs => "primask_ns",
+            Self::BasepriNs => "basepri_ns",
+            Self::FaultmaskNs => "faultmask_ns",
+            Self::ControlNs => "control_ns",
+            Self::SpNs => "sp_ns",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The APSR fields written by `msr`, decoded from the `mask` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ApsrMask {
+    /// Writes the N, Z, C, V and Q flags.
+    pub nzcvq: bool,
+    /// Writes the GE flags.
+    pub g: bool,
+}
+
+impl From<u8> for ApsrMask {
+    fn from(value: u8) -> Self {
+        Self {
+            nzcvq: value & 0b10 != 0,
+            g: value & 0b01 != 0,
+        }
+    }
+}
+
+impl From<ApsrMask> for u8 {
+    fn from(value: ApsrMask) -> Self {
+        (value.nzcvq as u8) << 1 | value.g as u8
+    }
+}
+
+/// Renders the mask as the suffix of the register name, i.e. `_nzcvq`.
+impl Display for ApsrMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.nzcvq, self.g) {
+            (true, true) => write!(f, "_nzcvqg"),
+            (true, false) => write!(f, "_nzcvq"),
+            (false, true) => write!(f, "_g"),
+            (false, false) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for sysm in 0..=u8::MAX {
+            if let Ok(register) = SpecialRegister::try_from(sysm) {
+                assert_eq!(u8::from(register), sysm);
+            }
+        }
+        assert!(SpecialRegister::try_from(4).is_err());
+        assert!(SpecialRegister::try_from(21).is_err());
+        assert!(SpecialRegister::try_from(0x92).is_err());
+    }
+
+    #[test]
+    fn test_mask() {
+        let mask = ApsrMask::from(0b10);
+        assert!(mask.nzcvq && !mask.g);
+        assert_eq!(u8::from(mask), 0b10);
+        assert_eq!(ApsrMask::from(0b11).to_string(), "_nzcvqg");
+    }
+}
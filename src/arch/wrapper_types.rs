@@ -13,7 +13,7 @@ macro_rules! combine {
             #[allow(unused_assignments)]
             {
                 $(
-                    counter -= 8 - $size;
+                    counter -= $size;
                     sum |= (($id as $ret_ty) << counter) as $ret_ty;
                 )*
             }
@@ -43,8 +43,8 @@ impl Imm12 {
                     0 => bits.into(),
                     1 => combine!(zero:bits,8:zero,8:bits,8,u32),
                     2 => combine!(bits:zero,8:bits,8:zero,8,u32),
-                    3 => combine!(bits:bits,8:bits,8:bits,8,u32),
-                    _ => unreachable!("Masking function broken"),
+                    // The field is two bits wide.
+                    _ => combine!(bits:bits,8:bits,8:bits,8,u32),
                 },
                 None,
             );
@@ -54,6 +54,34 @@ impl Imm12 {
         let c = ret.mask::<31, 31>() == 1;
         (ret, Some(c))
     }
+
+    /// Finds the encoded immediate that [`expand_imm`](Imm12::expand_imm)
+    /// expands to `value`.
+    ///
+    /// Returns None if the value can not be represented as a modified
+    /// immediate. Every representable value has exactly one encoding, so
+    /// the carry out of [`expand_imm_c`](Imm12::expand_imm_c) is given by the
+    /// value itself.
+    pub fn try_from_expanded(value: u32) -> Option<Self> {
+        let low = value & 0xff;
+        let second = (value >> 8) & 0xff;
+        let repr = if value <= 0xff {
+            value
+        } else if value == low | (low << 16) {
+            0x100 | low
+        } else if value == (second << 8) | (second << 24) {
+            0x200 | second
+        } else if value == low * 0x0101_0101 {
+            0x300 | low
+        } else {
+            let rotation = (8..32).find(|rotation| {
+                let unrotated = value.rotate_left(*rotation);
+                unrotated & !0xff == 0 && unrotated & 0x80 != 0
+            })?;
+            (rotation << 7) | (value.rotate_left(rotation) & 0x7f)
+        };
+        Self::try_from(repr).ok()
+    }
 }
 
 mod sealed {
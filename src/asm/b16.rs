@@ -11,14 +11,23 @@ pub mod simply_defined;
 
 use super::Mask;
 use crate::{
-    asm::b16::{a_5_2::A5_2, a_5_3::A5_3, a_5_4::A5_4, a_5_5::A5_5, a_5_6::A5_6, a_5_8::A5_8},
+    asm::{
+        b16::{a_5_2::A5_2, a_5_3::A5_3, a_5_4::A5_4, a_5_5::A5_5, a_5_6::A5_6, a_5_8::A5_8},
+        Table,
+    },
+    profile::Profile,
     Parse,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
 /// A 16-bit wide instruction
 pub enum B16 {}
+
+impl Table for B16 {
+    const NAME: &'static str = "B16";
+}
 impl B16 {
     fn parse_internal<T: crate::Stream>(
         iter: &mut T,
@@ -26,42 +35,42 @@ impl B16 {
         let word: Option<u16> = iter.peek::<1>();
         let opcode: u16 = (match word {
             Some(val) => val,
-            None => return Err(ParseError::IncompleteProgram),
+            None => return Err(ParseErrorKind::IncompleteProgram.into()),
         })
         .mask::<10, 15>();
 
         match opcode {
-            0b010000 => return Ok(A5_3::parse(iter)?.encoding_specific_operations()),
-            0b010001 => return Ok(A5_4::parse(iter)?.encoding_specific_operations()),
+            0b010000 => return A5_3::parse_table(iter)?.encoding_specific_operations(),
+            0b010001 => return A5_4::parse_table(iter)?.encoding_specific_operations(),
             _ => {}
         };
 
         match opcode >> 1 {
-            0b01001 => return Ok(simply_defined::Ldr::parse(iter)?.encoding_specific_operations()),
-            0b10100 => return Ok(simply_defined::Adr::parse(iter)?.encoding_specific_operations()),
-            0b10101 => return Ok(simply_defined::Add::parse(iter)?.encoding_specific_operations()),
-            0b11000 => return Ok(simply_defined::Stm::parse(iter)?.encoding_specific_operations()),
-            0b11001 => return Ok(simply_defined::Ldm::parse(iter)?.encoding_specific_operations()),
-            0b11100 => return Ok(simply_defined::B::parse(iter)?.encoding_specific_operations()),
+            0b01001 => return simply_defined::Ldr::parse(iter)?.encoding_specific_operations(),
+            0b10100 => return simply_defined::Adr::parse(iter)?.encoding_specific_operations(),
+            0b10101 => return simply_defined::Add::parse(iter)?.encoding_specific_operations(),
+            0b11000 => return simply_defined::Stm::parse(iter)?.encoding_specific_operations(),
+            0b11001 => return simply_defined::Ldm::parse(iter)?.encoding_specific_operations(),
+            0b11100 => return simply_defined::B::parse(iter)?.encoding_specific_operations(),
 
             _ => {}
         };
 
         match opcode >> 2 {
-            0b0101 => return Ok(A5_5::parse(iter)?.encoding_specific_operations()),
-            0b1011 => return Ok(A5_6::parse(iter)?.encoding_specific_operations()),
-            0b1101 => return Ok(A5_8::parse(iter)?.encoding_specific_operations()),
+            0b0101 => return A5_5::parse_table(iter)?.encoding_specific_operations(),
+            0b1011 => return A5_6::parse_table(iter)?.encoding_specific_operations(),
+            0b1101 => return A5_8::parse_table(iter)?.encoding_specific_operations(),
             _ => {}
         };
 
         if opcode >> 3 == 0b011 || opcode >> 3 == 0b100 {
-            return Ok(A5_5::parse(iter)?.encoding_specific_operations());
+            return A5_5::parse_table(iter)?.encoding_specific_operations();
         }
 
         if opcode >> 4 == 0 {
-            return Ok(A5_2::parse(iter)?.encoding_specific_operations());
+            return A5_2::parse_table(iter)?.encoding_specific_operations();
         }
-        Err(ParseError::Invalid16Bit("Half word"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 impl Parse for B16 {
@@ -71,11 +80,88 @@ impl Parse for B16 {
     where
         Self: Sized,
     {
+        Self::parse_for(iter, Profile::default())
+    }
+}
+
+impl B16 {
+    /// Parses the instruction, rejecting it without consuming it if it is not
+    /// defined in the `profile`.
+    pub(crate) fn parse_for<T: crate::Stream>(
+        iter: &mut T,
+        profile: Profile,
+    ) -> Result<(usize, crate::operation::Operation), ParseError> {
         let ret = Self::parse_internal(iter)?;
+        profile.check(16, &ret)?;
         let _: u16 = match iter.consume::<1>() {
             Some(val) => val[0],
-            None => return Err(ParseError::IncompleteProgram),
+            None => return Err(ParseErrorKind::IncompleteProgram.into()),
         };
         Ok((16, ret))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Write;
+
+    use crate::{buffer::SliceStream, prelude::*};
+
+    /// The expected decoding of every halfword.
+    const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/golden/b16.txt");
+
+    /// Renders the decoding of every halfword, one line per halfword.
+    fn render() -> String {
+        let mut ret = String::new();
+        for halfword in 0..=u16::MAX {
+            if let 0b11101..=0b11111 = halfword >> 11 {
+                writeln!(ret, "{halfword:04x} 32-bit").unwrap();
+                continue;
+            }
+            let bytes = halfword.to_le_bytes();
+            match Operation::parse(&mut SliceStream::new(&bytes)) {
+                Ok((_, operation)) => writeln!(ret, "{halfword:04x} {operation:?}"),
+                Err(e) => writeln!(
+                    ret,
+                    "{halfword:04x} error {:?} ({})",
+                    e.kind,
+                    e.path.join(" -> ")
+                ),
+            }
+            .unwrap();
+        }
+        ret
+    }
+
+    /// Compares the decoder against the golden file.
+    ///
+    /// Run with `DISARMV7_BLESS=1` to regenerate the file, the diff of the file
+    /// shows which encodings a change affects.
+    #[test]
+    fn test_golden() {
+        let rendered = render();
+        if std::env::var_os("DISARMV7_BLESS").is_some() {
+            std::fs::write(GOLDEN, &rendered).expect("Could not write the golden file");
+            return;
+        }
+        let golden = std::fs::read_to_string(GOLDEN).expect("Could not read the golden file");
+
+        let changed: Vec<String> = golden
+            .lines()
+            .zip(rendered.lines())
+            .filter(|(expected, actual)| expected != actual)
+            .map(|(expected, actual)| format!("-{expected}\n+{actual}"))
+            .collect();
+        assert!(
+            changed.is_empty() && golden.lines().count() == 1 << 16,
+            "{} encodings differ from {GOLDEN}, run with DISARMV7_BLESS=1 to regenerate it\n{}",
+            changed.len(),
+            changed
+                .iter()
+                .take(32)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     prelude::{ImmShift, SetFlags, Shift},
     Parse,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -88,7 +89,7 @@ impl Parse for A5_2 {
     {
         let word: u16 = match iter.peek::<1>() {
             Some(val) => Ok(val),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let opcode = word.mask::<9, 13>();
         match opcode >> 2 {
@@ -109,14 +110,14 @@ impl Parse for A5_2 {
             0b01101 => Ok(Self::Sub(Sub::parse(iter)?)),
             0b01110 => Ok(Self::AddImmediate3(AddImmediate3::parse(iter)?)),
             0b01111 => Ok(Self::SubImmediate3(SubImmediate3::parse(iter)?)),
-            _ => Err(ParseError::Invalid16Bit("A5_2")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 
 impl ToOperation for A5_2 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Lsl(lsl) => {
                 let shift = crate::arch::shift::ImmShift::from((Shift::Lsl, lsl.imm));
                 operation::LslImmediateBuilder::new()
@@ -203,7 +204,7 @@ impl ToOperation for A5_2 {
                 .set_imm(sub.imm as u32)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -1,7 +1,16 @@
 use paste::paste;
 
 use super::Mask;
-use crate::{arch, arch::Register, instruction, operation, Parse, ParseError, ToOperation};
+use crate::{
+    arch,
+    arch::Register,
+    instruction,
+    operation,
+    Parse,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
 macro_rules! instruction_5_3 {
     ($(
         $opcode:literal@$id:ident : {
@@ -30,11 +39,11 @@ macro_rules! instruction_5_3 {
 
                 let first_byte = match iter.peek::<1>() as Option<u8> {
                     Some(b) => Ok(b),
-                    None => Err(ParseError::Invalid16Bit("A5_3")),
+                    None => Err(ParseErrorKind::Undefined),
                 }?;
                 let second_byte = match iter.peek::<2>() as Option<u8> {
                     Some(b) => Ok(b),
-                    None => Err(ParseError::Invalid16Bit("A5_3")),
+                    None => Err(ParseErrorKind::Undefined),
                 }?;
                 let op = ((first_byte&0b11)<<2)|(second_byte>>6);
                 match op{
@@ -42,7 +51,7 @@ macro_rules! instruction_5_3 {
                         $opcode => {Ok(Self::$id($id::parse(iter)?))}
 
                     )+
-                        _       => {Err(ParseError::Invalid16Bit("A5_3"))}
+                        _       => {Err(ParseErrorKind::Undefined.into())}
 
                 }
 
@@ -119,8 +128,8 @@ instruction_5_3!(
 );
 
 impl ToOperation for A5_3 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::And(and) => operation::AndRegisterBuilder::new()
                 .set_s(Some(arch::SetFlags::InITBlock(false)))
                 .set_rd(None)
@@ -236,7 +245,7 @@ impl ToOperation for A5_3 {
                 .set_shift(None)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -8,6 +8,7 @@ use crate::{
     operation,
     Parse,
     ParseError,
+    ParseErrorKind,
     Stream,
     ToOperation,
 };
@@ -45,12 +46,12 @@ impl Parse for A5_4 {
     {
         let first_byte = match iter.peek::<1>() as Option<u8> {
             Some(b) => Ok(b),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let second_byte = match iter.peek::<2>() as Option<u8> {
             Some(b) => Ok(b),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let op = ((first_byte & 0b11) << 2) | (second_byte >> 6);
@@ -59,7 +60,7 @@ impl Parse for A5_4 {
         }
 
         if op == 0b0100 {
-            return Err(ParseError::Unpredictable);
+            return Err(ParseErrorKind::Unpredictable.into());
         }
 
         if op == 0b0101 || op & 0b1110 == 0b0110 {
@@ -78,16 +79,16 @@ impl Parse for A5_4 {
             return Ok(Self::Blx(Blx::parse(iter)?));
         }
 
-        Err(ParseError::Invalid16Bit("A5_4"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_4 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Add(el) => {
                 let (dn, rdn) = (el.dn, el.rdn);
-                let reg: Register = combine!(dn:rdn,3,u8).try_into().unwrap();
+                let reg: Register = combine!(dn:rdn,3,u8).try_into()?;
 
                 operation::AddRegister::builder()
                     .set_s(Some(false.into()))
@@ -100,7 +101,7 @@ impl ToOperation for A5_4 {
             }
             Self::Cmp(el) => {
                 let (n, rn) = (el.n, el.rn);
-                let reg: Register = combine!(n:rn,3,u8).try_into().unwrap();
+                let reg: Register = combine!(n:rn,3,u8).try_into()?;
                 operation::CmpRegister::builder()
                     .set_rn(reg)
                     .set_rm(el.rm)
@@ -110,7 +111,7 @@ impl ToOperation for A5_4 {
             }
             Self::Mov(el) => {
                 let (d, rd) = (el.d, el.rd);
-                let reg: Register = combine!(d:rd,3,u8).try_into().unwrap();
+                let reg: Register = combine!(d:rd,3,u8).try_into()?;
                 operation::MovRegister::builder()
                     .set_s(Some(false))
                     .set_rd(reg)
@@ -120,7 +121,7 @@ impl ToOperation for A5_4 {
             }
             Self::Bx(el) => operation::Bx::builder().set_rm(el.rm).complete().into(),
             Self::Blx(el) => operation::Blx::builder().set_rm(el.rm).complete().into(),
-        }
+        })
     }
 }
 #[cfg(test)]
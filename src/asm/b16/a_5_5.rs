@@ -1,7 +1,16 @@
 use paste::paste;
 
 use super::Mask;
-use crate::{arch::Register, instruction, operation, Parse, ParseError, Stream, ToOperation};
+use crate::{
+    arch::Register,
+    instruction,
+    operation,
+    Parse,
+    ParseError,
+    ParseErrorKind,
+    Stream,
+    ToOperation,
+};
 
 instruction!(
     size u16;  A5_5 contains
@@ -99,12 +108,11 @@ impl Parse for A5_5 {
     {
         let word = match iter.peek::<1>() as Option<u16> {
             Some(u) => Ok(u),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let op1 = word.mask::<12, 15>();
         let op2 = word.mask::<9, 11>();
-        assert!(op2 <= 7);
 
         if op1 == 0b0101 {
             return match op2 {
@@ -116,7 +124,7 @@ impl Parse for A5_5 {
                 5 => Ok(Self::Ldrh(Ldrh::parse(iter)?)),
                 6 => Ok(Self::Ldrb(Ldrb::parse(iter)?)),
                 7 => Ok(Self::Ldrsh(Ldrsh::parse(iter)?)),
-                _ => unreachable!("Ureachable due to previous asserts"),
+                _ => Err(ParseErrorKind::InternalError("Masking is broken op2 > 7").into()),
             };
         }
         if op1 == 0b0110 {
@@ -147,13 +155,13 @@ impl Parse for A5_5 {
                 Self::LdrRI(LdrRI::parse(iter)?)
             });
         }
-        Err(ParseError::Invalid16Bit("A5_5"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_5 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Str(el) => operation::StrRegister::builder()
                 .set_rt(el.rt)
                 .set_rn(el.rn)
@@ -271,7 +279,7 @@ impl ToOperation for A5_5 {
                 .set_index(Some(true))
                 .set_add(true)
                 .set_rt(el.rt)
-                .set_rn(13_u8.try_into().unwrap())
+                .set_rn(Register::SP)
                 .set_imm((el.imm8 as u32) << 2)
                 .complete()
                 .into(),
@@ -280,11 +288,11 @@ impl ToOperation for A5_5 {
                 .set_add(true)
                 .set_index(true)
                 .set_rt(el.rt)
-                .set_rn(13u8.try_into().unwrap())
+                .set_rn(Register::SP)
                 .set_imm((el.imm8 as u32) << 2)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 #[cfg(test)]
@@ -3,11 +3,13 @@ use paste::paste;
 use super::{a_5_7::A5_7, Mask};
 use crate::{
     arch::{Register, RegisterList},
+    asm::Table,
     combine,
     instruction,
     operation,
     Parse,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -27,6 +29,7 @@ instruction!(
     Cbz  : {
         rn as u8 : Register : 0 ->  2   try_into,
         imm5 as u8 : u8     : 3 ->  7,
+        i    as u8 : u8     : 9 ->  9,
         op   as u8 : u8     : 11 -> 11
     },
     Sxth : {
@@ -48,6 +51,7 @@ instruction!(
     Cbnz  : {
         rn as u8 : Register : 0 ->  2   try_into,
         imm5 as u8 : u8     : 3 ->  7,
+        i    as u8 : u8     : 9 ->  9,
         op   as u8 : u8     : 11 -> 11
     },
     Push : {
@@ -91,7 +95,7 @@ impl Parse for A5_6 {
     {
         let opcode = match iter.peek::<1>() as Option<u16> {
             Some(u) => Ok(u.mask::<5, 11>()),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         if opcode == 0b0110011 {
             p!(Cps from iter);
@@ -145,16 +149,16 @@ impl Parse for A5_6 {
             p!(Bkpt from iter);
         }
         if opcode & 0b1111000 == 0b1111000 {
-            return Ok(Self::SubtableA5_7(A5_7::parse(iter)?));
+            return Ok(Self::SubtableA5_7(A5_7::parse_table(iter)?));
         }
 
-        Err(ParseError::Invalid16Bit("A5_6"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_6 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Cps(el) => operation::Cps::builder()
                 .set_enable(el.im == 0)
                 .set_disable(el.im == 1)
@@ -174,12 +178,15 @@ impl ToOperation for A5_6 {
                 .set_imm((el.imm7 as u32) << 2)
                 .complete()
                 .into(),
-            Self::Cbz(el) => operation::Cbz::builder()
-                .set_non(Some(el.op == 1))
-                .set_rn(el.rn)
-                .set_imm((el.imm5 as u32) << 1)
-                .complete()
-                .into(),
+            Self::Cbz(el) => {
+                let (i, imm5) = (el.i, el.imm5);
+                operation::Cbz::builder()
+                    .set_non(Some(el.op == 1))
+                    .set_rn(el.rn)
+                    .set_imm(combine!(i:imm5,5:0,1,u32))
+                    .complete()
+                    .into()
+            }
             Self::Sxth(el) => operation::Sxth::builder()
                 .set_rd(el.rd)
                 .set_rm(el.rm)
@@ -229,16 +236,19 @@ impl ToOperation for A5_6 {
                 .set_rm(el.rm)
                 .complete()
                 .into(),
-            Self::Cbnz(el) => operation::Cbz::builder()
-                .set_non(Some(el.op == 1))
-                .set_rn(el.rn)
-                .set_imm((el.imm5 as u32) << 1)
-                .complete()
-                .into(),
+            Self::Cbnz(el) => {
+                let (i, imm5) = (el.i, el.imm5);
+                operation::Cbz::builder()
+                    .set_non(Some(el.op == 1))
+                    .set_rn(el.rn)
+                    .set_imm(combine!(i:imm5,5:0,1,u32))
+                    .complete()
+                    .into()
+            }
             Self::Pop(el) => {
                 let registers = el.register_list;
                 let p = el.p;
-                let registers = combine!(p:0,7:registers,8,u16).try_into().unwrap();
+                let registers = combine!(p:0,7:registers,8,u16).try_into()?;
                 operation::Pop::builder()
                     .set_registers(registers)
                     .complete()
@@ -248,8 +258,8 @@ impl ToOperation for A5_6 {
                 .set_imm(el.imm8 as u32)
                 .complete()
                 .into(),
-            Self::SubtableA5_7(el) => el.encoding_specific_operations(),
-        }
+            Self::SubtableA5_7(el) => el.encoding_specific_operations()?,
+        })
     }
 }
 
@@ -429,4 +439,20 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_cbz_i() {
+        // cbz r0, #64, the offset is i:imm5:'0'
+        let bin = [0b1011_0011u8, 0b0000_0000u8];
+        let mut stream = PeekableBuffer::from(bin.into_iter().rev());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Cbz::builder()
+            .set_non(Some(false))
+            .set_rn(Register::R0)
+            .set_imm(64)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
 }
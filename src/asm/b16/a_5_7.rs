@@ -3,7 +3,15 @@
 use paste::paste;
 
 use super::Mask;
-use crate::{arch::Condition, instruction, operation, Parse, ParseError, ToOperation};
+use crate::{
+    arch::Condition,
+    instruction,
+    operation,
+    Parse,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
 
 instruction!(
     size u16; A5_7 contains
@@ -38,14 +46,14 @@ impl Parse for A5_7 {
             2 => Self::Wfe(Wfe::parse(iter)?),
             3 => Self::Wfi(Wfi::parse(iter)?),
             4 => Self::Sev(Sev::parse(iter)?),
-            _ => return Err(ParseError::Invalid16Bit("A5_7")),
+            _ => return Err(ParseErrorKind::Undefined.into()),
         })
     }
 }
 
 impl ToOperation for A5_7 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::It(it) => operation::It::builder()
                 .set_conds((it.firstcond, it.mask).into())
                 .complete()
@@ -55,7 +63,7 @@ impl ToOperation for A5_7 {
             Self::Wfe(_) => operation::Wfe::builder().complete().into(),
             Self::Wfi(_) => operation::Wfi::builder().complete().into(),
             Self::Sev(_) => operation::Sev::builder().complete().into(),
-        }
+        })
     }
 }
 
@@ -73,7 +81,7 @@ mod test {
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
         let condition: Condition = Condition::try_from(0b0011u8).unwrap();
         let target: Operation = operation::It::builder()
-            .set_conds(ITCondition::try_from((condition, 0b0011)).unwrap())
+            .set_conds(ITCondition::from((condition, 0b0011)))
             .complete()
             .into();
         assert_eq!(instr, target)
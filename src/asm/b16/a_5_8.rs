@@ -7,6 +7,7 @@ use crate::{
     operation,
     Parse,
     ParseError,
+    ParseErrorKind,
     Stream,
     ToOperation,
 };
@@ -19,6 +20,9 @@ instruction!(
     },
     Svc : {
         imm8 as u8 :u8 : 0->7
+    },
+    Udf : {
+        imm8 as u8 :u8 : 0->7
     }
 );
 
@@ -31,26 +35,24 @@ impl Parse for A5_8 {
     {
         let opcode = match iter.peek::<1>() as Option<u8> {
             Some(u) => Ok(u & 0b1111),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         if opcode == 0b1111 {
             return Ok(Self::Svc(Svc::parse(iter)?));
         }
         if opcode == 0b1110 {
-            return Err(ParseError::Unpredictable);
+            return Ok(Self::Udf(Udf::parse(iter)?));
         }
         Ok(Self::B(B::parse(iter)?))
     }
 }
 impl ToOperation for A5_8 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::B(el) => {
                 let intermediate: u16 = el.imm8.into();
 
-                let value: u32 = Imm9::try_from(intermediate << 1)
-                    .expect("Imm9 is broken")
-                    .sign_extend();
+                let value: u32 = Imm9::try_from(intermediate << 1)?.sign_extend();
                 operation::B::builder()
                     .set_condition(el.cond)
                     .set_imm(value)
@@ -58,7 +60,11 @@ impl ToOperation for A5_8 {
                     .into()
             }
             Self::Svc(el) => operation::Svc::builder().set_imm(el.imm8).complete().into(),
-        }
+            Self::Udf(el) => operation::Udf::builder()
+                .set_imm(el.imm8 as u32)
+                .complete()
+                .into(),
+        })
     }
 }
 
@@ -81,4 +87,16 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_udf() {
+        // udf #7
+        let bin = [0x07, 0xde];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let (size, instr) = Operation::parse(&mut stream).expect("Parser broken");
+        let target: Operation = operation::Udf::builder().set_imm(7).complete().into();
+        assert_eq!(size, 16);
+        assert_eq!(instr, target);
+        assert_eq!(instr.encode().unwrap().to_le_bytes(), bin);
+    }
 }
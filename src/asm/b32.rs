@@ -18,17 +18,27 @@ pub mod a5_27;
 pub mod a5_28;
 pub mod a5_29;
 pub mod a5_30;
+pub mod a6_4;
+pub mod a6_5;
+pub mod a6_6;
+pub mod a6_7;
 
 use crate::{
-    asm::{b32::a5_30::A5_30, Mask},
+    asm::{b32::a5_30::A5_30, Mask, Table},
+    profile::Profile,
     Parse,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
 /// A 32-bit wide instruction
 pub enum B32 {}
 
+impl Table for B32 {
+    const NAME: &'static str = "B32";
+}
+
 impl Parse for B32 {
     type Target = (usize, crate::operation::Operation);
 
@@ -36,15 +46,22 @@ impl Parse for B32 {
     where
         Self: Sized,
     {
-        let ret = match Self::parse_internal(iter) {
-            Ok(e) => e,
-            Err(e) => {
-                return Err(e);
-            }
-        };
+        Self::parse_for(iter, Profile::default())
+    }
+}
+
+impl B32 {
+    /// Parses the instruction, rejecting it without consuming it if it is not
+    /// defined in the `profile`.
+    pub(crate) fn parse_for<T: crate::Stream>(
+        iter: &mut T,
+        profile: Profile,
+    ) -> Result<(usize, crate::operation::Operation), ParseError> {
+        let ret = Self::parse_internal(iter)?;
+        profile.check(32, &ret)?;
         let _: u32 = match iter.consume::<1>() {
             Some(val) => val[0],
-            None => return Err(ParseError::IncompleteProgram),
+            None => return Err(ParseErrorKind::IncompleteProgram.into()),
         };
 
         Ok((32, ret))
@@ -58,73 +75,73 @@ impl B32 {
     ) -> Result<crate::operation::Operation, crate::ParseError> {
         let word: u32 = match iter.peek::<1>() {
             Some(value) => value,
-            None => return Err(ParseError::IncompleteProgram),
+            None => return Err(ParseErrorKind::IncompleteProgram.into()),
         };
         let op1 = word.mask::<{ 16 + 11 }, { 16 + 12 }>();
         let op2 = word.mask::<{ 16 + 4 }, { 16 + 10 }>();
         let op = word.mask::<15, 15>();
 
         if op1 > 3 {
-            return Err(ParseError::InternalError("Masking is broken op1 > 3"));
+            return Err(ParseErrorKind::InternalError("Masking is broken op1 > 3").into());
         }
         if op > 1 {
-            return Err(ParseError::InternalError("Masking is broken op > 1"));
+            return Err(ParseErrorKind::InternalError("Masking is broken op > 1").into());
         }
 
         if op1 == 1 {
             if ((op2 >> 2) & 0b11001) == 0b00000 {
-                return Ok(a5_16::A5_16::parse(iter)?.encoding_specific_operations());
+                return a5_16::A5_16::parse_table(iter)?.encoding_specific_operations();
             }
             if ((op2 >> 2) & 0b11001) == 0b00001 {
-                return Ok(a5_17::A5_17::parse(iter)?.encoding_specific_operations());
+                return a5_17::A5_17::parse_table(iter)?.encoding_specific_operations();
             }
             if (op2 >> 5) == 1 {
-                return Ok(a5_22::A5_22::parse(iter)?.encoding_specific_operations());
+                return a5_22::A5_22::parse_table(iter)?.encoding_specific_operations();
             }
             if (op2 >> 6) == 1 {
-                return Ok(a5_30::A5_30::parse(iter)?.encoding_specific_operations());
+                return a5_30::A5_30::parse_table(iter)?.encoding_specific_operations();
             }
-            return Err(ParseError::Invalid32Bit("Invalid op2"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 2 {
             if op == 0 {
                 if (op2 & 0b0100000) == 0 {
-                    return Ok(a5_10::A5_10::parse(iter)?.encoding_specific_operations());
+                    return a5_10::A5_10::parse_table(iter)?.encoding_specific_operations();
                 }
-                return Ok(a5_12::A5_12::parse(iter)?.encoding_specific_operations());
+                return a5_12::A5_12::parse_table(iter)?.encoding_specific_operations();
             }
-            return Ok(a5_13::A5_13::parse(iter)?.encoding_specific_operations());
+            return a5_13::A5_13::parse_table(iter)?.encoding_specific_operations();
         }
 
         if (op2 & 0b1110001) == 0b0000000 {
-            return Ok(a5_21::A5_21::parse(iter)?.encoding_specific_operations());
+            return a5_21::A5_21::parse_table(iter)?.encoding_specific_operations();
         }
 
         match op2 & 0b1100111 {
-            0b0000001 => return Ok(a5_20::A5_20::parse(iter)?.encoding_specific_operations()),
-            0b0000011 => return Ok(a5_19::A5_19::parse(iter)?.encoding_specific_operations()),
-            0b0000101 => return Ok(a5_18::A5_18::parse(iter)?.encoding_specific_operations()),
-            0b0000111 => return Err(ParseError::Undefined),
+            0b0000001 => return a5_20::A5_20::parse_table(iter)?.encoding_specific_operations(),
+            0b0000011 => return a5_19::A5_19::parse_table(iter)?.encoding_specific_operations(),
+            0b0000101 => return a5_18::A5_18::parse_table(iter)?.encoding_specific_operations(),
+            0b0000111 => return Err(ParseErrorKind::Undefined.into()),
             _ => {}
         }
 
         if op2 >> 4 == 2 {
-            return Ok(a5_24::A5_24::parse(iter)?.encoding_specific_operations());
+            return a5_24::A5_24::parse_table(iter)?.encoding_specific_operations();
         }
 
         if op2 >> 3 == 0b0110 {
-            return Ok(a5_28::A5_28::parse(iter)?.encoding_specific_operations());
+            return a5_28::A5_28::parse_table(iter)?.encoding_specific_operations();
         }
 
         if op2 >> 3 == 0b0111 {
-            return Ok(a5_29::A5_29::parse(iter)?.encoding_specific_operations());
+            return a5_29::A5_29::parse_table(iter)?.encoding_specific_operations();
         }
 
         if op2 >> 6 == 1 {
             // Co processor things
-            return Ok(A5_30::parse(iter)?.encoding_specific_operations());
+            return A5_30::parse_table(iter)?.encoding_specific_operations();
         }
 
-        Err(ParseError::Invalid32Bit(""))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -139,7 +140,7 @@ macro_rules! fields {
     ) => {
         let word : $width = match $iter.peek::<1>(){
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram)
+            None => Err(ParseErrorKind::IncompleteProgram)
         }?;
         $(
             let $id : $type = (word.mask::<$start,$end>())$(.$map()?)?;
@@ -202,7 +203,7 @@ impl Parse for A5_10 {
             0b1010 => Ok(Self::Adc(Adc::parse(iter)?)),
             0b1011 => Ok(Self::Sbc(Sbc::parse(iter)?)),
             0b1110 => Ok(Self::Rsb(Rsb::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_10")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
@@ -216,19 +217,16 @@ macro_rules! combine_wrapper {
         {
             let $first_id = $el.$first_id;
             let ($($id),*) = ($($el.$id,)*);
-            match combine!($first_id:$($id,$size):*,$ret_ty).try_into() {
-                Ok(w) => w,
-                _ => unreachable!("This should never happen"),
-            }
+            combine!($first_id:$($id,$size):*,$ret_ty).try_into()?
         }
 
     };
 }
 
 impl ToOperation for A5_10 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
         use A5_10::*;
-        match self {
+        Ok(match self {
             And(el) => {
                 let imm: Imm12 = combine_wrapper!(el : {i:imm3,3:imm8,8,u32});
                 let (imm, carry) = imm.expand_imm_c();
@@ -242,8 +240,7 @@ impl ToOperation for A5_10 {
                     .into()
             }
             Tst(el) => {
-                let imm = combine_wrapper!(el : {i:imm3,3:imm8,8,u32});
-                let imm: Imm12 = Imm12::try_into(imm).unwrap();
+                let imm: Imm12 = combine_wrapper!(el : {i:imm3,3:imm8,8,u32});
                 let (imm, carry) = imm.expand_imm_c();
                 operation::TstImmediateBuilder::new()
                     .set_rn(el.rn)
@@ -405,7 +402,7 @@ impl ToOperation for A5_10 {
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -716,4 +713,23 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_mov_imm_replicated() {
+        // mov.w r0, #0x00ff00ff
+        let mut bin = vec![];
+        bin.extend([0b1111_0000u8, 0b0100_1111u8].into_iter().rev());
+        bin.extend([0b0001_0000u8, 0b1111_1111u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+        let target: Operation = operation::MovImmediate::builder()
+            .set_s(Some(false.into()))
+            .set_rd(Register::R0)
+            .set_imm(0x00FF_00FF)
+            .set_carry(None)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
 }
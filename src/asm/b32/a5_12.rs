@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -108,22 +109,20 @@ impl Parse for A5_12 {
         Self: Sized,
     {
         let _word: u32 = match iter.peek::<1>() {
-            Some(word) => word,
-            _ => {
-                panic!()
-            }
-        };
+            Some(word) => Ok(word),
+            None => Err(ParseErrorKind::IncompleteProgram),
+        }?;
         // NOTE! Only read half the word here to avoid adding to the mask
         let word: u16 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let rn = word.mask::<0, 3>();
         let op = word.mask::<4, 8>();
 
         let word: u16 = match iter.peek::<2>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let second_halfword_req = word.mask::<6, 7>() == 0 && word.mask::<12, 14>() == 0;
 
@@ -142,7 +141,7 @@ impl Parse for A5_12 {
             (0b11000, _, _) | (0b11010, _, false) => Ok(Self::Usat(Usat::parse(iter)?)),
             (0b11010, _, true) => Ok(Self::Usat16(Usat16::parse(iter)?)),
             (0b11100, _, _) => Ok(Self::Ubfx(Ubfx::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_12")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
@@ -156,17 +155,14 @@ macro_rules! combine_wrapper {
         {
             let $first_id = $el.$first_id;
             let ($($id),*) = ($($el.$id,)*);
-            match combine!($first_id:$($id,$size):*,$ret_ty).try_into() {
-                Ok(w) => w,
-                _ => unreachable!("This should never happen"),
-            }
+            combine!($first_id:$($id,$size):*,$ret_ty).try_into()?
         }
 
     };
 }
 impl ToOperation for A5_12 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Add(el) => {
                 let imm: Imm12 = combine_wrapper!(el : {i:imm3,3:imm8,8,u32});
                 operation::AddImmediateBuilder::new()
@@ -187,7 +183,8 @@ impl ToOperation for A5_12 {
                     .into()
             }
             Self::Mov(el) => {
-                let imm: u32 = combine_wrapper!(el : {imm4:i,1:imm3,3:imm8,8,u32});
+                let (imm4, i, imm3, imm8) = (el.imm4, el.i, el.imm3, el.imm8);
+                let imm: u32 = combine!(imm4:i,1:imm3,3:imm8,8,u32);
                 operation::MovImmediateBuilder::new()
                     .set_s(Some(false.into()))
                     .set_rd(el.rd)
@@ -208,7 +205,8 @@ impl ToOperation for A5_12 {
                     .into()
             }
             Self::Movt(el) => {
-                let imm: u16 = combine_wrapper!(el : {imm4:i,1:imm3,3:imm8,8,u16});
+                let (imm4, i, imm3, imm8) = (el.imm4, el.i, el.imm3, el.imm8);
+                let imm: u16 = combine!(imm4:i,1:imm3,3:imm8,8,u16);
                 operation::MovtBuilder::new()
                     .set_rd(el.rd)
                     .set_imm(imm)
@@ -219,7 +217,7 @@ impl ToOperation for A5_12 {
                 let (imm3, imm2, sh) = (el.imm3, el.imm2, el.sh << 1);
                 let shift_n: u8 = combine!(imm3:imm2,2,u8);
                 // TODO! Remove this unwrap
-                let shift: Shift = sh.try_into().unwrap();
+                let shift: Shift = sh.try_into()?;
                 let shift = ImmShift::from((shift, shift_n));
                 operation::SsatBuilder::new()
                     .set_rd(el.rd)
@@ -254,7 +252,7 @@ impl ToOperation for A5_12 {
                 let (imm3, imm2, sh) = (el.imm3, el.imm2, el.sh << 1);
                 let shift_n: u8 = combine!(imm3:imm2,2,u8);
                 // TODO! Remove this unwrap
-                let shift: Shift = sh.try_into().unwrap();
+                let shift: Shift = sh.try_into()?;
                 let shift = ImmShift::from((shift, shift_n));
                 operation::UsatBuilder::new()
                     .set_rd(el.rd)
@@ -304,7 +302,7 @@ impl ToOperation for A5_12 {
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -377,7 +375,7 @@ mod test {
         let mut stream = PeekableBuffer::from(bin.into_iter());
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
         let target: Operation = operation::MovImmediate::builder()
-            .set_imm(0b0100_1_001_10001001u32)
+            .set_imm(0b0100_1001_1000_1001_u32)
             .set_rd(Register::R1)
             .set_s(Some(false.into()))
             .set_carry(None)
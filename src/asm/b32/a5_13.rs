@@ -5,11 +5,13 @@ use crate::{
         b32::{a5_14::A5_14, a5_15::A5_15},
         LocalTryInto,
         Mask,
+        Table,
     },
     combine,
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -25,20 +27,20 @@ instruction!(
         s       as u8   : bool      : 26 -> 26 local_try_into
     },
     Msr : {
-        sysm    as u8   : u8        : 0 -> 7,
-        mask    as u8   : Imm2      : 10 -> 11 try_into,
+        sysm    as u8   : SpecialRegister : 0 -> 7 try_into,
+        mask    as u8   : u8        : 10 -> 11,
         rn      as u8   : Register  : 16 -> 19 try_into
     },
     -> A5_14,
     -> A5_15,
     Mrs : {
-        sysm    as u8   : u8        : 0 -> 7,
+        sysm    as u8   : SpecialRegister : 0 -> 7 try_into,
         rd      as u8   : Register  : 8 -> 11 try_into
     },
     // Permanently undefined
     Udf : {
         imm12   as u16  : u16       : 0 -> 11,
-        imm4    as u16  : u16       : 0 -> 3
+        imm4    as u16  : u16       : 16 -> 19
     },
     BT4 : {
         imm11           : u32       : 0 -> 10,
@@ -65,7 +67,7 @@ impl Parse for A5_13 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op1 = word.mask::<12, 14>();
         let op = word.mask::<20, 26>();
@@ -75,16 +77,21 @@ impl Parse for A5_13 {
                 return Ok(Self::BT3(BT3::parse(iter)?));
             }
             if op >> 1 == 0b11100 {
-                return Ok(Self::Msr(Msr::parse(iter)?));
+                let msr = Msr::parse(iter).map_err(reserved)?;
+                // Only the APSR has fields other than nzcvq.
+                if msr.mask == 0 || (msr.mask != 0b10 && !msr.sysm.includes_apsr()) {
+                    return Err(ParseErrorKind::Unpredictable.into());
+                }
+                return Ok(Self::Msr(msr));
             }
             if op >> 1 == 0b011111 {
-                return Ok(Self::Mrs(Mrs::parse(iter)?));
+                return Ok(Self::Mrs(Mrs::parse(iter).map_err(reserved)?));
             };
             if op == 0b0111010 {
-                return Ok(Self::SubtableA5_14(A5_14::parse(iter)?));
+                return Ok(Self::SubtableA5_14(A5_14::parse_table(iter)?));
             }
             if op == 0b0111011 {
-                return Ok(Self::SubtableA5_15(A5_15::parse(iter)?));
+                return Ok(Self::SubtableA5_15(A5_15::parse_table(iter)?));
             }
         }
         if op1 == 0b10 {
@@ -97,18 +104,24 @@ impl Parse for A5_13 {
         if op1 & 0b101 == 0b101 {
             return Ok(Self::Bl(Bl::parse(iter)?));
         }
-        Err(ParseError::Invalid32Bit("A5_13"))
+        Err(ParseErrorKind::Undefined.into())
+    }
+}
+
+/// Reserved `SYSm` values are UNPREDICTABLE rather than undefined.
+fn reserved(error: ParseError) -> ParseError {
+    match error.kind {
+        ParseErrorKind::ArchError(_) => ParseErrorKind::Unpredictable.into(),
+        _ => error,
     }
 }
 
 impl ToOperation for A5_13 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::BT3(el) => {
                 let (s, j2, j1, imm6, imm11) = (el.s, el.j2, el.j1, el.imm6, el.imm11);
-                let mut imm: Imm21 = combine!(s:j2,1:j1,1:imm6,6:imm11,11:0,1,u32)
-                    .try_into()
-                    .unwrap();
+                let mut imm: Imm21 = combine!(s:j2,1:j1,1:imm6,6:imm11,11:0,1,u32).try_into()?;
 
                 operation::BBuilder::new()
                     .set_condition(el.cond)
@@ -120,9 +133,7 @@ impl ToOperation for A5_13 {
                 let (s, j2, j1, imm10, imm11) = (el.s, el.j2, el.j1, el.imm10, el.imm11);
                 let i1 = !(j1 ^ s);
                 let i2 = !(j2 ^ s);
-                let mut imm: Imm25 = combine!(s:i1,1:i2,1:imm10,10:imm11,11:0,1,u32)
-                    .try_into()
-                    .unwrap();
+                let mut imm: Imm25 = combine!(s:i1,1:i2,1:imm10,10:imm11,11:0,1,u32).try_into()?;
 
                 operation::BBuilder::new()
                     .set_condition(Condition::None)
@@ -132,7 +143,7 @@ impl ToOperation for A5_13 {
             }
             Self::Msr(el) => operation::Msr::builder()
                 .set_rn(el.rn)
-                .set_mask(el.mask)
+                .set_mask(el.mask.into())
                 .set_sysm(el.sysm)
                 .complete()
                 .into(),
@@ -146,27 +157,21 @@ impl ToOperation for A5_13 {
                 let (i1, i2) = (!(j1 ^ s), !(j2 ^ s));
                 let num = combine!(s:i1,1:i2,1:imm10,10:imm11,11:0,1,u32);
 
-                let mut imm: Imm25 = num
-                    .try_into()
-                    .map_err(|e| {
-                        println!("tried to fit {num} into Imm25");
-                        e
-                    })
-                    .unwrap();
+                let mut imm: Imm25 = num.try_into()?;
 
                 operation::BlBuilder::new()
                     .set_imm(imm.sign_extend())
                     .complete()
                     .into()
             }
-            Self::SubtableA5_14(table) => table.encoding_specific_operations(),
-            Self::SubtableA5_15(table) => table.encoding_specific_operations(),
+            Self::SubtableA5_14(table) => table.encoding_specific_operations()?,
+            Self::SubtableA5_15(table) => table.encoding_specific_operations()?,
             Self::Udf(udf) => {
                 let (imm4, imm12) = (udf.imm4, udf.imm12);
                 let imm = combine!(imm4:imm12,12,u32);
                 operation::UdfBuilder::new().set_imm(imm).complete().into()
             }
-        }
+        })
     }
 }
 
@@ -229,8 +234,11 @@ mod test {
 
         let target: Operation = operation::Msr::builder()
             .set_rn(Register::R2)
-            .set_mask(Imm2::try_from(0b10u8).expect("Malformed test invalid mask"))
-            .set_sysm(0b00000011u8)
+            .set_mask(ApsrMask {
+                nzcvq: true,
+                g: false,
+            })
+            .set_sysm(SpecialRegister::Xpsr)
             .complete()
             .into();
         assert_eq!(instr, target)
@@ -240,19 +248,39 @@ mod test {
     fn test_parse_mrs() {
         let mut bin = vec![];
         bin.extend([0b11110011u8, 0b11101111u8].into_iter().rev());
-        bin.extend([0b10000010u8, 0b10000001u8].into_iter().rev());
+        bin.extend([0b10000010u8, 0b10001001u8].into_iter().rev());
 
         let mut stream = PeekableBuffer::from(bin.into_iter());
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
 
         let target: Operation = operation::Mrs::builder()
             .set_rd(Register::R2)
-            .set_sysm(0b10000001u8)
+            .set_sysm(SpecialRegister::PspNs)
             .complete()
             .into();
         assert_eq!(instr, target)
     }
 
+    #[test]
+    fn test_parse_reserved_sysm() {
+        let mut bin = vec![];
+        bin.extend([0b11110011u8, 0b11101111u8].into_iter().rev());
+        bin.extend([0b10000010u8, 0b10000001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let err = Operation::parse(&mut stream).expect_err("Parsed reserved SYSm");
+        assert_eq!(err.kind, ParseErrorKind::Unpredictable);
+
+        // msr control_g, r2 does not exist.
+        let mut bin = vec![];
+        bin.extend([0b11110011u8, 0b10000010u8].into_iter().rev());
+        bin.extend([0b10000100u8, 0b00010100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let err = Operation::parse(&mut stream).expect_err("Parsed invalid mask");
+        assert_eq!(err.kind, ParseErrorKind::Unpredictable);
+    }
+
     #[test]
     fn test_parse_bl() {
         let mut bin = vec![];
@@ -269,4 +297,18 @@ mod test {
         let target: Operation = operation::Bl::builder().set_imm(imm).complete().into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_udf() {
+        // udf.w #0x1234, the immediate is imm4:imm12
+        let mut bin = vec![];
+        bin.extend([0b1111_0111u8, 0b1111_0001u8].into_iter().rev());
+        bin.extend([0b1010_0010u8, 0b0011_0100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Udf::builder().set_imm(0x1234).complete().into();
+        assert_eq!(instr, target)
+    }
 }
@@ -1,7 +1,13 @@
 //! Defines marker instructions
 //!
 //! These have one or no fields but might have side-effects
-use crate::{asm::Mask, prelude::*, ParseError, ToOperation};
+use crate::{
+    asm::{Mask, Table},
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
 
 /// Defines some maker instructions
 #[derive(Debug)]
@@ -17,7 +23,11 @@ pub enum A5_14 {
     /// Send event
     Sev,
     /// Debug
-    Dbg(u8),
+    Dbg(DebugHint),
+}
+
+impl Table for A5_14 {
+    const NAME: &'static str = "A5_14";
 }
 
 impl Parse for A5_14 {
@@ -29,13 +39,13 @@ impl Parse for A5_14 {
     {
         let word: u16 = match iter.peek::<2>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op1 = word.mask::<8, 10>();
         let op2 = word.mask::<0, 8>();
 
         if op1 != 0 {
-            return Err(ParseError::Undefined);
+            return Err(ParseErrorKind::Undefined.into());
         }
         match op2 {
             0 => return Ok(Self::Nop),
@@ -46,16 +56,16 @@ impl Parse for A5_14 {
             _ => {}
         }
         if op2 >> 4 == 0b1111 {
-            let option: u8 = (op2 & 0b1111) as u8;
+            let option = DebugHint::try_from((op2 & 0b1111) as u8)?;
             return Ok(Self::Dbg(option));
         }
-        Err(ParseError::Invalid32Bit("A5_14"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_14 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Nop => operation::NopBuilder::new().complete().into(),
             Self::Yield => operation::YieldBuilder::new().complete().into(),
             Self::Wfe => operation::WfeBuilder::new().complete().into(),
@@ -65,7 +75,7 @@ impl ToOperation for A5_14 {
                 .set_option(el)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -149,7 +159,7 @@ mod test {
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
 
         let target: Operation = operation::Dbg::builder()
-            .set_option(0b0010)
+            .set_option(DebugHint::try_from(0b0010).unwrap())
             .complete()
             .into();
         assert_eq!(instr, target)
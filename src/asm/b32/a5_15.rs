@@ -1,4 +1,10 @@
-use crate::{asm::Mask, prelude::*, ParseError, ToOperation};
+use crate::{
+    asm::{Mask, Table},
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
 
 /// Defines some maker instructions
 #[derive(Debug)]
@@ -6,11 +12,18 @@ pub enum A5_15 {
     /// Clear exclusive
     Clrex,
     /// Data synchronization barrier
-    Dsb(u8),
+    Dsb(BarrierOption),
     /// Data memory barrier
-    Dmb(u8),
+    Dmb(BarrierOption),
     /// Instruction synchronization barrier
-    Isb(u8),
+    ///
+    /// Armv7-M only defines `sy`, the other options are decoded as they are
+    /// since the processor executes them as `sy`.
+    Isb(BarrierOption),
+}
+
+impl Table for A5_15 {
+    const NAME: &'static str = "A5_15";
 }
 
 impl Parse for A5_15 {
@@ -22,23 +35,23 @@ impl Parse for A5_15 {
     {
         let word: u16 = match iter.peek::<2>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op = word.mask::<4, 7>();
-        let inner_op = word.mask::<0, 3>() as u8;
+        let option = BarrierOption::from(word.mask::<0, 3>() as u8);
         match op {
             0b10 => Ok(Self::Clrex),
-            0b100 => Ok(Self::Dsb(inner_op)),
-            0b101 => Ok(Self::Dmb(inner_op)),
-            0b110 => Ok(Self::Isb(inner_op)),
-            _ => Err(ParseError::Invalid32Bit("A5_14")),
+            0b100 => Ok(Self::Dsb(option)),
+            0b101 => Ok(Self::Dmb(option)),
+            0b110 => Ok(Self::Isb(option)),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 
 impl ToOperation for A5_15 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Clrex => operation::ClrexBuilder::new().complete().into(),
             Self::Dsb(opt) => operation::DsbBuilder::new()
                 .set_option(Some(opt))
@@ -49,10 +62,10 @@ impl ToOperation for A5_15 {
                 .complete()
                 .into(),
             Self::Isb(opt) => operation::IsbBuilder::new()
-                .set_option(Some(opt.try_into().unwrap()))
+                .set_option(Some(opt))
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -84,7 +97,7 @@ mod test {
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
 
         let target: Operation = operation::Dsb::builder()
-            .set_option(Some(0b0010))
+            .set_option(Some(BarrierOption::Oshst))
             .complete()
             .into();
         assert_eq!(instr, target)
@@ -100,7 +113,7 @@ mod test {
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
 
         let target: Operation = operation::Dmb::builder()
-            .set_option(Some(0b0010))
+            .set_option(Some(BarrierOption::Oshst))
             .complete()
             .into();
         assert_eq!(instr, target)
@@ -116,9 +129,23 @@ mod test {
         let instr = Operation::parse(&mut stream).expect("Parser broken").1;
 
         let target: Operation = operation::Isb::builder()
-            .set_option(Some(
-                Imm4::try_from(0b0010u8).expect("Malformed test, imm too large"),
-            ))
+            .set_option(Some(BarrierOption::Oshst))
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_isb_reserved() {
+        let mut bin = vec![];
+        bin.extend([0b11110011u8, 0b10111111u8].into_iter().rev());
+        bin.extend([0b10001111u8, 0b01101100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Isb::builder()
+            .set_option(Some(BarrierOption::Reserved(0b1100)))
             .complete()
             .into();
         assert_eq!(instr, target)
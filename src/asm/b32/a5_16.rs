@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -57,7 +58,7 @@ impl Parse for A5_16 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(val) => Ok(val),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op = word.mask::<23, 24>();
         let l = (word.mask::<20, 20>() as u8).local_try_into()?;
@@ -74,7 +75,7 @@ impl Parse for A5_16 {
             return Ok(Self::Ldm(Ldm::parse(iter)?));
         }
         if op != 2 {
-            return Err(ParseError::Invalid32Bit("A5_16"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if l {
             return Ok(Self::Ldmdb(Ldmdb::parse(iter)?));
@@ -87,15 +88,15 @@ impl Parse for A5_16 {
 }
 
 impl ToOperation for A5_16 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Stm(el) => {
                 let (m, registers) = (el.m, el.register_list);
                 let registers = combine!(m:0,1:registers,13,u16);
                 operation::Stm::builder()
                     .set_w(Some(el.w))
                     .set_rn(el.rn)
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
@@ -105,7 +106,7 @@ impl ToOperation for A5_16 {
                 operation::Ldm::builder()
                     .set_w(Some(el.w))
                     .set_rn(el.rn)
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
@@ -114,7 +115,7 @@ impl ToOperation for A5_16 {
                 let registers = combine!(p:m,1:0,1:registers,13,u16);
 
                 operation::Pop::builder()
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
@@ -124,7 +125,7 @@ impl ToOperation for A5_16 {
                 operation::Stmdb::builder()
                     .set_w(Some(el.w))
                     .set_rn(el.rn)
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
@@ -132,7 +133,7 @@ impl ToOperation for A5_16 {
                 let (m, registers) = (el.m, el.register_list);
                 let registers = combine!(m:0,1:registers,13,u16);
                 operation::Push::builder()
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
@@ -143,11 +144,11 @@ impl ToOperation for A5_16 {
                 operation::Ldmdb::builder()
                     .set_w(Some(el.w))
                     .set_rn(el.rn)
-                    .set_registers(registers.try_into().unwrap())
+                    .set_registers(registers.try_into()?)
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -5,6 +5,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -74,7 +75,7 @@ impl Parse for A5_17 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(val) => Ok(val),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op3 = word.mask::<4, 7>();
         let op2 = word.mask::<20, 21>();
@@ -94,7 +95,7 @@ impl Parse for A5_17 {
             return Ok(Self::Ldrd(Ldrd::parse(iter)?));
         }
         if op1 != 0b01 {
-            return Err(ParseError::Invalid32Bit("A5_17"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         match (op2, op3) {
             (0, 0b100) => Ok(Self::Strexb(Strexb::parse(iter)?)),
@@ -102,13 +103,13 @@ impl Parse for A5_17 {
             (1, 0) | (1, 1) => Ok(Self::Tbb(Tbb::parse(iter)?)),
             (1, 0b100) => Ok(Self::Ldrexb(Ldrexb::parse(iter)?)),
             (1, 0b101) => Ok(Self::Ldrexh(Ldrexh::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_17")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 impl ToOperation for A5_17 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Strex(el) => {
                 let imm = (el.imm as u32) << 2;
                 operation::Strex::builder()
@@ -176,7 +177,7 @@ impl ToOperation for A5_17 {
                 .set_rn(el.rn)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -58,7 +59,7 @@ impl Parse for A5_18 {
             if op1 >> 1 == 0 {
                 return Ok(Self::LdrLiteral(LdrLiteral::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_18"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 1 {
             return Ok(Self::LdrImmediateT3(LdrImmediateT3::parse(iter)?));
@@ -74,13 +75,13 @@ impl Parse for A5_18 {
                 return Ok(Self::LdrRegister(LdrRegister::parse(iter)?));
             }
         }
-        Err(ParseError::Invalid32Bit("A5_18"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_18 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::LdrImmediateT3(el) => operation::LdrImmediate::builder()
                 .set_w(Some(false))
                 .set_add(true)
@@ -123,7 +124,7 @@ impl ToOperation for A5_18 {
                 .set_imm(el.imm12.into())
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
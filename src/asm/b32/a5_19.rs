@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -80,7 +81,7 @@ impl Parse for A5_19 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op2 = word.mask::<6, 11>();
         let rt = word.mask::<12, 15>();
@@ -89,7 +90,8 @@ impl Parse for A5_19 {
         let op1 = word.mask::<23, 24>();
 
         if rt == 0b1111 {
-            return Err(ParseError::Invalid32Bit("A5_19 or strangly encoded NOP"));
+            // Memory hints and strangely encoded NOPs.
+            return Err(ParseErrorKind::Unimplemented.into());
         }
         if rn == 0b1111 {
             // Two options, ldrh or Ldrsh
@@ -108,7 +110,7 @@ impl Parse for A5_19 {
             if op2 >> 2 == 0b1110 {
                 return Ok(Self::Ldrht(Ldrht::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_19"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 1 {
             return Ok(Self::LdrhImmediateT2(LdrhImmediateT2::parse(iter)?));
@@ -123,19 +125,19 @@ impl Parse for A5_19 {
             if op2 >> 2 == 0b1110 {
                 return Ok(Self::Ldrsht(Ldrsht::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_19"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 3 {
             return Ok(Self::LdrshImmediateT1(LdrshImmediateT1::parse(iter)?));
         }
         // This should be unreachable
-        Err(ParseError::Invalid32Bit("A5_19"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_19 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::LdrhLiteral(el) => operation::LdrhLiteral::builder()
                 .set_rt(el.rt)
                 .set_add(Some(el.u))
@@ -216,7 +218,7 @@ impl ToOperation for A5_19 {
                 .set_imm(Some(el.imm8 as u32))
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -114,7 +115,7 @@ impl Parse for A5_20 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let op2 = word.mask::<6, 11>();
@@ -143,12 +144,12 @@ impl Parse for A5_20 {
                     return Ok(Self::PldImmediateT2(PldImmediateT2::parse(iter)?));
                 }
                 if (op2 >> 2) == 0b1110 {
-                    return Err(ParseError::Unpredictable);
+                    return Err(ParseErrorKind::Unpredictable.into());
                 }
                 if (op2 & 0b100100) == 0b100100 {
-                    return Err(ParseError::Unpredictable);
+                    return Err(ParseErrorKind::Unpredictable.into());
                 }
-                return Err(ParseError::Invalid32Bit("A5_20"));
+                return Err(ParseErrorKind::Undefined.into());
             }
             if op1 == 2 && op2 >> 2 == 0b1100 {
                 return Ok(Self::PliImmediateT2(PliImmediateT2::parse(iter)?));
@@ -156,7 +157,7 @@ impl Parse for A5_20 {
             if op1 == 2 && op2 == 0 {
                 return Ok(Self::PliRegister(PliRegister::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_20"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         // first half of table
         if rn == 0b1111 {
@@ -178,7 +179,7 @@ impl Parse for A5_20 {
             if op2 & 0b100100 == 0b100100 {
                 return Ok(Self::LdrbImmediateT3(LdrbImmediateT3::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_20"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 1 {
             return Ok(Self::LdrbImmediateT2(LdrbImmediateT2::parse(iter)?));
@@ -199,13 +200,13 @@ impl Parse for A5_20 {
         if (op2 & 0b100100) == 0b100100 {
             return Ok(Self::LdrsbImmediateT2(LdrsbImmediateT2::parse(iter)?));
         }
-        Err(ParseError::Invalid32Bit("A5_20"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_20 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::LdrbLiteral(el) => operation::LdrbLiteral::builder()
                 .set_add(Some(el.u))
                 .set_rt(el.rt)
@@ -327,7 +328,7 @@ impl ToOperation for A5_20 {
                 .into(),
             Self::PliImmediateT3(el) => operation::PliImmediate::builder()
                 .set_add(Some(el.u))
-                .set_rn(Some(Register::try_from(15_u8).unwrap()))
+                .set_rn(Some(Register::PC))
                 .set_imm(el.imm12.into())
                 .complete()
                 .into(),
@@ -340,7 +341,7 @@ impl ToOperation for A5_20 {
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -462,7 +463,7 @@ mod test {
 
         let target: Operation = operation::LdrsbLiteral::builder()
             .set_rt(Register::R3)
-            .set_imm(0b111_00010_1111)
+            .set_imm(0b1110_0010_1111)
             .set_add(true)
             .complete()
             .into();
@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -25,6 +26,11 @@ instruction!(
         rt      as u8       :   Register    : 12 -> 15 try_into,
         rn      as u8       :   Register    : 16 -> 19 try_into
     },
+    Strbt : {
+        imm8    as u8       :   u8          : 0 -> 7,
+        rt      as u8       :   Register    : 12 -> 15 try_into,
+        rn      as u8       :   Register    : 16 -> 19 try_into
+    },
     StrbReg : {
         rm      as u8       :   Register    : 0 -> 3 try_into,
         imm     as u8       :   u8          : 4 -> 5,
@@ -45,6 +51,11 @@ instruction!(
         rt      as u8       :   Register    : 12 -> 15 try_into,
         rn      as u8       :   Register    : 16 -> 19 try_into
     },
+    Strht : {
+        imm8    as u8       :   u8          : 0 -> 7,
+        rt      as u8       :   Register    : 12 -> 15 try_into,
+        rn      as u8       :   Register    : 16 -> 19 try_into
+    },
     StrhReg : {
         rm      as u8       :   Register    : 0 -> 3 try_into,
         imm     as u8       :   u8          : 4 -> 5,
@@ -65,6 +76,11 @@ instruction!(
         rt      as u8       :   Register    : 12 -> 15 try_into,
         rn      as u8       :   Register    : 16 -> 19 try_into
     },
+    Strt : {
+        imm8    as u8       :   u8          : 0 -> 7,
+        rt      as u8       :   Register    : 12 -> 15 try_into,
+        rn      as u8       :   Register    : 16 -> 19 try_into
+    },
     StrReg : {
         rm      as u8       :   Register    : 0 -> 3 try_into,
         imm     as u8       :   u8          : 4 -> 5,
@@ -82,29 +98,38 @@ impl Parse for A5_21 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
-        // Only concerned with first bit
-        let op2 = word.mask::<11, 11>();
+        let op2 = word.mask::<6, 11>();
         let op1 = word.mask::<21, 23>();
 
-        match (op1, op2) {
-            (0b100, _) => Ok(Self::StrbT2(StrbT2::parse(iter)?)),
-            (0b000, 1) => Ok(Self::StrbT3(StrbT3::parse(iter)?)),
-            (0b000, 0) => Ok(Self::StrbReg(StrbReg::parse(iter)?)),
-            (0b101, _) => Ok(Self::StrhIT2(StrhIT2::parse(iter)?)),
-            (0b001, 1) => Ok(Self::StrhIT3(StrhIT3::parse(iter)?)),
-            (0b001, 0) => Ok(Self::StrhReg(StrhReg::parse(iter)?)),
-            (0b110, _) => Ok(Self::StrIT3(StrIT3::parse(iter)?)),
-            (0b010, 1) => Ok(Self::StrIT4(StrIT4::parse(iter)?)),
-            (0b010, 0) => Ok(Self::StrReg(StrReg::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_21")),
+        match op1 {
+            0b100 => return Ok(Self::StrbT2(StrbT2::parse(iter)?)),
+            0b101 => return Ok(Self::StrhIT2(StrhIT2::parse(iter)?)),
+            0b110 => return Ok(Self::StrIT3(StrIT3::parse(iter)?)),
+            _ => {}
+        }
+        let immediate = op2 & 0b100100 == 0b100100 || op2 >> 2 == 0b1100;
+        let unprivileged = op2 >> 2 == 0b1110;
+        let register = op2 == 0;
+
+        match op1 {
+            0b000 if immediate => Ok(Self::StrbT3(StrbT3::parse(iter)?)),
+            0b000 if unprivileged => Ok(Self::Strbt(Strbt::parse(iter)?)),
+            0b000 if register => Ok(Self::StrbReg(StrbReg::parse(iter)?)),
+            0b001 if immediate => Ok(Self::StrhIT3(StrhIT3::parse(iter)?)),
+            0b001 if unprivileged => Ok(Self::Strht(Strht::parse(iter)?)),
+            0b001 if register => Ok(Self::StrhReg(StrhReg::parse(iter)?)),
+            0b010 if immediate => Ok(Self::StrIT4(StrIT4::parse(iter)?)),
+            0b010 if unprivileged => Ok(Self::Strt(Strt::parse(iter)?)),
+            0b010 if register => Ok(Self::StrReg(StrReg::parse(iter)?)),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 impl ToOperation for A5_21 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::StrbT2(el) => operation::StrbImmediate::builder()
                 .set_w(Some(false))
                 .set_index(Some(true))
@@ -123,6 +148,12 @@ impl ToOperation for A5_21 {
                 .set_imm(el.imm8 as u32)
                 .complete()
                 .into(),
+            Self::Strbt(el) => operation::Strbt::builder()
+                .set_rt(el.rt)
+                .set_rn(el.rn)
+                .set_imm(Some(el.imm8 as u32))
+                .complete()
+                .into(),
             Self::StrbReg(el) => {
                 let shift = Some(ImmShift::from((Shift::Lsl, el.imm)));
                 operation::StrbRegister::builder()
@@ -151,6 +182,12 @@ impl ToOperation for A5_21 {
                 .set_imm(Some(el.imm8 as u32))
                 .complete()
                 .into(),
+            Self::Strht(el) => operation::Strht::builder()
+                .set_rt(el.rt)
+                .set_rn(el.rn)
+                .set_imm(Some(el.imm8 as u32))
+                .complete()
+                .into(),
             Self::StrhReg(el) => {
                 let shift = Some(ImmShift::from((Shift::Lsl, el.imm)));
                 operation::StrhRegister::builder()
@@ -179,6 +216,12 @@ impl ToOperation for A5_21 {
                 .set_imm(el.imm8 as u32)
                 .complete()
                 .into(),
+            Self::Strt(el) => operation::Strt::builder()
+                .set_rt(el.rt)
+                .set_rn(el.rn)
+                .set_imm(Some(el.imm8 as u32))
+                .complete()
+                .into(),
             Self::StrReg(el) => {
                 let shift = Some(ImmShift::from((Shift::Lsl, el.imm)));
                 operation::StrRegister::builder()
@@ -189,7 +232,7 @@ impl ToOperation for A5_21 {
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -383,4 +426,23 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_strt() {
+        // strt r0, [r1, #4]
+        let mut bin = vec![];
+        bin.extend([0b1111_1000u8, 0b0100_0001u8].into_iter().rev());
+        bin.extend([0b0000_1110u8, 0b0000_0100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Strt::builder()
+            .set_rt(Register::R0)
+            .set_rn(Register::R1)
+            .set_imm(Some(4))
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
 }
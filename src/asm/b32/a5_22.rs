@@ -1,11 +1,12 @@
 use paste::paste;
 
 use crate::{
-    asm::{b32::a5_23::A5_23, LocalTryInto, Mask},
+    asm::{b32::a5_23::A5_23, LocalTryInto, Mask, Table},
     combine,
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -159,7 +160,7 @@ macro_rules! fields {
     ) => {
         let word : $width = match $iter.peek::<1>(){
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram)
+            None => Err(ParseErrorKind::IncompleteProgram)
         }?;
         $(
             let $id : $type = (word.mask::<$start,$end>())$(.$map() ?)?;
@@ -183,7 +184,7 @@ impl Parse for A5_22 {
         );
         if op == 0 {
             if rd == 0b1111 && !s {
-                return Err(ParseError::Unpredictable);
+                return Err(ParseErrorKind::Unpredictable.into());
             }
             if rd != 0b1111 {
                 return Ok(Self::And(And::parse(iter)?));
@@ -197,7 +198,7 @@ impl Parse for A5_22 {
         }
         if op == 2 {
             if rn == 0b1111 {
-                return Ok(Self::SubtableA5_23(A5_23::parse(iter)?));
+                return Ok(Self::SubtableA5_23(A5_23::parse_table(iter)?));
             }
             return Ok(Self::Orr(Orr::parse(iter)?));
         }
@@ -213,7 +214,7 @@ impl Parse for A5_22 {
             }
             return match s {
                 true => Ok(Self::Teq(Teq::parse(iter)?)),
-                false => Err(ParseError::Unpredictable),
+                false => Err(ParseErrorKind::Unpredictable.into()),
             };
         }
         if op == 6 {
@@ -224,7 +225,7 @@ impl Parse for A5_22 {
                 return Ok(Self::Add(Add::parse(iter)?));
             }
             if !s {
-                return Err(ParseError::Unpredictable);
+                return Err(ParseErrorKind::Unpredictable.into());
             }
             return Ok(Self::Cmn(Cmn::parse(iter)?));
         }
@@ -239,11 +240,11 @@ impl Parse for A5_22 {
                 return Ok(Self::Sub(Sub::parse(iter)?));
             }
             if !s {
-                return Err(ParseError::Unpredictable);
+                return Err(ParseErrorKind::Unpredictable.into());
             }
             return Ok(Self::Cmp(Cmp::parse(iter)?));
         }
-        Err(ParseError::Invalid32Bit("A5_22"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 macro_rules! shift {
@@ -257,8 +258,8 @@ macro_rules! shift {
     };
 }
 impl ToOperation for A5_22 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::And(el) => {
                 let (ty, imm3, imm2) = (el.ty, el.imm3, el.imm2);
                 let shift = Some(ImmShift::from((ty, combine!(imm3:imm2,2,u8))));
@@ -308,7 +309,7 @@ impl ToOperation for A5_22 {
                     .complete()
                     .into()
             }
-            Self::SubtableA5_23(el) => el.encoding_specific_operations(),
+            Self::SubtableA5_23(el) => el.encoding_specific_operations()?,
             Self::Orn(el) => operation::OrnRegister::builder()
                 .set_s(Some(el.s))
                 .set_rd(Some(el.rd))
@@ -340,7 +341,7 @@ impl ToOperation for A5_22 {
                 .into(),
             Self::Pkh(el) => {
                 let (tb, _t, imm3, imm2) = (el.tb, el.t, el.imm3, el.imm2);
-                let ty = Shift::try_from((tb as u8) << 1).unwrap();
+                let ty = Shift::try_from((tb as u8) << 1)?;
                 let shift = Some(ImmShift::from((ty, combine!(imm3:imm2,2,u8))));
 
                 operation::Pkh::builder()
@@ -404,7 +405,7 @@ impl ToOperation for A5_22 {
                 .set_shift(shift!(el))
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -618,6 +619,28 @@ mod test {
         assert_eq!(instr, target)
     }
 
+    #[test]
+    fn test_parse_add_reg_lsr() {
+        // add.w r0, r1, r2, lsr #3
+        let bin = [0x01, 0xeb, 0xd2, 0x00];
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::AddRegister::builder()
+            .set_rn(Register::R1)
+            .set_s(Some(false.into()))
+            .set_rd(Some(Register::R0))
+            .set_rm(Register::R2)
+            .set_shift(Some(ImmShift {
+                shift_t: Shift::Lsr,
+                shift_n: 3,
+            }))
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
     #[test]
     fn test_parse_add_reg() {
         let mut bin = vec![];
@@ -774,4 +797,30 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_add_reg_asr_32() {
+        // add.w r0, r1, r2, asr #32
+        let mut bin = vec![];
+        bin.extend([0b1110_1011u8, 0b0000_0001u8].into_iter().rev());
+        bin.extend([0b0000_0000u8, 0b0010_0010u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        // An immediate of 0 encodes a shift by 32 for asr.
+        let shift = ImmShift {
+            shift_t: Shift::Asr,
+            shift_n: 32,
+        };
+        let target: Operation = operation::AddRegister::builder()
+            .set_s(Some(false.into()))
+            .set_rd(Some(Register::R0))
+            .set_rn(Register::R1)
+            .set_rm(Register::R2)
+            .set_shift(Some(shift))
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
 }
@@ -6,6 +6,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -60,7 +61,7 @@ impl Parse for A5_23 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let ty = word.mask::<4, 5>();
@@ -74,7 +75,7 @@ impl Parse for A5_23 {
             (2, _, _) => Ok(Self::Asr(Asr::parse(iter)?)),
             (3, 0, 0) => Ok(Self::Rrx(Rrx::parse(iter)?)),
             (3, _, _) => Ok(Self::Ror(Ror::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_6")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
@@ -88,8 +89,8 @@ macro_rules! imm {
 }
 
 impl ToOperation for A5_23 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Mov(el) => operation::MovRegister::builder()
                 .set_s(Some(el.s))
                 .set_rd(el.rd)
@@ -142,7 +143,7 @@ impl ToOperation for A5_23 {
                     .complete()
                     .into()
             }
-        }
+        })
     }
 }
 
@@ -3,10 +3,11 @@ use paste::paste;
 use super::{a5_25::A5_25, a5_26::A5_26, a5_27::A5_27};
 use crate::{
     arch::wrapper_types::*,
-    asm::{LocalTryInto, Mask},
+    asm::{LocalTryInto, Mask, Table},
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -145,21 +146,21 @@ impl Parse for A5_24 {
         }
         if op1 >> 3 == 1 {
             match op2 >> 2 {
-                0 => return Ok(Self::SubtableA5_25(A5_25::parse(iter)?)),
-                1 => return Ok(Self::SubtableA5_26(A5_26::parse(iter)?)),
+                0 => return Ok(Self::SubtableA5_25(A5_25::parse_table(iter)?)),
+                1 => return Ok(Self::SubtableA5_26(A5_26::parse_table(iter)?)),
                 _ => {}
             }
         }
         if op1 >> 2 == 2 && op2 >> 2 == 2 {
-            return Ok(Self::SubtableA5_27(A5_27::parse(iter)?));
+            return Ok(Self::SubtableA5_27(A5_27::parse_table(iter)?));
         }
-        Err(ParseError::Invalid32Bit("A5_24"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 
 impl ToOperation for A5_24 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Lsl(el) => operation::LslRegister::builder()
                 .set_s(Some(el.s.into()))
                 .set_rd(el.rd)
@@ -266,10 +267,10 @@ impl ToOperation for A5_24 {
                 .set_rotation(Some(<arch::Imm2 as Into<u32>>::into(el.rotate) << 3))
                 .complete()
                 .into(),
-            Self::SubtableA5_25(el) => el.encoding_specific_operations(),
-            Self::SubtableA5_26(el) => el.encoding_specific_operations(),
-            Self::SubtableA5_27(el) => el.encoding_specific_operations(),
-        }
+            Self::SubtableA5_25(el) => el.encoding_specific_operations()?,
+            Self::SubtableA5_26(el) => el.encoding_specific_operations()?,
+            Self::SubtableA5_27(el) => el.encoding_specific_operations()?,
+        })
     }
 }
 
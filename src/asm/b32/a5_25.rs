@@ -1,6 +1,6 @@
 use paste::paste;
 
-use crate::{asm::Mask, instruction, prelude::*, ParseError, ToOperation};
+use crate::{asm::Mask, instruction, prelude::*, ParseError, ParseErrorKind, ToOperation};
 
 instruction!(
     size u32; A5_25 contains
@@ -105,7 +105,7 @@ impl Parse for A5_25 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
         let op1 = word.mask::<20, 22>();
         let op2 = word.mask::<4, 5>();
@@ -128,7 +128,7 @@ impl Parse for A5_25 {
             (0b101, 0b10) => Ok(Self::Shsub16(Shsub16::parse(iter)?)),
             (0b000, 0b10) => Ok(Self::Shadd8(Shadd8::parse(iter)?)),
             (0b100, 0b10) => Ok(Self::Shsub8(Shsub8::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_25")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
@@ -144,11 +144,11 @@ macro_rules! translate {
     };
 }
 impl ToOperation for A5_25 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        translate!(
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(translate!(
             self, Sadd16, Sasx, Ssax, Ssub16, Sadd8, Ssub8, Qadd16, Qasx, Qsax, Qsub16, Qadd8,
             Qsub8, Shadd16, Shasx, Shsax, Shsub16, Shadd8, Shsub8
-        )
+        ))
     }
 }
 
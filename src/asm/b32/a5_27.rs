@@ -1,6 +1,6 @@
 use paste::paste;
 
-use crate::{asm::Mask, instruction, prelude::*, ParseError, ToOperation};
+use crate::{asm::Mask, instruction, prelude::*, ParseError, ParseErrorKind, ToOperation};
 
 instruction!(
     size u32; A5_27 contains
@@ -60,7 +60,7 @@ impl Parse for A5_27 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram),
         }?;
 
         let op1 = word.mask::<20, 21>();
@@ -70,38 +70,38 @@ impl Parse for A5_27 {
             if op2 == 0 {
                 return Ok(Self::Clz(Clz::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_27"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 0b10 {
             if op2 == 0 {
                 return Ok(Self::Sel(Sel::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_27"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 0b01 {
-            return Ok(match op2 {
-                0b00 => Self::Rev(Rev::parse(iter)?),
-                0b01 => Self::Rev16(Rev16::parse(iter)?),
-                0b10 => Self::Rbit(Rbit::parse(iter)?),
-                0b11 => Self::Revsh(Revsh::parse(iter)?),
-                _ => unreachable!("masking malfunction"),
-            });
+            return match op2 {
+                0b00 => Ok(Self::Rev(Rev::parse(iter)?)),
+                0b01 => Ok(Self::Rev16(Rev16::parse(iter)?)),
+                0b10 => Ok(Self::Rbit(Rbit::parse(iter)?)),
+                0b11 => Ok(Self::Revsh(Revsh::parse(iter)?)),
+                _ => Err(ParseErrorKind::InternalError("Masking is broken op2 > 3").into()),
+            };
+        }
+        match op2 {
+            0b00 => Ok(Self::Qadd(Qadd::parse(iter)?)),
+            0b01 => Ok(Self::Qdadd(Qdadd::parse(iter)?)),
+            0b10 => Ok(Self::Qsub(Qsub::parse(iter)?)),
+            0b11 => Ok(Self::Qdsub(Qdsub::parse(iter)?)),
+            _ => Err(ParseErrorKind::InternalError("Masking is broken op2 > 3").into()),
         }
-        Ok(match op2 {
-            0b00 => Self::Qadd(Qadd::parse(iter)?),
-            0b01 => Self::Qdadd(Qdadd::parse(iter)?),
-            0b10 => Self::Qsub(Qsub::parse(iter)?),
-            0b11 => Self::Qdsub(Qdsub::parse(iter)?),
-            _ => unreachable!("masking malfunctioned"),
-        })
     }
 }
 
 impl ToOperation for A5_27 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
         use A5_27::*;
 
-        match self {
+        Ok(match self {
             Qadd(el) => operation::QaddBuilder::new()
                 .set_rd(Some(el.rd))
                 .set_rm(el.rm)
@@ -157,7 +157,7 @@ impl ToOperation for A5_27 {
                 .set_rm(el.rm)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -5,6 +5,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -144,13 +145,13 @@ impl Parse for A5_28 {
             (0b110, 0, _) | (0b110, 1, _) => Ok(Self::Smmls(Smmls::parse(iter)?)),
             (0b111, 0, 0b1111) => Ok(Self::Usad8(Usad8::parse(iter)?)),
             (0b111, 0, _) => Ok(Self::Usada8(Usada8::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_28")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 impl ToOperation for A5_28 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Mla(el) => operation::Mla::builder()
                 .set_rd(el.rd)
                 .set_rn(el.rn)
@@ -270,7 +271,7 @@ impl ToOperation for A5_28 {
                 .set_rm(el.rm)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -5,6 +5,7 @@ use crate::{
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -95,13 +96,13 @@ impl Parse for A5_29 {
             if op2 >> 1 == 0b110 {
                 return Ok(Self::Smlald(Smlald::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_29"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         if op1 == 0b101 {
             if op2 >> 1 == 0b110 {
                 return Ok(Self::Smlsld(Smlsld::parse(iter)?));
             }
-            return Err(ParseError::Invalid32Bit("A5_29"));
+            return Err(ParseErrorKind::Undefined.into());
         }
         match (op1, op2) {
             (0b000, 0b0000) => Ok(Self::Smull(Smull::parse(iter)?)),
@@ -110,13 +111,13 @@ impl Parse for A5_29 {
             (0b011, 0b1111) => Ok(Self::Udiv(Udiv::parse(iter)?)),
             (0b110, 0b0000) => Ok(Self::Umlal(Umlal::parse(iter)?)),
             (0b110, 0b0110) => Ok(Self::Umaal(Umaal::parse(iter)?)),
-            _ => Err(ParseError::Invalid32Bit("A5_29")),
+            _ => Err(ParseErrorKind::Undefined.into()),
         }
     }
 }
 impl ToOperation for A5_29 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::Smull(el) => operation::Smull::builder()
                 .set_rdlo(el.rdlo)
                 .set_rdhi(el.rdhi)
@@ -189,7 +190,7 @@ impl ToOperation for A5_29 {
                 .set_rm(el.rm)
                 .complete()
                 .into(),
-        }
+        })
     }
 }
 
@@ -4,11 +4,13 @@ use arch::CoProcessor;
 use operation::{Cdp, LdcImmediate, LdcLiteral, Mcr, Mcrr, Mrc, Mrrc, Stc};
 use paste::paste;
 
+use super::{a6_4::A6_4, a6_5::A6_5, a6_6::A6_6, a6_7::A6_7};
 use crate::{
-    asm::{LocalTryInto, Mask},
+    asm::{LocalTryInto, Mask, Table},
     instruction,
     prelude::*,
     ParseError,
+    ParseErrorKind,
     ToOperation,
 };
 
@@ -148,7 +150,11 @@ instruction!(
         rt as u8        : Register  : 12 -> 15 try_into,
         crn as u8       : u8        : 16 -> 19,
         opc1 as u8      : u8        : 21 -> 23
-    }
+    },
+    -> A6_4,
+    -> A6_5,
+    -> A6_6,
+    -> A6_7
 );
 
 impl Parse for A5_30 {
@@ -160,31 +166,41 @@ impl Parse for A5_30 {
     {
         let word: u32 = match iter.peek::<1>() {
             Some(word) => word,
-            None => return Err(ParseError::IncompleteProgram),
+            None => return Err(ParseErrorKind::IncompleteProgram.into()),
         };
 
         let op = word.mask::<4, 4>();
-        assert!(op <= 1);
         let enc = word.mask::<{ 16 + 12 }, { 16 + 12 }>();
-        assert!(enc <= 1);
         let op1 = word.mask::<{ 16 + 4 }, { 16 + 9 }>();
-        assert!(op1 < (1 << (9 - 4 + 1)) - 1);
         let rn = word.mask::<16, 19>();
-        assert!(rn < (1 << (19 - 16 + 1)));
+
+        if word.mask::<9, 11>() == 0b101 {
+            // Co processors 10 and 11 are reserved for the floating point
+            // extension.
+            return match (enc, word.mask::<24, 27>(), op) {
+                (_, 0b1110, 0) => Ok(Self::SubtableA6_4(A6_4::parse_table(iter)?)),
+                (0, 0b1110, _) => Ok(Self::SubtableA6_6(A6_6::parse_table(iter)?)),
+                (0, _, _) if op1 & 0b111110 == 0b000100 => {
+                    Ok(Self::SubtableA6_7(A6_7::parse_table(iter)?))
+                }
+                (0, _, _) => Ok(Self::SubtableA6_5(A6_5::parse_table(iter)?)),
+                _ => Err(ParseErrorKind::Undefined.into()),
+            };
+        }
 
         if op1 == 0b000100 {
-            match enc + 1 {
-                1 => return Ok(Self::McrrT1(McrrT1::parse(iter)?)),
-                2 => return Ok(Self::McrrT2(McrrT2::parse(iter)?)),
-                _ => unreachable!("This is unreachable due to previous asserts"),
-            }
+            return match enc + 1 {
+                1 => Ok(Self::McrrT1(McrrT1::parse(iter)?)),
+                2 => Ok(Self::McrrT2(McrrT2::parse(iter)?)),
+                _ => Err(ParseErrorKind::InternalError("Masking is broken enc > 1").into()),
+            };
         }
         if op1 == 0b000101 {
-            match enc + 1 {
-                1 => return Ok(Self::MrrcT1(MrrcT1::parse(iter)?)),
-                2 => return Ok(Self::MrrcT2(MrrcT2::parse(iter)?)),
-                _ => unreachable!("This is unreachable due to previous asserts"),
-            }
+            return match enc + 1 {
+                1 => Ok(Self::MrrcT1(MrrcT1::parse(iter)?)),
+                2 => Ok(Self::MrrcT2(MrrcT2::parse(iter)?)),
+                _ => Err(ParseErrorKind::InternalError("Masking is broken enc > 1").into()),
+            };
         }
         match (enc + 1, op1 & 0b110001, op) {
             (1, 0b100000, 1) => return Ok(Self::McrT1(McrT1::parse(iter)?)),
@@ -207,13 +223,12 @@ impl Parse for A5_30 {
             (2, 0b000001, _) => return Ok(Self::LdcImmediateT2(LdcImmediateT2::parse(iter)?)),
             _ => {}
         }
-        Err(ParseError::Invalid32Bit("a5_30"))
+        Err(ParseErrorKind::Undefined.into())
     }
 }
 impl ToOperation for A5_30 {
-    fn encoding_specific_operations(self) -> crate::operation::Operation {
-        println!("A5_30 : {self:?}");
-        match self {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
             Self::StcT1(stc) => Stc::builder()
                 .set_coproc(stc.coproc)
                 .set_crd(stc.crd)
@@ -356,7 +371,11 @@ impl ToOperation for A5_30 {
                 .set_crm(el.crm)
                 .complete()
                 .into(),
-        }
+            Self::SubtableA6_4(table) => table.encoding_specific_operations()?,
+            Self::SubtableA6_5(table) => table.encoding_specific_operations()?,
+            Self::SubtableA6_6(table) => table.encoding_specific_operations()?,
+            Self::SubtableA6_7(table) => table.encoding_specific_operations()?,
+        })
     }
 }
 #[cfg(test)]
@@ -737,4 +756,16 @@ mod test {
             .into();
         assert_eq!(instr, target)
     }
+
+    #[test]
+    fn test_parse_op1_all_ones() {
+        // op1 = 0b111111 is unallocated, it is rejected rather than tripping
+        // the bounds check of the field.
+        let mut bin = vec![];
+        bin.extend([0b1110_1111u8, 0b1111_0000u8].into_iter().rev());
+        bin.extend([0b0000_0000u8, 0b0000_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        assert!(Operation::parse(&mut stream).is_err());
+    }
 }
@@ -0,0 +1,909 @@
+use arch::{Condition, F32Register, F64Register, RoundingMode};
+use paste::paste;
+
+use crate::{
+    asm::{LocalTryInto, Mask},
+    instruction,
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
+
+instruction!(
+    size u32; A6_4 contains
+    Vml : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 6 -> 6 local_try_into,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vnml : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 6 -> 6 local_try_into,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vnmul : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vmul : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vadd : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vsub : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vdiv : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vfnm : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 6 -> 6 local_try_into,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vfm : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 6 -> 6 local_try_into,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VmovImmediate : {
+        imm4l       as u8   : u8            : 0 -> 3,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        imm4h       as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VmovRegister : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vabs : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vneg : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vsqrt : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtHalf : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        t           as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        op          as u8   : bool          : 16 -> 16 local_try_into,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vcmp : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        e           as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcmpZero : {
+        e           as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vrint : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vrintx : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtPrecision : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtFromInt : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtToInt : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        signed      as u8   : bool          : 16 -> 16 local_try_into,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtFixed : {
+        imm4        as u8   : u8            : 0 -> 3,
+        i           as u8   : u8            : 5 -> 5,
+        sx          as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        u           as u8   : bool          : 16 -> 16 local_try_into,
+        op          as u8   : bool          : 18 -> 18 local_try_into,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vsel : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        cc          as u8   : u8            : 20 -> 21,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vminmaxnm : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 6 -> 6 local_try_into,
+        n           as u8   : u8            : 7 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        vn          as u8   : u8            : 16 -> 19,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VrintDirected : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        mode        as u8   : RoundingMode  : 16 -> 17 try_into,
+        d           as u8   : u8            : 22 -> 22
+    },
+    VcvtDirected : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        op          as u8   : bool          : 7 -> 7 local_try_into,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        mode        as u8   : RoundingMode  : 16 -> 17 try_into,
+        d           as u8   : u8            : 22 -> 22
+    }
+);
+
+/// The `D:Vd` register operand.
+pub(super) const D: (usize, usize) = (22, 12);
+/// The `N:Vn` register operand.
+pub(super) const N: (usize, usize) = (7, 16);
+/// The `M:Vm` register operand.
+pub(super) const M: (usize, usize) = (5, 0);
+
+/// Single precision registers are encoded as `Vx:X`.
+pub(super) fn single(vx: u8, x: u8) -> Result<F32Register, ParseError> {
+    Ok(F32Register::try_from((vx & 0xF) << 1 | (x & 1))?)
+}
+
+/// Double precision registers are encoded as `X:Vx`.
+///
+/// The M-profile only provides `D0-D15`, the `X` bit is checked by
+/// [`double_registers`] while parsing.
+pub(super) fn double(vx: u8) -> Result<F64Register, ParseError> {
+    Ok(F64Register::try_from(vx & 0xF)?)
+}
+
+/// Ensures that the double precision `operands` address one of `D0-D15`.
+///
+/// Does nothing unless `dp` is set, the operands are given as the bit
+/// positions of the `X` bit and the `Vx` field.
+pub(super) fn double_registers(
+    word: u32,
+    dp: bool,
+    operands: &[(usize, usize)],
+) -> Result<(), ParseError> {
+    for (x, vx) in operands {
+        if dp && (word >> x) & 1 == 1 {
+            return Err(ParseErrorKind::InvalidRegister(0x10 | ((word >> vx) & 0xF) as u8).into());
+        }
+    }
+    Ok(())
+}
+
+/// Expands the 8-bit floating point immediate as defined in section A6.4.1.
+pub(crate) fn vfp_expand_imm(imm8: u8, sz: bool) -> u64 {
+    let imm8 = imm8 as u64;
+    let sign = imm8 >> 7;
+    let b6 = (imm8 >> 6) & 1;
+    let (exponent, fraction) = match sz {
+        false => (
+            (b6 ^ 1) << 7 | (b6 * 0b11111) << 2 | (imm8 >> 4) & 0b11,
+            (imm8 & 0xF) << 19,
+        ),
+        true => (
+            (b6 ^ 1) << 10 | (b6 * 0xFF) << 2 | (imm8 >> 4) & 0b11,
+            (imm8 & 0xF) << 48,
+        ),
+    };
+    match sz {
+        false => sign << 31 | exponent << 23 | fraction,
+        true => sign << 63 | exponent << 52 | fraction,
+    }
+}
+
+impl Parse for A6_4 {
+    type Target = Self;
+
+    fn parse<T: Stream>(iter: &mut T) -> Result<Self::Target, ParseError>
+    where
+        Self: Sized,
+    {
+        let word: u32 = iter.next()?;
+        let sz = word.mask::<8, 8>() == 1;
+        let opc1 = word.mask::<20, 23>() & 0b1011;
+        let opc2 = word.mask::<16, 19>();
+        let opc3 = word.mask::<6, 7>();
+
+        if word.mask::<28, 28>() == 1 {
+            // Floating point extension version 5 additions.
+            return match (opc1, opc2 >> 2, opc3) {
+                (0b0000..=0b0011, _, 0b00 | 0b10) => {
+                    double_registers(word, sz, &[D, N, M])?;
+                    Ok(Self::Vsel(Vsel::parse(iter)?))
+                }
+                (0b1000, _, _) => {
+                    double_registers(word, sz, &[D, N, M])?;
+                    Ok(Self::Vminmaxnm(Vminmaxnm::parse(iter)?))
+                }
+                (0b1011, 0b10, 0b01) => {
+                    double_registers(word, sz, &[D, M])?;
+                    Ok(Self::VrintDirected(VrintDirected::parse(iter)?))
+                }
+                (0b1011, 0b11, 0b01 | 0b11) => {
+                    double_registers(word, sz, &[M])?;
+                    Ok(Self::VcvtDirected(VcvtDirected::parse(iter)?))
+                }
+                _ => Err(ParseErrorKind::Undefined.into()),
+            };
+        }
+
+        match opc1 {
+            0b0000 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return Ok(Self::Vml(Vml::parse(iter)?));
+            }
+            0b0001 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return Ok(Self::Vnml(Vnml::parse(iter)?));
+            }
+            0b0010 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return match opc3 & 0b1 {
+                    1 => Ok(Self::Vnmul(Vnmul::parse(iter)?)),
+                    _ => Ok(Self::Vmul(Vmul::parse(iter)?)),
+                };
+            }
+            0b0011 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return match opc3 & 0b1 {
+                    1 => Ok(Self::Vsub(Vsub::parse(iter)?)),
+                    _ => Ok(Self::Vadd(Vadd::parse(iter)?)),
+                };
+            }
+            0b1000 if opc3 & 0b1 == 0 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return Ok(Self::Vdiv(Vdiv::parse(iter)?));
+            }
+            0b1001 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return Ok(Self::Vfnm(Vfnm::parse(iter)?));
+            }
+            0b1010 => {
+                double_registers(word, sz, &[D, N, M])?;
+                return Ok(Self::Vfm(Vfm::parse(iter)?));
+            }
+            0b1011 => {}
+            _ => return Err(ParseErrorKind::Undefined.into()),
+        }
+
+        if opc3 & 0b1 == 0 {
+            double_registers(word, sz, &[D])?;
+            return Ok(Self::VmovImmediate(VmovImmediate::parse(iter)?));
+        }
+
+        match (opc2, opc3) {
+            (0b0000, 0b01) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::VmovRegister(VmovRegister::parse(iter)?))
+            }
+            (0b0000, 0b11) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vabs(Vabs::parse(iter)?))
+            }
+            (0b0001, 0b01) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vneg(Vneg::parse(iter)?))
+            }
+            (0b0001, 0b11) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vsqrt(Vsqrt::parse(iter)?))
+            }
+            (0b0010 | 0b0011, _) => {
+                // Converting to half precision reads a double, converting from
+                // half precision writes a double.
+                match opc2 & 0b1 {
+                    1 => double_registers(word, sz, &[M])?,
+                    _ => double_registers(word, sz, &[D])?,
+                }
+                Ok(Self::VcvtHalf(VcvtHalf::parse(iter)?))
+            }
+            (0b0100, _) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vcmp(Vcmp::parse(iter)?))
+            }
+            (0b0101, _) => {
+                double_registers(word, sz, &[D])?;
+                Ok(Self::VcmpZero(VcmpZero::parse(iter)?))
+            }
+            (0b0110, _) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vrint(Vrint::parse(iter)?))
+            }
+            (0b0111, 0b01) => {
+                double_registers(word, sz, &[D, M])?;
+                Ok(Self::Vrintx(Vrintx::parse(iter)?))
+            }
+            (0b0111, 0b11) => {
+                // Converts between the precisions, the destination is a double
+                // if the source is a single.
+                double_registers(word, !sz, &[D])?;
+                double_registers(word, sz, &[M])?;
+                Ok(Self::VcvtPrecision(VcvtPrecision::parse(iter)?))
+            }
+            (0b1000, _) => {
+                double_registers(word, sz, &[D])?;
+                Ok(Self::VcvtFromInt(VcvtFromInt::parse(iter)?))
+            }
+            (0b1100 | 0b1101, _) => {
+                double_registers(word, sz, &[M])?;
+                Ok(Self::VcvtToInt(VcvtToInt::parse(iter)?))
+            }
+            (0b1010 | 0b1011 | 0b1110 | 0b1111, _) => {
+                double_registers(word, sz, &[D])?;
+                let size = match word.mask::<7, 7>() {
+                    1 => 32,
+                    _ => 16,
+                };
+                if word.mask::<0, 3>() << 1 | word.mask::<5, 5>() > size {
+                    return Err(ParseErrorKind::Unpredictable.into());
+                }
+                Ok(Self::VcvtFixed(VcvtFixed::parse(iter)?))
+            }
+            _ => Err(ParseErrorKind::Undefined.into()),
+        }
+    }
+}
+
+/// Builds the single or double precision variant of an operation on `Vd`,
+/// `Vn` and `Vm`.
+macro_rules! three {
+    ($el:ident, $single:ident, $double:ident) => {
+        match $el.sz {
+            false => operation::$single::builder()
+                .set_sd(single($el.vd, $el.d)?)
+                .set_sn(single($el.vn, $el.n)?)
+                .set_sm(single($el.vm, $el.m)?)
+                .complete()
+                .into(),
+            true => operation::$double::builder()
+                .set_dd(double($el.vd)?)
+                .set_dn(double($el.vn)?)
+                .set_dm(double($el.vm)?)
+                .complete()
+                .into(),
+        }
+    };
+}
+
+/// Builds the single or double precision variant of an operation on `Vd`
+/// and `Vm`.
+macro_rules! two {
+    ($el:ident, $single:ident, $double:ident) => {
+        match $el.sz {
+            false => operation::$single::builder()
+                .set_sd(single($el.vd, $el.d)?)
+                .set_sm(single($el.vm, $el.m)?)
+                .complete()
+                .into(),
+            true => operation::$double::builder()
+                .set_dd(double($el.vd)?)
+                .set_dm(double($el.vm)?)
+                .complete()
+                .into(),
+        }
+    };
+}
+
+impl ToOperation for A6_4 {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
+            Self::Vml(el) => match el.op {
+                false => three!(el, VmlaF32, VmlaF64),
+                true => three!(el, VmlsF32, VmlsF64),
+            },
+            Self::Vnml(el) => match el.op {
+                false => three!(el, VnmlsF32, VnmlsF64),
+                true => three!(el, VnmlaF32, VnmlaF64),
+            },
+            Self::Vnmul(el) => three!(el, VnmulF32, VnmulF64),
+            Self::Vmul(el) => three!(el, VmulF32, VmulF64),
+            Self::Vadd(el) => three!(el, VaddF32, VaddF64),
+            Self::Vsub(el) => three!(el, VsubF32, VsubF64),
+            Self::Vdiv(el) => three!(el, VdivF32, VdivF64),
+            Self::Vfnm(el) => match el.op {
+                false => three!(el, VfnmsF32, VfnmsF64),
+                true => three!(el, VfnmaF32, VfnmaF64),
+            },
+            Self::Vfm(el) => match el.op {
+                false => three!(el, VfmaF32, VfmaF64),
+                true => three!(el, VfmsF32, VfmsF64),
+            },
+            Self::VmovImmediate(el) => {
+                let imm = vfp_expand_imm(el.imm4h << 4 | el.imm4l, el.sz);
+                match el.sz {
+                    false => operation::VmovImmediateF32::builder()
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_imm(imm as u32)
+                        .complete()
+                        .into(),
+                    true => operation::VmovImmediateF64::builder()
+                        .set_dd(double(el.vd)?)
+                        .set_imm(imm)
+                        .complete()
+                        .into(),
+                }
+            }
+            Self::VmovRegister(el) => two!(el, VmovRegisterF32, VmovRegisterF64),
+            Self::Vabs(el) => two!(el, VabsF32, VabsF64),
+            Self::Vneg(el) => two!(el, VnegF32, VnegF64),
+            Self::Vsqrt(el) => two!(el, VsqrtF32, VsqrtF64),
+            Self::VcvtHalf(el) => match (el.sz, el.op) {
+                (false, false) => operation::VcvtF32F16::builder()
+                    .set_top(el.t)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                (false, true) => operation::VcvtF16F32::builder()
+                    .set_top(el.t)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                (true, false) => operation::VcvtF64F16::builder()
+                    .set_top(el.t)
+                    .set_dd(double(el.vd)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                (true, true) => operation::VcvtF16F64::builder()
+                    .set_top(el.t)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_dm(double(el.vm)?)
+                    .complete()
+                    .into(),
+            },
+            Self::Vcmp(el) => match el.sz {
+                false => operation::VcmpF32::builder()
+                    .set_e(Some(el.e))
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                true => operation::VcmpF64::builder()
+                    .set_e(Some(el.e))
+                    .set_dd(double(el.vd)?)
+                    .set_dm(double(el.vm)?)
+                    .complete()
+                    .into(),
+            },
+            Self::VcmpZero(el) => match el.sz {
+                false => operation::VcmpZeroF32::builder()
+                    .set_e(Some(el.e))
+                    .set_sd(single(el.vd, el.d)?)
+                    .complete()
+                    .into(),
+                true => operation::VcmpZeroF64::builder()
+                    .set_e(Some(el.e))
+                    .set_dd(double(el.vd)?)
+                    .complete()
+                    .into(),
+            },
+            Self::Vrint(el) => {
+                let r = match el.op {
+                    true => Some(RoundingMode::Zero),
+                    false => None,
+                };
+                match el.sz {
+                    false => operation::VrintF32::builder()
+                        .set_r(r)
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_sm(single(el.vm, el.m)?)
+                        .complete()
+                        .into(),
+                    true => operation::VrintF64::builder()
+                        .set_r(r)
+                        .set_dd(double(el.vd)?)
+                        .set_dm(double(el.vm)?)
+                        .complete()
+                        .into(),
+                }
+            }
+            Self::Vrintx(el) => two!(el, VrintxF32, VrintxF64),
+            Self::VcvtPrecision(el) => match el.sz {
+                false => operation::VcvtF64F32::builder()
+                    .set_dd(double(el.vd)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                true => operation::VcvtF32F64::builder()
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_dm(double(el.vm)?)
+                    .complete()
+                    .into(),
+            },
+            Self::VcvtFromInt(el) => match el.sz {
+                false => operation::VcvtFromIntF32::builder()
+                    .set_signed(el.op)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                true => operation::VcvtFromIntF64::builder()
+                    .set_signed(el.op)
+                    .set_dd(double(el.vd)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+            },
+            Self::VcvtToInt(el) => {
+                let r = match el.op {
+                    true => Some(RoundingMode::Zero),
+                    false => None,
+                };
+                match el.sz {
+                    false => operation::VcvtToIntF32::builder()
+                        .set_signed(el.signed)
+                        .set_r(r)
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_sm(single(el.vm, el.m)?)
+                        .complete()
+                        .into(),
+                    true => operation::VcvtToIntF64::builder()
+                        .set_signed(el.signed)
+                        .set_r(r)
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_dm(double(el.vm)?)
+                        .complete()
+                        .into(),
+                }
+            }
+            Self::VcvtFixed(el) => {
+                let size: u8 = match el.sx {
+                    true => 32,
+                    false => 16,
+                };
+                let fbits = size - (el.imm4 << 1 | el.i);
+                match el.sz {
+                    false => operation::VcvtFixedF32::builder()
+                        .set_to_fixed(el.op)
+                        .set_signed(!el.u)
+                        .set_size(size)
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_fbits(fbits)
+                        .complete()
+                        .into(),
+                    true => operation::VcvtFixedF64::builder()
+                        .set_to_fixed(el.op)
+                        .set_signed(!el.u)
+                        .set_size(size)
+                        .set_dd(double(el.vd)?)
+                        .set_fbits(fbits)
+                        .complete()
+                        .into(),
+                }
+            }
+            Self::Vsel(el) => {
+                let cond = match el.cc {
+                    0b00 => Condition::Eq,
+                    0b01 => Condition::Vs,
+                    0b10 => Condition::Ge,
+                    _ => Condition::Gt,
+                };
+                match el.sz {
+                    false => operation::VselF32::builder()
+                        .set_cond(cond)
+                        .set_sd(single(el.vd, el.d)?)
+                        .set_sn(single(el.vn, el.n)?)
+                        .set_sm(single(el.vm, el.m)?)
+                        .complete()
+                        .into(),
+                    true => operation::VselF64::builder()
+                        .set_cond(cond)
+                        .set_dd(double(el.vd)?)
+                        .set_dn(double(el.vn)?)
+                        .set_dm(double(el.vm)?)
+                        .complete()
+                        .into(),
+                }
+            }
+            Self::Vminmaxnm(el) => match el.op {
+                false => three!(el, VmaxnmF32, VmaxnmF64),
+                true => three!(el, VminnmF32, VminnmF64),
+            },
+            Self::VrintDirected(el) => match el.sz {
+                false => operation::VrintF32::builder()
+                    .set_r(Some(el.mode))
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                true => operation::VrintF64::builder()
+                    .set_r(Some(el.mode))
+                    .set_dd(double(el.vd)?)
+                    .set_dm(double(el.vm)?)
+                    .complete()
+                    .into(),
+            },
+            Self::VcvtDirected(el) => match el.sz {
+                false => operation::VcvtToIntF32::builder()
+                    .set_signed(el.op)
+                    .set_r(Some(el.mode))
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_sm(single(el.vm, el.m)?)
+                    .complete()
+                    .into(),
+                true => operation::VcvtToIntF64::builder()
+                    .set_signed(el.op)
+                    .set_r(Some(el.mode))
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_dm(double(el.vm)?)
+                    .complete()
+                    .into(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_parse_vadd() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b0011_0000u8].into_iter().rev());
+        bin.extend([0b0000_1010u8, 0b1000_0001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VaddF32::builder()
+            .set_sd(F32Register::S0)
+            .set_sn(F32Register::S1)
+            .set_sm(F32Register::S2)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vnmls() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b0001_0000u8].into_iter().rev());
+        bin.extend([0b1111_1011u8, 0b0000_0111u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VnmlsF64::builder()
+            .set_dd(F64Register::D15)
+            .set_dn(F64Register::D0)
+            .set_dm(F64Register::D7)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vmov_immediate() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1111_1111u8].into_iter().rev());
+        bin.extend([0b0001_1010u8, 0b0000_0100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VmovImmediateF32::builder()
+            .set_sd(F32Register::S3)
+            .set_imm((-1.25f32).to_bits())
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vcmpe_zero() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1011_0101u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b1100_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VcmpZeroF64::builder()
+            .set_e(Some(true))
+            .set_dd(F64Register::D0)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vcvt_to_int() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1011_1100u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b0100_0001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VcvtToIntF64::builder()
+            .set_signed(false)
+            .set_r(None)
+            .set_sd(F32Register::S0)
+            .set_dm(F64Register::D1)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vcvt_fixed() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1011_1110u8].into_iter().rev());
+        bin.extend([0b0000_1010u8, 0b0110_0110u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VcvtFixedF32::builder()
+            .set_to_fixed(true)
+            .set_signed(true)
+            .set_size(16)
+            .set_sd(F32Register::S0)
+            .set_fbits(3)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vsel() {
+        let mut bin = vec![];
+        bin.extend([0b1111_1110u8, 0b0011_0001u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b0000_0010u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VselF64::builder()
+            .set_cond(Condition::Gt)
+            .set_dd(F64Register::D0)
+            .set_dn(F64Register::D1)
+            .set_dm(F64Register::D2)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vrint_directed() {
+        let mut bin = vec![];
+        bin.extend([0b1111_1110u8, 0b1011_1011u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b0100_0001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VrintF64::builder()
+            .set_r(Some(RoundingMode::MinusInfinity))
+            .set_dd(F64Register::D0)
+            .set_dm(F64Register::D1)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_double_register_out_of_range() {
+        // vadd.f64 d16, d0, d0 does not exist in the M-profile.
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b0111_0000u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b0000_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        assert!(Operation::parse(&mut stream).is_err());
+    }
+}
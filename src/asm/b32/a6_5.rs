@@ -0,0 +1,324 @@
+use arch::{F32Register, F64Register};
+use paste::paste;
+
+use super::a6_4::{double, double_registers, single, D};
+use crate::{
+    asm::{LocalTryInto, Mask},
+    instruction,
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
+
+instruction!(
+    size u32; A6_5 contains
+    Vstm : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        rn          as u8   : Register      : 16 -> 19 try_into,
+        w           as u8   : bool          : 21 -> 21 local_try_into,
+        d           as u8   : u8            : 22 -> 22,
+        u           as u8   : bool          : 23 -> 23 local_try_into
+    },
+    Vstr : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        rn          as u8   : Register      : 16 -> 19 try_into,
+        d           as u8   : u8            : 22 -> 22,
+        u           as u8   : bool          : 23 -> 23 local_try_into
+    },
+    Vpush : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vldm : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        rn          as u8   : Register      : 16 -> 19 try_into,
+        w           as u8   : bool          : 21 -> 21 local_try_into,
+        d           as u8   : u8            : 22 -> 22,
+        u           as u8   : bool          : 23 -> 23 local_try_into
+    },
+    Vpop : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        d           as u8   : u8            : 22 -> 22
+    },
+    Vldr : {
+        imm8        as u8   : u8            : 0 -> 7,
+        sz          as u8   : bool          : 8 -> 8 local_try_into,
+        vd          as u8   : u8            : 12 -> 15,
+        rn          as u8   : Register      : 16 -> 19 try_into,
+        d           as u8   : u8            : 22 -> 22,
+        u           as u8   : bool          : 23 -> 23 local_try_into
+    }
+);
+
+/// Ensures that the register list is non empty and that it fits in the
+/// register file.
+fn register_list(word: u32) -> Result<(), ParseError> {
+    let sz = word.mask::<8, 8>() == 1;
+    double_registers(word, sz, &[D])?;
+    let imm8 = word.mask::<0, 7>();
+    let (first, count, registers) = match sz {
+        false => (word.mask::<12, 15>() << 1 | word.mask::<22, 22>(), imm8, 32),
+        true => (word.mask::<12, 15>(), imm8 >> 1, 16),
+    };
+    if count == 0 || first + count > registers {
+        return Err(ParseErrorKind::Unpredictable.into());
+    }
+    Ok(())
+}
+
+impl Parse for A6_5 {
+    type Target = Self;
+
+    fn parse<T: Stream>(iter: &mut T) -> Result<Self::Target, ParseError>
+    where
+        Self: Sized,
+    {
+        let word: u32 = iter.next()?;
+        let opcode = word.mask::<20, 24>();
+        let rn = word.mask::<16, 19>();
+
+        match (opcode & 0b11011, rn) {
+            (0b10010, 0b1101) => {
+                register_list(word)?;
+                Ok(Self::Vpush(Vpush::parse(iter)?))
+            }
+            (0b01011, 0b1101) => {
+                register_list(word)?;
+                Ok(Self::Vpop(Vpop::parse(iter)?))
+            }
+            // Only VLDR may use the PC as base register in thumb state.
+            (0b01000 | 0b01010 | 0b10010 | 0b01001 | 0b01011 | 0b10011, 0b1111)
+            | (0b10000 | 0b11000, 0b1111) => Err(ParseErrorKind::Unpredictable.into()),
+            (0b01000 | 0b01010 | 0b10010, _) => {
+                register_list(word)?;
+                Ok(Self::Vstm(Vstm::parse(iter)?))
+            }
+            (0b01001 | 0b01011 | 0b10011, _) => {
+                register_list(word)?;
+                Ok(Self::Vldm(Vldm::parse(iter)?))
+            }
+            (0b10000 | 0b11000, _) => {
+                double_registers(word, word.mask::<8, 8>() == 1, &[D])?;
+                Ok(Self::Vstr(Vstr::parse(iter)?))
+            }
+            (0b10001 | 0b11001, _) => {
+                double_registers(word, word.mask::<8, 8>() == 1, &[D])?;
+                Ok(Self::Vldr(Vldr::parse(iter)?))
+            }
+            _ => Err(ParseErrorKind::Undefined.into()),
+        }
+    }
+}
+
+/// Lists `count` consecutive single precision registers.
+fn singles(vd: u8, d: u8, count: u8) -> Result<Vec<F32Register>, ParseError> {
+    let first = u8::from(single(vd, d)?);
+    Ok((first..first.saturating_add(count))
+        .filter_map(|idx| F32Register::try_from(idx).ok())
+        .collect())
+}
+
+/// Lists `count` consecutive double precision registers.
+fn doubles(vd: u8, count: u8) -> Result<Vec<F64Register>, ParseError> {
+    let first = u8::from(double(vd)?);
+    Ok((first..first.saturating_add(count))
+        .filter_map(|idx| F64Register::try_from(idx).ok())
+        .collect())
+}
+
+/// Builds the single or double precision variant of a multiple register
+/// transfer.
+macro_rules! multiple {
+    ($el:ident, $single:ident, $double:ident $(, $field:ident : $value:expr)*) => {
+        match $el.sz {
+            false => operation::$single::builder()
+                $(.$field($value))*
+                .set_registers(singles($el.vd, $el.d, $el.imm8)?)
+                .complete()
+                .into(),
+            true => operation::$double::builder()
+                $(.$field($value))*
+                .set_registers(doubles($el.vd, $el.imm8 >> 1)?)
+                .complete()
+                .into(),
+        }
+    };
+}
+
+impl ToOperation for A6_5 {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
+            Self::Vstm(el) => multiple!(
+                el, VstmF32, VstmF64,
+                set_add: el.u,
+                set_w: el.w,
+                set_rn: el.rn
+            ),
+            Self::Vldm(el) => multiple!(
+                el, VldmF32, VldmF64,
+                set_add: el.u,
+                set_w: el.w,
+                set_rn: el.rn
+            ),
+            Self::Vpush(el) => multiple!(el, VpushF32, VpushF64),
+            Self::Vpop(el) => multiple!(el, VpopF32, VpopF64),
+            Self::Vstr(el) => match el.sz {
+                false => operation::VstrF32::builder()
+                    .set_add(el.u)
+                    .set_rn(el.rn)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_imm((el.imm8 as u32) << 2)
+                    .complete()
+                    .into(),
+                true => operation::VstrF64::builder()
+                    .set_add(el.u)
+                    .set_rn(el.rn)
+                    .set_dd(double(el.vd)?)
+                    .set_imm((el.imm8 as u32) << 2)
+                    .complete()
+                    .into(),
+            },
+            Self::Vldr(el) => match el.sz {
+                false => operation::VldrF32::builder()
+                    .set_add(el.u)
+                    .set_rn(el.rn)
+                    .set_sd(single(el.vd, el.d)?)
+                    .set_imm((el.imm8 as u32) << 2)
+                    .complete()
+                    .into(),
+                true => operation::VldrF64::builder()
+                    .set_add(el.u)
+                    .set_rn(el.rn)
+                    .set_dd(double(el.vd)?)
+                    .set_imm((el.imm8 as u32) << 2)
+                    .complete()
+                    .into(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_parse_vpush() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1101u8, 0b0010_1101u8].into_iter().rev());
+        bin.extend([0b1000_1011u8, 0b0000_0100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VpushF64::builder()
+            .set_registers(vec![F64Register::D8, F64Register::D9])
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vpop() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1100u8, 0b1011_1101u8].into_iter().rev());
+        bin.extend([0b0000_1010u8, 0b0000_0001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VpopF32::builder()
+            .set_registers(vec![F32Register::S0])
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vldmdb() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1101u8, 0b0011_0010u8].into_iter().rev());
+        bin.extend([0b0010_1010u8, 0b0000_0010u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VldmF32::builder()
+            .set_add(false)
+            .set_w(true)
+            .set_rn(Register::R2)
+            .set_registers(vec![F32Register::S4, F32Register::S5])
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vstm() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1100u8, 0b1010_0001u8].into_iter().rev());
+        bin.extend([0b0000_1011u8, 0b0000_0110u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VstmF64::builder()
+            .set_add(true)
+            .set_w(true)
+            .set_rn(Register::R1)
+            .set_registers(vec![F64Register::D0, F64Register::D1, F64Register::D2])
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vldr_literal() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1101u8, 0b0001_1111u8].into_iter().rev());
+        bin.extend([0b0001_1011u8, 0b0000_0100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VldrF64::builder()
+            .set_add(false)
+            .set_rn(Register::PC)
+            .set_dd(F64Register::D1)
+            .set_imm(16)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vstr() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1101u8, 0b0000_0000u8].into_iter().rev());
+        bin.extend([0b0000_1010u8, 0b0000_0010u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VstrF32::builder()
+            .set_add(false)
+            .set_rn(Register::R0)
+            .set_sd(F32Register::S0)
+            .set_imm(8)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+}
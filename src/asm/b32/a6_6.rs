@@ -0,0 +1,169 @@
+use paste::paste;
+
+use super::a6_4::{double, single};
+use crate::{
+    asm::{LocalTryInto, Mask},
+    instruction,
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
+
+instruction!(
+    size u32; A6_6 contains
+    VmovCore : {
+        n           as u8   : u8            : 7 -> 7,
+        rt          as u8   : Register      : 12 -> 15 try_into,
+        vn          as u8   : u8            : 16 -> 19,
+        op          as u8   : bool          : 20 -> 20 local_try_into
+    },
+    Vmsr : {
+        rt          as u8   : Register      : 12 -> 15 try_into
+    },
+    Vmrs : {
+        rt          as u8   : Register      : 12 -> 15 try_into
+    },
+    VmovScalar : {
+        rt          as u8   : Register      : 12 -> 15 try_into,
+        vd          as u8   : u8            : 16 -> 19,
+        op          as u8   : bool          : 20 -> 20 local_try_into,
+        h           as u8   : u8            : 21 -> 21
+    }
+);
+
+impl Parse for A6_6 {
+    type Target = Self;
+
+    fn parse<T: Stream>(iter: &mut T) -> Result<Self::Target, ParseError>
+    where
+        Self: Sized,
+    {
+        let word: u32 = iter.next()?;
+        let l = word.mask::<20, 20>();
+        let c = word.mask::<8, 8>();
+        let a = word.mask::<21, 23>();
+        let b = word.mask::<5, 6>();
+
+        match (c, a, b) {
+            (0, 0b000, _) => Ok(Self::VmovCore(VmovCore::parse(iter)?)),
+            (0, 0b111, _) => {
+                // The M-profile only provides the FPSCR.
+                if word.mask::<16, 19>() != 0b0001 {
+                    return Err(ParseErrorKind::Unpredictable.into());
+                }
+                match l {
+                    1 => Ok(Self::Vmrs(Vmrs::parse(iter)?)),
+                    _ => Ok(Self::Vmsr(Vmsr::parse(iter)?)),
+                }
+            }
+            (1, 0b000 | 0b001, 0b00) => {
+                if word.mask::<7, 7>() == 1 {
+                    return Err(ParseErrorKind::InvalidRegister(
+                        0x10 | word.mask::<16, 19>() as u8,
+                    )
+                    .into());
+                }
+                Ok(Self::VmovScalar(VmovScalar::parse(iter)?))
+            }
+            _ => Err(ParseErrorKind::Undefined.into()),
+        }
+    }
+}
+
+impl ToOperation for A6_6 {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
+            Self::VmovCore(el) => operation::VmovCoreF32::builder()
+                .set_to_core(el.op)
+                .set_rt(el.rt)
+                .set_sn(single(el.vn, el.n)?)
+                .complete()
+                .into(),
+            Self::Vmsr(el) => operation::Vmsr::builder().set_rt(el.rt).complete().into(),
+            Self::Vmrs(el) => operation::Vmrs::builder().set_rt(el.rt).complete().into(),
+            Self::VmovScalar(el) => operation::VmovCoreScalar::builder()
+                .set_to_core(el.op)
+                .set_rt(el.rt)
+                .set_dd(double(el.vd)?)
+                .set_index(el.h)
+                .complete()
+                .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_parse_vmov_core() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b0000_1111u8].into_iter().rev());
+        bin.extend([0b1100_1010u8, 0b1001_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VmovCoreF32::builder()
+            .set_to_core(false)
+            .set_rt(Register::R12)
+            .set_sn(F32Register::S31)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vmrs_apsr() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1111_0001u8].into_iter().rev());
+        bin.extend([0b1111_1010u8, 0b0001_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Vmrs::builder()
+            .set_rt(Register::PC)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vmsr() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b1110_0001u8].into_iter().rev());
+        bin.extend([0b0011_1010u8, 0b0001_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::Vmsr::builder()
+            .set_rt(Register::R3)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vmov_scalar() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1110u8, 0b0011_1111u8].into_iter().rev());
+        bin.extend([0b0010_1011u8, 0b0001_0000u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VmovCoreScalar::builder()
+            .set_to_core(true)
+            .set_rt(Register::R2)
+            .set_dd(F64Register::D15)
+            .set_index(1)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+}
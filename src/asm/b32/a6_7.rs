@@ -0,0 +1,127 @@
+use arch::F32Register;
+use paste::paste;
+
+use super::a6_4::{double, double_registers, single, M};
+use crate::{
+    asm::{LocalTryInto, Mask},
+    instruction,
+    prelude::*,
+    ParseError,
+    ParseErrorKind,
+    ToOperation,
+};
+
+instruction!(
+    size u32; A6_7 contains
+    VmovCore2 : {
+        vm          as u8   : u8            : 0 -> 3,
+        m           as u8   : u8            : 5 -> 5,
+        rt          as u8   : Register      : 12 -> 15 try_into,
+        rt2         as u8   : Register      : 16 -> 19 try_into,
+        op          as u8   : bool          : 20 -> 20 local_try_into
+    },
+    VmovCoreDouble : {
+        vm          as u8   : u8            : 0 -> 3,
+        rt          as u8   : Register      : 12 -> 15 try_into,
+        rt2         as u8   : Register      : 16 -> 19 try_into,
+        op          as u8   : bool          : 20 -> 20 local_try_into
+    }
+);
+
+impl Parse for A6_7 {
+    type Target = Self;
+
+    fn parse<T: Stream>(iter: &mut T) -> Result<Self::Target, ParseError>
+    where
+        Self: Sized,
+    {
+        let word: u32 = iter.next()?;
+        if word.mask::<4, 7>() & 0b1101 != 0b0001 {
+            return Err(ParseErrorKind::Undefined.into());
+        }
+        match word.mask::<8, 8>() {
+            0 => {
+                // The second register is the next register in the bank.
+                if word.mask::<0, 3>() << 1 | word.mask::<5, 5>() == 31 {
+                    return Err(ParseErrorKind::Unpredictable.into());
+                }
+                Ok(Self::VmovCore2(VmovCore2::parse(iter)?))
+            }
+            _ => {
+                double_registers(word, true, &[M])?;
+                Ok(Self::VmovCoreDouble(VmovCoreDouble::parse(iter)?))
+            }
+        }
+    }
+}
+
+impl ToOperation for A6_7 {
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError> {
+        Ok(match self {
+            Self::VmovCore2(el) => {
+                let sm = single(el.vm, el.m)?;
+                let sm1 = F32Register::try_from(u8::from(sm) + 1).unwrap_or(sm);
+                operation::VmovCore2F32::builder()
+                    .set_to_core(el.op)
+                    .set_rt(el.rt)
+                    .set_rt2(el.rt2)
+                    .set_sm(sm)
+                    .set_sm1(sm1)
+                    .complete()
+                    .into()
+            }
+            Self::VmovCoreDouble(el) => operation::VmovCoreF64::builder()
+                .set_to_core(el.op)
+                .set_rt(el.rt)
+                .set_rt2(el.rt2)
+                .set_dm(double(el.vm)?)
+                .complete()
+                .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_parse_vmov_core_singles() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1100u8, 0b0101_0001u8].into_iter().rev());
+        bin.extend([0b0000_1010u8, 0b0001_0001u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VmovCore2F32::builder()
+            .set_to_core(true)
+            .set_rt(Register::R0)
+            .set_rt2(Register::R1)
+            .set_sm(F32Register::S2)
+            .set_sm1(F32Register::S3)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+
+    #[test]
+    fn test_parse_vmov_core_double() {
+        let mut bin = vec![];
+        bin.extend([0b1110_1100u8, 0b0100_0110u8].into_iter().rev());
+        bin.extend([0b0101_1011u8, 0b0001_1111u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let instr = Operation::parse(&mut stream).expect("Parser broken").1;
+
+        let target: Operation = operation::VmovCoreF64::builder()
+            .set_to_core(false)
+            .set_rt(Register::R5)
+            .set_rt2(Register::R6)
+            .set_dm(F64Register::D15)
+            .complete()
+            .into();
+        assert_eq!(instr, target)
+    }
+}
@@ -0,0 +1,392 @@
+//! Compares the decoder against the reference listings in
+//! `fixtures/differential`.
+//!
+//! The listings are produced by `llvm-objdump -d` from the fixture images,
+//! the commands are given at the top of each assembly source. Every
+//! instruction in a listing is decoded from the raw bytes of that line, both
+//! sides are normalised to the same UAL spelling and any disagreement is
+//! reported, grouped by the decode table that the encoding belongs to. With
+//! the `elf` feature the images themselves are decoded as well, which checks
+//! that every encoding is decoded at the address, and with the width, of the
+//! listing. No external tools are needed to run the test.
+
+use std::collections::BTreeMap;
+
+use super::{
+    b16::{a_5_2::A5_2, a_5_3::A5_3, a_5_4::A5_4, a_5_5::A5_5, a_5_6::A5_6, a_5_8::A5_8, B16},
+    lookup::wide_table,
+    Table,
+};
+use crate::{buffer::SliceStream, it_block::ItState, operation::Operation, Parse};
+
+/// A fixture image and its reference listing.
+struct Fixture {
+    name: &'static str,
+    listing: &'static str,
+    #[cfg_attr(not(feature = "elf"), allow(dead_code))]
+    image: &'static [u8],
+}
+
+/// The fixtures in `fixtures/differential`.
+const FIXTURES: [Fixture; 3] = [
+    Fixture {
+        name: "cortex_m0",
+        listing: include_str!("../../fixtures/differential/cortex_m0.lst"),
+        image: include_bytes!("../../fixtures/differential/cortex_m0.elf"),
+    },
+    Fixture {
+        name: "cortex_m3",
+        listing: include_str!("../../fixtures/differential/cortex_m3.lst"),
+        image: include_bytes!("../../fixtures/differential/cortex_m3.elf"),
+    },
+    Fixture {
+        name: "cortex_m4f",
+        listing: include_str!("../../fixtures/differential/cortex_m4f.lst"),
+        image: include_bytes!("../../fixtures/differential/cortex_m4f.elf"),
+    },
+];
+
+/// An instruction in a reference listing.
+#[derive(Debug)]
+struct Line {
+    address: u32,
+    /// The encoding in memory order.
+    bytes: Vec<u8>,
+    /// The mnemonic and the operands.
+    text: String,
+}
+
+/// A disagreement between the decoder and the reference listing.
+#[derive(Debug)]
+struct Mismatch {
+    fixture: &'static str,
+    address: u32,
+    bytes: Vec<u8>,
+    expected: String,
+    decoded: String,
+}
+
+/// Parses the instructions out of a listing, labels, headers and data
+/// directives such as `.word` are skipped.
+fn instructions(listing: &str) -> Vec<Line> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let (address, rest) = line.trim_start().split_once(':')?;
+            let address = u32::from_str_radix(address, 16).ok()?;
+            let mut fields = rest.trim_start().splitn(2, '\t');
+            let bytes = fields
+                .next()?
+                .split_whitespace()
+                .map(|byte| u8::from_str_radix(byte, 16).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            let text = fields.next()?.trim();
+            if text.starts_with('.') || bytes.is_empty() {
+                return None;
+            }
+            Some(Line {
+                address,
+                bytes,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rewrites an absolute branch target as the offset from the PC, which is
+/// how the decoder renders it.
+fn relative(token: &str, address: u32) -> Option<String> {
+    let target = u32::from_str_radix(token.strip_prefix("0x")?, 16).ok()?;
+    Some(format!(
+        "#{}",
+        target.wrapping_sub(address.wrapping_add(4)) as i32
+    ))
+}
+
+/// Rewrites hexadecimal immediates as decimal.
+fn decimal(token: &str) -> String {
+    let (sign, digits) = match token.strip_prefix("#-0x") {
+        Some(digits) => ("-", digits),
+        None => match token.strip_prefix("#0x") {
+            Some(digits) => ("", digits),
+            None => return token.to_string(),
+        },
+    };
+    match u32::from_str_radix(digits, 16) {
+        Ok(value) => format!("#{sign}{value}"),
+        Err(_) => token.to_string(),
+    }
+}
+
+/// Mnemonics that both spellings of UAL accept, mapped to the canonical one.
+const ALIASES: [(&str, &str); 7] = [
+    ("addw", "add"),
+    ("subw", "sub"),
+    ("movw", "mov"),
+    ("ldmia", "ldm"),
+    ("stmia", "stm"),
+    ("vldmia", "vldm"),
+    ("vstmia", "vstm"),
+];
+
+/// Mnemonics where UAL allows the destination to be omitted if it is the same
+/// as the first source register.
+const FOLDED: [&str; 17] = [
+    "adc", "add", "and", "asr", "bic", "eor", "lsl", "lsr", "mul", "orn", "orr", "ror", "rsb",
+    "sbc", "sdiv", "sub", "udiv",
+];
+
+/// The condition codes that may follow a mnemonic.
+const CONDITIONS: [&str; 17] = [
+    "eq", "ne", "cs", "hs", "cc", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le",
+    "al",
+];
+
+/// Returns true if UAL allows the destination of `mnemonic` to be omitted,
+/// the mnemonic may carry an `s` and a condition.
+fn folds(mnemonic: &str) -> bool {
+    FOLDED.iter().any(|base| {
+        let Some(rest) = mnemonic.strip_prefix(base) else {
+            return false;
+        };
+        let rest = rest.strip_prefix('s').unwrap_or(rest);
+        rest.is_empty() || CONDITIONS.contains(&rest)
+    })
+}
+
+/// Rewrites a floating point immediate in a single canonical form.
+fn float(token: &str) -> String {
+    match token.strip_prefix('#').map(str::parse::<f64>) {
+        Some(Ok(value)) => format!("#{value:?}"),
+        _ => token.to_string(),
+    }
+}
+
+/// Normalises the text of an instruction at `address`, returning the width
+/// in bits that the `.n` or `.w` qualifier requests, if any.
+///
+/// Comments and symbolic annotations are removed, the whitespace is
+/// collapsed, immediates are rendered in decimal and absolute branch targets
+/// are rewritten relative to the PC. The spellings that UAL leaves to the
+/// disassembler are made canonical, aliased mnemonics are renamed, a
+/// destination that repeats the first source is omitted where UAL allows it
+/// and a zero `opc2` of `mcr` and `mrc` is omitted.
+fn normalise(text: &str, address: u32) -> (Option<usize>, String) {
+    let text = text.split('@').next().unwrap_or_default();
+    let text = match text.find('<') {
+        Some(start) => &text[..start],
+        None => text,
+    };
+    let text = text.to_lowercase();
+    let mut tokens = text.split_whitespace();
+    let mnemonic = tokens.next().unwrap_or_default();
+    let (width, mnemonic) = match (mnemonic.strip_suffix(".w"), mnemonic.strip_suffix(".n")) {
+        (Some(mnemonic), _) => (Some(32), mnemonic),
+        (_, Some(mnemonic)) => (Some(16), mnemonic),
+        _ => (None, mnemonic),
+    };
+    let mnemonic = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == mnemonic)
+        .map_or(mnemonic, |(_, canonical)| canonical)
+        .to_string();
+    let operands = tokens.collect::<Vec<_>>().join(" ");
+    let mut operands = operands
+        .split(',')
+        .map(str::trim)
+        .filter(|operand| !operand.is_empty())
+        .map(|operand| relative(operand, address).unwrap_or_else(|| decimal(operand)))
+        .map(|operand| match mnemonic.starts_with('v') {
+            true => float(&operand),
+            false => operand,
+        })
+        .collect::<Vec<_>>();
+    if operands.len() >= 3 && operands[0] == operands[1] && folds(&mnemonic) {
+        operands.remove(0);
+    }
+    if (mnemonic.starts_with("mcr") || mnemonic.starts_with("mrc"))
+        && operands.len() == 6
+        && operands[5] == "#0"
+    {
+        operands.pop();
+    }
+    let text = match operands.is_empty() {
+        true => mnemonic,
+        false => format!("{mnemonic} {}", operands.join(", ")),
+    };
+    (width, text)
+}
+
+/// Returns the name of the decode table that the encoding is dispatched to.
+fn table(bytes: &[u8]) -> &'static str {
+    let halfword = u16::from_le_bytes([bytes[0], bytes[1]]);
+    if bytes.len() == 4 {
+        let low = u16::from_le_bytes([bytes[2], bytes[3]]);
+        return wide_table(((halfword as u32) << 16) | low as u32);
+    }
+    let opcode = halfword >> 10;
+    match opcode {
+        0b010000 => A5_3::NAME,
+        0b010001 => A5_4::NAME,
+        _ if opcode >> 4 == 0 => A5_2::NAME,
+        _ if opcode >> 2 == 0b0101 || opcode >> 3 == 0b011 || opcode >> 3 == 0b100 => A5_5::NAME,
+        _ if opcode >> 2 == 0b1011 => A5_6::NAME,
+        _ if opcode >> 2 == 0b1101 => A5_8::NAME,
+        _ => B16::NAME,
+    }
+}
+
+/// Decodes every instruction in the listing and collects the disagreements.
+fn compare(fixture: &'static str, listing: &str) -> Vec<Mismatch> {
+    let mut state = ItState::new();
+    let mut mismatches = Vec::new();
+    for line in instructions(listing) {
+        let (width, expected) = normalise(&line.text, line.address);
+        let decoded = match Operation::parse(&mut SliceStream::new(&line.bytes)) {
+            Ok((size, _)) if size / 8 != line.bytes.len() || width.is_some_and(|w| w != size) => {
+                format!("a {size}-bit encoding")
+            }
+            Ok((size, operation)) => {
+                let conditional = state.advance(&operation);
+                normalise(
+                    &operation
+                        .ual()
+                        .size(size)
+                        .in_it_block(conditional.in_it_block)
+                        .condition(conditional.condition)
+                        .to_string(),
+                    line.address,
+                )
+                .1
+            }
+            Err(error) => format!("error, {}", error.kind),
+        };
+        if expected != decoded {
+            mismatches.push(Mismatch {
+                fixture,
+                address: line.address,
+                bytes: line.bytes,
+                expected,
+                decoded,
+            });
+        }
+    }
+    mismatches
+}
+
+/// Renders the mismatches grouped by decode table.
+fn report(mismatches: &[Mismatch]) -> String {
+    let mut tables: BTreeMap<&str, Vec<&Mismatch>> = BTreeMap::new();
+    for mismatch in mismatches {
+        tables
+            .entry(table(&mismatch.bytes))
+            .or_default()
+            .push(mismatch);
+    }
+    let mut report = format!(
+        "{} mismatches against the reference listings\n",
+        mismatches.len()
+    );
+    for (table, mismatches) in tables {
+        report.push_str(&format!("{table} ({})\n", mismatches.len()));
+        for mismatch in mismatches {
+            let bytes = mismatch
+                .bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            report.push_str(&format!(
+                "  {} {:#010x} [{bytes}] expected `{}`, decoded `{}`\n",
+                mismatch.fixture, mismatch.address, mismatch.expected, mismatch.decoded
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compare, instructions, normalise, report, table, FIXTURES};
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(
+            normalise("b.w\t0x8000100 <main>        @ imm = #252", 0x0800_0000),
+            (Some(32), "b #252".to_string())
+        );
+        assert_eq!(
+            normalise("tst.w\tr2, #0xff00", 0x0800_0000),
+            (Some(32), "tst r2, #65280".to_string())
+        );
+        assert_eq!(
+            normalise("ldr\tr1, [pc, #16]           @ 0x8000118 <$d.2>", 0),
+            (None, "ldr r1, [pc, #16]".to_string())
+        );
+        assert_eq!(
+            normalise("addw\tr0, r0, #4095", 0),
+            (None, "add r0, #4095".to_string())
+        );
+        assert_eq!(
+            normalise("lsls.n\tr4, r5", 0),
+            (Some(16), "lsls r4, r5".to_string())
+        );
+        assert_eq!(
+            normalise("vmov.f32\ts0, #1.000000e+00", 0),
+            (None, "vmov.f32 s0, #1.0".to_string())
+        );
+        assert_eq!(
+            normalise("mrc\tp15, #0, r0, c1, c0, #0", 0),
+            (None, "mrc p15, #0, r0, c1, c0".to_string())
+        );
+        assert_eq!(table(&[0x00, 0xf0, 0x7e, 0xb8]), "A5_13");
+        assert_eq!(table(&[0x70, 0x47]), "A5_4");
+    }
+
+    #[test]
+    fn test_normalise_destination() {
+        assert_eq!(normalise("subsle\tr1, r1, r2", 0).1, "subsle r1, r2");
+        // UAL requires the destination of these.
+        assert_eq!(normalise("ldrd\tr0, r0, [r1]", 0).1, "ldrd r0, r0, [r1]");
+        assert_eq!(
+            normalise("vcvt.s16.f32\ts0, s0, #16", 0).1,
+            "vcvt.s16.f32 s0, s0, #16.0"
+        );
+    }
+
+    #[test]
+    fn test_differential() {
+        let mut mismatches = Vec::new();
+        let mut compared = 0;
+        for fixture in FIXTURES {
+            compared += instructions(fixture.listing).len();
+            mismatches.extend(compare(fixture.name, fixture.listing));
+        }
+        assert!(compared > 300, "The listings were not parsed");
+        assert!(mismatches.is_empty(), "{}", report(&mismatches));
+    }
+
+    #[cfg(feature = "elf")]
+    #[test]
+    fn test_addresses() {
+        use crate::{decoder::Record, elf};
+
+        for fixture in FIXTURES {
+            let expected: Vec<(u32, usize)> = instructions(fixture.listing)
+                .iter()
+                .map(|line| (line.address, line.bytes.len() * 8))
+                .collect();
+            let sections = elf::decode(fixture.image).expect("Malformed fixture");
+            let decoded: Vec<(u32, usize)> = sections
+                .iter()
+                .inspect(|section| assert!(section.error.is_none(), "{}", fixture.name))
+                .flat_map(|section| &section.records)
+                .filter_map(|record| match record {
+                    Record::Instruction(address, size, _) => Some((*address, *size)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(decoded, expected, "{}", fixture.name);
+        }
+    }
+}
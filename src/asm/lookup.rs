@@ -0,0 +1,304 @@
+//! Defines the table driven decode path.
+//!
+//! Every 16-bit encoding is decoded once, the first time that a 16-bit
+//! encoding is requested, and stored in a table indexed by the halfword.
+//! The 32-bit encodings are dispatched to the table in section A5.3 using a
+//! decision table that is generated at compile time from the `op1`, `op2`
+//! and `op` fields, which skips the nested conditions in
+//! [`B32`](super::b32::B32).
+//!
+//! Both paths produce exactly the same results as [`B16::parse_for`] and
+//! [`B32::parse_for`](super::b32::B32::parse_for), which remain the reference
+//! implementation.
+
+use std::sync::LazyLock;
+
+use super::{
+    b16::B16,
+    b32::{
+        a5_10::A5_10,
+        a5_12::A5_12,
+        a5_13::A5_13,
+        a5_16::A5_16,
+        a5_17::A5_17,
+        a5_18::A5_18,
+        a5_19::A5_19,
+        a5_20::A5_20,
+        a5_21::A5_21,
+        a5_22::A5_22,
+        a5_24::A5_24,
+        a5_28::A5_28,
+        a5_29::A5_29,
+        a5_30::A5_30,
+    },
+    Table,
+};
+use crate::{
+    buffer::SliceStream,
+    operation::Operation,
+    profile::Profile,
+    Parse,
+    ParseError,
+    ParseErrorKind,
+    Stream,
+    ToOperation,
+};
+
+/// The decoded operation for every halfword, or `None` if the halfword does
+/// not decode.
+///
+/// The halfwords that start 32-bit encodings are never looked up.
+static NARROW: LazyLock<Box<[Option<Operation>]>> = LazyLock::new(|| {
+    (0..=u16::MAX)
+        .map(|halfword| {
+            let bytes = halfword.to_le_bytes();
+            B16::parse(&mut SliceStream::new(&bytes))
+                .ok()
+                .map(|(_, operation)| operation)
+        })
+        .collect()
+});
+
+/// Decodes a 16-bit encoding, see [`B16::parse_for`].
+pub(crate) fn narrow<T: Stream>(
+    iter: &mut T,
+    profile: Profile,
+) -> Result<(usize, Operation), ParseError> {
+    let halfword: u16 = match iter.peek::<1>() {
+        Some(halfword) => halfword,
+        None => return Err(ParseErrorKind::IncompleteProgram.into()),
+    };
+    let operation = match &NARROW[halfword as usize] {
+        Some(operation) => operation.clone(),
+        // Decoding the halfword again reports the same error as the
+        // reference implementation.
+        None => return B16::parse_for(iter, profile),
+    };
+    profile.check(16, &operation)?;
+    let _: [u16; 1] = match iter.consume::<1>() {
+        Some(val) => val,
+        None => return Err(ParseErrorKind::IncompleteProgram.into()),
+    };
+    Ok((16, operation))
+}
+
+/// The tables that 32-bit encodings are dispatched to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Wide {
+    A5_10,
+    A5_12,
+    A5_13,
+    A5_16,
+    A5_17,
+    A5_18,
+    A5_19,
+    A5_20,
+    A5_21,
+    A5_22,
+    A5_24,
+    A5_28,
+    A5_29,
+    A5_30,
+    Undefined,
+}
+
+impl Wide {
+    /// The name of the table, as recorded in [`ParseError::path`].
+    #[cfg(test)]
+    const fn name(self) -> &'static str {
+        match self {
+            Self::A5_10 => A5_10::NAME,
+            Self::A5_12 => A5_12::NAME,
+            Self::A5_13 => A5_13::NAME,
+            Self::A5_16 => A5_16::NAME,
+            Self::A5_17 => A5_17::NAME,
+            Self::A5_18 => A5_18::NAME,
+            Self::A5_19 => A5_19::NAME,
+            Self::A5_20 => A5_20::NAME,
+            Self::A5_21 => A5_21::NAME,
+            Self::A5_22 => A5_22::NAME,
+            Self::A5_24 => A5_24::NAME,
+            Self::A5_28 => A5_28::NAME,
+            Self::A5_29 => A5_29::NAME,
+            Self::A5_30 => A5_30::NAME,
+            Self::Undefined => super::b32::B32::NAME,
+        }
+    }
+}
+
+/// Returns the name of the table that a 32-bit encoding is dispatched to.
+#[cfg(test)]
+pub(crate) fn wide_table(word: u32) -> &'static str {
+    WIDE[index(word)].name()
+}
+
+/// Extracts the `op1:op2:op` index in to [`WIDE`] from an encoding.
+const fn index(word: u32) -> usize {
+    (((word >> 19) & 0b11_1111_1110) | ((word >> 15) & 1)) as usize
+}
+
+/// The table that each encoding is dispatched to, indexed by `op1:op2:op`.
+static WIDE: [Wide; 1 << 10] = wide_tree();
+
+/// Generates the [`WIDE`] decision table.
+const fn wide_tree() -> [Wide; 1 << 10] {
+    let mut tree = [Wide::Undefined; 1 << 10];
+    let mut idx = 0;
+    while idx < tree.len() {
+        tree[idx] = select(
+            (idx >> 8) as u32,
+            ((idx >> 1) & 0b111_1111) as u32,
+            (idx & 1) as u32,
+        );
+        idx += 1;
+    }
+    tree
+}
+
+/// Selects the table for an encoding, mirrors the conditions in
+/// [`B32`](super::b32::B32).
+const fn select(op1: u32, op2: u32, op: u32) -> Wide {
+    if op1 == 1 {
+        if (op2 >> 2) & 0b11001 == 0b00000 {
+            return Wide::A5_16;
+        }
+        if (op2 >> 2) & 0b11001 == 0b00001 {
+            return Wide::A5_17;
+        }
+        if op2 >> 5 == 1 {
+            return Wide::A5_22;
+        }
+        if op2 >> 6 == 1 {
+            return Wide::A5_30;
+        }
+        return Wide::Undefined;
+    }
+    if op1 == 2 {
+        if op == 1 {
+            return Wide::A5_13;
+        }
+        if op2 & 0b010_0000 == 0 {
+            return Wide::A5_10;
+        }
+        return Wide::A5_12;
+    }
+    if op2 & 0b111_0001 == 0 {
+        return Wide::A5_21;
+    }
+    match op2 & 0b110_0111 {
+        0b000_0001 => return Wide::A5_20,
+        0b000_0011 => return Wide::A5_19,
+        0b000_0101 => return Wide::A5_18,
+        0b000_0111 => return Wide::Undefined,
+        _ => {}
+    }
+    if op2 >> 4 == 2 {
+        return Wide::A5_24;
+    }
+    if op2 >> 3 == 0b0110 {
+        return Wide::A5_28;
+    }
+    if op2 >> 3 == 0b0111 {
+        return Wide::A5_29;
+    }
+    if op2 >> 6 == 1 {
+        return Wide::A5_30;
+    }
+    Wide::Undefined
+}
+
+/// Decodes a 32-bit encoding, see
+/// [`B32::parse_for`](super::b32::B32::parse_for).
+pub(crate) fn wide<T: Stream>(
+    iter: &mut T,
+    profile: Profile,
+) -> Result<(usize, Operation), ParseError> {
+    let word: u32 = match iter.peek::<1>() {
+        Some(word) => word,
+        None => return Err(ParseErrorKind::IncompleteProgram.into()),
+    };
+    let operation = match WIDE[index(word)] {
+        Wide::A5_10 => A5_10::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_12 => A5_12::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_13 => A5_13::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_16 => A5_16::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_17 => A5_17::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_18 => A5_18::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_19 => A5_19::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_20 => A5_20::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_21 => A5_21::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_22 => A5_22::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_24 => A5_24::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_28 => A5_28::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_29 => A5_29::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::A5_30 => A5_30::parse_table(iter)?.encoding_specific_operations()?,
+        Wide::Undefined => return Err(ParseErrorKind::Undefined.into()),
+    };
+    profile.check(32, &operation)?;
+    let _: [u32; 1] = match iter.consume::<1>() {
+        Some(val) => val,
+        None => return Err(ParseErrorKind::IncompleteProgram.into()),
+    };
+    Ok((32, operation))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{narrow, wide, B16};
+    use crate::{
+        asm::b32::B32,
+        bench::XorShift,
+        buffer::SliceStream,
+        profile::{Architecture, Profile},
+        Stream,
+    };
+
+    #[test]
+    fn test_narrow_equivalence() {
+        let profiles = [Profile::default(), Architecture::V6M.into()];
+        for halfword in 0..=u16::MAX {
+            let bytes = halfword.to_le_bytes();
+            for profile in profiles {
+                let mut reference = SliceStream::new(&bytes);
+                let mut table = SliceStream::new(&bytes);
+                assert_eq!(
+                    narrow(&mut table, profile),
+                    B16::parse_for(&mut reference, profile),
+                    "{halfword:#06x}"
+                );
+                assert_eq!(table.offset(), reference.offset());
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_equivalence() {
+        // Every entry in the decision table with a spread of operands.
+        let mut random = XorShift::new(0x2545_f491);
+        for idx in 0..1u32 << 10 {
+            for state in random.by_ref().take(256) {
+                let word = 0xe000_0000
+                    | (idx >> 1) << 20
+                    | (idx & 1) << 15
+                    | (state & 0x000f_7fff)
+                    | ((idx >> 8).max(1) << 27);
+                let bytes = [
+                    (word >> 16) as u8,
+                    (word >> 24) as u8,
+                    word as u8,
+                    (word >> 8) as u8,
+                ];
+                for profile in [Profile::default(), Architecture::V7M.into()] {
+                    let mut reference = SliceStream::new(&bytes);
+                    let mut table = SliceStream::new(&bytes);
+                    assert_eq!(
+                        wide(&mut table, profile),
+                        B32::parse_for(&mut reference, profile),
+                        "{word:#010x}"
+                    );
+                    assert_eq!(table.offset(), reference.offset());
+                }
+            }
+        }
+    }
+}
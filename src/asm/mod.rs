@@ -1,9 +1,27 @@
 //! Defines the statements available in armv7.
 
-use crate::ParseError;
+use crate::{Parse, ParseError, ParseErrorKind, Stream};
 
 pub mod b16;
 pub mod b32;
+#[cfg(test)]
+mod differential;
+pub(crate) mod lookup;
+
+/// A decode table, i.e. a set of encodings that are selected between using
+/// a few bits of the encoding.
+pub(crate) trait Table: Parse {
+    /// The name of the table in the architecture reference manual.
+    const NAME: &'static str;
+
+    /// Parses the table, recording the table in the path of any error.
+    fn parse_table<T: Stream>(iter: &mut T) -> Result<Self::Target, ParseError>
+    where
+        Self: Sized,
+    {
+        Self::parse(iter).map_err(|e| e.within(Self::NAME))
+    }
+}
 
 pub(crate) trait LocalTryInto<T> {
     fn local_try_into(self) -> Result<T, ParseError>;
@@ -16,9 +34,9 @@ pub(crate) trait Mask {
 impl LocalTryInto<bool> for u8 {
     fn local_try_into(self) -> Result<bool, ParseError> {
         if self > 1 {
-            return Err(ParseError::InvalidField(format!(
-                "Invalid masking of bool {self}"
-            )));
+            return Err(
+                ParseErrorKind::InvalidField(format!("Invalid masking of bool {self}")).into(),
+            );
         }
         Ok(self != 0)
     }
@@ -26,32 +44,24 @@ impl LocalTryInto<bool> for u8 {
 impl LocalTryInto<bool> for u32 {
     fn local_try_into(self) -> Result<bool, ParseError> {
         if self > 1 {
-            return Err(ParseError::InvalidField(format!(
-                "Invalid masking of bool {self}"
-            )));
+            return Err(
+                ParseErrorKind::InvalidField(format!("Invalid masking of bool {self}")).into(),
+            );
         }
         Ok(self != 0)
     }
 }
 impl Mask for u16 {
     fn mask<const START: usize, const END: usize>(&self) -> u16 {
-        let intermediate = self >> START;
-        let mask = ((1 << (END - START + 1) as u16) as u16) - 1u16;
-
-        let ret = intermediate & mask;
-        assert!(ret <= mask);
-        ret
+        let mask = u16::MAX >> (15 - (END - START));
+        (self >> START) & mask
     }
 }
 
 impl Mask for u32 {
     fn mask<const START: usize, const END: usize>(&self) -> u32 {
-        let intermediate = self >> START;
-        let mask = ((1 << (END - START + 1) as u32) as u32) - 1u32;
-
-        let ret = intermediate & mask;
-        assert!(ret <= mask);
-        ret
+        let mask = u32::MAX >> (31 - (END - START));
+        (self >> START) & mask
     }
 }
 #[cfg(test)]
@@ -0,0 +1,71 @@
+//! Defines the inputs of the benchmarks and the reference decoder that they
+//! are compared against.
+//!
+//! This module is only available with the `bench` feature enabled, it is not
+//! part of the stable API.
+
+use crate::{
+    asm::{b16::B16, b32::B32},
+    buffer::SliceStream,
+    operation::Operation,
+    profile::Profile,
+    Parse,
+    ParseError,
+    ParseErrorKind,
+    Stream,
+};
+
+/// A xorshift generator, the images are pseudo random but the same on every
+/// run.
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u32,
+}
+
+impl XorShift {
+    /// Creates a new generator from a non zero `seed`.
+    pub const fn new(seed: u32) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Iterator for XorShift {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        Some(self.state)
+    }
+}
+
+/// Generates an image of at least `size` bytes of pseudo random instructions
+/// of the given `sizes`, encodings that do not decode are discarded such that
+/// the image resembles firmware.
+pub fn image(size: usize, sizes: &[usize]) -> Vec<u8> {
+    let mut image = Vec::with_capacity(size + 4);
+    for word in XorShift::new(0x2545_f491) {
+        if image.len() >= size {
+            break;
+        }
+        let candidate = word.rotate_left(16).to_le_bytes();
+        if let Ok((size, _)) = Operation::parse(&mut SliceStream::new(&candidate)) {
+            if sizes.contains(&size) {
+                image.extend(&candidate[..size / 8]);
+            }
+        }
+    }
+    image
+}
+
+/// Parses a single operation using the decoders that walk the decode tables
+/// in section A5, i.e. without the lookup tables that
+/// [`Operation::parse`] uses.
+pub fn reference<T: Stream>(iter: &mut T) -> Result<(usize, Operation), ParseError> {
+    let halfword: u16 = iter.peek::<1>().ok_or(ParseErrorKind::IncompleteProgram)?;
+    match halfword >> 11 {
+        0b11101..=0b11111 => B32::parse_for(iter, Profile::default()),
+        _ => B16::parse_for(iter, Profile::default()),
+    }
+}
@@ -0,0 +1,204 @@
+//! Loads the images that the disassembler accepts.
+//!
+//! Every image is turned in to a set of [`Region`]s, contiguous bytes at a
+//! known load address. ELF images carry their own addresses and `$d` mapping
+//! symbols, Intel HEX files carry their own addresses and raw binaries are
+//! placed at the base address given on the command line.
+
+use std::ops::Range;
+
+use clap::ValueEnum;
+use disarmv7::elf;
+use ihex::{Reader, Record};
+
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+/// The formats that the disassembler accepts.
+pub enum Format {
+    /// Raw bytes, placed at the base address.
+    Raw,
+    /// An ELF32 little endian image.
+    Elf,
+    /// An Intel HEX file.
+    Ihex,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Contiguous bytes at a known load address.
+pub struct Region {
+    /// The name of the region, sections keep their names.
+    pub name: String,
+
+    /// The load address of the first byte in the region.
+    pub address: u32,
+
+    /// The contents of the region.
+    pub bytes: Vec<u8>,
+
+    /// The address ranges that are known to contain data.
+    pub data: Vec<Range<u32>>,
+}
+
+impl Format {
+    /// Guesses the format from the contents of the image.
+    pub fn detect(image: &[u8]) -> Self {
+        if image.starts_with(b"\x7fELF") {
+            return Self::Elf;
+        }
+        let text = image.trim_ascii_start();
+        match text.first() {
+            Some(b':') if text.is_ascii() => Self::Ihex,
+            _ => Self::Raw,
+        }
+    }
+}
+
+impl Region {
+    /// Returns the address one past the last byte in the region.
+    fn end(&self) -> u64 {
+        self.address as u64 + self.bytes.len() as u64
+    }
+
+    /// Returns the part of the region that lies within `start..end`, if any.
+    pub fn trim(&self, start: Option<u32>, end: Option<u32>) -> Option<Self> {
+        let low = start.map_or(self.address as u64, |start| {
+            (start as u64).max(self.address as u64)
+        });
+        let high = end.map_or(self.end(), |end| (end as u64).min(self.end()));
+        if low >= high {
+            return None;
+        }
+        let offset = (low - self.address as u64) as usize;
+        let len = (high - low) as usize;
+        Some(Self {
+            name: self.name.clone(),
+            address: low as u32,
+            bytes: self.bytes[offset..offset + len].to_vec(),
+            data: self.data.clone(),
+        })
+    }
+}
+
+/// Splits the image in to regions.
+///
+/// The `base` address is only used for raw binaries, the other formats
+/// record their own load addresses.
+pub fn load(image: &[u8], format: Format, base: u32) -> Result<Vec<Region>, Error> {
+    match format {
+        Format::Raw => Ok(vec![Region {
+            name: "raw".to_string(),
+            address: base,
+            bytes: image.to_vec(),
+            data: Vec::new(),
+        }]),
+        Format::Elf => Ok(elf::regions(image)?
+            .into_iter()
+            .map(|region| Region {
+                name: region.name,
+                address: region.address,
+                bytes: region.bytes.to_vec(),
+                data: region.data,
+            })
+            .collect()),
+        Format::Ihex => {
+            let text = std::str::from_utf8(image).map_err(|_| Error::NotText)?;
+            ihex(text)
+        }
+    }
+}
+
+/// Collects the data records of an Intel HEX file in to regions, records that
+/// are adjacent in memory are merged.
+fn ihex(text: &str) -> Result<Vec<Region>, Error> {
+    let mut upper = 0u32;
+    let mut chunks: Vec<(u32, Vec<u8>)> = Vec::new();
+    for record in Reader::new(text) {
+        match record? {
+            Record::Data { offset, value } => {
+                chunks.push((upper.wrapping_add(offset as u32), value))
+            }
+            Record::ExtendedSegmentAddress(segment) => upper = (segment as u32) << 4,
+            Record::ExtendedLinearAddress(address) => upper = (address as u32) << 16,
+            Record::EndOfFile => break,
+            Record::StartSegmentAddress { .. } | Record::StartLinearAddress(_) => {}
+        }
+    }
+    chunks.sort_by_key(|(address, _)| *address);
+
+    let mut regions: Vec<Region> = Vec::new();
+    for (address, bytes) in chunks {
+        match regions.last_mut() {
+            Some(region) if region.end() == address as u64 => region.bytes.extend(bytes),
+            Some(region) if region.end() > address as u64 => {
+                return Err(Error::Overlap(address));
+            }
+            _ => regions.push(Region {
+                name: format!("region{}", regions.len()),
+                address,
+                bytes,
+                data: Vec::new(),
+            }),
+        }
+    }
+    Ok(regions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{load, Format, Region};
+    use crate::Error;
+
+    const MINIMAL: &[u8] = include_bytes!("../../../fixtures/elf/minimal.elf");
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Format::detect(MINIMAL), Format::Elf);
+        assert_eq!(Format::detect(b":00000001FF\n"), Format::Ihex);
+        assert_eq!(Format::detect(&[0x70, 0x47]), Format::Raw);
+        assert_eq!(Format::detect(&[]), Format::Raw);
+    }
+
+    #[test]
+    fn test_ihex() {
+        // Two adjacent records at 0x0800_0000 and one at 0x0800_0100.
+        let text = ":020000040800F2\n\
+                    :0400000010B500BF78\n\
+                    :02000400704743\n\
+                    :02010000704746\n\
+                    :00000001FF\n";
+        let regions = load(text.as_bytes(), Format::Ihex, 0).unwrap();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].address, 0x0800_0000);
+        assert_eq!(regions[0].bytes, [0x10, 0xb5, 0x00, 0xbf, 0x70, 0x47]);
+        assert_eq!(regions[1].address, 0x0800_0100);
+
+        // The second record overlaps the first.
+        let text = ":0400000010B500BF78\n:0200020000BF3D\n:00000001FF\n";
+        assert!(matches!(
+            load(text.as_bytes(), Format::Ihex, 0),
+            Err(Error::Overlap(2))
+        ));
+        // The checksum of the record is wrong.
+        assert!(matches!(
+            load(b":0400000010B500BF79\n", Format::Ihex, 0),
+            Err(Error::Hex(_))
+        ));
+    }
+
+    #[test]
+    fn test_trim() {
+        let region = Region {
+            name: "raw".to_string(),
+            address: 0x100,
+            bytes: vec![0x10, 0xb5, 0x00, 0xbf, 0x70, 0x47],
+            data: Vec::new(),
+        };
+        let trimmed = region.trim(Some(0x102), None).unwrap();
+        assert_eq!((trimmed.address, trimmed.bytes.len()), (0x102, 4));
+        let trimmed = region.trim(Some(0xF0), Some(0x104)).unwrap();
+        assert_eq!((trimmed.address, trimmed.bytes.len()), (0x100, 4));
+        assert_eq!(region.trim(Some(0x106), None), None);
+        assert_eq!(region.trim(None, Some(0x100)), None);
+    }
+}
@@ -0,0 +1,280 @@
+//! Disassembles Armv6-M and Armv7-M firmware images.
+//!
+//! ```text
+//! disarmv7 firmware.elf --arch v7em --fpu sp
+//! disarmv7 firmware.bin --base-address 0x08000000 --start 0x08000100 --end 0x08000200
+//! disarmv7 firmware.hex --json
+//! ```
+//!
+//! Every line lists the address, the raw halfwords and the UAL text of an
+//! instruction, followed by the resolved target of PC relative operands. With
+//! `--json` every line is instead a JSON object with the same fields.
+
+#![deny(clippy::all)]
+
+mod load;
+mod render;
+
+use std::{
+    fmt::Display,
+    io::{self, BufWriter, ErrorKind, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Parser, ValueEnum};
+use disarmv7::{
+    elf::ElfError,
+    profile::{Architecture, Extensions, Profile},
+};
+use load::Format;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+/// The architecture profiles that can be selected on the command line.
+enum Arch {
+    /// Armv6-M, e.g. the Cortex-M0 and Cortex-M0+.
+    V6m,
+    /// Armv7-M, e.g. the Cortex-M3.
+    V7m,
+    /// Armv7-M with the DSP extension, e.g. the Cortex-M4 and Cortex-M7.
+    V7em,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+/// The floating point extensions that can be selected on the command line.
+enum Fpu {
+    /// Single precision.
+    Sp,
+    /// Single and double precision.
+    Dp,
+}
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Disassembles Armv6-M and Armv7-M firmware images")]
+struct Args {
+    /// The image to disassemble.
+    input: PathBuf,
+
+    /// The format of the image, detected from its contents if omitted.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// The load address of a raw binary.
+    #[arg(long, value_parser = address)]
+    base_address: Option<u32>,
+
+    /// Only list the addresses at or above this address.
+    #[arg(long, value_parser = address)]
+    start: Option<u32>,
+
+    /// Only list the addresses below this address.
+    #[arg(long, value_parser = address)]
+    end: Option<u32>,
+
+    /// Only decode the encodings that are defined in this architecture,
+    /// every encoding is decoded if omitted.
+    #[arg(long, value_enum)]
+    arch: Option<Arch>,
+
+    /// The floating point extension of the core.
+    #[arg(long, value_enum, requires = "arch")]
+    fpu: Option<Fpu>,
+
+    /// Print one JSON object per line instead of a listing.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug)]
+/// Enumerates the errors that stop the disassembler.
+pub enum Error {
+    /// The image at the path could not be read.
+    Read(PathBuf, io::Error),
+
+    /// The listing could not be written.
+    Io(io::Error),
+
+    /// The ELF image could not be loaded.
+    Elf(ElfError),
+
+    /// The Intel HEX file is malformed.
+    Hex(ihex::ReaderError),
+
+    /// The Intel HEX file is not valid text.
+    NotText,
+
+    /// Two records of the Intel HEX file overlap at the address.
+    Overlap(u32),
+
+    /// The command line arguments contradict each other.
+    Usage(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "could not read {}, {e}", path.display()),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Elf(e) => write!(f, "{e}"),
+            Self::Hex(e) => write!(f, "malformed Intel HEX file, {e}"),
+            Self::NotText => write!(f, "the Intel HEX file is not valid text"),
+            Self::Overlap(address) => {
+                write!(
+                    f,
+                    "the Intel HEX file has overlapping records at {address:#010x}"
+                )
+            }
+            Self::Usage(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ElfError> for Error {
+    fn from(value: ElfError) -> Self {
+        Self::Elf(value)
+    }
+}
+
+impl From<ihex::ReaderError> for Error {
+    fn from(value: ihex::ReaderError) -> Self {
+        Self::Hex(value)
+    }
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal address.
+fn address(value: &str) -> Result<u32, String> {
+    let digits = value.replace('_', "");
+    let parsed = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => digits.parse(),
+    };
+    parsed.map_err(|e| format!("{value} is not a valid address, {e}"))
+}
+
+impl Args {
+    /// Checks that the arguments apply to an image of the `format`.
+    fn check(&self, format: Format) -> Result<(), Error> {
+        if self.base_address.is_some() && format != Format::Raw {
+            return Err(Error::Usage(
+                "--base-address only applies to raw binaries, the other formats carry their own addresses",
+            ));
+        }
+        if self.base_address.is_some_and(|base| base % 2 != 0) {
+            return Err(Error::Usage("--base-address must be halfword aligned"));
+        }
+        if self.start.is_some_and(|start| start % 2 != 0) {
+            return Err(Error::Usage("--start must be halfword aligned"));
+        }
+        Ok(())
+    }
+
+    /// Returns the profile that was selected.
+    fn profile(&self) -> Result<Profile, Error> {
+        let Some(arch) = self.arch else {
+            return Ok(Profile::default());
+        };
+        let fpu = match self.fpu {
+            None => Extensions::NONE,
+            Some(Fpu::Sp) => Extensions::FP_SINGLE,
+            Some(Fpu::Dp) => Extensions::FP_SINGLE | Extensions::FP_DOUBLE,
+        };
+        Ok(match arch {
+            Arch::V6m if !fpu.is_empty() => {
+                return Err(Error::Usage("Armv6-M has no floating point extension"))
+            }
+            Arch::V6m => Profile::new(Architecture::V6M, Extensions::NONE),
+            Arch::V7m => Profile::new(Architecture::V7M, fpu),
+            Arch::V7em => Profile::new(Architecture::V7M, Extensions::DSP | fpu),
+        })
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    let profile = args.profile()?;
+    let image = std::fs::read(&args.input).map_err(|e| Error::Read(args.input.clone(), e))?;
+    let format = args.format.unwrap_or_else(|| Format::detect(&image));
+    args.check(format)?;
+
+    let regions = load::load(&image, format, args.base_address.unwrap_or(0))?;
+    let mut out = BufWriter::new(io::stdout().lock());
+    for region in regions
+        .iter()
+        .filter_map(|region| region.trim(args.start, args.end))
+    {
+        render::region(&mut out, &region, profile, args.json)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The listing was piped in to e.g. `head`.
+        Err(Error::Io(e)) if e.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("disarmv7: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use disarmv7::profile::{Architecture, Extensions, Profile};
+
+    use super::{address, Args, Error};
+    use crate::load::Format;
+
+    #[test]
+    fn test_address() {
+        assert_eq!(address("0x0800_0100"), Ok(0x0800_0100));
+        assert_eq!(address("256"), Ok(256));
+        assert!(address("0x1_0000_0000").is_err());
+        assert!(address("pc").is_err());
+    }
+
+    #[test]
+    fn test_profile() {
+        let args = Args::parse_from(["disarmv7", "image", "--arch", "v7em", "--fpu", "sp"]);
+        assert_eq!(
+            args.profile().unwrap(),
+            Profile::new(Architecture::V7M, Extensions::DSP | Extensions::FP_SINGLE)
+        );
+        let args = Args::parse_from(["disarmv7", "image", "--arch", "v6m", "--fpu", "dp"]);
+        assert!(args.profile().is_err());
+        assert!(Args::try_parse_from(["disarmv7", "image", "--fpu", "dp"]).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let args = Args::parse_from(["disarmv7", "image", "--base-address", "0x0800_0000"]);
+        assert!(args.check(Format::Raw).is_ok());
+        assert!(matches!(args.check(Format::Elf), Err(Error::Usage(_))));
+        let args = Args::parse_from(["disarmv7", "image", "--base-address", "0x0800_0001"]);
+        assert!(matches!(args.check(Format::Raw), Err(Error::Usage(_))));
+        let args = Args::parse_from(["disarmv7", "image", "--start", "0x0800_0001"]);
+        assert!(matches!(args.check(Format::Raw), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_read_error() {
+        let args = Args::parse_from(["disarmv7", "missing.bin"]);
+        let error = super::run(&args).unwrap_err();
+        assert!(matches!(error, Error::Read(..)));
+        assert!(error
+            .to_string()
+            .starts_with("could not read missing.bin, "));
+    }
+}
@@ -0,0 +1,234 @@
+//! Renders the decoded regions as text or as JSON lines.
+
+use std::io::{self, Write};
+
+use disarmv7::{
+    buffer::SliceStream,
+    decoder::{MappedDecoder, Record},
+    it_block::ItState,
+    profile::Profile,
+};
+use serde_json::json;
+
+use crate::load::Region;
+
+/// A line of the listing.
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    /// The address of the first byte.
+    address: u32,
+    /// The encoding or the data in memory order.
+    bytes: &'a [u8],
+    /// Either `instruction`, `data` or `unknown`.
+    kind: &'static str,
+    /// The UAL text of the instruction or the data directive.
+    text: String,
+    /// The resolved target of a PC relative operand.
+    target: Option<u32>,
+    /// The reason that the encoding could not be decoded.
+    error: Option<String>,
+}
+
+impl Entry<'_> {
+    /// Returns the halfwords of the entry, a trailing byte is omitted.
+    fn halfwords(&self) -> Vec<u16> {
+        self.bytes
+            .chunks_exact(2)
+            .map(|halfword| u16::from_le_bytes([halfword[0], halfword[1]]))
+            .collect()
+    }
+
+    /// Renders the entry in the style of `objdump -d`.
+    fn text(&self) -> String {
+        let halfwords = self
+            .halfwords()
+            .iter()
+            .map(|halfword| format!("{halfword:04x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut line = format!("{:08x}:  {halfwords:<9}  {}", self.address, self.text);
+        if let Some(target) = self.target {
+            line.push_str(&format!("  @ {target:#010x}"));
+        }
+        if let Some(error) = &self.error {
+            line.push_str(&format!("  @ {error}"));
+        }
+        line
+    }
+
+    /// Renders the entry as a JSON object.
+    fn json(&self, section: &str) -> serde_json::Value {
+        json!({
+            "section": section,
+            "address": self.address,
+            "kind": self.kind,
+            "halfwords": self.halfwords(),
+            "text": self.text,
+            "target": self.target,
+            "error": self.error,
+        })
+    }
+}
+
+/// Renders data as `.word`, `.short` and `.byte` directives of at most four
+/// bytes each.
+fn data(address: u32, bytes: &[u8]) -> Vec<Entry<'_>> {
+    bytes
+        .chunks(4)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let text = match *chunk {
+                [a, b, c, d] => format!(".word {:#010x}", u32::from_le_bytes([a, b, c, d])),
+                [a, b] => format!(".short {:#06x}", u16::from_le_bytes([a, b])),
+                _ => {
+                    let bytes = chunk
+                        .iter()
+                        .map(|byte| format!("{byte:#04x}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!(".byte {bytes}")
+                }
+            };
+            Entry {
+                address: address.wrapping_add(idx as u32 * 4),
+                bytes: chunk,
+                kind: "data",
+                text,
+                target: None,
+                error: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the bytes of the record at `address` that is `len` bytes long.
+fn slice(region: &Region, address: u32, len: usize) -> &[u8] {
+    let offset = address.wrapping_sub(region.address) as usize;
+    region.bytes.get(offset..offset + len).unwrap_or_default()
+}
+
+/// Decodes the region in to entries, encodings that are not defined in the
+/// `profile` are listed as unknown.
+fn entries(region: &Region, profile: Profile) -> Vec<Entry<'_>> {
+    let mut stream = SliceStream::new(&region.bytes);
+    let decoder = MappedDecoder::new(&mut stream, region.address, region.data.clone())
+        .recovering()
+        .profile(profile);
+    let mut state = ItState::new();
+    let mut entries = Vec::new();
+    for record in decoder {
+        match record {
+            Ok(Record::Instruction(address, size, operation)) => {
+                let conditional = state.advance(&operation);
+                entries.push(Entry {
+                    address,
+                    bytes: slice(region, address, size / 8),
+                    kind: "instruction",
+                    text: operation
+                        .ual()
+                        .size(size)
+                        .in_it_block(conditional.in_it_block)
+                        .condition(conditional.condition)
+                        .to_string(),
+                    target: operation.pc_relative_target(address),
+                    error: None,
+                });
+            }
+            Ok(Record::Data(address, bytes)) => {
+                entries.extend(data(address, slice(region, address, bytes.len())));
+            }
+            Ok(Record::Undecodable {
+                address,
+                size,
+                error,
+                ..
+            }) => entries.push(Entry {
+                address,
+                bytes: slice(region, address, size / 8),
+                kind: "unknown",
+                text: "<unknown>".to_string(),
+                target: None,
+                error: Some(error.kind.to_string()),
+            }),
+            Err(_) => break,
+        }
+    }
+    entries
+}
+
+/// Writes the listing of the region to `out`.
+pub fn region<W: Write>(
+    out: &mut W,
+    region: &Region,
+    profile: Profile,
+    json: bool,
+) -> io::Result<()> {
+    let entries = entries(region, profile);
+    if json {
+        for entry in entries {
+            writeln!(out, "{}", entry.json(&region.name))?;
+        }
+        return Ok(());
+    }
+    writeln!(out, "\nDisassembly of {}:\n", region.name)?;
+    for entry in entries {
+        writeln!(out, "{}", entry.text())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use disarmv7::profile::{Architecture, Profile};
+
+    use super::entries;
+    use crate::load::Region;
+
+    fn region(bytes: Vec<u8>, data: Vec<std::ops::Range<u32>>) -> Region {
+        Region {
+            name: "raw".to_string(),
+            address: 0x0800_0000,
+            bytes,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_text() {
+        // b.w #4 ; it eq ; moveq r0, #1 ; .word 0x12345678
+        let pool = 0x0800_0008..0x0800_000C;
+        let region = region(
+            vec![
+                0x00, 0xf0, 0x02, 0xb8, 0x08, 0xbf, 0x01, 0x20, 0x78, 0x56, 0x34, 0x12,
+            ],
+            vec![pool],
+        );
+        let lines: Vec<String> = entries(&region, Profile::default())
+            .iter()
+            .map(|entry| entry.text())
+            .collect();
+        assert_eq!(lines, vec![
+            "08000000:  f000 b802  b.w #4  @ 0x08000008",
+            "08000004:  bf08       it eq",
+            "08000006:  2001       moveq r0, #1",
+            "08000008:  5678 1234  .word 0x12345678",
+        ]);
+    }
+
+    #[test]
+    fn test_json() {
+        // sdiv r0, r1, r2
+        let region = region(vec![0x91, 0xfb, 0xf2, 0xf0], Vec::new());
+        let decoded = entries(&region, Profile::default());
+        assert_eq!(
+            decoded[0].json("raw").to_string(),
+            r#"{"address":134217728,"error":null,"halfwords":[64401,61682],"kind":"instruction","section":"raw","target":null,"text":"sdiv r0, r1, r2"}"#
+        );
+
+        // Armv6-M has no division.
+        let rejected = entries(&region, Architecture::V6M.into());
+        assert_eq!(rejected[0].kind, "unknown");
+        assert_eq!(rejected[0].halfwords(), vec![0xfb91, 0xf0f2]);
+        assert!(rejected[0].error.is_some());
+    }
+}
@@ -7,6 +7,10 @@
 //! Armv7 encoding, this allows for a 1:1 parsing in the implementors
 //! of [`Parse`](crate::Parse).
 //!
+//! Images that are already in memory should be decoded using a
+//! [`SliceStream`] instead, which peeks and consumes in constant time
+//! without copying the bytes.
+//!
 //!
 //! ## Usage
 //!
@@ -40,7 +44,7 @@
 //! assert!(value == target);
 //! ```
 
-use std::{fmt::Debug, usize};
+use std::fmt::Debug;
 
 use crate::{Consume, Peek, Stream};
 
@@ -55,6 +59,7 @@ use crate::{Consume, Peek, Stream};
 pub struct PeekableBuffer<I: Sized, T: Iterator<Item = I>> {
     iter: T,
     peeked_elements: Vec<u8>,
+    consumed: usize,
 }
 impl<T: Sized + Iterator<Item = u8>> PeekableBuffer<u8, T> {
     // Peeks a u16 in to the peeked elements buffer
@@ -73,6 +78,29 @@ impl<T: Sized + Iterator<Item = u8>> PeekableBuffer<u8, T> {
         self.peeked_elements.extend(intermediate.iter().rev());
         counter == 2
     }
+
+    /// Peeks until at least `count` bytes are buffered, returns false if the
+    /// iterator is exhausted before that.
+    fn fill(&mut self, count: usize) -> bool {
+        while self.peeked_elements.len() < count {
+            if !self.peek_count() {
+                break;
+            }
+        }
+        self.peeked_elements.len() >= count
+    }
+
+    /// Returns the halfword that starts `idx` halfwords after the current
+    /// position.
+    fn halfword(&mut self, idx: usize) -> Option<u16> {
+        let offset = idx.checked_mul(2)?;
+        if !self.fill(offset.checked_add(2)?) {
+            // Insufficient elements
+            return None;
+        }
+        let els = &self.peeked_elements;
+        Some(u16::from_ne_bytes([els[offset + 1], els[offset]]))
+    }
 }
 
 impl<T: Sized + Iterator<Item = u8>> Peek<u32> for PeekableBuffer<u8, T>
@@ -80,8 +108,9 @@ where
     Self: Peek<u16>,
 {
     fn peek<const N: usize>(&mut self) -> Option<u32> {
-        let first: u16 = self.peek::<1>()?;
-        let second: u16 = self.peek::<2>()?;
+        let idx = N.checked_sub(1)?.checked_mul(2)?;
+        let first = self.halfword(idx)?;
+        let second = self.halfword(idx.checked_add(1)?)?;
         let ret = ((first as u32) << 16) | (second as u32);
 
         // Get the new byte and return it as a u32
@@ -91,44 +120,26 @@ where
 
 impl<T: Sized + Iterator<Item = u8>> Peek<u16> for PeekableBuffer<u8, T> {
     fn peek<const N: usize>(&mut self) -> Option<u16> {
-        let mut peeked = self.peeked_elements.len();
-
-        // Need to have peeked 2 u8s per u16
-        while peeked < N * 2 {
-            if !self.peek_count() {
-                // Insufficient elements
-                return None;
-            }
-            peeked = self.peeked_elements.len();
-        }
-        let offset = (N - 1) * 2;
-        let els = &self.peeked_elements;
-        let data = [els[offset + 1], els[offset]];
-
-        // Get the new byte and return it as a u16
-        Some(u16::from_ne_bytes(data))
+        self.halfword(N.checked_sub(1)?)
     }
 }
 
 impl<T: Sized + Iterator<Item = u8>> Peek<u8> for PeekableBuffer<u8, T> {
     fn peek<const N: usize>(&mut self) -> Option<u8> {
-        let mut peeked = self.peeked_elements.len();
-        // Need to have peeked 2 u8s per u8 to make the peek invariant
-        while peeked < N {
-            if !self.peek_count() {
-                // Insufficient elements
-                return None;
-            }
-            peeked = self.peeked_elements.len();
+        let idx = N.checked_sub(1)?;
+        if !self.fill(idx.checked_add(1)?) {
+            // Insufficient elements
+            return None;
         }
-
-        //
-        Some(self.peeked_elements[N - 1])
+        Some(self.peeked_elements[idx])
     }
 }
 
 impl<T: Iterator<Item = u8> + Debug> Consume<u32> for PeekableBuffer<u8, T> {
     fn consume<const N: usize>(&mut self) -> Option<[u32; N]> {
+        if N == 0 {
+            return Some([0; N]);
+        }
         <Self as Peek<u32>>::peek::<N>(self)?;
 
         if N == 1 {
@@ -145,6 +156,9 @@ impl<T: Iterator<Item = u8> + Debug> Consume<u32> for PeekableBuffer<u8, T> {
 }
 impl<T: Iterator<Item = u8> + Debug> Consume<u16> for PeekableBuffer<u8, T> {
     fn consume<const N: usize>(&mut self) -> Option<[u16; N]> {
+        if N == 0 {
+            return Some([0; N]);
+        }
         <Self as Peek<u16>>::peek::<N>(self)?;
         if N == 1 {
             let [first, second]: [u8; 2] = self.consume::<2>()?;
@@ -161,10 +175,16 @@ impl<T: Iterator<Item = u8> + Debug> Consume<u16> for PeekableBuffer<u8, T> {
 
 impl<T: Iterator<Item = u8> + Debug> Consume<u8> for PeekableBuffer<u8, T> {
     fn consume<const N: usize>(&mut self) -> Option<[u8; N]> {
+        if N == 0 {
+            return Some([0; N]);
+        }
         <Self as Peek<u8>>::peek::<N>(self)?;
         if N == 1 {
             return match self.peeked_elements.first() {
-                Some(_val) => Some([self.peeked_elements.remove(0); N]),
+                Some(_val) => {
+                    self.consumed += 1;
+                    Some([self.peeked_elements.remove(0); N])
+                }
                 None => {
                     let _: u8 = self.peek::<1>()?;
                     self.consume()
@@ -180,13 +200,246 @@ impl<T: Iterator<Item = u8> + Debug> Consume<u8> for PeekableBuffer<u8, T> {
     }
 }
 
-impl<T: Iterator<Item = u8> + Debug> Stream for PeekableBuffer<u8, T> {}
+impl<T: Iterator<Item = u8> + Debug> Stream for PeekableBuffer<u8, T> {
+    fn offset(&self) -> Option<usize> {
+        Some(self.consumed)
+    }
+}
 
 impl<I: Sized, T: Iterator<Item = I>> From<T> for PeekableBuffer<I, T> {
     fn from(iter: T) -> Self {
         Self {
             iter,
             peeked_elements: Vec::new(),
+            consumed: 0,
+        }
+    }
+}
+
+/// A [`Stream`] over a slice of bytes in memory order.
+///
+/// Peeking and consuming are constant time operations that neither allocate
+/// nor copy the slice, and the bytes are reordered in the same way as by the
+/// [`PeekableBuffer`].
+///
+/// ```
+/// use disarmv7::{buffer::SliceStream, prelude::*};
+///
+/// // bx lr ; nop
+/// let bin = [0x70, 0x47, 0x00, 0xbf];
+/// let mut stream = SliceStream::new(&bin);
+///
+/// let value: u16 = stream.peek::<2>().unwrap();
+/// assert_eq!(value, 0xbf00);
+///
+/// let (size, _) = Operation::parse(&mut stream).unwrap();
+/// assert_eq!(size, 16);
+/// assert_eq!(stream.offset(), Some(2));
+/// assert_eq!(stream.remaining(), &[0x00, 0xbf]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SliceStream<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> SliceStream<'a> {
+    /// Creates a new stream that starts at the first byte of `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    /// Returns the bytes that have not been consumed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.offset..]
+    }
+
+    /// Returns true if all of the bytes have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    /// Returns the byte `idx` bytes after the current position.
+    ///
+    /// The bytes of each halfword are swapped, apart from a trailing byte
+    /// that does not form a complete halfword.
+    #[inline(always)]
+    fn byte(&self, idx: usize) -> Option<u8> {
+        let idx = self.offset.checked_add(idx)?;
+        if idx >= self.bytes.len() {
+            return None;
+        }
+        match self.bytes.get(idx ^ 1) {
+            Some(byte) => Some(*byte),
+            None => Some(self.bytes[idx]),
+        }
+    }
+
+    /// Returns the halfword that starts `idx` bytes after the current
+    /// position.
+    #[inline(always)]
+    fn halfword(&self, idx: usize) -> Option<u16> {
+        let high = self.byte(idx)?;
+        let low = self.byte(idx.checked_add(1)?)?;
+        Some(u16::from_be_bytes([high, low]))
+    }
+
+    /// Returns the word that starts `idx` bytes after the current position.
+    #[inline(always)]
+    fn word(&self, idx: usize) -> Option<u32> {
+        let first = self.halfword(idx)?;
+        let second = self.halfword(idx.checked_add(2)?)?;
+        Some(((first as u32) << 16) | (second as u32))
+    }
+}
+
+impl<'a> From<&'a [u8]> for SliceStream<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl Peek<u32> for SliceStream<'_> {
+    fn peek<const N: usize>(&mut self) -> Option<u32> {
+        self.word(N.checked_sub(1)? * 4)
+    }
+}
+
+impl Peek<u16> for SliceStream<'_> {
+    fn peek<const N: usize>(&mut self) -> Option<u16> {
+        self.halfword(N.checked_sub(1)? * 2)
+    }
+}
+
+impl Peek<u8> for SliceStream<'_> {
+    fn peek<const N: usize>(&mut self) -> Option<u8> {
+        self.byte(N.checked_sub(1)?)
+    }
+}
+
+impl Consume<u32> for SliceStream<'_> {
+    fn consume<const N: usize>(&mut self) -> Option<[u32; N]> {
+        let mut ret = [0; N];
+        for (idx, el) in ret.iter_mut().enumerate() {
+            *el = self.word(idx * 4)?;
         }
+        self.offset += N * 4;
+        Some(ret)
+    }
+}
+
+impl Consume<u16> for SliceStream<'_> {
+    fn consume<const N: usize>(&mut self) -> Option<[u16; N]> {
+        let mut ret = [0; N];
+        for (idx, el) in ret.iter_mut().enumerate() {
+            *el = self.halfword(idx * 2)?;
+        }
+        self.offset += N * 2;
+        Some(ret)
+    }
+}
+
+impl Consume<u8> for SliceStream<'_> {
+    fn consume<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let mut ret = [0; N];
+        for (idx, el) in ret.iter_mut().enumerate() {
+            *el = self.byte(idx)?;
+        }
+        self.offset += N;
+        Some(ret)
+    }
+}
+
+impl Stream for SliceStream<'_> {
+    fn offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PeekableBuffer, SliceStream};
+    use crate::{decoder::linear_sweep, Consume, Peek, Stream};
+
+    const BIN: [u8; 9] = [1, 0, 3, 2, 5, 4, 7, 6, 8];
+
+    #[test]
+    fn test_peek_matches_buffer() {
+        let mut buffer = PeekableBuffer::from(BIN.into_iter());
+        let mut stream = SliceStream::new(&BIN);
+
+        let (a, b): (Option<u16>, Option<u16>) = (buffer.peek::<2>(), stream.peek::<2>());
+        assert_eq!(a, b);
+        let (a, b): (Option<u8>, Option<u8>) = (buffer.peek::<3>(), stream.peek::<3>());
+        assert_eq!(a, b);
+        let (a, b): (Option<u32>, Option<u32>) = (buffer.peek::<1>(), stream.peek::<1>());
+        assert_eq!(a, b);
+
+        let (a, b): (Option<[u8; 1]>, Option<[u8; 1]>) =
+            (buffer.consume::<1>(), stream.consume::<1>());
+        assert_eq!(a, b);
+        let (a, b): (Option<u8>, Option<u8>) = (buffer.peek::<1>(), stream.peek::<1>());
+        assert_eq!(a, b);
+        let (a, b): (Option<[u8; 3]>, Option<[u8; 3]>) =
+            (buffer.consume::<3>(), stream.consume::<3>());
+        assert_eq!(a, b);
+        let (a, b): (Option<[u16; 2]>, Option<[u16; 2]>) =
+            (buffer.consume::<2>(), stream.consume::<2>());
+        assert_eq!(a, b);
+        assert_eq!(buffer.offset(), stream.offset());
+
+        // The trailing byte does not form a halfword.
+        let word: Option<[u16; 1]> = stream.consume::<1>();
+        assert_eq!(word, None);
+        assert_eq!(stream.offset(), Some(8));
+        let byte: Option<[u8; 1]> = stream.consume::<1>();
+        assert_eq!(byte, Some([8]));
+        assert!(stream.is_empty());
+    }
+
+    #[test]
+    fn test_partial_consume() {
+        let mut stream = SliceStream::new(&BIN[..6]);
+        let words: Option<[u32; 2]> = stream.consume::<2>();
+        assert_eq!(words, None);
+        assert_eq!(stream.offset(), Some(0));
+
+        let word: Option<u32> = stream.peek::<1>();
+        assert_eq!(word, Some(0x0001_0203));
+        let word: Option<[u32; 1]> = stream.consume::<1>();
+        assert_eq!(word, Some([0x0001_0203]));
+        assert_eq!(stream.remaining(), &[5, 4]);
+    }
+
+    #[test]
+    fn test_peek_out_of_range() {
+        let mut buffer = PeekableBuffer::from(BIN.into_iter());
+        let mut stream = SliceStream::new(&BIN);
+
+        let (a, b): (Option<u16>, Option<u16>) = (buffer.peek::<0>(), stream.peek::<0>());
+        assert_eq!((a, b), (None, None));
+        let (a, b): (Option<u8>, Option<u8>) = (buffer.peek::<0>(), stream.peek::<0>());
+        assert_eq!((a, b), (None, None));
+        let (a, b): (Option<u32>, Option<u32>) = (buffer.peek::<2>(), stream.peek::<2>());
+        assert_eq!((a, b), (Some(0x0405_0607), Some(0x0405_0607)));
+        let (a, b): (Option<u32>, Option<u32>) = (buffer.peek::<3>(), stream.peek::<3>());
+        assert_eq!((a, b), (None, None));
+        let (a, b): (Option<[u8; 0]>, Option<[u8; 0]>) =
+            (buffer.consume::<0>(), stream.consume::<0>());
+        assert_eq!((a, b), (Some([]), Some([])));
+
+        // A lone trailing byte.
+        let mut buffer = PeekableBuffer::from([8].into_iter());
+        let (a, b): (Option<u8>, Option<u8>) = (buffer.peek::<1>(), stream.peek::<9>());
+        assert_eq!((a, b), (Some(8), Some(8)));
+    }
+
+    #[test]
+    fn test_sweep_matches_buffer() {
+        // mov.w r0, #1 ; an unallocated encoding ; bx lr ; the first half of a
+        // 32-bit encoding
+        let bin = [0x4f, 0xf0, 0x01, 0x00, 0x80, 0xba, 0x70, 0x47, 0x4f, 0xf0];
+        let expected = linear_sweep(&mut PeekableBuffer::from(bin.into_iter()), 0x100);
+        assert_eq!(linear_sweep(&mut SliceStream::new(&bin), 0x100), expected);
     }
 }
@@ -0,0 +1,709 @@
+//! Estimates the number of cycles that an [`Operation`] takes to execute.
+//!
+//! The figures for the Cortex-M3 and Cortex-M4 follow the instruction timing
+//! tables in the respective technical reference manuals, assuming zero wait
+//! state memory. Memory system stalls, exception entry and the time barriers
+//! spend waiting for outstanding transactions are not included.
+//!
+//! The Cortex-M7 dual issues instructions and predicts branches, so its
+//! figures are estimates of a single instruction executed in isolation with
+//! tightly coupled memories, the range covers both a correctly and an
+//! incorrectly predicted branch.
+//!
+//! ```
+//! use disarmv7::{cycles::{Cycles, Core}, prelude::*};
+//!
+//! let pop: Operation = operation::Pop::builder()
+//!     .set_registers(RegisterList {
+//!         registers: vec![Register::R4, Register::R5, Register::PC],
+//!     })
+//!     .complete()
+//!     .into();
+//! // 1 + N + P, where the pipeline refill P is 1 to 3 cycles.
+//! assert_eq!(pop.cycles(Core::CortexM4), Some(Cycles::new(5, 7)));
+//! ```
+
+use std::{fmt::Display, iter::Sum, ops::Add};
+
+use crate::{
+    arch::{Condition, Register},
+    operation::Operation,
+};
+
+/// The processors that the cycle model supports.
+///
+/// The Cortex-M4 is assumed to have the single precision floating point unit
+/// and the Cortex-M7 the double precision one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Core {
+    /// Armv7-M without the DSP extension or a floating point unit.
+    CortexM3,
+    /// Armv7E-M with the FPv4-SP floating point unit.
+    CortexM4,
+    /// Armv7E-M with the FPv5 double precision floating point unit.
+    CortexM7,
+}
+
+/// An inclusive range of cycle counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Cycles {
+    /// The smallest number of cycles the operation can take.
+    pub min: u32,
+    /// The largest number of cycles the operation can take.
+    pub max: u32,
+}
+
+impl Cycles {
+    /// Creates a new range from `min` to `max`.
+    pub const fn new(min: u32, max: u32) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates a range that only contains `cycles`.
+    pub const fn fixed(cycles: u32) -> Self {
+        Self::new(cycles, cycles)
+    }
+
+    /// Widens the range to also cover `other`.
+    pub const fn union(self, other: Self) -> Self {
+        Self {
+            min: if self.min < other.min {
+                self.min
+            } else {
+                other.min
+            },
+            max: if self.max > other.max {
+                self.max
+            } else {
+                other.max
+            },
+        }
+    }
+}
+
+impl Add for Cycles {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.min.saturating_add(rhs.min),
+            self.max.saturating_add(rhs.max),
+        )
+    }
+}
+
+impl Add<u32> for Cycles {
+    type Output = Self;
+
+    fn add(self, rhs: u32) -> Self {
+        self + Self::fixed(rhs)
+    }
+}
+
+impl Sum for Cycles {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl Display for Cycles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.min == self.max {
+            true => write!(f, "{}", self.min),
+            false => write!(f, "{}-{}", self.min, self.max),
+        }
+    }
+}
+
+impl Core {
+    /// The number of cycles needed to refill the pipeline after a branch.
+    ///
+    /// This depends on the alignment and width of the target instruction and,
+    /// on the Cortex-M7, whether or not the branch was predicted.
+    pub const fn refill(&self) -> Cycles {
+        match self {
+            Self::CortexM3 | Self::CortexM4 => Cycles::new(1, 3),
+            Self::CortexM7 => Cycles::new(0, 6),
+        }
+    }
+
+    const fn has_dsp(&self) -> bool {
+        !matches!(self, Self::CortexM3)
+    }
+
+    const fn has_fpu(&self) -> bool {
+        !matches!(self, Self::CortexM3)
+    }
+
+    const fn has_double(&self) -> bool {
+        matches!(self, Self::CortexM7)
+    }
+
+    /// A single load, neighbouring loads are pipelined.
+    const fn load(&self) -> Cycles {
+        Cycles::new(1, 2)
+    }
+
+    /// A single store, the write buffer hides the data phase.
+    const fn store(&self) -> Cycles {
+        match self {
+            Self::CortexM3 | Self::CortexM4 => Cycles::new(1, 2),
+            Self::CortexM7 => Cycles::fixed(1),
+        }
+    }
+
+    /// Loads or stores `words` consecutive words.
+    const fn multiple(&self, words: u32) -> Cycles {
+        match self {
+            Self::CortexM3 | Self::CortexM4 => Cycles::fixed(1 + words),
+            // The 64 bit bus transfers two words per cycle.
+            Self::CortexM7 => Cycles::new(1 + words.div_ceil(2), 1 + words),
+        }
+    }
+
+    /// A branch that is taken if `condition` passes.
+    fn branch(&self, condition: &Condition) -> Cycles {
+        let taken = self.refill() + 1;
+        match condition {
+            Condition::None => taken,
+            _ => taken.union(Cycles::fixed(1)),
+        }
+    }
+
+    /// A multiply accumulate, also used for the DSP multiplies.
+    const fn multiply_accumulate(&self) -> Cycles {
+        match self {
+            Self::CortexM3 | Self::CortexM4 => Cycles::fixed(2),
+            Self::CortexM7 => Cycles::fixed(1),
+        }
+    }
+
+    /// A 32 x 32 -> 64 bit multiply.
+    const fn long_multiply(&self, accumulate: bool) -> Cycles {
+        match (self, accumulate) {
+            // Terminates early depending on the magnitude of the operands.
+            (Self::CortexM3, false) => Cycles::new(3, 5),
+            (Self::CortexM3, true) => Cycles::new(4, 7),
+            _ => Cycles::fixed(1),
+        }
+    }
+
+    /// `sdiv` or `udiv`, which terminate early depending on the number of
+    /// leading zeros of the operands.
+    const fn divide(&self) -> Cycles {
+        Cycles::new(2, 12)
+    }
+
+    /// A floating point operation on doubles.
+    const fn double(&self, cycles: Cycles) -> Option<Cycles> {
+        match self.has_double() {
+            true => Some(cycles),
+            false => None,
+        }
+    }
+}
+
+impl Operation {
+    /// Returns the number of cycles the operation takes on `core`, or
+    /// `None` if the processor cannot execute it.
+    ///
+    /// The range covers all operand values and, for conditional branches,
+    /// both the taken and the not taken case. Operations that are predicated
+    /// by an IT block, e.g. `bxeq lr` or `ldreq pc, [r0]`, are assumed to
+    /// execute, see [`Operation::predicated_cycles`].
+    #[allow(clippy::too_many_lines)]
+    pub fn cycles(&self, core: Core) -> Option<Cycles> {
+        let refill = core.refill();
+        let single = Cycles::fixed(1);
+        let dsp = |cycles: Cycles| core.has_dsp().then_some(cycles);
+        let fpu = |cycles: Cycles| core.has_fpu().then_some(cycles);
+        let registers = |count: usize, pc: bool| {
+            let cycles = core.multiple(count as u32);
+            match pc {
+                true => cycles + refill,
+                false => cycles,
+            }
+        };
+        let load = |rt: &Register| match rt {
+            Register::PC => refill + 2,
+            _ => core.load(),
+        };
+
+        Some(match self {
+            // ================================ Branches ================================
+            Self::B(el) => core.branch(&el.condition),
+            Self::Cbz(_) => core.branch(&Condition::Eq),
+            Self::Bl(_) | Self::Bx(_) | Self::Blx(_) => refill + 1,
+            Self::Tb(_) => refill + 2,
+            // Folded in to the preceding 16 bit instruction if possible.
+            Self::It(_) => Cycles::new(0, 1),
+            Self::MovRegister(el) if el.rd == Register::PC => refill + 1,
+            Self::AddRegister(el) if el.rd.unwrap_or(el.rn) == Register::PC => refill + 1,
+
+            // ============================ Loads and stores ============================
+            Self::LdrImmediate(el) => load(&el.rt),
+            Self::LdrLiteral(el) => load(&el.rt),
+            Self::LdrRegister(el) => load(&el.rt),
+            Self::LdrbImmediate(_)
+            | Self::LdrbLiteral(_)
+            | Self::LdrbRegister(_)
+            | Self::Ldrbt(_)
+            | Self::LdrhImmediate(_)
+            | Self::LdrhLiteral(_)
+            | Self::LdrhRegister(_)
+            | Self::Ldrht(_)
+            | Self::LdrsbImmediate(_)
+            | Self::LdrsbLiteral(_)
+            | Self::LdrsbRegister(_)
+            | Self::Ldrsbt(_)
+            | Self::LdrshImmediate(_)
+            | Self::LdrshLiteral(_)
+            | Self::LdrshRegister(_)
+            | Self::Ldrsht(_)
+            | Self::Ldrt(_) => core.load(),
+            Self::StrImmediate(_)
+            | Self::StrRegister(_)
+            | Self::StrbImmediate(_)
+            | Self::StrbRegister(_)
+            | Self::Strbt(_)
+            | Self::StrhImmediate(_)
+            | Self::StrhRegister(_)
+            | Self::Strht(_)
+            | Self::Strt(_) => core.store(),
+            Self::LdrdImmediate(_) | Self::LdrdLiteral(_) | Self::StrdImmediate(_) => {
+                core.multiple(2)
+            }
+            Self::Ldrex(_)
+            | Self::Ldrexb(_)
+            | Self::Ldrexh(_)
+            | Self::Strex(_)
+            | Self::Strexb(_)
+            | Self::Strexh(_) => Cycles::fixed(2),
+            Self::Ldm(el) => registers(
+                el.registers.registers.len(),
+                el.registers.registers.contains(&Register::PC),
+            ),
+            Self::Ldmdb(el) => registers(
+                el.registers.registers.len(),
+                el.registers.registers.contains(&Register::PC),
+            ),
+            Self::Pop(el) => registers(
+                el.registers.registers.len(),
+                el.registers.registers.contains(&Register::PC),
+            ),
+            Self::Push(el) => registers(el.registers.registers.len(), false),
+            Self::Stm(el) => registers(el.registers.registers.len(), false),
+            Self::Stmdb(el) => registers(el.registers.registers.len(), false),
+
+            // ============================= Multiplication =============================
+            Self::Mul(_) => single,
+            Self::Mla(_) | Self::Mls(_) => core.multiply_accumulate(),
+            Self::Smull(_) | Self::Umull(_) => core.long_multiply(false),
+            Self::Smlal(_) | Self::Umlal(_) => core.long_multiply(true),
+            Self::Sdiv(_) | Self::Udiv(_) => core.divide(),
+            Self::Smla(_)
+            | Self::Smlad(_)
+            | Self::Smlaw(_)
+            | Self::Smlsd(_)
+            | Self::Smmla(_)
+            | Self::Smmls(_)
+            | Self::Smmul(_)
+            | Self::Smuad(_)
+            | Self::Smul(_)
+            | Self::Smulw(_)
+            | Self::Smusd(_)
+            | Self::SmlalSelective(_)
+            | Self::Smlald(_)
+            | Self::Smlsld(_)
+            | Self::Umaal(_) => dsp(single)?,
+
+            // ============================== DSP extension =============================
+            Self::Pkh(_)
+            | Self::Qadd(_)
+            | Self::Qadd16(_)
+            | Self::Qadd8(_)
+            | Self::Qasx(_)
+            | Self::Qdadd(_)
+            | Self::Qdsub(_)
+            | Self::Qsax(_)
+            | Self::Qsub(_)
+            | Self::Qsub16(_)
+            | Self::Qsub8(_)
+            | Self::Sadd16(_)
+            | Self::Sadd8(_)
+            | Self::Sasx(_)
+            | Self::Sel(_)
+            | Self::Shadd16(_)
+            | Self::Shadd8(_)
+            | Self::Shasx(_)
+            | Self::Shsax(_)
+            | Self::Shsub16(_)
+            | Self::Shsub8(_)
+            | Self::Ssat16(_)
+            | Self::Ssax(_)
+            | Self::Ssub16(_)
+            | Self::Ssub8(_)
+            | Self::Sxtab(_)
+            | Self::Sxtab16(_)
+            | Self::Sxtah(_)
+            | Self::Sxtb16(_)
+            | Self::Uadd16(_)
+            | Self::Uadd8(_)
+            | Self::Uasx(_)
+            | Self::Uhadd16(_)
+            | Self::Uhadd8(_)
+            | Self::Uhasx(_)
+            | Self::Uhsax(_)
+            | Self::Uhsub16(_)
+            | Self::Uhsub8(_)
+            | Self::Uqadd16(_)
+            | Self::Uqadd8(_)
+            | Self::Uqasx(_)
+            | Self::Uqsax(_)
+            | Self::Uqsub16(_)
+            | Self::Uqsub8(_)
+            | Self::Usad8(_)
+            | Self::Usada8(_)
+            | Self::Usat16(_)
+            | Self::Usax(_)
+            | Self::Usub16(_)
+            | Self::Usub8(_)
+            | Self::Uxtab(_)
+            | Self::Uxtab16(_)
+            | Self::Uxtah(_)
+            | Self::Uxtb16(_) => dsp(single)?,
+
+            // ================================= System =================================
+            Self::Mrs(_) | Self::Msr(_) => Cycles::fixed(2),
+            // Not a real instruction, never produced by the decoder.
+            Self::Uqsad8(_) => return None,
+            Self::Udf(_) => return None,
+            Self::Cdp(_)
+            | Self::LdcImmediate(_)
+            | Self::LdcLiteral(_)
+            | Self::Mcr(_)
+            | Self::Mcrr(_)
+            | Self::Mrc(_)
+            | Self::Mrrc(_)
+            | Self::Stc(_) => return None,
+
+            // ============================== Floating point ============================
+            Self::VabsF32(_)
+            | Self::VaddF32(_)
+            | Self::VcmpF32(_)
+            | Self::VcmpZeroF32(_)
+            | Self::VcvtF32F16(_)
+            | Self::VcvtF16F32(_)
+            | Self::VcvtFromIntF32(_)
+            | Self::VcvtToIntF32(_)
+            | Self::VcvtFixedF32(_)
+            | Self::VmovImmediateF32(_)
+            | Self::VmovRegisterF32(_)
+            | Self::VmovCoreF32(_)
+            | Self::VmovCoreScalar(_)
+            | Self::Vmrs(_)
+            | Self::Vmsr(_)
+            | Self::VmulF32(_)
+            | Self::VnegF32(_)
+            | Self::VnmulF32(_)
+            | Self::VsubF32(_) => fpu(single)?,
+            Self::VmovCore2F32(_) | Self::VmovCoreF64(_) => fpu(Cycles::fixed(2))?,
+            Self::VfmaF32(_)
+            | Self::VfmsF32(_)
+            | Self::VfnmaF32(_)
+            | Self::VfnmsF32(_)
+            | Self::VmlaF32(_)
+            | Self::VmlsF32(_)
+            | Self::VnmlaF32(_)
+            | Self::VnmlsF32(_) => fpu(Cycles::fixed(3))?,
+            Self::VdivF32(_) | Self::VsqrtF32(_) => fpu(Cycles::fixed(14))?,
+            Self::VldrF32(_) => fpu(core.load())?,
+            Self::VstrF32(_) => fpu(core.store())?,
+            Self::VldrF64(_) | Self::VstrF64(_) => fpu(core.multiple(2))?,
+            Self::VldmF32(el) => fpu(core.multiple(el.registers.len() as u32))?,
+            Self::VstmF32(el) => fpu(core.multiple(el.registers.len() as u32))?,
+            Self::VpopF32(el) => fpu(core.multiple(el.registers.len() as u32))?,
+            Self::VpushF32(el) => fpu(core.multiple(el.registers.len() as u32))?,
+            Self::VldmF64(el) => fpu(core.multiple(2 * el.registers.len() as u32))?,
+            Self::VstmF64(el) => fpu(core.multiple(2 * el.registers.len() as u32))?,
+            Self::VpopF64(el) => fpu(core.multiple(2 * el.registers.len() as u32))?,
+            Self::VpushF64(el) => fpu(core.multiple(2 * el.registers.len() as u32))?,
+            // Introduced by FPv5.
+            Self::VmaxnmF32(_)
+            | Self::VminnmF32(_)
+            | Self::VrintF32(_)
+            | Self::VrintxF32(_)
+            | Self::VselF32(_) => core.double(single)?,
+            Self::VabsF64(_)
+            | Self::VaddF64(_)
+            | Self::VcmpF64(_)
+            | Self::VcmpZeroF64(_)
+            | Self::VcvtF64F16(_)
+            | Self::VcvtF16F64(_)
+            | Self::VcvtF64F32(_)
+            | Self::VcvtF32F64(_)
+            | Self::VcvtFromIntF64(_)
+            | Self::VcvtToIntF64(_)
+            | Self::VcvtFixedF64(_)
+            | Self::VmaxnmF64(_)
+            | Self::VminnmF64(_)
+            | Self::VmovImmediateF64(_)
+            | Self::VmovRegisterF64(_)
+            | Self::VmulF64(_)
+            | Self::VnegF64(_)
+            | Self::VnmulF64(_)
+            | Self::VrintF64(_)
+            | Self::VrintxF64(_)
+            | Self::VselF64(_)
+            | Self::VsubF64(_) => core.double(Cycles::new(1, 3))?,
+            Self::VfmaF64(_)
+            | Self::VfmsF64(_)
+            | Self::VfnmaF64(_)
+            | Self::VfnmsF64(_)
+            | Self::VmlaF64(_)
+            | Self::VmlsF64(_)
+            | Self::VnmlaF64(_)
+            | Self::VnmlsF64(_) => core.double(Cycles::new(3, 6))?,
+            Self::VdivF64(_) | Self::VsqrtF64(_) => core.double(Cycles::fixed(29))?,
+
+            // ============================= Data processing ============================
+            Self::AdcImmediate(_)
+            | Self::AdcRegister(_)
+            | Self::AddImmediate(_)
+            | Self::AddRegister(_)
+            | Self::AddSPImmediate(_)
+            | Self::AddSPRegister(_)
+            | Self::Adr(_)
+            | Self::AndImmediate(_)
+            | Self::AndRegister(_)
+            | Self::AsrImmediate(_)
+            | Self::AsrRegister(_)
+            | Self::Bfc(_)
+            | Self::Bfi(_)
+            | Self::BicImmediate(_)
+            | Self::BicRegister(_)
+            | Self::Clz(_)
+            | Self::CmnImmediate(_)
+            | Self::CmnRegister(_)
+            | Self::CmpImmediate(_)
+            | Self::CmpRegister(_)
+            | Self::EorImmediate(_)
+            | Self::EorRegister(_)
+            | Self::LslImmediate(_)
+            | Self::LslRegister(_)
+            | Self::LsrImmediate(_)
+            | Self::LsrRegister(_)
+            | Self::MovImmediate(_)
+            | Self::MovRegister(_)
+            | Self::Movt(_)
+            | Self::MvnImmediate(_)
+            | Self::MvnRegister(_)
+            | Self::OrnImmediate(_)
+            | Self::OrnRegister(_)
+            | Self::OrrImmediate(_)
+            | Self::OrrRegister(_)
+            | Self::Rbit(_)
+            | Self::Rev(_)
+            | Self::Rev16(_)
+            | Self::Revsh(_)
+            | Self::RorImmediate(_)
+            | Self::RorRegister(_)
+            | Self::Rrx(_)
+            | Self::RsbImmediate(_)
+            | Self::RsbRegister(_)
+            | Self::SbcImmediate(_)
+            | Self::SbcRegister(_)
+            | Self::Sbfx(_)
+            | Self::Ssat(_)
+            | Self::SubImmediate(_)
+            | Self::SubRegister(_)
+            | Self::SubSpMinusImmediate(_)
+            | Self::SubSpMinusRegister(_)
+            | Self::Sxtb(_)
+            | Self::Sxth(_)
+            | Self::TeqImmediate(_)
+            | Self::TeqRegister(_)
+            | Self::TstImmediate(_)
+            | Self::TstRegister(_)
+            | Self::Ubfx(_)
+            | Self::Usat(_)
+            | Self::Uxtb(_)
+            | Self::Uxth(_) => single,
+
+            // =========================== Hints and barriers ===========================
+            Self::Bkpt(_)
+            | Self::Clrex(_)
+            | Self::Cps(_)
+            | Self::Dbg(_)
+            | Self::Dmb(_)
+            | Self::Dsb(_)
+            | Self::Isb(_)
+            | Self::Nop(_)
+            | Self::PldImmediate(_)
+            | Self::PldLiteral(_)
+            | Self::PldRegister(_)
+            | Self::PliImmediate(_)
+            | Self::PliRegister(_)
+            | Self::Sev(_)
+            | Self::Svc(_)
+            | Self::Wfe(_)
+            | Self::Wfi(_)
+            | Self::Yield(_) => single,
+        })
+    }
+
+    /// Returns the number of cycles the operation takes on `core` when it
+    /// only executes if `condition` passes, or `None` if the processor cannot
+    /// execute it.
+    ///
+    /// An operation that is predicated by an IT block takes a single cycle
+    /// if its condition fails, the range is widened to cover that case.
+    ///
+    /// ```
+    /// use disarmv7::{cycles::{Cycles, Core}, prelude::*};
+    ///
+    /// // bxeq lr
+    /// let bx: Operation = operation::Bx::builder().set_rm(Register::LR).complete().into();
+    /// assert_eq!(bx.cycles(Core::CortexM4), Some(Cycles::new(2, 4)));
+    /// assert_eq!(
+    ///     bx.predicated_cycles(Core::CortexM4, &Condition::Eq),
+    ///     Some(Cycles::new(1, 4))
+    /// );
+    /// ```
+    pub fn predicated_cycles(&self, core: Core, condition: &Condition) -> Option<Cycles> {
+        let cycles = self.cycles(core)?;
+        match condition {
+            Condition::None => Some(cycles),
+            _ => Some(cycles.union(Cycles::fixed(1))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    fn list(registers: &[Register]) -> RegisterList {
+        RegisterList {
+            registers: registers.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_branch() {
+        let b: Operation = operation::B::builder()
+            .set_condition(Condition::Ne)
+            .set_imm(8)
+            .complete()
+            .into();
+        assert_eq!(b.cycles(Core::CortexM3), Some(Cycles::new(1, 4)));
+
+        let b: Operation = operation::B::builder()
+            .set_condition(Condition::None)
+            .set_imm(8)
+            .complete()
+            .into();
+        assert_eq!(b.cycles(Core::CortexM4), Some(Cycles::new(2, 4)));
+
+        let bx: Operation = operation::Bx::builder()
+            .set_rm(Register::LR)
+            .complete()
+            .into();
+        assert_eq!(bx.cycles(Core::CortexM7), Some(Cycles::new(1, 7)));
+
+        let mov: Operation = operation::MovRegister::builder()
+            .set_s(None)
+            .set_rd(Register::PC)
+            .set_rm(Register::R0)
+            .complete()
+            .into();
+        assert_eq!(mov.cycles(Core::CortexM4), Some(Cycles::new(2, 4)));
+
+        assert_eq!(
+            mov.predicated_cycles(Core::CortexM4, &Condition::None),
+            Some(Cycles::new(2, 4))
+        );
+        assert_eq!(
+            mov.predicated_cycles(Core::CortexM4, &Condition::Ne),
+            Some(Cycles::new(1, 4))
+        );
+    }
+
+    #[test]
+    fn test_register_lists() {
+        let push: Operation = operation::Push::builder()
+            .set_registers(list(&[Register::R4, Register::R5, Register::LR]))
+            .complete()
+            .into();
+        assert_eq!(push.cycles(Core::CortexM3), Some(Cycles::fixed(4)));
+        assert_eq!(push.cycles(Core::CortexM7), Some(Cycles::new(3, 4)));
+
+        let ldm: Operation = operation::Ldm::builder()
+            .set_w(Some(true))
+            .set_rn(Register::R0)
+            .set_registers(list(&[Register::R1, Register::R2]))
+            .complete()
+            .into();
+        assert_eq!(ldm.cycles(Core::CortexM4), Some(Cycles::fixed(3)));
+
+        let ldm: Operation = operation::Ldm::builder()
+            .set_w(Some(false))
+            .set_rn(Register::R0)
+            .set_registers(list(&[Register::R1, Register::PC]))
+            .complete()
+            .into();
+        assert_eq!(ldm.cycles(Core::CortexM4), Some(Cycles::new(4, 6)));
+    }
+
+    #[test]
+    fn test_multiply() {
+        let smull: Operation = operation::Smull::builder()
+            .set_rdlo(Register::R0)
+            .set_rdhi(Register::R1)
+            .set_rn(Register::R2)
+            .set_rm(Register::R3)
+            .complete()
+            .into();
+        assert_eq!(smull.cycles(Core::CortexM3), Some(Cycles::new(3, 5)));
+        assert_eq!(smull.cycles(Core::CortexM4), Some(Cycles::fixed(1)));
+
+        let udiv: Operation = operation::Udiv::builder()
+            .set_rd(None)
+            .set_rn(Register::R0)
+            .set_rm(Register::R1)
+            .complete()
+            .into();
+        assert_eq!(udiv.cycles(Core::CortexM3), Some(Cycles::new(2, 12)));
+    }
+
+    #[test]
+    fn test_extensions() {
+        let sadd8: Operation = operation::Sadd8::builder()
+            .set_rd(None)
+            .set_rn(Register::R0)
+            .set_rm(Register::R1)
+            .complete()
+            .into();
+        assert_eq!(sadd8.cycles(Core::CortexM3), None);
+        assert_eq!(sadd8.cycles(Core::CortexM4), Some(Cycles::fixed(1)));
+
+        let vdiv: Operation = operation::VdivF64::builder()
+            .set_dd(F64Register::D0)
+            .set_dn(F64Register::D1)
+            .set_dm(F64Register::D2)
+            .complete()
+            .into();
+        assert_eq!(vdiv.cycles(Core::CortexM4), None);
+        assert_eq!(vdiv.cycles(Core::CortexM7), Some(Cycles::fixed(29)));
+    }
+
+    #[test]
+    fn test_sum() {
+        let total: Cycles = [Cycles::fixed(1), Cycles::new(1, 4), Cycles::new(0, 1)]
+            .into_iter()
+            .sum();
+        assert_eq!(total, Cycles::new(2, 6));
+        assert_eq!(total.to_string(), "2-6");
+    }
+}
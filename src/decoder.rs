@@ -0,0 +1,516 @@
+//! Defines an address aware decoder.
+//!
+//! The [`Decoder`] tracks the address of every decoded [`Operation`] which
+//! allows resolving the PC relative operands, see
+//! [`Operation::pc_relative_target`].
+//!
+//! ```
+//! use disarmv7::prelude::*;
+//!
+//! // b.n #-4 ; nop
+//! let bin = [0xfe, 0xe7, 0x00, 0xbf];
+//! let mut stream = PeekableBuffer::from(bin.into_iter());
+//!
+//! let decoded: Vec<(u32, usize, Operation)> = Decoder::new(&mut stream, 0x0800_0000)
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(decoded[1].0, 0x0800_0002);
+//! assert_eq!(decoded[0].2.branch_target(decoded[0].0), Some(0x0800_0000));
+//! ```
+//!
+//! Literal pools and jump tables that are embedded in code can be skipped by
+//! marking them as data, for example using the `$d` and `$t` mapping symbols
+//! of an ELF file, see [`MappedDecoder`]. For binaries without such
+//! information the [`linear_sweep`] keeps decoding past invalid encodings.
+//! The tables of `tbb` and `tbh` can be recovered from the code itself, see
+//! [`jump_table::sweep`](crate::analysis::jump_table::sweep).
+
+use std::ops::Range;
+
+use crate::{
+    encode::Encoded,
+    operation::Operation,
+    profile::Profile,
+    ParseError,
+    ParseErrorKind,
+    Stream,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A record emitted by the [`MappedDecoder`].
+pub enum Record {
+    /// A decoded instruction, contains the address, the size of the encoding
+    /// in bits and the decoded operation.
+    Instruction(u32, usize, Operation),
+
+    /// Bytes that are marked as data, contains the address and the bytes in
+    /// memory order.
+    Data(u32, Vec<u8>),
+
+    /// An encoding that could not be decoded.
+    Undecodable {
+        /// The address of the encoding.
+        address: u32,
+        /// The number of bits that were skipped, either 16 or 32.
+        size: usize,
+        /// The skipped bits, for 32-bit encodings the first halfword is stored
+        /// in the upper 16 bits.
+        bits: u32,
+        /// The reason that the encoding could not be decoded.
+        error: ParseError,
+    },
+}
+
+impl Record {
+    /// Returns the address of the first byte in the record.
+    pub fn address(&self) -> u32 {
+        match self {
+            Self::Instruction(address, _, _)
+            | Self::Data(address, _)
+            | Self::Undecodable { address, .. } => *address,
+        }
+    }
+
+    /// Returns the decoded operation, if any.
+    pub fn operation(&self) -> Option<&Operation> {
+        match self {
+            Self::Instruction(_, _, operation) => Some(operation),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes [`Operation`]s from a [`Stream`] that is placed at a known
+/// address.
+///
+/// Every item is a `(address, size, operation)` record where the size is the
+/// width of the encoding in bits. The decoder stops after the first error,
+/// a trailing byte that does not form a halfword is reported as
+/// [`IncompleteProgram`](ParseErrorKind::IncompleteProgram).
+#[derive(Debug)]
+pub struct Decoder<'a, T: Stream> {
+    stream: &'a mut T,
+    address: u32,
+    failed: bool,
+    profile: Profile,
+}
+
+impl<'a, T: Stream> Decoder<'a, T> {
+    /// Creates a new decoder where the first byte of `stream` is located at
+    /// `base`.
+    pub fn new(stream: &'a mut T, base: u32) -> Self {
+        Self {
+            stream,
+            address: base,
+            failed: false,
+            profile: Profile::default(),
+        }
+    }
+
+    /// Only decodes the encodings that are defined in the `profile`, see
+    /// [`profile`](crate::profile).
+    pub fn profile(mut self, profile: impl Into<Profile>) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Returns the address of the next instruction.
+    pub fn address(&self) -> u32 {
+        self.address
+    }
+}
+
+impl<'a, T: Stream> Iterator for Decoder<'a, T> {
+    type Item = Result<(u32, usize, Operation), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        if (self.stream.peek::<1>() as Option<u16>).is_none() {
+            let _byte: u8 = self.stream.peek::<1>()?;
+            self.failed = true;
+            return Some(Err(ParseError {
+                offset: self.stream.offset(),
+                ..ParseErrorKind::IncompleteProgram.into()
+            }));
+        }
+        match Operation::parse_for(self.stream, self.profile) {
+            Ok((size, operation)) => {
+                let address = self.address;
+                self.address = address.wrapping_add(size as u32 / 8);
+                Some(Ok((address, size, operation)))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Decodes [`Operation`]s from a [`Stream`] that is placed at a known
+/// address, emitting [`Record::Data`] for all halfwords that overlap the
+/// ranges marked as data.
+///
+/// Instructions are halfword aligned, data ranges are therefore extended to
+/// halfword boundaries, a trailing byte that does not form a halfword is
+/// emitted as [`Record::Data`]. The first halfword of a 32-bit encoding that is
+/// followed by data is emitted as a 16-bit [`Record::Undecodable`]. The
+/// decoder stops after the first error unless it is
+/// [`recovering`](Self::recovering).
+///
+/// ```
+/// use disarmv7::{decoder::Record, prelude::*};
+///
+/// // ldr r0, [pc, #0] ; bx lr ; .word 0x12345678
+/// let bin = [0x00, 0x48, 0x70, 0x47, 0x78, 0x56, 0x34, 0x12];
+/// let mut stream = PeekableBuffer::from(bin.into_iter());
+///
+/// let records: Vec<Record> = MappedDecoder::new(&mut stream, 0x100, vec![0x104..0x108])
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(records.len(), 3);
+/// assert_eq!(records[2], Record::Data(0x104, vec![0x78, 0x56, 0x34, 0x12]));
+/// ```
+#[derive(Debug)]
+pub struct MappedDecoder<'a, T: Stream> {
+    decoder: Decoder<'a, T>,
+    data: Vec<Range<u32>>,
+    recover: bool,
+}
+
+impl<'a, T: Stream> MappedDecoder<'a, T> {
+    /// Creates a new decoder where the first byte of `stream` is located at
+    /// `base` and the bytes in `data` are not decoded.
+    pub fn new(stream: &'a mut T, base: u32, data: Vec<Range<u32>>) -> Self {
+        Self {
+            decoder: Decoder::new(stream, base),
+            data,
+            recover: false,
+        }
+    }
+
+    /// Emits a [`Record::Undecodable`] and skips the encoding instead of
+    /// stopping at the first error.
+    ///
+    /// The width of the skipped encoding is determined by the first
+    /// halfword, as for valid encodings.
+    pub fn recovering(mut self) -> Self {
+        self.recover = true;
+        self
+    }
+
+    /// Only decodes the encodings that are defined in the `profile`, see
+    /// [`profile`](crate::profile).
+    pub fn profile(mut self, profile: impl Into<Profile>) -> Self {
+        self.decoder.profile = profile.into();
+        self
+    }
+
+    /// Returns the address of the next record.
+    pub fn address(&self) -> u32 {
+        self.decoder.address
+    }
+
+    /// Returns true if the halfword at `address` overlaps a data range.
+    fn is_data(&self, address: u32) -> bool {
+        let end = address.saturating_add(2);
+        self.data
+            .iter()
+            .any(|range| range.start < end && address < range.end)
+    }
+}
+
+impl<'a, T: Stream> Iterator for MappedDecoder<'a, T> {
+    type Item = Result<Record, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.decoder.address;
+        if self.decoder.failed || !self.is_data(start) {
+            if let Some(record) = self.truncated().or_else(|| self.trailing()) {
+                return Some(Ok(record));
+            }
+            return match self.decoder.next()? {
+                Ok((address, size, op)) => Some(Ok(Record::Instruction(address, size, op))),
+                Err(error) if self.recover => Some(Ok(self.skip(error))),
+                Err(error) => Some(Err(error)),
+            };
+        }
+
+        let mut bytes = Vec::new();
+        while self.is_data(self.decoder.address) {
+            let halfword: Option<[u16; 1]> = self.decoder.stream.consume::<1>();
+            match halfword {
+                Some([halfword]) => bytes.extend(halfword.to_le_bytes()),
+                None => {
+                    if let Some(byte) = self.decoder.stream.step() {
+                        bytes.push(byte);
+                        self.decoder.address = self.decoder.address.wrapping_add(1);
+                    }
+                    break;
+                }
+            }
+            self.decoder.address = self.decoder.address.wrapping_add(2);
+        }
+        match bytes.is_empty() {
+            true => None,
+            false => Some(Ok(Record::Data(start, bytes))),
+        }
+    }
+}
+
+impl<'a, T: Stream> MappedDecoder<'a, T> {
+    /// Skips the first halfword of a 32-bit encoding when the second
+    /// halfword is marked as data.
+    fn truncated(&mut self) -> Option<Record> {
+        let address = self.decoder.address;
+        if self.decoder.failed || !self.is_data(address.wrapping_add(2)) {
+            return None;
+        }
+        let halfword: u16 = self.decoder.stream.peek::<1>()?;
+        if !matches!(halfword >> 11, 0b11101..=0b11111) {
+            return None;
+        }
+        let offset = self.decoder.stream.offset();
+        let _: Option<[u16; 1]> = self.decoder.stream.consume::<1>();
+        self.decoder.address = address.wrapping_add(2);
+        Some(Record::Undecodable {
+            address,
+            size: 16,
+            bits: halfword as u32,
+            error: ParseError::from(ParseErrorKind::IncompleteProgram)
+                .at(offset, Encoded::Narrow(halfword)),
+        })
+    }
+
+    /// Emits a trailing byte that does not form a halfword as data.
+    fn trailing(&mut self) -> Option<Record> {
+        let address = self.decoder.address;
+        if self.decoder.failed || (self.decoder.stream.peek::<1>() as Option<u16>).is_some() {
+            return None;
+        }
+        let byte = self.decoder.stream.step()?;
+        self.decoder.address = address.wrapping_add(1);
+        Some(Record::Data(address, vec![byte]))
+    }
+
+    /// Skips the encoding that caused the `error`.
+    fn skip(&mut self, error: ParseError) -> Record {
+        self.decoder.failed = false;
+        let address = self.decoder.address;
+        let halfword: Option<[u16; 1]> = self.decoder.stream.consume::<1>();
+        let halfword = halfword.map_or(0, |[halfword]| halfword);
+
+        let (size, bits) = match halfword >> 11 {
+            0b11101..=0b11111 => {
+                let second: Option<[u16; 1]> = self.decoder.stream.consume::<1>();
+                match second {
+                    Some([second]) => (32, (halfword as u32) << 16 | second as u32),
+                    None => (16, halfword as u32),
+                }
+            }
+            _ => (16, halfword as u32),
+        };
+        self.decoder.address = address.wrapping_add(size as u32 / 8);
+        Record::Undecodable {
+            address,
+            size,
+            bits,
+            error,
+        }
+    }
+}
+
+/// Decodes the entire stream, emitting a [`Record::Undecodable`] for each
+/// encoding that could not be decoded.
+///
+/// ```
+/// use disarmv7::{decoder::{linear_sweep, Record}, prelude::*};
+///
+/// // An unallocated encoding ; bx lr
+/// let bin = [0x80, 0xba, 0x70, 0x47];
+/// let mut stream = PeekableBuffer::from(bin.into_iter());
+///
+/// let records = linear_sweep(&mut stream, 0x100);
+/// assert!(matches!(records[0], Record::Undecodable { address: 0x100, size: 16, bits: 0xba80, .. }));
+/// assert_eq!(records[1].address(), 0x102);
+/// ```
+pub fn linear_sweep<T: Stream>(stream: &mut T, base: u32) -> Vec<Record> {
+    MappedDecoder::new(stream, base, Vec::new())
+        .recovering()
+        .filter_map(Result::ok)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{linear_sweep, MappedDecoder, Record};
+    use crate::{prelude::*, ParseErrorKind};
+
+    #[test]
+    fn test_addresses() {
+        let mut bin = vec![];
+        // bl
+        bin.extend([0b11110100u8, 0b11001100u8].into_iter().rev());
+        bin.extend([0b11111000u8, 0b00000011u8].into_iter().rev());
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // ldr r1, [pc, #852]
+        bin.extend([0b01001001u8, 0b11010101u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let decoded = Decoder::new(&mut stream, 0x1000)
+            .map(|el| el.map(|(address, size, _)| (address, size)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(decoded, vec![(0x1000, 32), (0x1004, 16), (0x1006, 16)]);
+    }
+
+    #[test]
+    fn test_targets() {
+        let mut bin = vec![];
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // ldr r1, [pc, #852]
+        bin.extend([0b01001001u8, 0b11010101u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let (address, _, ldr) = Decoder::new(&mut stream, 0x1000).nth(1).unwrap().unwrap();
+        assert_eq!(ldr.literal_address(address), Some(0x1004 + 852));
+    }
+
+    #[test]
+    fn test_stops_after_error() {
+        // An incomplete 32-bit instruction.
+        let bin = [0b11001100u8, 0b11110100u8];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let mut decoder = Decoder::new(&mut stream, 0);
+        assert!(matches!(decoder.next(), Some(Err(_))));
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn test_data_ranges() {
+        let mut bin = vec![];
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // The first halfword of a 32-bit instruction, i.e. garbage.
+        bin.extend([0b11110100u8, 0b11001100u8].into_iter().rev());
+        bin.extend([0x00u8, 0x00u8]);
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let records = MappedDecoder::new(&mut stream, 0x200, vec![0x202..0x204, 0x204..0x205])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let addresses: Vec<u32> = records.iter().map(Record::address).collect();
+        assert_eq!(addresses, vec![0x200, 0x202, 0x206]);
+        assert_eq!(
+            records[1],
+            Record::Data(0x202, vec![0xcc, 0xf4, 0x00, 0x00])
+        );
+        assert!(records[2].operation().is_some());
+    }
+
+    #[test]
+    fn test_prefix_before_data() {
+        let mut bin = vec![];
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // The first halfword of a 32-bit instruction.
+        bin.extend([0b11110100u8, 0b11001100u8].into_iter().rev());
+        // .word 0x12345678
+        bin.extend([0x78u8, 0x56, 0x34, 0x12]);
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let records = MappedDecoder::new(&mut stream, 0x200, vec![0x204..0x206, 0x206..0x208])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let addresses: Vec<u32> = records.iter().map(Record::address).collect();
+        assert_eq!(addresses, vec![0x200, 0x202, 0x204, 0x208]);
+        let Record::Undecodable {
+            size, bits, error, ..
+        } = &records[1]
+        else {
+            panic!("Expected the prefix to be undecodable")
+        };
+        assert_eq!((*size, *bits), (16, 0xf4cc));
+        assert_eq!(error.kind, ParseErrorKind::IncompleteProgram);
+        assert_eq!(
+            records[2],
+            Record::Data(0x204, vec![0x78, 0x56, 0x34, 0x12])
+        );
+        assert!(records[3].operation().is_some());
+    }
+
+    #[test]
+    fn test_data_at_end() {
+        let bin = [0x70u8, 0x47, 0x01, 0x02, 0x03];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let records = MappedDecoder::new(&mut stream, 0, vec![2..4, 4..5])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        // The trailing byte is not a complete halfword.
+        assert_eq!(records[1], Record::Data(2, vec![0x01, 0x02, 0x03]));
+        assert_eq!(records.len(), 2);
+
+        let bin = [0x70u8, 0x47, 0xff];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let records = linear_sweep(&mut stream, 0);
+        assert_eq!(records[1], Record::Data(2, vec![0xff]));
+        assert_eq!(records.len(), 2);
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let mut decoder = Decoder::new(&mut stream, 0);
+        assert!(matches!(decoder.next(), Some(Ok((0, 16, _)))));
+        assert!(
+            matches!(decoder.next(), Some(Err(e)) if e.kind == ParseErrorKind::IncompleteProgram)
+        );
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn test_linear_sweep() {
+        let mut bin = vec![];
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // Unallocated in the miscellaneous 16-bit instructions, hlt in Armv8-M.
+        bin.extend([0xbau8, 0x80u8].into_iter().rev());
+        // An undefined 32-bit encoding in A5_30.
+        bin.extend([0xffu8, 0xffu8].into_iter().rev());
+        bin.extend([0xffu8, 0xffu8].into_iter().rev());
+        // lsls r3, r0
+        bin.extend([0b01000000u8, 0b10000011u8].into_iter().rev());
+        // The first halfword of a 32-bit instruction.
+        bin.extend([0b11110100u8, 0b11001100u8].into_iter().rev());
+
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let records = linear_sweep(&mut stream, 0x1000);
+        let listing: Vec<(u32, Option<(usize, u32)>)> = records
+            .iter()
+            .map(|record| {
+                (record.address(), match record {
+                    Record::Undecodable { size, bits, .. } => Some((*size, *bits)),
+                    _ => None,
+                })
+            })
+            .collect();
+        assert_eq!(listing, vec![
+            (0x1000, None),
+            (0x1002, Some((16, 0xba80))),
+            (0x1004, Some((32, 0xffff_ffff))),
+            (0x1008, None),
+            (0x100A, Some((16, 0xf4cc))),
+        ]);
+        let Some(Record::Undecodable { error, .. }) = records.last() else {
+            panic!("Expected the trailing halfword to be undecodable")
+        };
+        assert_eq!(error.kind, ParseErrorKind::IncompleteProgram);
+        assert_eq!(error.offset, Some(10));
+    }
+}
@@ -0,0 +1,388 @@
+//! Loads ELF32 little endian firmware images.
+//!
+//! This module is only available with the `elf` feature enabled. It walks the
+//! executable sections of the image, or the executable segments if the image
+//! has no section headers, and decodes them at their load addresses. The
+//! `$d` mapping symbols are used to skip literal pools and other data that is
+//! embedded in the executable sections.
+//!
+//! ```
+//! use disarmv7::elf;
+//!
+//! let image = include_bytes!("../fixtures/elf/minimal.elf");
+//! let sections = elf::decode(image).unwrap();
+//!
+//! let text = sections.iter().find(|el| el.name == ".text").unwrap();
+//! assert_eq!(text.address, 0x0800_0100);
+//! assert_eq!(text.records[0].address(), 0x0800_0100);
+//! ```
+
+use std::ops::Range;
+
+use object::{
+    elf::{EM_ARM, PF_X},
+    read::elf::{ElfFile32, FileHeader},
+    LittleEndian,
+    Object,
+    ObjectSection,
+    ObjectSegment,
+    ObjectSymbol,
+    SectionIndex,
+    SectionKind,
+    SegmentFlags,
+};
+
+use crate::{
+    buffer::SliceStream,
+    decoder::{MappedDecoder, Record},
+    ParseError,
+};
+
+#[derive(Debug)]
+/// Enumerates the errors that might occur while loading an ELF image.
+pub enum ElfError {
+    /// The image is not a well formed ELF32 little endian file.
+    Malformed(object::Error),
+
+    /// The image targets a machine other than ARM, contains the `e_machine`
+    /// field.
+    UnsupportedMachine(u16),
+
+    /// The named section extends past the end of the address space.
+    AddressOverflow(String),
+}
+
+impl std::fmt::Display for ElfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(e) => write!(f, "malformed ELF image, {e}"),
+            Self::UnsupportedMachine(machine) => {
+                write!(f, "the ELF image targets machine {machine}, not ARM")
+            }
+            Self::AddressOverflow(name) => {
+                write!(
+                    f,
+                    "the section {name} extends past the end of the address space"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ElfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Malformed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// The decoded contents of an executable section or segment.
+pub struct Section {
+    /// The name of the section, segments are named `segment<index>`.
+    pub name: String,
+
+    /// The load address of the first byte in the section.
+    pub address: u32,
+
+    /// The decoded instructions and the data that was skipped.
+    pub records: Vec<Record>,
+
+    /// The address and cause of the error that stopped decoding, if any.
+    pub error: Option<(u32, ParseError)>,
+}
+
+impl From<object::Error> for ElfError {
+    fn from(value: object::Error) -> Self {
+        Self::Malformed(value)
+    }
+}
+
+/// Parses the image, ensuring that it targets ARM.
+fn parse(image: &[u8]) -> Result<ElfFile32<'_, LittleEndian>, ElfError> {
+    let file = ElfFile32::<LittleEndian>::parse(image)?;
+    let machine = file.elf_header().e_machine(LittleEndian);
+    if machine != EM_ARM {
+        return Err(ElfError::UnsupportedMachine(machine));
+    }
+    Ok(file)
+}
+
+#[derive(Debug)]
+/// An executable section or segment of the image, before decoding.
+pub struct Region<'a> {
+    /// The name of the section, segments are named `segment<index>`.
+    pub name: String,
+
+    /// The load address of the first byte in the region.
+    pub address: u32,
+
+    /// The contents of the region.
+    pub bytes: &'a [u8],
+
+    /// The address ranges that are marked as data by the `$d` mapping
+    /// symbols, see [`MappedDecoder`].
+    pub data: Vec<Range<u32>>,
+}
+
+/// Returns all of the executable sections in the image without decoding them.
+///
+/// If the image has been stripped of its section headers the executable
+/// `PT_LOAD` segments are returned instead.
+pub fn regions(image: &[u8]) -> Result<Vec<Region<'_>>, ElfError> {
+    let file = parse(image)?;
+
+    let mut regions = Vec::new();
+    for section in file.sections() {
+        if section.kind() != SectionKind::Text {
+            continue;
+        }
+        let name = section.name()?.to_string();
+        let address = section.address() as u32;
+        let bytes = section.data()?;
+        let end = end(address, bytes.len() as u64, &name)?;
+        regions.push(Region {
+            data: section_data_ranges(&file, section.index(), address..end),
+            name,
+            address,
+            bytes,
+        });
+    }
+    if !regions.is_empty() {
+        return Ok(regions);
+    }
+
+    for (idx, segment) in file.segments().enumerate() {
+        match segment.flags() {
+            SegmentFlags::Elf { p_flags } if p_flags & PF_X != 0 => {}
+            _ => continue,
+        }
+        regions.push(Region {
+            name: format!("segment{idx}"),
+            address: segment.address() as u32,
+            bytes: segment.data()?,
+            data: Vec::new(),
+        });
+    }
+    Ok(regions)
+}
+
+/// Decodes all of the executable sections in the image.
+///
+/// If the image has been stripped of its section headers the executable
+/// `PT_LOAD` segments are decoded instead, see [`regions`].
+pub fn decode(image: &[u8]) -> Result<Vec<Section>, ElfError> {
+    Ok(regions(image)?.into_iter().map(decode_region).collect())
+}
+
+/// Returns the address one past the last byte of the section `name`, that
+/// starts at `address` and is `size` bytes long.
+fn end(address: u32, size: u64, name: &str) -> Result<u32, ElfError> {
+    u32::try_from(size)
+        .ok()
+        .and_then(|size| address.checked_add(size))
+        .ok_or_else(|| ElfError::AddressOverflow(name.to_string()))
+}
+
+/// Returns the address ranges that are marked as data by the `$d` mapping
+/// symbols in the executable sections of the image.
+pub fn data_ranges(image: &[u8]) -> Result<Vec<Range<u32>>, ElfError> {
+    let file = parse(image)?;
+    let mut ranges = Vec::new();
+    for section in file.sections() {
+        if section.kind() != SectionKind::Text {
+            continue;
+        }
+        let address = section.address() as u32;
+        let end = end(address, section.size(), section.name()?)?;
+        ranges.extend(section_data_ranges(&file, section.index(), address..end));
+    }
+    Ok(ranges)
+}
+
+/// Returns the ranges within `bounds` that are marked as data in the section.
+///
+/// Each `$d` symbol starts a data range that lasts until the next mapping
+/// symbol in the same section or the end of the section.
+fn section_data_ranges(
+    file: &ElfFile32<'_, LittleEndian>,
+    index: SectionIndex,
+    bounds: Range<u32>,
+) -> Vec<Range<u32>> {
+    let mut symbols: Vec<(u32, bool)> = file
+        .symbols()
+        .filter(|symbol| symbol.section_index() == Some(index))
+        .filter_map(|symbol| {
+            let data = match symbol.name().ok()?.split('.').next()? {
+                "$d" => true,
+                "$t" | "$a" => false,
+                _ => return None,
+            };
+            Some((symbol.address() as u32, data))
+        })
+        .collect();
+    symbols.sort_by_key(|(address, _)| *address);
+
+    let mut ranges = Vec::new();
+    for (idx, (start, data)) in symbols.iter().enumerate() {
+        if !data {
+            continue;
+        }
+        let end = symbols
+            .get(idx + 1)
+            .map_or(bounds.end, |(address, _)| *address);
+        ranges.push(*start..end.min(bounds.end));
+    }
+    ranges
+}
+
+/// Decodes the bytes in a region.
+fn decode_region(region: Region<'_>) -> Section {
+    let mut stream = SliceStream::new(region.bytes);
+    let mut decoder = MappedDecoder::new(&mut stream, region.address, region.data);
+    let mut records = Vec::new();
+    let mut error = None;
+    while let Some(record) = decoder.next() {
+        match record {
+            Ok(record) => records.push(record),
+            Err(e) => error = Some((decoder.address(), e)),
+        }
+    }
+    Section {
+        name: region.name,
+        address: region.address,
+        records,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{data_ranges, decode, regions, ElfError};
+    use crate::{decoder::Record, prelude::*};
+
+    const MINIMAL: &[u8] = include_bytes!("../fixtures/elf/minimal.elf");
+    const SEGMENTS: &[u8] = include_bytes!("../fixtures/elf/segments.elf");
+
+    #[test]
+    fn test_sections() {
+        let sections = decode(MINIMAL).unwrap();
+        let names: Vec<(&str, u32)> = sections
+            .iter()
+            .map(|el| (el.name.as_str(), el.address))
+            .collect();
+        assert_eq!(names, vec![
+            (".vectors", 0x0800_0000),
+            (".text", 0x0800_0100)
+        ]);
+
+        let vectors = &sections[0];
+        assert!(vectors.error.is_none());
+        let Record::Instruction(address, size, b) = &vectors.records[0] else {
+            panic!("Expected an instruction")
+        };
+        assert_eq!((*address, *size), (0x0800_0000, 32));
+        assert_eq!(b.branch_target(*address), Some(0x0800_0100));
+
+        let text = &sections[1];
+        assert!(text.error.is_none());
+        let push: Operation = operation::Push::builder()
+            .set_registers(RegisterList::try_from(0b0100_0000_0001_0000u16).unwrap())
+            .complete()
+            .into();
+        assert_eq!(text.records[0], Record::Instruction(0x0800_0100, 16, push));
+
+        // ldr r1, =value
+        let ldr = &text.records[2];
+        assert_eq!(
+            ldr.operation().unwrap().literal_address(ldr.address()),
+            Some(0x0800_0118)
+        );
+
+        // The literal pool is marked by a `$d` mapping symbol.
+        assert_eq!(
+            text.records.last(),
+            Some(&Record::Data(0x0800_0118, vec![0x1c, 0x01, 0x00, 0x08]))
+        );
+        assert_eq!(data_ranges(MINIMAL).unwrap(), vec![
+            0x0800_0118..0x0800_011C
+        ]);
+    }
+
+    #[test]
+    fn test_segments() {
+        let sections = decode(SEGMENTS).unwrap();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].name, "segment0");
+        assert_eq!(sections[0].address, 0x0800_0000);
+        // The segment spans both of the executable sections, without the
+        // mapping symbols the literal pool is decoded as instructions.
+        assert_eq!(
+            sections[0].records.last().map(Record::address),
+            Some(0x0800_011A)
+        );
+    }
+
+    #[test]
+    fn test_regions() {
+        let regions = regions(MINIMAL).unwrap();
+        let text = regions.iter().find(|el| el.name == ".text").unwrap();
+        assert_eq!(text.address, 0x0800_0100);
+        assert_eq!(text.bytes.len(), 0x1C);
+        // push {r4, lr}
+        assert_eq!(&text.bytes[..2], &[0x10, 0xb5]);
+        assert_eq!(text.data, vec![0x0800_0118..0x0800_011C]);
+    }
+
+    #[test]
+    fn test_rejects_other_images() {
+        let mut elf64 = MINIMAL.to_vec();
+        // EI_CLASS = ELFCLASS64
+        elf64[4] = 2;
+        assert!(matches!(decode(&elf64), Err(ElfError::Malformed(_))));
+
+        let mut big_endian = MINIMAL.to_vec();
+        // EI_DATA = ELFDATA2MSB
+        big_endian[5] = 2;
+        assert!(matches!(decode(&big_endian), Err(ElfError::Malformed(_))));
+
+        let mut x86 = MINIMAL.to_vec();
+        // e_machine = EM_386
+        x86[18] = 3;
+        assert!(matches!(decode(&x86), Err(ElfError::UnsupportedMachine(3))));
+
+        assert!(decode(&[0; 16]).is_err());
+    }
+
+    #[test]
+    fn test_address_overflow() {
+        let mut image = MINIMAL.to_vec();
+        let word = |image: &[u8], at: usize| {
+            u32::from_le_bytes(image[at..at + 4].try_into().unwrap()) as usize
+        };
+        let half =
+            |image: &[u8], at: usize| u16::from_le_bytes([image[at], image[at + 1]]) as usize;
+        // Moves the executable sections to the end of the address space.
+        let (shoff, shentsize, shnum) =
+            (word(&image, 0x20), half(&image, 0x2e), half(&image, 0x30));
+        for header in (0..shnum).map(|idx| shoff + idx * shentsize) {
+            // sh_flags & SHF_EXECINSTR
+            if word(&image, header + 0x08) & 0x4 != 0 {
+                // sh_addr
+                image[header + 0x0c..header + 0x10].copy_from_slice(&0xffff_fffeu32.to_le_bytes());
+            }
+        }
+        let error = regions(&image).unwrap_err();
+        assert!(matches!(error, ElfError::AddressOverflow(_)));
+        assert_eq!(
+            error.to_string(),
+            "the section .vectors extends past the end of the address space"
+        );
+        assert!(matches!(
+            data_ranges(&image),
+            Err(ElfError::AddressOverflow(_))
+        ));
+    }
+}
@@ -0,0 +1,296 @@
+//! Defines the [`Encode`] implementation for [`Operation`].
+//!
+//! Encoding is the inverse of [`parsing`](crate::Parse), the encoder
+//! picks the shortest legal encoding unless a specific size is requested.
+//! All encodings assume that the operation is executed outside of an IT
+//! block, i.e. [`SetFlags::InITBlock`](crate::arch::SetFlags::InITBlock) is
+//! resolved as if not in an IT block.
+//!
+//! ```
+//! use disarmv7::prelude::*;
+//!
+//! let op: Operation = operation::MovImmediate::builder()
+//!     .set_s(Some(SetFlags::InITBlock(false)))
+//!     .set_rd(Register::R1)
+//!     .set_imm(42)
+//!     .set_carry(None)
+//!     .complete()
+//!     .into();
+//!
+//! let encoded = op.encode().unwrap();
+//! assert_eq!(encoded, Encoded::Narrow(0x212A));
+//!
+//! let mut stream = PeekableBuffer::from(encoded.to_le_bytes().into_iter());
+//! assert_eq!(Operation::parse(&mut stream).unwrap(), (16, op));
+//! ```
+
+mod b16;
+mod b32;
+mod vfp;
+
+use crate::{
+    arch::{Register, RegisterList},
+    operation::Operation,
+    Encode,
+    EncodeError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An encoded [`Operation`].
+pub enum Encoded {
+    /// A 16-bit encoding.
+    Narrow(u16),
+    /// A 32-bit encoding, the first halfword is stored in the upper 16 bits.
+    Wide(u32),
+}
+
+impl Encoded {
+    /// Returns the size of the encoding in bits.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Narrow(_) => 16,
+            Self::Wide(_) => 32,
+        }
+    }
+
+    /// Returns the halfwords in the order that they are stored in memory.
+    pub fn halfwords(&self) -> Vec<u16> {
+        match self {
+            Self::Narrow(halfword) => vec![*halfword],
+            Self::Wide(word) => vec![(word >> 16) as u16, *word as u16],
+        }
+    }
+
+    /// Returns the bytes in the order that they are stored in memory.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.halfwords()
+            .into_iter()
+            .flat_map(|halfword| halfword.to_le_bytes())
+            .collect()
+    }
+}
+
+impl Encode for Operation {
+    type Target = Encoded;
+
+    fn encode_with_size(&self, size: Option<usize>) -> Result<Encoded, EncodeError> {
+        match size {
+            Some(16) => b16::encode(self).map(Encoded::Narrow),
+            Some(32) => b32::encode(self).map(Encoded::Wide),
+            Some(size) => Err(EncodeError::InvalidSize(size)),
+            None => match (b16::encode(self), b32::encode(self)) {
+                (Ok(halfword), _) => Ok(Encoded::Narrow(halfword)),
+                (_, Ok(word)) => Ok(Encoded::Wide(word)),
+                // Report the error from the width that has a form for the operation.
+                (Err(e), Err(EncodeError::NoEncoding(_)))
+                    if !matches!(e, EncodeError::NoEncoding(_)) =>
+                {
+                    Err(e)
+                }
+                (_, Err(e)) => Err(e),
+            },
+        }
+    }
+}
+
+/// Returns the register number.
+fn reg(register: Register) -> u32 {
+    u8::from(register) as u32
+}
+
+/// Ensures that the value fits in `max`.
+fn bounded(value: u32, max: u32) -> Result<u32, EncodeError> {
+    match value <= max {
+        true => Ok(value),
+        false => Err(EncodeError::ImmediateOutOfRange(value)),
+    }
+}
+
+/// Ensures that the value is a multiple of `1 << scale` and that the
+/// scaled value fits in `max`, returning the scaled value.
+fn scaled(value: u32, scale: u32, max: u32) -> Result<u32, EncodeError> {
+    match value & ((1 << scale) - 1) {
+        0 => bounded(value >> scale, max).map_err(|_| EncodeError::ImmediateOutOfRange(value)),
+        _ => Err(EncodeError::ImmediateOutOfRange(value)),
+    }
+}
+
+/// Ensures that the value is in `min..=max`.
+fn ranged(value: u32, min: u32, max: u32) -> Result<u32, EncodeError> {
+    match value >= min {
+        true => bounded(value, max),
+        false => Err(EncodeError::ImmediateOutOfRange(value)),
+    }
+}
+
+/// Converts a register list in to a bit vector, ensuring that only the
+/// registers in `allowed` are used.
+fn register_mask(list: &RegisterList, allowed: u16) -> Result<u16, EncodeError> {
+    let mut mask = 0;
+    for register in list.registers.iter() {
+        let bit = 1 << u8::from(*register);
+        if allowed & bit == 0 {
+            return Err(EncodeError::RegisterOutOfRange(*register));
+        }
+        mask |= bit;
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{arch::set_flags::SetFlags, prelude::*, EncodeError};
+
+    fn decode(bytes: Vec<u8>) -> Option<(usize, Operation)> {
+        let mut stream = PeekableBuffer::from(bytes.into_iter());
+        Operation::parse(&mut stream).ok()
+    }
+
+    fn round_trip(size: usize, op: &Operation) {
+        let encoded = op
+            .encode_with_size(Some(size))
+            .unwrap_or_else(|e| panic!("Could not encode {op:?}, {e:?}"));
+        let decoded = decode(encoded.to_le_bytes());
+        assert_eq!(
+            decoded,
+            Some((size, op.clone())),
+            "{op:?} encoded as {encoded:x?}"
+        );
+    }
+
+    #[test]
+    fn round_trip_16_bit() {
+        for halfword in 0..=u16::MAX {
+            if let 0b11101..=0b11111 = halfword >> 11 {
+                continue;
+            }
+            if let Some((16, op)) = decode(halfword.to_le_bytes().to_vec()) {
+                round_trip(16, &op);
+            }
+        }
+    }
+
+    /// Samples each 32-bit table with a linear congruential generator.
+    #[test]
+    fn round_trip_32_bit() {
+        let tables: [(&str, u32, u32); 25] = [
+            ("A5_10", 0xFA00_8000, 0xF000_0000),
+            ("A5_12", 0xFA00_8000, 0xF200_0000),
+            ("A5_13", 0xF800_8000, 0xF000_8000),
+            ("A5_14", 0xFFF0_D700, 0xF3A0_8000),
+            ("A5_15", 0xFFF0_D000, 0xF3B0_8000),
+            ("A5_16", 0xFE40_0000, 0xE800_0000),
+            ("A5_17", 0xFE40_0000, 0xE840_0000),
+            ("A5_18", 0xFE70_0000, 0xF850_0000),
+            ("A5_19", 0xFE70_0000, 0xF830_0000),
+            ("A5_20", 0xFE70_0000, 0xF810_0000),
+            ("A5_21", 0xFF10_0000, 0xF800_0000),
+            ("A5_22", 0xFE00_0000, 0xEA00_0000),
+            ("A5_23", 0xFFEF_0000, 0xEA4F_0000),
+            ("A5_24", 0xFF00_0000, 0xFA00_0000),
+            ("A5_25", 0xFF80_00C0, 0xFA80_0000),
+            ("A5_26", 0xFF80_00C0, 0xFA80_0040),
+            ("A5_27", 0xFFC0_00C0, 0xFA80_0080),
+            ("A5_28", 0xFF80_0000, 0xFB00_0000),
+            ("A5_29", 0xFF80_0000, 0xFB80_0000),
+            ("A5_30", 0xEC00_0000, 0xEC00_0000),
+            ("A6_4", 0xEF00_0E10, 0xEE00_0A00),
+            ("A6_4", 0xFF00_0E10, 0xFE00_0A00),
+            ("A6_5", 0xEE00_0E00, 0xEC00_0A00),
+            ("A6_6", 0xFF00_0E10, 0xEE00_0A10),
+            ("A6_7", 0xFFE0_0ED0, 0xEC40_0A10),
+        ];
+        let mut seed: u32 = 0x1234_5678;
+        for (table, mask, value) in tables {
+            let mut decoded = 0;
+            for _ in 0..2000 {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let word = (seed & !mask) | value;
+                let mut bytes = ((word >> 16) as u16).to_le_bytes().to_vec();
+                bytes.extend((word as u16).to_le_bytes());
+                if let Some((32, op)) = decode(bytes) {
+                    round_trip(32, &op);
+                    decoded += 1;
+                }
+            }
+            assert!(decoded > 0, "No samples from {table} decoded");
+        }
+    }
+
+    #[test]
+    fn shortest_encoding() {
+        let add: Operation = operation::AddImmediate::builder()
+            .set_s(Some(SetFlags::Literal(true)))
+            .set_rd(None)
+            .set_rn(Register::R2)
+            .set_imm(200)
+            .complete()
+            .into();
+        assert_eq!(add.encode(), Ok(Encoded::Narrow(0x32C8)));
+
+        let add: Operation = operation::AddImmediate::builder()
+            .set_s(Some(SetFlags::Literal(false)))
+            .set_rd(None)
+            .set_rn(Register::R2)
+            .set_imm(200)
+            .complete()
+            .into();
+        assert_eq!(add.encode(), Ok(Encoded::Wide(0xF102_02C8)));
+        assert!(add.encode_with_size(Some(16)).is_err());
+
+        let b: Operation = operation::B::builder()
+            .set_condition(Condition::None)
+            .set_imm(4096)
+            .complete()
+            .into();
+        assert_eq!(b.encode(), Ok(Encoded::Wide(0xF001_B800)));
+    }
+
+    #[test]
+    fn encode_errors() {
+        let add: Operation = operation::AddRegister::builder()
+            .set_s(Some(SetFlags::InITBlock(false)))
+            .set_rd(Some(Register::R8))
+            .set_rn(Register::R1)
+            .set_rm(Register::R2)
+            .set_shift(None)
+            .complete()
+            .into();
+        assert_eq!(
+            add.encode_with_size(Some(16)),
+            Err(EncodeError::RegisterOutOfRange(Register::R8))
+        );
+
+        let and: Operation = operation::AndImmediate::builder()
+            .set_s(Some(false))
+            .set_rd(None)
+            .set_rn(Register::R1)
+            .set_imm(0x1234_5678)
+            .set_carry(None)
+            .complete()
+            .into();
+        assert_eq!(and.encode(), Err(EncodeError::NotExpandable(0x1234_5678)));
+
+        let bl: Operation = operation::Bl::builder().set_imm(4).complete().into();
+        assert_eq!(
+            bl.encode_with_size(Some(16)),
+            Err(EncodeError::NoEncoding(16))
+        );
+        assert_eq!(
+            bl.encode_with_size(Some(8)),
+            Err(EncodeError::InvalidSize(8))
+        );
+        assert_eq!(
+            EncodeError::InvalidSize(8).to_string(),
+            "invalid encoding size 8, expected 16 or 32"
+        );
+
+        let cbz: Operation = operation::Cbz::builder()
+            .set_non(Some(false))
+            .set_rn(Register::R0)
+            .set_imm(200)
+            .complete()
+            .into();
+        assert_eq!(cbz.encode(), Err(EncodeError::ImmediateOutOfRange(200)));
+    }
+}
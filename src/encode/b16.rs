@@ -0,0 +1,490 @@
+//! Encodes [`Operation`]s in to the 16-bit encodings.
+
+use super::{bounded, ranged, reg, register_mask, scaled};
+use crate::{
+    arch::{set_flags::LocalUnwrap, Condition, ImmShift, Register, SetFlags, Shift},
+    operation::Operation,
+    EncodeError,
+};
+
+/// Returns the register number if it is one of the low registers.
+fn low(register: Register) -> Result<u16, EncodeError> {
+    match u8::from(register) {
+        value @ 0..=7 => Ok(value as u16),
+        _ => Err(EncodeError::RegisterOutOfRange(register)),
+    }
+}
+
+/// Returns the register number split in to the high bit and the low bits.
+fn split(register: Register) -> u16 {
+    let value = reg(register) as u16;
+    ((value >> 3) << 7) | (value & 0b111)
+}
+
+/// The data processing encodings always set the flags outside of IT blocks.
+fn sets_flags(s: Option<SetFlags>) -> Result<(), EncodeError> {
+    match s.local_unwrap(false) {
+        true => Ok(()),
+        false => Err(EncodeError::InvalidOperands(
+            "16-bit data processing encodings always set the flags",
+        )),
+    }
+}
+
+/// The high register encodings never set the flags.
+fn keeps_flags(s: bool) -> Result<(), EncodeError> {
+    match s {
+        false => Ok(()),
+        true => Err(EncodeError::InvalidOperands(
+            "16-bit encoding can not set the flags",
+        )),
+    }
+}
+
+/// The 16-bit encodings can not shift the operand.
+fn unshifted(shift: &Option<ImmShift>) -> Result<(), EncodeError> {
+    match shift {
+        None => Ok(()),
+        Some(ImmShift {
+            shift_t: Shift::Lsl,
+            shift_n: 0,
+        }) => Ok(()),
+        Some(_) => Err(EncodeError::InvalidOperands(
+            "16-bit encodings can not shift the operand",
+        )),
+    }
+}
+
+/// The two operand encodings require the destination to be the first
+/// source.
+fn same(rd: Option<Register>, rn: Register) -> Result<Register, EncodeError> {
+    match rd {
+        Some(rd) if rd != rn => Err(EncodeError::InvalidOperands(
+            "16-bit encoding requires rd to be rn",
+        )),
+        _ => Ok(rn),
+    }
+}
+
+/// The 16-bit load and store encodings only support positive offsets.
+fn offset(w: bool, index: bool, add: bool) -> Result<(), EncodeError> {
+    match (w, index, add) {
+        (false, true, true) => Ok(()),
+        _ => Err(EncodeError::InvalidOperands(
+            "16-bit encodings only support positive offset addressing",
+        )),
+    }
+}
+
+fn a5_3(op: u16, rdn: Register, rm: Register) -> Result<u16, EncodeError> {
+    Ok(0x4000 | (op << 6) | (low(rm)? << 3) | low(rdn)?)
+}
+
+fn a5_5(op: u16, rt: Register, rn: Register, rm: Register) -> Result<u16, EncodeError> {
+    Ok(0x5000 | (op << 9) | (low(rm)? << 6) | (low(rn)? << 3) | low(rt)?)
+}
+
+/// Loads and stores that use a low base register and a scaled immediate.
+fn a5_5_imm(
+    base: u16,
+    scale: u32,
+    rt: Register,
+    rn: Register,
+    imm: u32,
+) -> Result<u16, EncodeError> {
+    let imm = scaled(imm, scale, 0b11111)? as u16;
+    Ok(base | (imm << 6) | (low(rn)? << 3) | low(rt)?)
+}
+
+fn extend(
+    base: u16,
+    rd: Register,
+    rm: Register,
+    rotation: Option<u32>,
+) -> Result<u16, EncodeError> {
+    match rotation.unwrap_or(0) {
+        0 => Ok(base | (low(rm)? << 3) | low(rd)?),
+        rotation => Err(EncodeError::ImmediateOutOfRange(rotation)),
+    }
+}
+
+/// Computes the IT mask from the condition vector.
+fn it_mask(conditions: &[Condition]) -> Result<(u16, u16), EncodeError> {
+    let (first, rest) = match conditions.split_first() {
+        Some((first, rest)) if rest.len() < 4 => (u8::from(first.clone()) as u16, rest),
+        _ => {
+            return Err(EncodeError::InvalidOperands(
+                "IT blocks contain one to four conditions",
+            ))
+        }
+    };
+    let mut mask = 1 << (3 - rest.len());
+    for (idx, condition) in rest.iter().enumerate() {
+        let condition = u8::from(condition.clone()) as u16;
+        let bit = match condition ^ first {
+            0 => first & 1,
+            1 => (first & 1) ^ 1,
+            _ => {
+                return Err(EncodeError::InvalidOperands(
+                    "IT block conditions must be the first condition or its inverse",
+                ))
+            }
+        };
+        mask |= bit << (3 - idx);
+    }
+    Ok((first, mask))
+}
+
+/// Encodes the operation using a 16-bit encoding.
+pub(super) fn encode(op: &Operation) -> Result<u16, EncodeError> {
+    use Operation::*;
+    Ok(match op {
+        // A5_2
+        LslImmediate(el) => {
+            sets_flags(el.s)?;
+            let imm = bounded(el.imm as u32, 31)? as u16;
+            (imm << 6) | (low(el.rm)? << 3) | low(el.rd)?
+        }
+        LsrImmediate(el) => {
+            sets_flags(el.s)?;
+            let imm = ranged(el.imm as u32, 1, 32)? as u16 & 0b11111;
+            0x0800 | (imm << 6) | (low(el.rm)? << 3) | low(el.rd)?
+        }
+        AsrImmediate(el) => {
+            sets_flags(el.s)?;
+            let imm = ranged(el.imm, 1, 32)? as u16 & 0b11111;
+            0x1000 | (imm << 6) | (low(el.rm)? << 3) | low(el.rd)?
+        }
+        AddRegister(el) => {
+            unshifted(&el.shift)?;
+            let rd = el.rd.unwrap_or(el.rn);
+            match el.s.local_unwrap(false) {
+                true => 0x1800 | (low(el.rm)? << 6) | (low(el.rn)? << 3) | low(rd)?,
+                false => 0x4400 | (reg(el.rm) as u16) << 3 | split(same(Some(rd), el.rn)?),
+            }
+        }
+        SubRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            let rd = el.rd.unwrap_or(el.rn);
+            0x1A00 | (low(el.rm)? << 6) | (low(el.rn)? << 3) | low(rd)?
+        }
+        AddImmediate(el) => {
+            sets_flags(el.s)?;
+            match el.rd {
+                Some(rd) if el.imm < 8 => {
+                    0x1C00 | (el.imm as u16) << 6 | (low(el.rn)? << 3) | low(rd)?
+                }
+                rd => {
+                    let rdn = low(same(rd, el.rn)?)?;
+                    0x3000 | (rdn << 8) | bounded(el.imm, 0xFF)? as u16
+                }
+            }
+        }
+        SubImmediate(el) => {
+            sets_flags(el.s)?;
+            match el.rd {
+                Some(rd) if el.imm < 8 => {
+                    0x1E00 | (el.imm as u16) << 6 | (low(el.rn)? << 3) | low(rd)?
+                }
+                rd => {
+                    let rdn = low(same(rd, el.rn)?)?;
+                    0x3800 | (rdn << 8) | bounded(el.imm, 0xFF)? as u16
+                }
+            }
+        }
+        MovImmediate(el) => {
+            sets_flags(el.s)?;
+            0x2000 | (low(el.rd)? << 8) | bounded(el.imm, 0xFF)? as u16
+        }
+        CmpImmediate(el) => 0x2800 | (low(el.rn)? << 8) | bounded(el.imm, 0xFF)? as u16,
+
+        // A5_3
+        AndRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b0000, same(el.rd, el.rn)?, el.rm)?
+        }
+        EorRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b0001, same(el.rd, el.rn)?, el.rm)?
+        }
+        LslRegister(el) => {
+            sets_flags(el.s)?;
+            a5_3(0b0010, same(Some(el.rd), el.rn)?, el.rm)?
+        }
+        LsrRegister(el) => {
+            sets_flags(el.s)?;
+            a5_3(0b0011, same(Some(el.rd), el.rn)?, el.rm)?
+        }
+        AsrRegister(el) => {
+            sets_flags(el.s)?;
+            a5_3(0b0100, same(Some(el.rd), el.rn)?, el.rm)?
+        }
+        AdcRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b0101, same(el.rd, el.rn)?, el.rm)?
+        }
+        SbcRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b0110, same(el.rd, el.rn)?, el.rm)?
+        }
+        RorRegister(el) => {
+            sets_flags(el.s)?;
+            a5_3(0b0111, same(Some(el.rd), el.rn)?, el.rm)?
+        }
+        TstRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_3(0b1000, el.rn, el.rm)?
+        }
+        RsbImmediate(el) => {
+            sets_flags(el.s)?;
+            if el.imm != 0 {
+                return Err(EncodeError::ImmediateOutOfRange(el.imm));
+            }
+            a5_3(0b1001, el.rd.unwrap_or(el.rn), el.rn)?
+        }
+        CmpRegister(el) => {
+            unshifted(&el.shift)?;
+            match (low(el.rn), low(el.rm)) {
+                (Ok(_), Ok(_)) => a5_3(0b1010, el.rn, el.rm)?,
+                _ => 0x4500 | (reg(el.rm) as u16) << 3 | split(el.rn),
+            }
+        }
+        CmnRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_3(0b1011, el.rn, el.rm)?
+        }
+        OrrRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b1100, same(el.rd, el.rn)?, el.rm)?
+        }
+        Mul(el) => {
+            sets_flags(el.s)?;
+            if el.rd.unwrap_or(el.rn) != el.rm {
+                return Err(EncodeError::InvalidOperands(
+                    "16-bit encoding requires rd to be rm",
+                ));
+            }
+            a5_3(0b1101, el.rm, el.rn)?
+        }
+        BicRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b1110, same(el.rd, el.rn)?, el.rm)?
+        }
+        MvnRegister(el) => {
+            sets_flags(el.s)?;
+            unshifted(&el.shift)?;
+            a5_3(0b1111, el.rd, el.rm)?
+        }
+
+        // A5_4
+        AddSPRegister(el) => {
+            keeps_flags(el.s.unwrap_or(false))?;
+            unshifted(&el.shift)?;
+            match el.rd.unwrap_or(Register::SP) {
+                Register::SP => 0x4485 | (reg(el.rm) as u16) << 3,
+                rd if rd == el.rm => 0x4468 | split(rd),
+                _ => {
+                    return Err(EncodeError::InvalidOperands(
+                        "16-bit encoding requires rd to be SP or rm",
+                    ))
+                }
+            }
+        }
+        MovRegister(el) => match el.s.unwrap_or(false) {
+            true => (low(el.rm)? << 3) | low(el.rd)?,
+            false => 0x4600 | (reg(el.rm) as u16) << 3 | split(el.rd),
+        },
+        Bx(el) => 0x4700 | (reg(el.rm) as u16) << 3,
+        Blx(el) => 0x4780 | (reg(el.rm) as u16) << 3,
+
+        // A5_5
+        StrRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b000, el.rt, el.rn, el.rm)?
+        }
+        StrhRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b001, el.rt, el.rn, el.rm)?
+        }
+        StrbRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b010, el.rt, el.rn, el.rm)?
+        }
+        LdrsbRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b011, el.rt, el.rn, el.rm)?
+        }
+        LdrRegister(el) => {
+            unshifted(&el.shift)?;
+            if el.w.unwrap_or(false) {
+                return Err(EncodeError::InvalidOperands(
+                    "16-bit encoding does not support write back",
+                ));
+            }
+            a5_5(0b100, el.rt, el.rn, el.rm)?
+        }
+        LdrhRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b101, el.rt, el.rn, el.rm)?
+        }
+        LdrbRegister(el) => {
+            unshifted(&el.shift)?;
+            if !el.add.unwrap_or(true) {
+                return Err(EncodeError::InvalidOperands(
+                    "16-bit encoding only adds the offset",
+                ));
+            }
+            a5_5(0b110, el.rt, el.rn, el.rm)?
+        }
+        LdrshRegister(el) => {
+            unshifted(&el.shift)?;
+            a5_5(0b111, el.rt, el.rn, el.rm)?
+        }
+        StrImmediate(el) => {
+            offset(el.w.unwrap_or(false), el.index.unwrap_or(true), el.add)?;
+            match el.rn {
+                Register::SP => 0x9000 | (low(el.rt)? << 8) | scaled(el.imm, 2, 0xFF)? as u16,
+                rn => a5_5_imm(0x6000, 2, el.rt, rn, el.imm)?,
+            }
+        }
+        LdrImmediate(el) => {
+            offset(el.w.unwrap_or(false), el.index, el.add)?;
+            match el.rn {
+                Register::SP => 0x9800 | (low(el.rt)? << 8) | scaled(el.imm, 2, 0xFF)? as u16,
+                rn => a5_5_imm(0x6800, 2, el.rt, rn, el.imm)?,
+            }
+        }
+        StrbImmediate(el) => {
+            offset(el.w.unwrap_or(false), el.index.unwrap_or(true), el.add)?;
+            a5_5_imm(0x7000, 0, el.rt, el.rn, el.imm)?
+        }
+        LdrbImmediate(el) => {
+            offset(el.w.unwrap_or(false), el.index, el.add.unwrap_or(true))?;
+            a5_5_imm(0x7800, 0, el.rt, el.rn, el.imm.unwrap_or(0))?
+        }
+        StrhImmediate(el) => {
+            offset(el.w, el.index, el.add)?;
+            a5_5_imm(0x8000, 1, el.rt, el.rn, el.imm.unwrap_or(0))?
+        }
+        LdrhImmediate(el) => {
+            offset(
+                el.w.unwrap_or(false),
+                el.index.unwrap_or(true),
+                el.add.unwrap_or(true),
+            )?;
+            a5_5_imm(0x8800, 1, el.rt, el.rn, el.imm)?
+        }
+
+        // A5_6
+        Cps(el) => {
+            if el.enable == el.disable {
+                return Err(EncodeError::InvalidOperands(
+                    "CPS either enables or disables interrupts",
+                ));
+            }
+            0xB660 | (el.disable as u16) << 4 | (el.affect_pri as u16) << 1 | el.affect_fault as u16
+        }
+        AddSPImmediate(el) => {
+            keeps_flags(el.s.unwrap_or(false))?;
+            match el.rd.unwrap_or(Register::SP) {
+                Register::SP => 0xB000 | scaled(el.imm, 2, 0x7F)? as u16,
+                rd => 0xA800 | (low(rd)? << 8) | scaled(el.imm, 2, 0xFF)? as u16,
+            }
+        }
+        SubSpMinusImmediate(el) => {
+            keeps_flags(el.s.unwrap_or(false))?;
+            same(el.rd, Register::SP)?;
+            0xB080 | scaled(el.imm, 2, 0x7F)? as u16
+        }
+        Cbz(el) => {
+            let imm = scaled(el.imm, 1, 0x3F)? as u16;
+            0xB100
+                | (el.non.unwrap_or(false) as u16) << 11
+                | (imm >> 5) << 9
+                | (imm & 0b11111) << 3
+                | low(el.rn)?
+        }
+        Sxth(el) => extend(0xB200, el.rd, el.rm, el.rotation)?,
+        Sxtb(el) => extend(0xB240, el.rd, el.rm, el.rotation)?,
+        Uxth(el) => extend(0xB280, el.rd, el.rm, el.rotation)?,
+        Uxtb(el) => extend(0xB2C0, el.rd, el.rm, el.rotation)?,
+        Push(el) => {
+            let mask = register_mask(&el.registers, 0x40FF)?;
+            0xB400 | (mask >> 14) << 8 | (mask & 0xFF)
+        }
+        Rev(el) => 0xBA00 | (low(el.rm)? << 3) | low(el.rd)?,
+        Rev16(el) => 0xBA40 | (low(el.rm)? << 3) | low(el.rd)?,
+        Revsh(el) => 0xBAC0 | (low(el.rm)? << 3) | low(el.rd)?,
+        Pop(el) => {
+            let mask = register_mask(&el.registers, 0x80FF)?;
+            0xBC00 | (mask >> 15) << 8 | (mask & 0xFF)
+        }
+        Bkpt(el) => 0xBE00 | bounded(el.imm, 0xFF)? as u16,
+
+        // A5_7
+        It(el) => {
+            let (firstcond, mask) = it_mask(&el.conds.conditions)?;
+            0xBF00 | firstcond << 4 | mask
+        }
+        Nop(_) => 0xBF00,
+        Yield(_) => 0xBF10,
+        Wfe(_) => 0xBF20,
+        Wfi(_) => 0xBF30,
+        Sev(_) => 0xBF40,
+
+        // A5_8 and the unconditional branch
+        B(el) => {
+            let imm = el.imm as i32;
+            if imm & 1 != 0 {
+                return Err(EncodeError::ImmediateOutOfRange(el.imm));
+            }
+            match el.condition {
+                Condition::None if (-2048..=2046).contains(&imm) => {
+                    0xE000 | ((imm >> 1) as u16 & 0x7FF)
+                }
+                Condition::None => return Err(EncodeError::ImmediateOutOfRange(el.imm)),
+                ref condition if (-256..=254).contains(&imm) => {
+                    0xD000 | (u8::from(condition.clone()) as u16) << 8 | ((imm >> 1) as u16 & 0xFF)
+                }
+                _ => return Err(EncodeError::ImmediateOutOfRange(el.imm)),
+            }
+        }
+        Udf(el) => 0xDE00 | bounded(el.imm, 0xFF)? as u16,
+        Svc(el) => 0xDF00 | el.imm as u16,
+
+        // Simply defined
+        LdrLiteral(el) => {
+            offset(false, true, el.add)?;
+            0x4800 | (low(el.rt)? << 8) | scaled(el.imm, 2, 0xFF)? as u16
+        }
+        Adr(el) => {
+            offset(false, true, el.add)?;
+            0xA000 | (low(el.rd)? << 8) | scaled(el.imm, 2, 0xFF)? as u16
+        }
+        Stm(el) => {
+            if !el.w.unwrap_or(true) {
+                return Err(EncodeError::InvalidOperands(
+                    "16-bit encoding always writes back",
+                ));
+            }
+            0xC000 | (low(el.rn)? << 8) | register_mask(&el.registers, 0xFF)?
+        }
+        Ldm(el) => {
+            let w = !el.registers.registers.contains(&el.rn);
+            if el.w.unwrap_or(w) != w {
+                return Err(EncodeError::InvalidOperands(
+                    "16-bit encoding writes back unless rn is loaded",
+                ));
+            }
+            0xC800 | (low(el.rn)? << 8) | register_mask(&el.registers, 0xFF)?
+        }
+        _ => return Err(EncodeError::NoEncoding(16)),
+    })
+}
@@ -0,0 +1,1578 @@
+//! Encodes [`Operation`]s in to the 32-bit encodings.
+
+use super::{bounded, ranged, reg, register_mask, scaled, vfp};
+use crate::{
+    arch::{set_flags::LocalUnwrap, CoProcessor, Condition, Imm12, ImmShift, Register, Shift},
+    operation::Operation,
+    EncodeError,
+};
+
+/// Rejects PC in operand positions where it selects a different
+/// instruction.
+fn not_pc(register: Register) -> Result<Register, EncodeError> {
+    match register {
+        Register::PC => Err(EncodeError::RegisterOutOfRange(register)),
+        register => Ok(register),
+    }
+}
+
+/// Spreads a 12-bit immediate in to the `i:imm3:imm8` fields.
+fn imm12(imm: u32) -> u32 {
+    ((imm >> 11) & 1) << 26 | ((imm >> 8) & 0b111) << 12 | (imm & 0xFF)
+}
+
+/// Encodes a value as a modified immediate constant.
+fn modified(imm: u32) -> Result<u32, EncodeError> {
+    match Imm12::try_from_expanded(imm) {
+        Some(encoded) => Ok(imm12(encoded.into())),
+        None => Err(EncodeError::NotExpandable(imm)),
+    }
+}
+
+/// Spreads a 5-bit shift amount or bit position in to the `imm3:imm2`
+/// fields.
+fn imm5(imm: u32) -> u32 {
+    (imm >> 2) << 12 | (imm & 0b11) << 6
+}
+
+/// Encodes the shift as the `imm3:imm2:type` fields.
+fn shift(shift: &Option<ImmShift>) -> Result<u32, EncodeError> {
+    let (ty, n) = match shift {
+        None => return Ok(0),
+        Some(ImmShift { shift_t, shift_n }) => (shift_t, *shift_n as u32),
+    };
+    let (ty, n) = match ty {
+        Shift::Lsl => (0b00, bounded(n, 31)?),
+        Shift::Lsr => (0b01, ranged(n, 1, 32)? & 0b11111),
+        Shift::Asr => (0b10, ranged(n, 1, 32)? & 0b11111),
+        Shift::Ror => (0b11, ranged(n, 1, 31)?),
+        Shift::Rrx => (0b11, 0),
+    };
+    Ok(imm5(n) | ty << 4)
+}
+
+/// Encodes the shift of a register offset, which is limited to `LSL #0-3`.
+fn offset_shift(shift: &Option<ImmShift>) -> Result<u32, EncodeError> {
+    match shift {
+        None => Ok(0),
+        Some(ImmShift {
+            shift_t: Shift::Lsl,
+            shift_n,
+        }) => Ok(bounded(*shift_n as u32, 0b11)? << 4),
+        Some(_) => Err(EncodeError::InvalidOperands(
+            "Register offsets can only be shifted left",
+        )),
+    }
+}
+
+/// Encodes the saturation shift, which is either `LSL` or `ASR`.
+fn saturate_shift(shift: &Option<ImmShift>) -> Result<u32, EncodeError> {
+    match shift {
+        None => Ok(0),
+        Some(ImmShift {
+            shift_t: Shift::Lsl,
+            shift_n,
+        }) => Ok(imm5(bounded(*shift_n as u32, 31)?)),
+        Some(ImmShift {
+            shift_t: Shift::Asr,
+            shift_n,
+        }) => Ok(1 << 21 | imm5(ranged(*shift_n as u32, 1, 31)?)),
+        Some(_) => Err(EncodeError::InvalidOperands(
+            "Saturation can only shift left or arithmetic right",
+        )),
+    }
+}
+
+/// Data processing (modified immediate).
+fn a5_10(op: u32, s: bool, rn: Register, rd: Register, imm: u32) -> Result<u32, EncodeError> {
+    Ok(0xF000_0000 | op << 21 | (s as u32) << 20 | reg(rn) << 16 | reg(rd) << 8 | modified(imm)?)
+}
+
+/// Data processing (shifted register).
+fn a5_22(
+    op: u32,
+    s: bool,
+    rn: Register,
+    rd: Register,
+    rm: Register,
+    shift_t: &Option<ImmShift>,
+) -> Result<u32, EncodeError> {
+    Ok(0xEA00_0000
+        | op << 21
+        | (s as u32) << 20
+        | reg(rn) << 16
+        | reg(rd) << 8
+        | shift(shift_t)?
+        | reg(rm))
+}
+
+/// Move and immediate shifts, i.e. data processing with `rn = PC`.
+fn a5_23(s: bool, rd: Register, rm: Register, ty: u32, imm: u32) -> u32 {
+    0xEA4F_0000 | (s as u32) << 20 | reg(rd) << 8 | imm5(imm) | ty << 4 | reg(rm)
+}
+
+/// `ADDW` and `SUBW`.
+fn plain_imm12(base: u32, rn: Register, rd: Register, imm: u32) -> Result<u32, EncodeError> {
+    Ok(base | reg(not_pc(rn)?) << 16 | reg(rd) << 8 | imm12(bounded(imm, 0xFFF)?))
+}
+
+/// Register controlled shifts and extends.
+fn a5_24(base: u32, rn: Register, rd: Register, rm: Register) -> u32 {
+    base | reg(rn) << 16 | reg(rd) << 8 | reg(rm)
+}
+
+/// Extends with an optional accumulator, `rn = PC` denotes no accumulator.
+fn extend(
+    op1: u32,
+    rn: Register,
+    rd: Register,
+    rm: Register,
+    rotation: Option<u32>,
+) -> Result<u32, EncodeError> {
+    let rotation = match rotation.unwrap_or(0) {
+        rotation @ (0 | 8 | 16 | 24) => rotation >> 3,
+        rotation => return Err(EncodeError::ImmediateOutOfRange(rotation)),
+    };
+    Ok(a5_24(0xFA00_F080 | op1 << 20 | rotation << 4, rn, rd, rm))
+}
+
+/// Parallel addition and subtraction as well as the miscellaneous
+/// operations.
+fn a5_25(base: u32, op1: u32, op2: u32, rd: Register, rn: Register, rm: Register) -> u32 {
+    base | op1 << 20 | op2 << 4 | reg(rn) << 16 | reg(rd) << 8 | reg(rm)
+}
+
+/// Multiply and multiply accumulate, `ra = PC` denotes no accumulator.
+fn a5_28(op1: u32, op2: u32, rd: Register, rn: Register, rm: Register, ra: Register) -> u32 {
+    0xFB00_0000 | op1 << 20 | reg(rn) << 16 | reg(ra) << 12 | reg(rd) << 8 | op2 << 4 | reg(rm)
+}
+
+/// Long multiply, long multiply accumulate and divide.
+fn a5_29(op1: u32, op2: u32, rdlo: Register, rdhi: Register, rn: Register, rm: Register) -> u32 {
+    0xFB80_0000 | op1 << 20 | reg(rn) << 16 | reg(rdlo) << 12 | reg(rdhi) << 8 | op2 << 4 | reg(rm)
+}
+
+/// Loads and stores with an immediate offset.
+///
+/// The 12-bit positive offset encoding is used when possible, otherwise the
+/// 8-bit encoding which supports indexing, write back and negative offsets.
+#[allow(clippy::too_many_arguments)]
+fn load_store(
+    imm12_base: u32,
+    imm8_base: u32,
+    w: bool,
+    index: bool,
+    add: bool,
+    rt: Register,
+    rn: Register,
+    imm: u32,
+) -> Result<u32, EncodeError> {
+    let registers = reg(rn) << 16 | reg(rt) << 12;
+    match (w, index, add) {
+        (false, true, true) => Ok(imm12_base | registers | bounded(imm, 0xFFF)?),
+        (false, false, _) => Err(EncodeError::InvalidOperands(
+            "Post indexed addressing requires write back",
+        )),
+        _ => Ok(imm8_base
+            | registers
+            | (index as u32) << 10
+            | (add as u32) << 9
+            | (w as u32) << 8
+            | bounded(imm, 0xFF)?),
+    }
+}
+
+/// Loads that can not target PC since that selects the preload hints.
+fn not_hint(rt: Register) -> Result<Register, EncodeError> {
+    not_pc(rt)
+}
+
+/// Literal loads, `rn = PC`.
+fn literal(base: u32, add: bool, rt: Register, imm: u32) -> Result<u32, EncodeError> {
+    Ok(base | (add as u32) << 23 | reg(rt) << 12 | bounded(imm, 0xFFF)?)
+}
+
+/// Loads and stores with a shifted register offset.
+fn register_offset(
+    base: u32,
+    rt: Register,
+    rn: Register,
+    rm: Register,
+    shift: &Option<ImmShift>,
+) -> Result<u32, EncodeError> {
+    Ok(base | reg(rn) << 16 | reg(rt) << 12 | offset_shift(shift)? | reg(rm))
+}
+
+/// Unprivileged loads and stores.
+fn unprivileged(base: u32, rt: Register, rn: Register, imm: u32) -> Result<u32, EncodeError> {
+    Ok(base | reg(rn) << 16 | reg(rt) << 12 | bounded(imm, 0xFF)?)
+}
+
+/// Dual loads and stores.
+#[allow(clippy::too_many_arguments)]
+fn dual(
+    base: u32,
+    w: bool,
+    index: bool,
+    add: bool,
+    rt: Register,
+    rt2: Register,
+    rn: Register,
+    imm: u32,
+) -> Result<u32, EncodeError> {
+    if !(w || index) {
+        return Err(EncodeError::InvalidOperands(
+            "Post indexed addressing requires write back",
+        ));
+    }
+    Ok(base
+        | (index as u32) << 24
+        | (add as u32) << 23
+        | (w as u32) << 21
+        | reg(rn) << 16
+        | reg(rt) << 12
+        | reg(rt2) << 8
+        | scaled(imm, 2, 0xFF)?)
+}
+
+/// Rejects the co processors that are reserved for the floating point
+/// extension.
+fn coproc_number(coproc: CoProcessor) -> Result<u32, EncodeError> {
+    match coproc {
+        CoProcessor::P10 | CoProcessor::P11 => Err(EncodeError::InvalidOperands(
+            "Co processors 10 and 11 are the floating point extension",
+        )),
+        coproc => Ok(u8::from(coproc) as u32),
+    }
+}
+
+/// Co processor loads and stores.
+#[allow(clippy::too_many_arguments)]
+fn coproc_load_store(
+    base: u32,
+    coproc: CoProcessor,
+    crd: u8,
+    rn: Register,
+    imm: u32,
+    add: bool,
+    w: bool,
+    index: bool,
+) -> Result<u32, EncodeError> {
+    Ok(base
+        | (index as u32) << 24
+        | (add as u32) << 23
+        | (w as u32) << 21
+        | reg(rn) << 16
+        | bounded(crd as u32, 0xF)? << 12
+        | coproc_number(coproc)? << 8
+        | scaled(imm, 2, 0xFF)?)
+}
+
+/// Co processor register transfers.
+fn coproc_transfer(
+    base: u32,
+    coproc: CoProcessor,
+    opc1: u8,
+    opc2: Option<u8>,
+    rt: Register,
+    crm: u8,
+    crn: u8,
+) -> Result<u32, EncodeError> {
+    Ok(base
+        | bounded(opc1 as u32, 0b111)? << 21
+        | bounded(crn as u32, 0xF)? << 16
+        | reg(rt) << 12
+        | coproc_number(coproc)? << 8
+        | bounded(opc2.unwrap_or(0) as u32, 0b111)? << 5
+        | bounded(crm as u32, 0xF)?)
+}
+
+/// Co processor two register transfers.
+fn coproc_transfer_dual(
+    base: u32,
+    coproc: CoProcessor,
+    opc1: u8,
+    rt: Register,
+    rt2: Register,
+    crm: u8,
+) -> Result<u32, EncodeError> {
+    Ok(base
+        | reg(rt2) << 16
+        | reg(rt) << 12
+        | coproc_number(coproc)? << 8
+        | bounded(opc1 as u32, 0xF)? << 4
+        | bounded(crm as u32, 0xF)?)
+}
+
+/// Bit field operations.
+fn bit_field(
+    base: u32,
+    rn: Register,
+    rd: Register,
+    lsb: u32,
+    last: u32,
+) -> Result<u32, EncodeError> {
+    Ok(base | reg(rn) << 16 | reg(rd) << 8 | imm5(bounded(lsb, 31)?) | bounded(last, 31)?)
+}
+
+/// Extract operations, the field is `width - 1`.
+fn extract(
+    base: u32,
+    rd: Register,
+    rn: Register,
+    lsb: u32,
+    width: u32,
+) -> Result<u32, EncodeError> {
+    let width = ranged(width, 1, 32)?;
+    bit_field(base, rn, rd, lsb, width - 1)
+}
+
+/// The `S:I1:I2:imm10:imm11` branches.
+fn branch(base: u32, imm: u32) -> Result<u32, EncodeError> {
+    let offset = imm as i32;
+    if offset & 1 != 0 || !(-(1 << 24)..(1 << 24)).contains(&offset) {
+        return Err(EncodeError::ImmediateOutOfRange(imm));
+    }
+    let s = (imm >> 24) & 1;
+    let j1 = !((imm >> 23) ^ s) & 1;
+    let j2 = !((imm >> 22) ^ s) & 1;
+    Ok(base | s << 26 | ((imm >> 12) & 0x3FF) << 16 | j1 << 13 | j2 << 11 | ((imm >> 1) & 0x7FF))
+}
+
+/// The conditional `S:J2:J1:imm6:imm11` branch.
+fn conditional_branch(condition: Condition, imm: u32) -> Result<u32, EncodeError> {
+    let offset = imm as i32;
+    if offset & 1 != 0 || !(-(1 << 20)..(1 << 20)).contains(&offset) {
+        return Err(EncodeError::ImmediateOutOfRange(imm));
+    }
+    Ok(0xF000_8000
+        | ((imm >> 20) & 1) << 26
+        | (u8::from(condition) as u32) << 22
+        | ((imm >> 12) & 0x3F) << 16
+        | ((imm >> 18) & 1) << 13
+        | ((imm >> 19) & 1) << 11
+        | ((imm >> 1) & 0x7FF))
+}
+
+/// Hints and barriers.
+fn option(base: u32, option: Option<u8>) -> Result<u32, EncodeError> {
+    Ok(base | bounded(option.unwrap_or(0xF) as u32, 0xF)?)
+}
+
+/// Encodes the operation using a 32-bit encoding.
+pub(super) fn encode(op: &Operation) -> Result<u32, EncodeError> {
+    use Operation::*;
+    Ok(match op {
+        // A5_10 and A5_12
+        AndImmediate(el) => a5_10(
+            0b0000,
+            el.s.unwrap_or(false),
+            el.rn,
+            not_pc(el.rd.unwrap_or(el.rn))?,
+            el.imm,
+        )?,
+        TstImmediate(el) => a5_10(0b0000, true, el.rn, Register::PC, el.imm)?,
+        BicImmediate(el) => a5_10(
+            0b0001,
+            el.s.unwrap_or(false),
+            el.rn,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        OrrImmediate(el) => a5_10(
+            0b0010,
+            el.s.unwrap_or(false),
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        MovImmediate(el) => {
+            let s = el.s.local_unwrap(false);
+            let encoded = Imm12::try_from_expanded(el.imm);
+            match encoded {
+                Some(encoded) if encoded.expand_imm_c().1 == el.carry => {
+                    a5_10(0b0010, s, Register::PC, el.rd, el.imm)?
+                }
+                _ if !s && el.imm <= 0xFFFF => {
+                    0xF240_0000 | (el.imm >> 12) << 16 | reg(el.rd) << 8 | imm12(el.imm & 0xFFF)
+                }
+                _ => a5_10(0b0010, s, Register::PC, el.rd, el.imm)?,
+            }
+        }
+        OrnImmediate(el) => a5_10(
+            0b0011,
+            el.s.unwrap_or(false),
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        MvnImmediate(el) => a5_10(0b0011, el.s.unwrap_or(false), Register::PC, el.rd, el.imm)?,
+        EorImmediate(el) => a5_10(
+            0b0100,
+            el.s.unwrap_or(false),
+            el.rn,
+            not_pc(el.rd.unwrap_or(el.rn))?,
+            el.imm,
+        )?,
+        TeqImmediate(el) => a5_10(0b0100, true, el.rn, Register::PC, el.imm)?,
+        AddImmediate(el) => {
+            let s = el.s.local_unwrap(false);
+            let rd = el.rd.unwrap_or(el.rn);
+            match not_pc(rd).and_then(|rd| a5_10(0b1000, s, el.rn, rd, el.imm)) {
+                Err(_) if !s => plain_imm12(0xF200_0000, el.rn, rd, el.imm)?,
+                encoded => encoded?,
+            }
+        }
+        AddSPImmediate(el) => {
+            let s = el.s.unwrap_or(false);
+            let rd = el.rd.unwrap_or(Register::SP);
+            match not_pc(rd).and_then(|rd| a5_10(0b1000, s, Register::SP, rd, el.imm)) {
+                Err(_) if !s => plain_imm12(0xF200_0000, Register::SP, rd, el.imm)?,
+                encoded => encoded?,
+            }
+        }
+        CmnImmediate(el) => a5_10(0b1000, true, el.rn, Register::PC, el.imm)?,
+        AdcImmediate(el) => a5_10(
+            0b1010,
+            el.s.unwrap_or(false),
+            el.rn,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        SbcImmediate(el) => a5_10(
+            0b1011,
+            el.s.unwrap_or(false),
+            el.rn,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        SubImmediate(el) => {
+            let s = el.s.local_unwrap(false);
+            let rd = el.rd.unwrap_or(el.rn);
+            match not_pc(rd).and_then(|rd| a5_10(0b1101, s, el.rn, rd, el.imm)) {
+                Err(_) if !s => plain_imm12(0xF2A0_0000, el.rn, rd, el.imm)?,
+                encoded => encoded?,
+            }
+        }
+        SubSpMinusImmediate(el) => {
+            let s = el.s.unwrap_or(false);
+            let rd = el.rd.unwrap_or(Register::SP);
+            match not_pc(rd).and_then(|rd| a5_10(0b1101, s, Register::SP, rd, el.imm)) {
+                Err(_) if !s => plain_imm12(0xF2A0_0000, Register::SP, rd, el.imm)?,
+                encoded => encoded?,
+            }
+        }
+        CmpImmediate(el) => a5_10(0b1101, true, el.rn, Register::PC, el.imm)?,
+        RsbImmediate(el) => a5_10(
+            0b1110,
+            el.s.local_unwrap(false),
+            el.rn,
+            el.rd.unwrap_or(el.rn),
+            el.imm,
+        )?,
+        Adr(el) => {
+            let base = match el.add {
+                true => 0xF20F_0000,
+                false => 0xF2AF_0000,
+            };
+            base | reg(el.rd) << 8 | imm12(bounded(el.imm, 0xFFF)?)
+        }
+        Movt(el) => {
+            let imm = el.imm as u32;
+            0xF2C0_0000 | (imm >> 12) << 16 | reg(el.rd) << 8 | imm12(imm & 0xFFF)
+        }
+        Ssat(el) => {
+            let sat = ranged(el.imm, 1, 32)? - 1;
+            0xF300_0000 | reg(el.rn) << 16 | reg(el.rd) << 8 | saturate_shift(&el.shift)? | sat
+        }
+        Ssat16(el) => {
+            let sat = ranged(el.imm, 1, 16)? - 1;
+            0xF320_0000 | reg(el.rn) << 16 | reg(el.rd) << 8 | sat
+        }
+        Sbfx(el) => extract(0xF340_0000, el.rd, el.rn, el.lsb, el.width)?,
+        Bfi(el) => bit_field(0xF360_0000, not_pc(el.rn)?, el.rd, el.lsb, el.msb)?,
+        Bfc(el) => bit_field(0xF360_0000, Register::PC, el.rd, el.lsb, el.msb)?,
+        Usat(el) => {
+            let sat = bounded(el.imm, 31)?;
+            0xF380_0000 | reg(el.rn) << 16 | reg(el.rd) << 8 | saturate_shift(&el.shift)? | sat
+        }
+        Usat16(el) => {
+            let sat = bounded(el.imm, 15)?;
+            0xF3A0_0000 | reg(el.rn) << 16 | reg(el.rd) << 8 | sat
+        }
+        Ubfx(el) => extract(0xF3C0_0000, el.rd, el.rn, el.lsb, el.width)?,
+
+        // A5_13, A5_14 and A5_15
+        B(el) => match el.condition {
+            Condition::None => branch(0xF000_9000, el.imm)?,
+            ref condition => conditional_branch(condition.clone(), el.imm)?,
+        },
+        Bl(el) => branch(0xF000_D000, el.imm)?,
+        Msr(el) => {
+            0xF380_8000
+                | reg(el.rn) << 16
+                | (u8::from(el.mask) as u32) << 10
+                | u8::from(el.sysm) as u32
+        }
+        Mrs(el) => 0xF3EF_8000 | reg(el.rd) << 8 | u8::from(el.sysm) as u32,
+        Udf(el) => {
+            let imm = bounded(el.imm, 0xFFFF)?;
+            0xF7F0_A000 | (imm >> 12) << 16 | (imm & 0xFFF)
+        }
+        Nop(_) => 0xF3AF_8000,
+        Yield(_) => 0xF3AF_8001,
+        Wfe(_) => 0xF3AF_8002,
+        Wfi(_) => 0xF3AF_8003,
+        Sev(_) => 0xF3AF_8004,
+        Dbg(el) => option(0xF3AF_80F0, Some(u8::from(el.option)))?,
+        Clrex(_) => 0xF3BF_8F2F,
+        Dsb(el) => option(0xF3BF_8F40, el.option.map(u8::from))?,
+        Dmb(el) => option(0xF3BF_8F50, el.option.map(u8::from))?,
+        Isb(el) => option(0xF3BF_8F60, el.option.map(u8::from))?,
+
+        // A5_16
+        Stm(el) => {
+            let list = register_mask(&el.registers, 0x5FFF)? as u32;
+            0xE880_0000 | (el.w.unwrap_or(false) as u32) << 21 | reg(el.rn) << 16 | list
+        }
+        Ldm(el) => {
+            let list = register_mask(&el.registers, 0xDFFF)? as u32;
+            0xE890_0000 | (el.w.unwrap_or(false) as u32) << 21 | reg(el.rn) << 16 | list
+        }
+        Pop(el) => 0xE8BD_0000 | register_mask(&el.registers, 0xDFFF)? as u32,
+        Stmdb(el) => {
+            let list = register_mask(&el.registers, 0x5FFF)? as u32;
+            0xE900_0000 | (el.w.unwrap_or(false) as u32) << 21 | reg(el.rn) << 16 | list
+        }
+        Push(el) => 0xE92D_0000 | register_mask(&el.registers, 0x5FFF)? as u32,
+        Ldmdb(el) => {
+            let list = register_mask(&el.registers, 0xDFFF)? as u32;
+            0xE910_0000 | (el.w.unwrap_or(false) as u32) << 21 | reg(el.rn) << 16 | list
+        }
+
+        // A5_17
+        Strex(el) => {
+            let imm = scaled(el.imm.unwrap_or(0), 2, 0xFF)?;
+            0xE840_0000 | reg(el.rn) << 16 | reg(el.rt) << 12 | reg(el.rd) << 8 | imm
+        }
+        Ldrex(el) => 0xE850_0F00 | reg(el.rn) << 16 | reg(el.rt) << 12 | scaled(el.imm, 2, 0xFF)?,
+        StrdImmediate(el) => dual(
+            0xE840_0000,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add,
+            el.rt,
+            el.rt2,
+            el.rn,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrdImmediate(el) => dual(
+            0xE850_0000,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add.unwrap_or(true),
+            el.rt,
+            el.rt2,
+            el.rn,
+            el.imm,
+        )?,
+        LdrdLiteral(el) => dual(
+            0xE850_0000,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add.unwrap_or(true),
+            el.rt,
+            el.rt2,
+            Register::PC,
+            el.imm,
+        )?,
+        Strexb(el) => 0xE8C0_0F40 | reg(el.rn) << 16 | reg(el.rt) << 12 | reg(el.rd),
+        Strexh(el) => 0xE8C0_0F50 | reg(el.rn) << 16 | reg(el.rt) << 12 | reg(el.rd),
+        Tb(el) => {
+            0xE8D0_F000 | reg(el.rn) << 16 | (el.is_tbh.unwrap_or(false) as u32) << 4 | reg(el.rm)
+        }
+        Ldrexb(el) => 0xE8D0_0F4F | reg(el.rn) << 16 | reg(el.rt) << 12,
+        Ldrexh(el) => 0xE8D0_0F5F | reg(el.rn) << 16 | reg(el.rt) << 12,
+
+        // A5_18
+        LdrImmediate(el) => load_store(
+            0xF8D0_0000,
+            0xF850_0800,
+            el.w.unwrap_or(false),
+            el.index,
+            el.add,
+            el.rt,
+            not_pc(el.rn)?,
+            el.imm,
+        )?,
+        Ldrt(el) => unprivileged(0xF850_0E00, el.rt, not_pc(el.rn)?, el.imm.unwrap_or(0))?,
+        LdrRegister(el) => {
+            if el.w.unwrap_or(false) {
+                return Err(EncodeError::InvalidOperands(
+                    "Register offset loads can not write back",
+                ));
+            }
+            register_offset(0xF850_0000, el.rt, not_pc(el.rn)?, el.rm, &el.shift)?
+        }
+        LdrLiteral(el) => literal(0xF85F_0000, el.add, el.rt, el.imm)?,
+
+        // A5_19
+        LdrhImmediate(el) => load_store(
+            0xF8B0_0000,
+            0xF830_0800,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add.unwrap_or(true),
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm,
+        )?,
+        LdrhLiteral(el) => literal(
+            0xF83F_0000,
+            el.add.unwrap_or(true),
+            not_hint(el.rt)?,
+            el.imm,
+        )?,
+        LdrhRegister(el) => register_offset(
+            0xF830_0000,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.rm,
+            &el.shift,
+        )?,
+        Ldrht(el) => unprivileged(
+            0xF830_0E00,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrshImmediate(el) => load_store(
+            0xF9B0_0000,
+            0xF930_0800,
+            el.wback,
+            el.index,
+            el.add,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrshLiteral(el) => literal(0xF93F_0000, el.add, not_hint(el.rt)?, el.imm)?,
+        LdrshRegister(el) => register_offset(
+            0xF930_0000,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.rm,
+            &el.shift,
+        )?,
+        Ldrsht(el) => unprivileged(
+            0xF930_0E00,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+
+        // A5_20
+        LdrbImmediate(el) => load_store(
+            0xF890_0000,
+            0xF810_0800,
+            el.w.unwrap_or(false),
+            el.index,
+            el.add.unwrap_or(true),
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrbLiteral(el) => literal(
+            0xF81F_0000,
+            el.add.unwrap_or(true),
+            not_hint(el.rt)?,
+            el.imm,
+        )?,
+        LdrbRegister(el) => {
+            if !el.add.unwrap_or(true) {
+                return Err(EncodeError::InvalidOperands(
+                    "Register offsets are always added",
+                ));
+            }
+            register_offset(
+                0xF810_0000,
+                not_hint(el.rt)?,
+                not_pc(el.rn)?,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        Ldrbt(el) => unprivileged(
+            0xF810_0E00,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrsbImmediate(el) => load_store(
+            0xF990_0000,
+            0xF910_0800,
+            el.wback,
+            el.index,
+            el.add,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+        )?,
+        LdrsbLiteral(el) => literal(0xF91F_0000, el.add, not_hint(el.rt)?, el.imm)?,
+        LdrsbRegister(el) => register_offset(
+            0xF910_0000,
+            not_hint(el.rt)?,
+            not_pc(el.rn)?,
+            el.rm,
+            &el.shift,
+        )?,
+        Ldrsbt(el) => unprivileged(0xF910_0E00, not_hint(el.rt)?, not_pc(el.rn)?, el.imm)?,
+        PldImmediate(el) => match el.add.unwrap_or(true) {
+            true => 0xF890_F000 | reg(not_pc(el.rn)?) << 16 | bounded(el.imm, 0xFFF)?,
+            false => 0xF810_FC00 | reg(not_pc(el.rn)?) << 16 | bounded(el.imm, 0xFF)?,
+        },
+        PldLiteral(el) => literal(0xF81F_F000, el.add.unwrap_or(true), Register::PC, el.imm)?,
+        PldRegister(el) => {
+            register_offset(0xF810_F000, Register::PC, not_pc(el.rn)?, el.rm, &el.shift)?
+        }
+        PliImmediate(el) => match (el.rn.unwrap_or(Register::PC), el.add.unwrap_or(true)) {
+            (Register::PC, add) => literal(0xF91F_F000, add, Register::PC, el.imm)?,
+            (rn, true) => 0xF990_F000 | reg(rn) << 16 | bounded(el.imm, 0xFFF)?,
+            (rn, false) => 0xF910_FC00 | reg(rn) << 16 | bounded(el.imm, 0xFF)?,
+        },
+        PliRegister(el) => {
+            register_offset(0xF910_F000, Register::PC, not_pc(el.rn)?, el.rm, &el.shift)?
+        }
+
+        // A5_21
+        StrbImmediate(el) => load_store(
+            0xF880_0000,
+            0xF800_0800,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add,
+            el.rt,
+            el.rn,
+            el.imm,
+        )?,
+        Strbt(el) => unprivileged(0xF800_0E00, el.rt, el.rn, el.imm.unwrap_or(0))?,
+        StrbRegister(el) => register_offset(0xF800_0000, el.rt, el.rn, el.rm, &el.shift)?,
+        StrhImmediate(el) => load_store(
+            0xF8A0_0000,
+            0xF820_0800,
+            el.w,
+            el.index,
+            el.add,
+            el.rt,
+            el.rn,
+            el.imm.unwrap_or(0),
+        )?,
+        Strht(el) => unprivileged(0xF820_0E00, el.rt, el.rn, el.imm.unwrap_or(0))?,
+        StrhRegister(el) => register_offset(0xF820_0000, el.rt, el.rn, el.rm, &el.shift)?,
+        StrImmediate(el) => load_store(
+            0xF8C0_0000,
+            0xF840_0800,
+            el.w.unwrap_or(false),
+            el.index.unwrap_or(true),
+            el.add,
+            el.rt,
+            el.rn,
+            el.imm,
+        )?,
+        Strt(el) => unprivileged(0xF840_0E00, el.rt, el.rn, el.imm.unwrap_or(0))?,
+        StrRegister(el) => register_offset(0xF840_0000, el.rt, el.rn, el.rm, &el.shift)?,
+
+        // A5_22
+        AndRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(el.rn))?;
+            a5_22(
+                0b0000,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        TstRegister(el) => a5_22(0b0000, true, el.rn, Register::PC, el.rm, &el.shift)?,
+        BicRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(
+                0b0001,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        OrrRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(
+                0b0010,
+                el.s.local_unwrap(false),
+                not_pc(el.rn)?,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        OrnRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(
+                0b0011,
+                el.s.unwrap_or(false),
+                not_pc(el.rn)?,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        MvnRegister(el) => {
+            let s = el.s.local_unwrap(false);
+            a5_22(0b0011, s, Register::PC, el.rd, el.rm, &el.shift)?
+        }
+        EorRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(el.rn))?;
+            a5_22(
+                0b0100,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        TeqRegister(el) => a5_22(0b0100, true, el.rn, Register::PC, el.rm, &el.shift)?,
+        Pkh(el) => {
+            let amount = match (el.tb, &el.shift) {
+                (_, None) => 0,
+                (
+                    false,
+                    Some(ImmShift {
+                        shift_t: Shift::Lsl,
+                        shift_n,
+                    }),
+                ) => bounded(*shift_n as u32, 31)?,
+                (
+                    true,
+                    Some(ImmShift {
+                        shift_t: Shift::Asr,
+                        shift_n,
+                    }),
+                ) => ranged(*shift_n as u32, 1, 32)? & 0b11111,
+                _ => {
+                    return Err(EncodeError::InvalidOperands(
+                        "PKHBT shifts left and PKHTB shifts arithmetic right",
+                    ))
+                }
+            };
+            let rd = el.rd.unwrap_or(el.rn);
+            0xEAC0_0000
+                | reg(el.rn) << 16
+                | reg(rd) << 8
+                | imm5(amount)
+                | (el.tb as u32) << 5
+                | reg(el.rm)
+        }
+        AddRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(el.rn))?;
+            a5_22(
+                0b1000,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        AddSPRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(Register::SP))?;
+            a5_22(
+                0b1000,
+                el.s.unwrap_or(false),
+                Register::SP,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        CmnRegister(el) => a5_22(0b1000, true, el.rn, Register::PC, el.rm, &el.shift)?,
+        AdcRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(
+                0b1010,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        SbcRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(
+                0b1011,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        SubRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(el.rn))?;
+            a5_22(
+                0b1101,
+                el.s.local_unwrap(false),
+                el.rn,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        SubSpMinusRegister(el) => {
+            let rd = not_pc(el.rd.unwrap_or(Register::SP))?;
+            a5_22(
+                0b1101,
+                el.s.unwrap_or(false),
+                Register::SP,
+                rd,
+                el.rm,
+                &el.shift,
+            )?
+        }
+        CmpRegister(el) => a5_22(0b1101, true, el.rn, Register::PC, el.rm, &el.shift)?,
+        RsbRegister(el) => {
+            let rd = el.rd.unwrap_or(el.rn);
+            a5_22(0b1110, el.s.unwrap_or(false), el.rn, rd, el.rm, &el.shift)?
+        }
+
+        // A5_23
+        MovRegister(el) => a5_23(el.s.unwrap_or(false), el.rd, el.rm, 0b00, 0),
+        LslImmediate(el) => {
+            let imm = bounded(el.imm as u32, 31)?;
+            a5_23(el.s.local_unwrap(false), el.rd, el.rm, 0b00, imm)
+        }
+        LsrImmediate(el) => {
+            let imm = ranged(el.imm as u32, 1, 32)? & 0b11111;
+            a5_23(el.s.local_unwrap(false), el.rd, el.rm, 0b01, imm)
+        }
+        AsrImmediate(el) => {
+            let imm = ranged(el.imm, 1, 32)? & 0b11111;
+            a5_23(el.s.local_unwrap(false), el.rd, el.rm, 0b10, imm)
+        }
+        Rrx(el) => a5_23(el.s.unwrap_or(false), el.rd, el.rm, 0b11, 0),
+        RorImmediate(el) => {
+            let imm = ranged(el.imm, 1, 31)?;
+            a5_23(el.s.unwrap_or(false), el.rd, el.rm, 0b11, imm)
+        }
+
+        // A5_24
+        LslRegister(el) => {
+            let s = (el.s.local_unwrap(false) as u32) << 20;
+            a5_24(0xFA00_F000 | s, el.rn, el.rd, el.rm)
+        }
+        LsrRegister(el) => {
+            let s = (el.s.local_unwrap(false) as u32) << 20;
+            a5_24(0xFA20_F000 | s, el.rn, el.rd, el.rm)
+        }
+        AsrRegister(el) => {
+            let s = (el.s.local_unwrap(false) as u32) << 20;
+            a5_24(0xFA40_F000 | s, el.rn, el.rd, el.rm)
+        }
+        RorRegister(el) => {
+            let s = (el.s.local_unwrap(false) as u32) << 20;
+            a5_24(0xFA60_F000 | s, el.rn, el.rd, el.rm)
+        }
+        Sxtah(el) => extend(
+            0b000,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Sxth(el) => extend(0b000, Register::PC, el.rd, el.rm, el.rotation)?,
+        Uxtah(el) => extend(
+            0b001,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Uxth(el) => extend(0b001, Register::PC, el.rd, el.rm, el.rotation)?,
+        Sxtab16(el) => extend(
+            0b010,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Sxtb16(el) => extend(
+            0b010,
+            Register::PC,
+            el.rd.unwrap_or(el.rm),
+            el.rm,
+            el.rotation,
+        )?,
+        Uxtab16(el) => extend(
+            0b011,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Uxtb16(el) => extend(
+            0b011,
+            Register::PC,
+            el.rd.unwrap_or(el.rm),
+            el.rm,
+            el.rotation,
+        )?,
+        Sxtab(el) => extend(
+            0b100,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Sxtb(el) => extend(0b100, Register::PC, el.rd, el.rm, el.rotation)?,
+        Uxtab(el) => extend(
+            0b101,
+            not_pc(el.rn)?,
+            el.rd.unwrap_or(el.rn),
+            el.rm,
+            el.rotation,
+        )?,
+        Uxtb(el) => extend(0b101, Register::PC, el.rd, el.rm, el.rotation)?,
+
+        // A5_25
+        Sadd8(el) => a5_25(
+            0xFA80_F000,
+            0b000,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Sadd16(el) => a5_25(
+            0xFA80_F000,
+            0b001,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Sasx(el) => a5_25(
+            0xFA80_F000,
+            0b010,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Ssub8(el) => a5_25(
+            0xFA80_F000,
+            0b100,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Ssub16(el) => a5_25(
+            0xFA80_F000,
+            0b101,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Ssax(el) => a5_25(
+            0xFA80_F000,
+            0b110,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qadd8(el) => a5_25(
+            0xFA80_F000,
+            0b000,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qadd16(el) => a5_25(
+            0xFA80_F000,
+            0b001,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qasx(el) => a5_25(
+            0xFA80_F000,
+            0b010,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qsub8(el) => a5_25(
+            0xFA80_F000,
+            0b100,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qsub16(el) => a5_25(
+            0xFA80_F000,
+            0b101,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Qsax(el) => a5_25(
+            0xFA80_F000,
+            0b110,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shadd8(el) => a5_25(
+            0xFA80_F000,
+            0b000,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shadd16(el) => a5_25(
+            0xFA80_F000,
+            0b001,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shasx(el) => a5_25(
+            0xFA80_F000,
+            0b010,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shsub8(el) => a5_25(
+            0xFA80_F000,
+            0b100,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shsub16(el) => a5_25(
+            0xFA80_F000,
+            0b101,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Shsax(el) => a5_25(
+            0xFA80_F000,
+            0b110,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+
+        // A5_26
+        Uadd8(el) => a5_25(
+            0xFA80_F040,
+            0b000,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uadd16(el) => a5_25(
+            0xFA80_F040,
+            0b001,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uasx(el) => a5_25(
+            0xFA80_F040,
+            0b010,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Usub8(el) => a5_25(
+            0xFA80_F040,
+            0b100,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Usub16(el) => a5_25(
+            0xFA80_F040,
+            0b101,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Usax(el) => a5_25(
+            0xFA80_F040,
+            0b110,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqadd8(el) => a5_25(
+            0xFA80_F040,
+            0b000,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqadd16(el) => a5_25(
+            0xFA80_F040,
+            0b001,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqasx(el) => a5_25(
+            0xFA80_F040,
+            0b010,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqsub8(el) => a5_25(
+            0xFA80_F040,
+            0b100,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqsub16(el) => a5_25(
+            0xFA80_F040,
+            0b101,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uqsax(el) => a5_25(
+            0xFA80_F040,
+            0b110,
+            0b01,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhadd8(el) => a5_25(
+            0xFA80_F040,
+            0b000,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhadd16(el) => a5_25(
+            0xFA80_F040,
+            0b001,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhasx(el) => a5_25(
+            0xFA80_F040,
+            0b010,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhsub8(el) => a5_25(
+            0xFA80_F040,
+            0b100,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhsub16(el) => a5_25(
+            0xFA80_F040,
+            0b101,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Uhsax(el) => a5_25(
+            0xFA80_F040,
+            0b110,
+            0b10,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+
+        // A5_27
+        Qadd(el) => a5_25(
+            0xFA80_F080,
+            0b00,
+            0b00,
+            el.rd.unwrap_or(el.rm),
+            el.rn,
+            el.rm,
+        ),
+        Qdadd(el) => a5_25(
+            0xFA80_F080,
+            0b00,
+            0b01,
+            el.rd.unwrap_or(el.rm),
+            el.rn,
+            el.rm,
+        ),
+        Qsub(el) => a5_25(
+            0xFA80_F080,
+            0b00,
+            0b10,
+            el.rd.unwrap_or(el.rm),
+            el.rn,
+            el.rm,
+        ),
+        Qdsub(el) => a5_25(
+            0xFA80_F080,
+            0b00,
+            0b11,
+            el.rd.unwrap_or(el.rm),
+            el.rn,
+            el.rm,
+        ),
+        Rev(el) => a5_25(0xFA80_F080, 0b01, 0b00, el.rd, el.rm, el.rm),
+        Rev16(el) => a5_25(0xFA80_F080, 0b01, 0b01, el.rd, el.rm, el.rm),
+        Rbit(el) => a5_25(0xFA80_F080, 0b01, 0b10, el.rd, el.rm, el.rm),
+        Revsh(el) => a5_25(0xFA80_F080, 0b01, 0b11, el.rd, el.rm, el.rm),
+        Sel(el) => a5_25(
+            0xFA80_F080,
+            0b10,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Clz(el) => a5_25(0xFA80_F080, 0b11, 0b00, el.rd, el.rm, el.rm),
+
+        // A5_28
+        Mul(el) => {
+            if el.s.local_unwrap(false) {
+                return Err(EncodeError::InvalidOperands(
+                    "32-bit multiply can not set the flags",
+                ));
+            }
+            a5_28(
+                0b000,
+                0b00,
+                el.rd.unwrap_or(el.rn),
+                el.rn,
+                el.rm,
+                Register::PC,
+            )
+        }
+        Mla(el) => a5_28(0b000, 0b00, el.rd, el.rn, el.rm, not_pc(el.ra)?),
+        Mls(el) => a5_28(0b000, 0b01, el.rd, el.rn, el.rm, el.ra),
+        Smla(el) => {
+            let op2 = (el.n_high as u32) << 1 | el.m_high as u32;
+            a5_28(0b001, op2, el.rd, el.rn, el.rm, not_pc(el.ra)?)
+        }
+        Smul(el) => {
+            let op2 = (el.n_high as u32) << 1 | el.m_high as u32;
+            a5_28(
+                0b001,
+                op2,
+                el.rd.unwrap_or(el.rn),
+                el.rn,
+                el.rm,
+                Register::PC,
+            )
+        }
+        Smlad(el) => {
+            let op2 = el.x.unwrap_or(false) as u32;
+            a5_28(0b010, op2, el.rd, el.rn, el.rm, not_pc(el.ra)?)
+        }
+        Smuad(el) => {
+            let op2 = el.m_swap.unwrap_or(false) as u32;
+            a5_28(0b010, op2, el.rd, el.rn, el.rm, Register::PC)
+        }
+        Smlaw(el) => {
+            let op2 = el.m_high as u32;
+            a5_28(0b011, op2, el.rd, el.rn, el.rm, not_pc(el.ra)?)
+        }
+        Smulw(el) => {
+            let op2 = el.m_high as u32;
+            a5_28(
+                0b011,
+                op2,
+                el.rd.unwrap_or(el.rn),
+                el.rn,
+                el.rm,
+                Register::PC,
+            )
+        }
+        Smlsd(el) => {
+            let op2 = el.m_swap.unwrap_or(false) as u32;
+            a5_28(0b100, op2, el.rd, el.rn, el.rm, not_pc(el.ra)?)
+        }
+        Smusd(el) => {
+            let op2 = el.m_swap.unwrap_or(false) as u32;
+            a5_28(
+                0b100,
+                op2,
+                el.rd.unwrap_or(el.rn),
+                el.rn,
+                el.rm,
+                Register::PC,
+            )
+        }
+        Smmla(el) => {
+            let op2 = el.round.unwrap_or(false) as u32;
+            a5_28(0b101, op2, el.rd, el.rn, el.rm, not_pc(el.ra)?)
+        }
+        Smmul(el) => {
+            let op2 = el.round.unwrap_or(false) as u32;
+            a5_28(0b101, op2, el.rd, el.rn, el.rm, Register::PC)
+        }
+        Smmls(el) => {
+            let op2 = el.round.unwrap_or(false) as u32;
+            a5_28(0b110, op2, el.rd, el.rn, el.rm, el.ra)
+        }
+        Usada8(el) => a5_28(0b111, 0b00, el.rd, el.rn, el.rm, not_pc(el.ra)?),
+        Usad8(el) => a5_28(
+            0b111,
+            0b00,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+            Register::PC,
+        ),
+
+        // A5_29
+        Smull(el) => a5_29(0b000, 0b0000, el.rdlo, el.rdhi, el.rn, el.rm),
+        Sdiv(el) => a5_29(
+            0b001,
+            0b1111,
+            Register::PC,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Umull(el) => a5_29(0b010, 0b0000, el.rdlo, el.rdhi, el.rn, el.rm),
+        Udiv(el) => a5_29(
+            0b011,
+            0b1111,
+            Register::PC,
+            el.rd.unwrap_or(el.rn),
+            el.rn,
+            el.rm,
+        ),
+        Smlal(el) => a5_29(0b100, 0b0000, el.rdlo, el.rdhi, el.rn, el.rm),
+        SmlalSelective(el) => {
+            let op2 = 0b1000 | (el.n_high as u32) << 1 | el.m_high as u32;
+            a5_29(0b100, op2, el.rdlo, el.rdhi, el.rn, el.rm)
+        }
+        Smlald(el) => {
+            let op2 = 0b1100 | el.x.unwrap_or(false) as u32;
+            a5_29(0b100, op2, el.rdlo, el.rdhi, el.rn, el.rm)
+        }
+        Smlsld(el) => {
+            let op2 = 0b1100 | el.m_swap.unwrap_or(false) as u32;
+            a5_29(0b101, op2, el.rdlo, el.rdhi, el.rn, el.rm)
+        }
+        Umlal(el) => a5_29(0b110, 0b0000, el.rdlo, el.rdhi, el.rn, el.rm),
+        Umaal(el) => a5_29(0b110, 0b0110, el.rdlo, el.rdhi, el.rn, el.rm),
+
+        // A5_30
+        Stc(el) => coproc_load_store(
+            0xEC00_0000,
+            el.coproc,
+            el.crd,
+            el.rn,
+            el.imm.unwrap_or(0),
+            el.add,
+            el.w,
+            el.index,
+        )?,
+        LdcImmediate(el) => coproc_load_store(
+            0xEC10_0000,
+            el.coproc,
+            el.crd,
+            not_pc(el.rn)?,
+            el.imm.unwrap_or(0),
+            el.add,
+            el.w,
+            el.index,
+        )?,
+        LdcLiteral(el) => coproc_load_store(
+            0xEC10_0000,
+            el.coproc,
+            el.crd,
+            Register::PC,
+            el.imm,
+            el.add,
+            false,
+            el.index,
+        )?,
+        Mcrr(el) => coproc_transfer_dual(0xEC40_0000, el.coproc, el.opc1, el.rt, el.rt2, el.crm)?,
+        Mrrc(el) => coproc_transfer_dual(0xEC50_0000, el.coproc, el.opc1, el.rt, el.rt2, el.crm)?,
+        Cdp(el) => {
+            0xEE00_0000
+                | bounded(el.opc1 as u32, 0xF)? << 20
+                | bounded(el.crn as u32, 0xF)? << 16
+                | bounded(el.crd as u32, 0xF)? << 12
+                | coproc_number(el.coproc)? << 8
+                | bounded(el.opc2 as u32, 0b111)? << 5
+                | bounded(el.crm as u32, 0xF)?
+        }
+        Mcr(el) => coproc_transfer(
+            0xEE00_0010,
+            el.coproc,
+            el.opc1,
+            el.opc2,
+            el.rt,
+            el.crm,
+            el.crn,
+        )?,
+        Mrc(el) => coproc_transfer(
+            0xEE10_0010,
+            el.coproc,
+            el.opc1,
+            el.opc2,
+            el.rt,
+            el.crm,
+            el.crn,
+        )?,
+        _ => return vfp::encode(op),
+    })
+}
@@ -0,0 +1,332 @@
+//! Encodes the floating point extension [`Operation`]s, see chapter A6.
+
+use super::{bounded, reg, scaled};
+use crate::{
+    arch::{Condition, F32Register, F64Register, Register, RoundingMode},
+    asm::b32::a6_4::vfp_expand_imm,
+    operation::Operation,
+    EncodeError,
+};
+
+/// The `D:Vd` register operand.
+const D: (u32, u32) = (22, 12);
+/// The `N:Vn` register operand.
+const N: (u32, u32) = (7, 16);
+/// The `M:Vm` register operand.
+const M: (u32, u32) = (5, 0);
+
+/// Single precision registers are encoded as `Vx:X`.
+fn single(register: F32Register, (x, vx): (u32, u32)) -> u32 {
+    let idx = u8::from(register) as u32;
+    (idx & 1) << x | (idx >> 1) << vx
+}
+
+/// Double precision registers are encoded as `X:Vx`, `X` is always zero in
+/// the M-profile.
+fn double(register: F64Register, (_, vx): (u32, u32)) -> u32 {
+    (u8::from(register) as u32) << vx
+}
+
+/// The size bit, set for double precision operations.
+const F64: u32 = 1 << 8;
+
+/// Single precision operations on `Sd`, `Sn` and `Sm`.
+fn single3(base: u32, sd: F32Register, sn: F32Register, sm: F32Register) -> u32 {
+    base | single(sd, D) | single(sn, N) | single(sm, M)
+}
+
+/// Double precision operations on `Dd`, `Dn` and `Dm`.
+fn double3(base: u32, dd: F64Register, dn: F64Register, dm: F64Register) -> u32 {
+    base | F64 | double(dd, D) | double(dn, N) | double(dm, M)
+}
+
+/// Single precision operations on `Sd` and `Sm`.
+fn single2(base: u32, sd: F32Register, sm: F32Register) -> u32 {
+    base | single(sd, D) | single(sm, M)
+}
+
+/// Double precision operations on `Dd` and `Dm`.
+fn double2(base: u32, dd: F64Register, dm: F64Register) -> u32 {
+    base | F64 | double(dd, D) | double(dm, M)
+}
+
+/// Encodes the `E` bit of `vcmp`.
+fn exception(e: Option<bool>) -> u32 {
+    (e.unwrap_or(false) as u32) << 7
+}
+
+/// Encodes the `RM` field of the directed rounding instructions.
+fn rounding_mode(r: RoundingMode) -> Result<u32, EncodeError> {
+    match r {
+        RoundingMode::TiesAway => Ok(0b00),
+        RoundingMode::TiesEven => Ok(0b01),
+        RoundingMode::PlusInfinity => Ok(0b10),
+        RoundingMode::MinusInfinity => Ok(0b11),
+        RoundingMode::Zero => Err(EncodeError::InvalidOperands(
+            "Round towards zero has no directed encoding",
+        )),
+    }
+}
+
+/// Selects the `vrint` encoding for the rounding mode.
+fn vrint(r: Option<RoundingMode>) -> Result<u32, EncodeError> {
+    Ok(match r {
+        None => 0xEEB6_0A40,
+        Some(RoundingMode::Zero) => 0xEEB6_0AC0,
+        Some(r) => 0xFEB8_0A40 | rounding_mode(r)? << 16,
+    })
+}
+
+/// Selects the `vcvt` to integer encoding for the rounding mode.
+fn vcvt_to_int(signed: bool, r: Option<RoundingMode>) -> Result<u32, EncodeError> {
+    Ok(match r {
+        None => 0xEEBC_0A40 | (signed as u32) << 16,
+        Some(RoundingMode::Zero) => 0xEEBC_0AC0 | (signed as u32) << 16,
+        Some(r) => 0xFEBC_0A40 | rounding_mode(r)? << 16 | (signed as u32) << 7,
+    })
+}
+
+/// Encodes the fixed point conversions.
+fn vcvt_fixed(to_fixed: bool, signed: bool, size: u8, fbits: u8) -> Result<u32, EncodeError> {
+    let sx = match size {
+        16 => 0,
+        32 => 1,
+        _ => {
+            return Err(EncodeError::InvalidOperands(
+                "Fixed point size must be 16 or 32",
+            ))
+        }
+    };
+    let imm = match size.checked_sub(fbits) {
+        Some(imm) => bounded(imm as u32, 0b11111)?,
+        None => return Err(EncodeError::ImmediateOutOfRange(fbits as u32)),
+    };
+    Ok(0xEEBA_0A40
+        | (to_fixed as u32) << 18
+        | (!signed as u32) << 16
+        | sx << 7
+        | (imm & 1) << 5
+        | imm >> 1)
+}
+
+/// Finds the 8-bit immediate that expands to `value`.
+fn vmov_immediate(value: u64, sz: bool) -> Result<u32, EncodeError> {
+    match (0..=u8::MAX).find(|imm8| vfp_expand_imm(*imm8, sz) == value) {
+        Some(imm8) => Ok((imm8 as u32 >> 4) << 16 | imm8 as u32 & 0xF),
+        None => Err(EncodeError::NotExpandable(match sz {
+            false => value as u32,
+            true => (value >> 32) as u32,
+        })),
+    }
+}
+
+/// Encodes the `cc` field of `vsel`.
+fn select(cond: &Condition) -> Result<u32, EncodeError> {
+    match cond {
+        Condition::Eq => Ok(0b00),
+        Condition::Vs => Ok(0b01),
+        Condition::Ge => Ok(0b10),
+        Condition::Gt => Ok(0b11),
+        _ => Err(EncodeError::InvalidOperands(
+            "vsel only supports the eq, vs, ge and gt conditions",
+        )),
+    }
+}
+
+/// Ensures that the registers are consecutive, returning the first register
+/// and the number of registers.
+fn consecutive<T: Copy + Into<u8>>(registers: &[T], max: u32) -> Result<(T, u32), EncodeError> {
+    let first = match registers.first() {
+        Some(first) => *first,
+        None => {
+            return Err(EncodeError::InvalidOperands(
+                "Register list must not be empty",
+            ))
+        }
+    };
+    for (idx, register) in registers.iter().enumerate() {
+        if (*register).into() as usize != first.into() as usize + idx {
+            return Err(EncodeError::InvalidOperands(
+                "Register list must be consecutive",
+            ));
+        }
+    }
+    Ok((first, bounded(registers.len() as u32, max)?))
+}
+
+/// Single precision multiple register transfers.
+fn single_list(base: u32, registers: &[F32Register]) -> Result<u32, EncodeError> {
+    let (first, count) = consecutive(registers, 32)?;
+    Ok(base | single(first, D) | count)
+}
+
+/// Double precision multiple register transfers.
+fn double_list(base: u32, registers: &[F64Register]) -> Result<u32, EncodeError> {
+    let (first, count) = consecutive(registers, 16)?;
+    Ok(base | F64 | double(first, D) | count << 1)
+}
+
+/// Encodes the addressing mode of `vldm` and `vstm`.
+fn multiple(add: bool, w: bool, rn: Register) -> Result<u32, EncodeError> {
+    if rn == Register::PC {
+        return Err(EncodeError::RegisterOutOfRange(rn));
+    }
+    match (add, w) {
+        (true, w) => Ok(0xEC80_0A00 | (w as u32) << 21 | reg(rn) << 16),
+        (false, true) => Ok(0xED20_0A00 | reg(rn) << 16),
+        (false, false) => Err(EncodeError::InvalidOperands(
+            "Decrement before requires write back",
+        )),
+    }
+}
+
+/// Encodes `vldr` and `vstr`.
+fn load_store(base: u32, add: bool, rn: Register, imm: u32) -> Result<u32, EncodeError> {
+    Ok(base | (add as u32) << 23 | reg(rn) << 16 | scaled(imm, 2, 0xFF)?)
+}
+
+/// Rejects PC as the base register for stores.
+fn store_base(rn: Register) -> Result<Register, EncodeError> {
+    match rn {
+        Register::PC => Err(EncodeError::RegisterOutOfRange(rn)),
+        rn => Ok(rn),
+    }
+}
+
+pub(super) fn encode(op: &Operation) -> Result<u32, EncodeError> {
+    use Operation::*;
+    Ok(match op {
+        VmlaF32(el) => single3(0xEE00_0A00, el.sd, el.sn, el.sm),
+        VmlaF64(el) => double3(0xEE00_0A00, el.dd, el.dn, el.dm),
+        VmlsF32(el) => single3(0xEE00_0A40, el.sd, el.sn, el.sm),
+        VmlsF64(el) => double3(0xEE00_0A40, el.dd, el.dn, el.dm),
+        VnmlsF32(el) => single3(0xEE10_0A00, el.sd, el.sn, el.sm),
+        VnmlsF64(el) => double3(0xEE10_0A00, el.dd, el.dn, el.dm),
+        VnmlaF32(el) => single3(0xEE10_0A40, el.sd, el.sn, el.sm),
+        VnmlaF64(el) => double3(0xEE10_0A40, el.dd, el.dn, el.dm),
+        VmulF32(el) => single3(0xEE20_0A00, el.sd, el.sn, el.sm),
+        VmulF64(el) => double3(0xEE20_0A00, el.dd, el.dn, el.dm),
+        VnmulF32(el) => single3(0xEE20_0A40, el.sd, el.sn, el.sm),
+        VnmulF64(el) => double3(0xEE20_0A40, el.dd, el.dn, el.dm),
+        VaddF32(el) => single3(0xEE30_0A00, el.sd, el.sn, el.sm),
+        VaddF64(el) => double3(0xEE30_0A00, el.dd, el.dn, el.dm),
+        VsubF32(el) => single3(0xEE30_0A40, el.sd, el.sn, el.sm),
+        VsubF64(el) => double3(0xEE30_0A40, el.dd, el.dn, el.dm),
+        VdivF32(el) => single3(0xEE80_0A00, el.sd, el.sn, el.sm),
+        VdivF64(el) => double3(0xEE80_0A00, el.dd, el.dn, el.dm),
+        VfnmsF32(el) => single3(0xEE90_0A00, el.sd, el.sn, el.sm),
+        VfnmsF64(el) => double3(0xEE90_0A00, el.dd, el.dn, el.dm),
+        VfnmaF32(el) => single3(0xEE90_0A40, el.sd, el.sn, el.sm),
+        VfnmaF64(el) => double3(0xEE90_0A40, el.dd, el.dn, el.dm),
+        VfmaF32(el) => single3(0xEEA0_0A00, el.sd, el.sn, el.sm),
+        VfmaF64(el) => double3(0xEEA0_0A00, el.dd, el.dn, el.dm),
+        VfmsF32(el) => single3(0xEEA0_0A40, el.sd, el.sn, el.sm),
+        VfmsF64(el) => double3(0xEEA0_0A40, el.dd, el.dn, el.dm),
+        VselF32(el) => single3(0xFE00_0A00 | select(&el.cond)? << 20, el.sd, el.sn, el.sm),
+        VselF64(el) => double3(0xFE00_0A00 | select(&el.cond)? << 20, el.dd, el.dn, el.dm),
+        VmaxnmF32(el) => single3(0xFE80_0A00, el.sd, el.sn, el.sm),
+        VmaxnmF64(el) => double3(0xFE80_0A00, el.dd, el.dn, el.dm),
+        VminnmF32(el) => single3(0xFE80_0A40, el.sd, el.sn, el.sm),
+        VminnmF64(el) => double3(0xFE80_0A40, el.dd, el.dn, el.dm),
+        VmovImmediateF32(el) => {
+            0xEEB0_0A00 | single(el.sd, D) | vmov_immediate(el.imm as u64, false)?
+        }
+        VmovImmediateF64(el) => {
+            0xEEB0_0A00 | F64 | double(el.dd, D) | vmov_immediate(el.imm, true)?
+        }
+        VmovRegisterF32(el) => single2(0xEEB0_0A40, el.sd, el.sm),
+        VmovRegisterF64(el) => double2(0xEEB0_0A40, el.dd, el.dm),
+        VabsF32(el) => single2(0xEEB0_0AC0, el.sd, el.sm),
+        VabsF64(el) => double2(0xEEB0_0AC0, el.dd, el.dm),
+        VnegF32(el) => single2(0xEEB1_0A40, el.sd, el.sm),
+        VnegF64(el) => double2(0xEEB1_0A40, el.dd, el.dm),
+        VsqrtF32(el) => single2(0xEEB1_0AC0, el.sd, el.sm),
+        VsqrtF64(el) => double2(0xEEB1_0AC0, el.dd, el.dm),
+        VcvtF32F16(el) => single2(0xEEB2_0A40 | (el.top as u32) << 7, el.sd, el.sm),
+        VcvtF16F32(el) => single2(0xEEB3_0A40 | (el.top as u32) << 7, el.sd, el.sm),
+        VcvtF64F16(el) => {
+            0xEEB2_0A40 | F64 | (el.top as u32) << 7 | double(el.dd, D) | single(el.sm, M)
+        }
+        VcvtF16F64(el) => {
+            0xEEB3_0A40 | F64 | (el.top as u32) << 7 | single(el.sd, D) | double(el.dm, M)
+        }
+        VcmpF32(el) => single2(0xEEB4_0A40 | exception(el.e), el.sd, el.sm),
+        VcmpF64(el) => double2(0xEEB4_0A40 | exception(el.e), el.dd, el.dm),
+        VcmpZeroF32(el) => 0xEEB5_0A40 | exception(el.e) | single(el.sd, D),
+        VcmpZeroF64(el) => 0xEEB5_0A40 | F64 | exception(el.e) | double(el.dd, D),
+        VrintF32(el) => single2(vrint(el.r)?, el.sd, el.sm),
+        VrintF64(el) => double2(vrint(el.r)?, el.dd, el.dm),
+        VrintxF32(el) => single2(0xEEB7_0A40, el.sd, el.sm),
+        VrintxF64(el) => double2(0xEEB7_0A40, el.dd, el.dm),
+        VcvtF64F32(el) => 0xEEB7_0AC0 | double(el.dd, D) | single(el.sm, M),
+        VcvtF32F64(el) => 0xEEB7_0AC0 | F64 | single(el.sd, D) | double(el.dm, M),
+        VcvtFromIntF32(el) => single2(0xEEB8_0A40 | (el.signed as u32) << 7, el.sd, el.sm),
+        VcvtFromIntF64(el) => {
+            0xEEB8_0A40 | F64 | (el.signed as u32) << 7 | double(el.dd, D) | single(el.sm, M)
+        }
+        VcvtToIntF32(el) => single2(vcvt_to_int(el.signed, el.r)?, el.sd, el.sm),
+        VcvtToIntF64(el) => {
+            vcvt_to_int(el.signed, el.r)? | F64 | single(el.sd, D) | double(el.dm, M)
+        }
+        VcvtFixedF32(el) => {
+            vcvt_fixed(el.to_fixed, el.signed, el.size, el.fbits)? | single(el.sd, D)
+        }
+        VcvtFixedF64(el) => {
+            vcvt_fixed(el.to_fixed, el.signed, el.size, el.fbits)? | F64 | double(el.dd, D)
+        }
+
+        // A6_5
+        VstmF32(el) => single_list(multiple(el.add, el.w, el.rn)?, &el.registers)?,
+        VstmF64(el) => double_list(multiple(el.add, el.w, el.rn)?, &el.registers)?,
+        VldmF32(el) => single_list(multiple(el.add, el.w, el.rn)? | 1 << 20, &el.registers)?,
+        VldmF64(el) => double_list(multiple(el.add, el.w, el.rn)? | 1 << 20, &el.registers)?,
+        VpushF32(el) => single_list(0xED2D_0A00, &el.registers)?,
+        VpushF64(el) => double_list(0xED2D_0A00, &el.registers)?,
+        VpopF32(el) => single_list(0xECBD_0A00, &el.registers)?,
+        VpopF64(el) => double_list(0xECBD_0A00, &el.registers)?,
+        VstrF32(el) => {
+            load_store(0xED00_0A00, el.add, store_base(el.rn)?, el.imm)? | single(el.sd, D)
+        }
+        VstrF64(el) => {
+            load_store(0xED00_0B00, el.add, store_base(el.rn)?, el.imm)? | double(el.dd, D)
+        }
+        VldrF32(el) => load_store(0xED10_0A00, el.add, el.rn, el.imm)? | single(el.sd, D),
+        VldrF64(el) => load_store(0xED10_0B00, el.add, el.rn, el.imm)? | double(el.dd, D),
+
+        // A6_6
+        VmovCoreF32(el) => {
+            0xEE00_0A10 | (el.to_core as u32) << 20 | single(el.sn, N) | reg(el.rt) << 12
+        }
+        Vmsr(el) => 0xEEE1_0A10 | reg(el.rt) << 12,
+        Vmrs(el) => 0xEEF1_0A10 | reg(el.rt) << 12,
+        VmovCoreScalar(el) => {
+            0xEE00_0B10
+                | bounded(el.index as u32, 1)? << 21
+                | (el.to_core as u32) << 20
+                | double(el.dd, N)
+                | reg(el.rt) << 12
+        }
+
+        // A6_7
+        VmovCore2F32(el) => {
+            if u8::from(el.sm1) != u8::from(el.sm) + 1 {
+                return Err(EncodeError::InvalidOperands(
+                    "The single precision registers must be consecutive",
+                ));
+            }
+            0xEC40_0A10
+                | (el.to_core as u32) << 20
+                | reg(el.rt2) << 16
+                | reg(el.rt) << 12
+                | single(el.sm, M)
+        }
+        VmovCoreF64(el) => {
+            0xEC40_0B10
+                | (el.to_core as u32) << 20
+                | reg(el.rt2) << 16
+                | reg(el.rt) << 12
+                | double(el.dm, M)
+        }
+        _ => return Err(EncodeError::NoEncoding(32)),
+    })
+}
@@ -0,0 +1,589 @@
+//! A reference interpreter for [`Operation`]s.
+//!
+//! The interpreter follows the pseudocode in the Armv7-M architecture
+//! reference manual and is intended as an executable oracle for the decoder,
+//! not as a cycle accurate simulator. It models a core in privileged thread
+//! mode without a floating point unit or coprocessors, executing any of those
+//! instructions raises a [`UsageFault::NoCoprocessor`].
+//!
+//! ```
+//! use disarmv7::{exec::{CpuState, Ram}, prelude::*};
+//!
+//! // movs r0, #5 ; adds r0, r0, #3
+//! let mut memory = Ram::new(0x100, vec![0x05, 0x20, 0xc0, 0x1c]);
+//! let mut state = CpuState::default();
+//! state.registers[15] = 0x100;
+//!
+//! state.step(&mut memory).unwrap();
+//! state.step(&mut memory).unwrap();
+//! assert_eq!(state.register(Register::R0), 8);
+//! assert_eq!(state.register(Register::PC), 0x104);
+//! ```
+
+mod alu;
+mod execute;
+
+use std::fmt::Display;
+
+use crate::{
+    arch::{Condition, Register},
+    buffer::SliceStream,
+    operation::Operation,
+    Parse,
+    ParseError,
+};
+
+/// The application program status register.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Apsr {
+    /// Negative condition flag.
+    pub n: bool,
+    /// Zero condition flag.
+    pub z: bool,
+    /// Carry condition flag.
+    pub c: bool,
+    /// Overflow condition flag.
+    pub v: bool,
+    /// Cumulative saturation flag.
+    pub q: bool,
+    /// Greater than or equal flags, one per byte lane.
+    pub ge: u8,
+}
+
+impl Apsr {
+    /// Returns the register as it is read by `mrs`.
+    pub fn bits(&self) -> u32 {
+        (self.n as u32) << 31
+            | (self.z as u32) << 30
+            | (self.c as u32) << 29
+            | (self.v as u32) << 28
+            | (self.q as u32) << 27
+            | ((self.ge & 0b1111) as u32) << 16
+    }
+}
+
+/// The state of a Cortex-M core executing in privileged thread mode.
+///
+/// The stack pointer that is not selected by `CONTROL.SPSEL` is kept aside
+/// and swapped in to `R13` when `CONTROL` is written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuState {
+    /// The general purpose registers, `R15` holds the address of the next
+    /// instruction to execute.
+    pub registers: [u32; 16],
+
+    /// The condition and saturation flags.
+    pub apsr: Apsr,
+
+    /// The `ITSTATE` bits of the EPSR, i.e. the base condition and the mask
+    /// of the current IT block.
+    pub itstate: u8,
+
+    /// Disables all exceptions with configurable priority.
+    pub primask: bool,
+
+    /// Disables all exceptions other than NMI.
+    pub faultmask: bool,
+
+    /// The minimum priority for exception processing.
+    pub basepri: u8,
+
+    /// `nPRIV` in bit 0 and `SPSEL` in bit 1.
+    pub control: u8,
+
+    /// The stack pointer that is not currently selected.
+    pub banked_sp: u32,
+
+    /// The address tagged by the last `ldrex`, if the local monitor is in the
+    /// exclusive access state.
+    pub exclusive: Option<u32>,
+}
+
+/// Enumerates the exceptions that the interpreter can raise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Exception {
+    /// Raised by `svc`, contains the immediate.
+    SupervisorCall(u8),
+
+    /// Raised by `bkpt`, contains the immediate.
+    Breakpoint(u32),
+
+    /// A fault caused by the instruction itself.
+    UsageFault(UsageFault),
+
+    /// The memory rejected an access to the contained address.
+    BusFault(u32),
+}
+
+/// Enumerates the causes of a [`Exception::UsageFault`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsageFault {
+    /// The instruction is permanently undefined, such as `udf`.
+    Undefined,
+
+    /// A branch cleared `EPSR.T`, i.e. the target address was even.
+    InvalidState,
+
+    /// The instruction accesses a coprocessor or the floating point unit.
+    NoCoprocessor,
+
+    /// A multi word or exclusive access was not word aligned.
+    Unaligned(u32),
+}
+
+/// Enumerates the errors that might occur while executing an [`Operation`].
+///
+/// The PC is left pointing at the instruction that caused the error.
+/// Faults that the architecture defers to the next instruction, such as
+/// clearing `EPSR.T`, are raised by the instruction that caused them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecError {
+    /// The operation raised an exception.
+    Exception(Exception),
+
+    /// The operation is UNPREDICTABLE with the given operands.
+    Unpredictable,
+
+    /// The instruction at the PC could not be decoded.
+    Decode(ParseError),
+}
+
+/// A fault raised by a [`Memory`] access, contains the faulting address.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusFault(pub u32);
+
+/// The memory that the interpreter loads from and stores to.
+///
+/// Accesses are little endian and never span more than four bytes, the
+/// interpreter performs the alignment checks required by the architecture.
+pub trait Memory {
+    /// Reads `data.len()` bytes starting at `address`.
+    fn read(&mut self, address: u32, data: &mut [u8]) -> Result<(), BusFault>;
+
+    /// Writes `data` starting at `address`.
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), BusFault>;
+}
+
+/// A contiguous block of memory placed at a base address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ram {
+    base: u32,
+    bytes: Vec<u8>,
+}
+
+impl Ram {
+    /// Places `bytes` at `base`.
+    pub fn new(base: u32, bytes: Vec<u8>) -> Self {
+        Self { base, bytes }
+    }
+
+    /// Returns the contents of the memory.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn range(&self, address: u32, len: usize) -> Result<std::ops::Range<usize>, BusFault> {
+        let start = address.wrapping_sub(self.base) as usize;
+        match start.checked_add(len) {
+            Some(end) if address >= self.base && end <= self.bytes.len() => Ok(start..end),
+            _ => Err(BusFault(address)),
+        }
+    }
+}
+
+impl Memory for Ram {
+    fn read(&mut self, address: u32, data: &mut [u8]) -> Result<(), BusFault> {
+        let range = self.range(address, data.len())?;
+        data.copy_from_slice(&self.bytes[range]);
+        Ok(())
+    }
+
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), BusFault> {
+        let range = self.range(address, data.len())?;
+        self.bytes[range].copy_from_slice(data);
+        Ok(())
+    }
+}
+
+impl CpuState {
+    /// Returns the raw value of the register.
+    ///
+    /// Unlike operands of an instruction the PC reads as the address of the
+    /// next instruction to execute.
+    pub fn register(&self, register: Register) -> u32 {
+        self.registers[u8::from(register) as usize]
+    }
+
+    /// Returns the main stack pointer.
+    pub fn msp(&self) -> u32 {
+        match self.control & 0b10 {
+            0 => self.registers[13],
+            _ => self.banked_sp,
+        }
+    }
+
+    /// Returns the process stack pointer.
+    pub fn psp(&self) -> u32 {
+        match self.control & 0b10 {
+            0 => self.banked_sp,
+            _ => self.registers[13],
+        }
+    }
+
+    /// Returns true if the core executes in privileged mode.
+    pub fn privileged(&self) -> bool {
+        self.control & 0b1 == 0
+    }
+
+    /// Returns true if the next instruction is inside of an IT block.
+    pub fn in_it_block(&self) -> bool {
+        self.itstate & 0b1111 != 0
+    }
+
+    /// Returns true if the next instruction is the last one in an IT block.
+    pub fn last_in_it_block(&self) -> bool {
+        self.itstate & 0b1111 == 0b1000
+    }
+
+    /// Returns the condition that applies to the next instruction due to
+    /// the current IT block.
+    pub fn it_condition(&self) -> Condition {
+        match self.in_it_block() {
+            true => Condition::try_from(self.itstate >> 4).unwrap_or(Condition::None),
+            false => Condition::None,
+        }
+    }
+
+    /// Evaluates the condition against the APSR flags.
+    pub fn condition_passed(&self, condition: &Condition) -> bool {
+        let Apsr { n, z, c, v, .. } = self.apsr;
+        match condition {
+            Condition::Eq => z,
+            Condition::Ne => !z,
+            Condition::Cs => c,
+            Condition::Cc => !c,
+            Condition::Mi => n,
+            Condition::Pl => !n,
+            Condition::Vs => v,
+            Condition::Vc => !v,
+            Condition::Hi => c && !z,
+            Condition::Ls => !c || z,
+            Condition::Ge => n == v,
+            Condition::Lt => n != v,
+            Condition::Gt => !z && n == v,
+            Condition::Le => z || n != v,
+            Condition::None => true,
+        }
+    }
+
+    /// Advances the IT block state after an instruction.
+    fn it_advance(&mut self) {
+        if self.itstate & 0b111 == 0 {
+            self.itstate = 0;
+        } else {
+            self.itstate = (self.itstate & 0b1110_0000) | ((self.itstate << 1) & 0b1_1111);
+        }
+    }
+
+    /// Executes an operation of `size` bits that was decoded at the PC.
+    ///
+    /// The operation is skipped if it fails the condition of the current IT
+    /// block, the PC and IT block state are advanced either way.
+    pub fn execute<M: Memory>(
+        &mut self,
+        memory: &mut M,
+        size: usize,
+        operation: &Operation,
+    ) -> Result<(), ExecError> {
+        let address = self.registers[15];
+        let next = address.wrapping_add(size as u32 / 8);
+        let is_it = matches!(operation, Operation::It(_));
+        let next = match self.condition_passed(&self.it_condition()) {
+            true => execute::execute(self, memory, address, next, operation)?,
+            false => next,
+        };
+        if !is_it {
+            self.it_advance();
+        }
+        self.registers[15] = next;
+        Ok(())
+    }
+
+    /// Fetches, decodes and executes the instruction at the PC.
+    pub fn step<M: Memory>(&mut self, memory: &mut M) -> Result<Operation, ExecError> {
+        let address = self.registers[15];
+        let mut bytes = [0; 4];
+        memory.read(address, &mut bytes[..2])?;
+        let len = match u16::from_le_bytes([bytes[0], bytes[1]]) >> 11 {
+            0b11101..=0b11111 => {
+                memory.read(address.wrapping_add(2), &mut bytes[2..])?;
+                4
+            }
+            _ => 2,
+        };
+        let mut stream = SliceStream::new(&bytes[..len]);
+        let (size, operation) = Operation::parse(&mut stream).map_err(ExecError::Decode)?;
+        self.execute(memory, size, &operation)?;
+        Ok(operation)
+    }
+}
+
+impl From<Exception> for ExecError {
+    fn from(value: Exception) -> Self {
+        Self::Exception(value)
+    }
+}
+
+impl From<UsageFault> for ExecError {
+    fn from(value: UsageFault) -> Self {
+        Self::Exception(Exception::UsageFault(value))
+    }
+}
+
+impl From<BusFault> for ExecError {
+    fn from(value: BusFault) -> Self {
+        Self::Exception(Exception::BusFault(value.0))
+    }
+}
+
+impl Display for Exception {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SupervisorCall(imm) => write!(f, "supervisor call #{imm}"),
+            Self::Breakpoint(imm) => write!(f, "breakpoint #{imm}"),
+            Self::UsageFault(UsageFault::Undefined) => write!(f, "undefined instruction"),
+            Self::UsageFault(UsageFault::InvalidState) => write!(f, "invalid state"),
+            Self::UsageFault(UsageFault::NoCoprocessor) => write!(f, "no coprocessor"),
+            Self::UsageFault(UsageFault::Unaligned(address)) => {
+                write!(f, "unaligned access to {address:#010x}")
+            }
+            Self::BusFault(address) => write!(f, "bus fault at {address:#010x}"),
+        }
+    }
+}
+
+impl Display for ExecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception(exception) => write!(f, "{exception}"),
+            Self::Unpredictable => write!(f, "UNPREDICTABLE operation"),
+            Self::Decode(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Runs `code` placed at address 0 until the PC leaves it, the memory
+    /// extends 0x100 bytes past the code for use as a stack.
+    fn run(state: &mut CpuState, code: &[u8]) -> Result<Ram, ExecError> {
+        let mut bytes = code.to_vec();
+        bytes.resize(code.len() + 0x100, 0);
+        let mut memory = Ram::new(0, bytes);
+        while (state.registers[15] as usize) < code.len() {
+            state.step(&mut memory)?;
+        }
+        Ok(memory)
+    }
+
+    #[test]
+    fn test_it_block() {
+        // cmp r0, #0 ; it eq ; addeq r1, r1, #1 ; adds r2, r2, #1
+        let code = [0x00, 0x28, 0x08, 0xbf, 0x49, 0x1c, 0x52, 0x1c];
+
+        let mut state = CpuState::default();
+        state.registers[2] = u32::MAX;
+        run(&mut state, &code).unwrap();
+        assert_eq!(state.registers[1], 1);
+        assert_eq!(state.registers[2], 0);
+        // The add outside of the block sets the flags.
+        assert!(state.apsr.z && state.apsr.c);
+        assert!(!state.in_it_block());
+
+        let mut state = CpuState::default();
+        state.registers[0] = 1;
+        state.registers[1] = u32::MAX;
+        run(&mut state, &code).unwrap();
+        // The add inside of the block is skipped and never sets the flags.
+        assert_eq!(state.registers[1], u32::MAX);
+        assert!(!state.apsr.z);
+    }
+
+    #[test]
+    fn test_it_then_else() {
+        // ite ne ; movne r3, #1 ; moveq r3, #2
+        let code = [0x14, 0xbf, 0x01, 0x23, 0x02, 0x23];
+
+        let mut state = CpuState::default();
+        run(&mut state, &code).unwrap();
+        assert_eq!(state.registers[3], 1);
+        // Flags are not set inside of an IT block.
+        assert!(!state.apsr.z);
+
+        let mut state = CpuState::default();
+        state.apsr.z = true;
+        run(&mut state, &code).unwrap();
+        assert_eq!(state.registers[3], 2);
+    }
+
+    #[test]
+    fn test_immediate_carry() {
+        // movs.w r0, #0x80000000
+        let mut state = CpuState::default();
+        run(&mut state, &[0x5f, 0xf0, 0x00, 0x40]).unwrap();
+        assert_eq!(state.registers[0], 0x8000_0000);
+        assert!(state.apsr.n && state.apsr.c);
+
+        // ands r4, r0, #0xff leaves the carry unchanged.
+        let mut state = CpuState::default();
+        state.apsr.c = true;
+        run(&mut state, &[0x10, 0xf0, 0xff, 0x04]).unwrap();
+        assert!(state.apsr.z && state.apsr.c);
+
+        // ands r4, r0, #0x80000000 clears it.
+        let mut state = CpuState::default();
+        state.apsr.c = true;
+        state.registers[0] = u32::MAX;
+        run(&mut state, &[0x10, 0xf0, 0x00, 0x44]).unwrap();
+        assert_eq!(state.registers[4], 0x8000_0000);
+        assert!(state.apsr.n && state.apsr.c);
+    }
+
+    #[test]
+    fn test_conditional_branch() {
+        // bne .+8 ; nop ; nop ; nop
+        let code = [0x02, 0xd1, 0x00, 0xbf, 0x00, 0xbf, 0x00, 0xbf];
+        let mut memory = Ram::new(0, code.to_vec());
+
+        let mut state = CpuState::default();
+        state.step(&mut memory).unwrap();
+        assert_eq!(state.registers[15], 8);
+
+        let mut state = CpuState::default();
+        state.apsr.z = true;
+        state.step(&mut memory).unwrap();
+        assert_eq!(state.registers[15], 2);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        // push {r0, r1, lr} ; pop {r2, r3, pc}
+        let code = [0x03, 0xb5, 0x0c, 0xbd];
+        let mut state = CpuState::default();
+        state.registers[13] = 0x104;
+        state.registers[0] = 1;
+        state.registers[1] = 2;
+        state.registers[14] = 0x11;
+        let memory = run(&mut state, &code).unwrap();
+        assert_eq!((state.registers[2], state.registers[3]), (1, 2));
+        assert_eq!(state.registers[13], 0x104);
+        assert_eq!(state.registers[15], 0x10);
+        assert_eq!(&memory.bytes()[0xf8..0xfc], &[1, 0, 0, 0]);
+
+        // The popped PC must have the thumb bit set.
+        let mut state = CpuState::default();
+        state.registers[13] = 0x104;
+        state.registers[14] = 0x10;
+        assert_eq!(run(&mut state, &code), Err(UsageFault::InvalidState.into()));
+        assert_eq!(state.registers[15], 2);
+    }
+
+    #[test]
+    fn test_ldm_writeback() {
+        // ldm r0!, {r1, r2}
+        let mut state = CpuState::default();
+        state.registers[0] = 0x0;
+        run(&mut state, &[0x06, 0xc8]).unwrap();
+        assert_eq!(state.registers[0], 8);
+        assert_eq!(state.registers[1], 0x0000_c806);
+
+        let mut state = CpuState::default();
+        state.registers[0] = 0x2;
+        assert_eq!(
+            run(&mut state, &[0x06, 0xc8]),
+            Err(UsageFault::Unaligned(2).into())
+        );
+    }
+
+    #[test]
+    fn test_parallel() {
+        // sadd8 r0, r1, r2 ; sel r3, r4, r5
+        let code = [0x81, 0xfa, 0x02, 0xf0, 0xa4, 0xfa, 0x85, 0xf3];
+        let mut state = CpuState::default();
+        state.registers[1] = 0x7f_80_01_ff;
+        state.registers[2] = 0x01_ff_01_00;
+        state.registers[4] = 0x11_22_33_44;
+        state.registers[5] = 0xaa_bb_cc_dd;
+        run(&mut state, &code).unwrap();
+        assert_eq!(state.registers[0], 0x80_7f_02_ff);
+        assert_eq!(state.apsr.ge, 0b1010);
+        assert_eq!(state.registers[3], 0x11_bb_33_dd);
+    }
+
+    #[test]
+    fn test_saturation() {
+        // ssat r0, #8, r1 ; qadd r0, r1, r2
+        let mut state = CpuState::default();
+        state.registers[1] = 300;
+        run(&mut state, &[0x01, 0xf3, 0x07, 0x00]).unwrap();
+        assert_eq!(state.registers[0], 127);
+        assert!(state.apsr.q);
+
+        let mut state = CpuState::default();
+        state.registers[1] = i32::MAX as u32;
+        state.registers[2] = 1;
+        run(&mut state, &[0x82, 0xfa, 0x81, 0xf0]).unwrap();
+        assert_eq!(state.registers[0], i32::MAX as u32);
+        assert!(state.apsr.q);
+    }
+
+    #[test]
+    fn test_special_registers() {
+        // msr control, r0 ; mrs r1, psp
+        let code = [0x80, 0xf3, 0x14, 0x88, 0xef, 0xf3, 0x09, 0x81];
+        let mut state = CpuState::default();
+        state.registers[0] = 0b10;
+        state.registers[13] = 0x200;
+        state.banked_sp = 0x100;
+        run(&mut state, &code).unwrap();
+        assert_eq!(state.registers[13], 0x100);
+        assert_eq!(state.registers[1], 0x100);
+        assert_eq!(state.msp(), 0x200);
+    }
+
+    #[test]
+    fn test_exceptions() {
+        // mov.w r0, #2 ; blx r0
+        let mut state = CpuState::default();
+        assert_eq!(
+            run(&mut state, &[0x4f, 0xf0, 0x02, 0x00, 0x80, 0x47]),
+            Err(UsageFault::InvalidState.into())
+        );
+        assert_eq!(state.registers[15], 4);
+
+        // svc #3
+        let mut state = CpuState::default();
+        assert_eq!(
+            run(&mut state, &[0x03, 0xdf]),
+            Err(Exception::SupervisorCall(3).into())
+        );
+
+        // vadd.f32 s0, s0, s1
+        let mut state = CpuState::default();
+        assert_eq!(
+            run(&mut state, &[0x30, 0xee, 0x20, 0x0a]),
+            Err(UsageFault::NoCoprocessor.into())
+        );
+    }
+}
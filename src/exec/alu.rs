@@ -0,0 +1,173 @@
+//! Implements the shared pseudocode functions from the architecture
+//! reference manual.
+
+use crate::arch::Shift;
+
+/// `AddWithCarry(x, y, carry_in)`, returns the result, carry and overflow.
+pub(super) fn add_with_carry(x: u32, y: u32, carry_in: bool) -> (u32, bool, bool) {
+    let unsigned_sum = x as u64 + y as u64 + carry_in as u64;
+    let signed_sum = x as i32 as i64 + y as i32 as i64 + carry_in as i64;
+    let result = unsigned_sum as u32;
+    (
+        result,
+        result as u64 != unsigned_sum,
+        result as i32 as i64 != signed_sum,
+    )
+}
+
+/// `Shift_C(value, type, amount, carry_in)`, returns the result and carry.
+pub(super) fn shift_c(value: u32, shift_t: &Shift, amount: u32, carry_in: bool) -> (u32, bool) {
+    if amount == 0 && *shift_t != Shift::Rrx {
+        return (value, carry_in);
+    }
+    match shift_t {
+        Shift::Lsl => match amount {
+            1..=31 => (value << amount, (value >> (32 - amount)) & 1 == 1),
+            32 => (0, value & 1 == 1),
+            _ => (0, false),
+        },
+        Shift::Lsr => match amount {
+            1..=31 => (value >> amount, (value >> (amount - 1)) & 1 == 1),
+            32 => (0, value >> 31 == 1),
+            _ => (0, false),
+        },
+        Shift::Asr => {
+            let amount = amount.min(32);
+            let result = ((value as i32 as i64) >> amount) as u32;
+            (result, ((value as i32 as i64) >> (amount - 1)) & 1 == 1)
+        }
+        Shift::Ror => {
+            let result = value.rotate_right(amount % 32);
+            (result, result >> 31 == 1)
+        }
+        Shift::Rrx => ((carry_in as u32) << 31 | value >> 1, value & 1 == 1),
+    }
+}
+
+/// `SignedSatQ(i, n)`, returns the result and wether or not it saturated.
+pub(super) fn signed_sat_q(value: i64, n: u32) -> (i64, bool) {
+    let max = (1i64 << (n - 1)) - 1;
+    let min = -(1i64 << (n - 1));
+    match value {
+        value if value > max => (max, true),
+        value if value < min => (min, true),
+        value => (value, false),
+    }
+}
+
+/// `UnsignedSatQ(i, n)`, returns the result and wether or not it saturated.
+pub(super) fn unsigned_sat_q(value: i64, n: u32) -> (i64, bool) {
+    let max = (1i64 << n) - 1;
+    match value {
+        value if value > max => (max, true),
+        value if value < 0 => (0, true),
+        value => (value, false),
+    }
+}
+
+/// The operation applied to one lane of a parallel addition or subtraction.
+#[derive(Clone, Copy)]
+pub(super) enum Lane {
+    Add,
+    Sub,
+}
+
+/// How the result of a lane is written back.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Mode {
+    /// Truncated to the lane width, sets the GE flags.
+    Modular,
+    /// Saturated to the lane width.
+    Saturating,
+    /// Halved, which always fits the lane width.
+    Halving,
+}
+
+/// The lanes of a parallel addition or subtraction.
+pub(super) struct Lanes {
+    /// The width of each lane in bits.
+    width: u32,
+    /// Swaps the halfwords of the second operand, as done by the `asx` and
+    /// `sax` variants.
+    exchange: bool,
+    /// The operation applied to each lane, least significant first.
+    ops: &'static [Lane],
+}
+
+pub(super) const ADD16: Lanes = Lanes {
+    width: 16,
+    exchange: false,
+    ops: &[Lane::Add; 2],
+};
+pub(super) const ADD8: Lanes = Lanes {
+    width: 8,
+    exchange: false,
+    ops: &[Lane::Add; 4],
+};
+pub(super) const ASX: Lanes = Lanes {
+    width: 16,
+    exchange: true,
+    ops: &[Lane::Sub, Lane::Add],
+};
+pub(super) const SAX: Lanes = Lanes {
+    width: 16,
+    exchange: true,
+    ops: &[Lane::Add, Lane::Sub],
+};
+pub(super) const SUB16: Lanes = Lanes {
+    width: 16,
+    exchange: false,
+    ops: &[Lane::Sub; 2],
+};
+pub(super) const SUB8: Lanes = Lanes {
+    width: 8,
+    exchange: false,
+    ops: &[Lane::Sub; 4],
+};
+
+/// Applies `lanes` to `n` and `m`, returns the result and the GE flags.
+pub(super) fn parallel(n: u32, m: u32, lanes: &Lanes, signed: bool, mode: Mode) -> (u32, u8) {
+    let Lanes {
+        width,
+        exchange,
+        ops,
+    } = *lanes;
+    let m = match exchange {
+        true => m.rotate_right(16),
+        false => m,
+    };
+    let lane_mask = (1u64 << width) - 1;
+    let extend = |value: u32, idx: usize| -> i64 {
+        let value = (value as u64 >> (idx as u32 * width)) & lane_mask;
+        match signed {
+            true => ((value << (64 - width)) as i64) >> (64 - width),
+            false => value as i64,
+        }
+    };
+    let ge_bits = 4 / ops.len() as u32;
+
+    let mut result = 0u32;
+    let mut ge = 0u8;
+    for (idx, lane) in ops.iter().enumerate() {
+        let (x, y) = (extend(n, idx), extend(m, idx));
+        let value = match lane {
+            Lane::Add => x + y,
+            Lane::Sub => x - y,
+        };
+        let passed = match (signed, lane) {
+            (false, Lane::Add) => value >= 1 << width,
+            _ => value >= 0,
+        };
+        let value = match (mode, signed) {
+            (Mode::Modular, _) => value,
+            (Mode::Saturating, true) => signed_sat_q(value, width).0,
+            (Mode::Saturating, false) => unsigned_sat_q(value, width).0,
+            (Mode::Halving, _) => value >> 1,
+        };
+        result |= ((value as u64 & lane_mask) << (idx as u32 * width)) as u32;
+        if passed {
+            ge |= (((1u32 << ge_bits) - 1) << (idx as u32 * ge_bits)) as u8;
+        }
+    }
+    (result, ge)
+}
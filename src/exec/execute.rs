@@ -0,0 +1,1524 @@
+//! Defines the semantics of every [`Operation`].
+
+use super::{
+    alu::{
+        add_with_carry,
+        parallel,
+        shift_c,
+        signed_sat_q,
+        unsigned_sat_q,
+        Lane,
+        Lanes,
+        Mode::{self, *},
+        ADD16,
+        ADD8,
+        ASX,
+        SAX,
+        SUB16,
+        SUB8,
+    },
+    CpuState,
+    Exception,
+    ExecError,
+    Memory,
+    UsageFault,
+};
+use crate::{
+    arch::{ApsrMask, Condition, ImmShift, Register, RegisterList, Shift, SpecialRegister},
+    operation::{pc, Operation},
+};
+
+/// The state that is available while executing a single operation.
+struct Context<'a, M: Memory> {
+    state: &'a mut CpuState,
+    memory: &'a mut M,
+    /// The address of the operation.
+    address: u32,
+    /// The address of the next operation to execute.
+    next: u32,
+}
+
+/// Executes the operation at `address`, returns the address of the next
+/// operation to execute.
+pub(super) fn execute<M: Memory>(
+    state: &mut CpuState,
+    memory: &mut M,
+    address: u32,
+    next: u32,
+    operation: &Operation,
+) -> Result<u32, ExecError> {
+    let mut ctx = Context {
+        state,
+        memory,
+        address,
+        next,
+    };
+    ctx.execute(operation)?;
+    Ok(ctx.next)
+}
+
+/// Returns the bits `lsb..=msb` of `value`.
+fn bits(value: u32, lsb: u32, width: u32) -> u32 {
+    match width {
+        32.. => value,
+        _ => (value >> lsb) & ((1 << width) - 1),
+    }
+}
+
+/// Sign extends the `width` least significant bits of `value`.
+fn sign_extend(value: u32, width: u32) -> i64 {
+    ((value as i64) << (64 - width)) >> (64 - width)
+}
+
+/// Returns the top or bottom halfword of `value` as a signed number.
+fn half(value: u32, high: bool) -> i64 {
+    match high {
+        true => (value >> 16) as i16 as i64,
+        false => value as i16 as i64,
+    }
+}
+
+impl<'a, M: Memory> Context<'a, M> {
+    /// Reads a register as an operand, the PC reads as the address of the
+    /// operation plus 4.
+    fn r(&self, register: &Register) -> u32 {
+        match register {
+            Register::PC => pc(self.address),
+            register => self.state.register(*register),
+        }
+    }
+
+    /// Writes the result of a data processing operation.
+    fn set(&mut self, register: &Register, value: u32) {
+        match register {
+            Register::PC => self.branch(value),
+            register => self.state.registers[u8::from(*register) as usize] = value,
+        }
+    }
+
+    /// Writes the result of a load, loads to the PC are interworking
+    /// branches.
+    fn load(&mut self, register: &Register, value: u32) -> Result<(), ExecError> {
+        match register {
+            Register::PC => self.bx(value),
+            register => {
+                self.set(register, value);
+                Ok(())
+            }
+        }
+    }
+
+    /// `BranchWritePC`.
+    fn branch(&mut self, target: u32) {
+        self.next = target & !1;
+    }
+
+    /// `BXWritePC`.
+    fn bx(&mut self, target: u32) -> Result<(), ExecError> {
+        if target & 1 == 0 {
+            return Err(UsageFault::InvalidState.into());
+        }
+        self.branch(target);
+        Ok(())
+    }
+
+    /// Resolves the flag setting behaviour of the operation.
+    /// Updates N and Z from the result, C and V are updated if given.
+    fn flags(&mut self, result: u32, c: Option<bool>, v: Option<bool>) {
+        self.state.apsr.n = result >> 31 == 1;
+        self.state.apsr.z = result == 0;
+        if let Some(c) = c {
+            self.state.apsr.c = c;
+        }
+        if let Some(v) = v {
+            self.state.apsr.v = v;
+        }
+    }
+
+    /// Writes the result of a logical operation, carry is the carry out of
+    /// the shift or immediate expansion if any.
+    fn logical(&mut self, rd: &Register, s: bool, result: u32, carry: Option<bool>) {
+        self.set(rd, result);
+        if s && *rd != Register::PC {
+            self.flags(result, carry, None);
+        }
+    }
+
+    /// Writes the result of `AddWithCarry(x, y, carry_in)`.
+    fn arithmetic(&mut self, rd: &Register, s: bool, x: u32, y: u32, carry_in: bool) {
+        let (result, c, v) = add_with_carry(x, y, carry_in);
+        self.set(rd, result);
+        if s && *rd != Register::PC {
+            self.flags(result, Some(c), Some(v));
+        }
+    }
+
+    /// Updates the flags from `AddWithCarry(x, y, carry_in)`.
+    fn compare(&mut self, x: u32, y: u32, carry_in: bool) {
+        let (result, c, v) = add_with_carry(x, y, carry_in);
+        self.flags(result, Some(c), Some(v));
+    }
+
+    /// Shifts `rm`, returns the result and the carry out of the shift.
+    fn shifted(&self, rm: &Register, shift: &Option<ImmShift>) -> (u32, bool) {
+        let value = self.r(rm);
+        match shift {
+            Some(shift) => shift_c(
+                value,
+                &shift.shift_t,
+                shift.shift_n as u32,
+                self.state.apsr.c,
+            ),
+            None => (value, self.state.apsr.c),
+        }
+    }
+
+    /// Sets the Q flag if `saturated`.
+    fn saturated(&mut self, saturated: bool) {
+        self.state.apsr.q |= saturated;
+    }
+
+    fn read(&mut self, address: u32, size: usize) -> Result<u32, ExecError> {
+        let mut data = [0; 4];
+        self.memory.read(address, &mut data[..size])?;
+        Ok(u32::from_le_bytes(data))
+    }
+
+    fn write(&mut self, address: u32, size: usize, value: u32) -> Result<(), ExecError> {
+        self.memory.write(address, &value.to_le_bytes()[..size])?;
+        Ok(())
+    }
+
+    /// `MemA`, faults if the access is not aligned.
+    fn read_aligned(&mut self, address: u32, size: usize) -> Result<u32, ExecError> {
+        if !address.is_multiple_of(size as u32) {
+            return Err(UsageFault::Unaligned(address).into());
+        }
+        self.read(address, size)
+    }
+
+    /// `MemA`, faults if the access is not aligned.
+    fn write_aligned(&mut self, address: u32, size: usize, value: u32) -> Result<(), ExecError> {
+        if !address.is_multiple_of(size as u32) {
+            return Err(UsageFault::Unaligned(address).into());
+        }
+        self.write(address, size, value)
+    }
+
+    /// Computes the addresses of an immediate offset access, returns the
+    /// accessed address and the offset address.
+    fn offset(&self, rn: &Register, imm: u32, add: bool, index: bool) -> (u32, u32) {
+        let base = self.r(rn);
+        let offset = match add {
+            true => base.wrapping_add(imm),
+            false => base.wrapping_sub(imm),
+        };
+        (if index { offset } else { base }, offset)
+    }
+
+    /// Loads `size` bytes using an immediate offset.
+    #[allow(clippy::too_many_arguments)]
+    fn load_immediate(
+        &mut self,
+        rt: &Register,
+        rn: &Register,
+        imm: u32,
+        add: bool,
+        index: bool,
+        wback: bool,
+        size: usize,
+        signed: bool,
+    ) -> Result<(), ExecError> {
+        let (address, offset) = self.offset(rn, imm, add, index);
+        let data = self.read(address, size)?;
+        let data = self.extend(data, size, signed);
+        if wback {
+            self.set(rn, offset);
+        }
+        self.load(rt, data)
+    }
+
+    /// Stores `size` bytes using an immediate offset.
+    #[allow(clippy::too_many_arguments)]
+    fn store_immediate(
+        &mut self,
+        rt: &Register,
+        rn: &Register,
+        imm: u32,
+        add: bool,
+        index: bool,
+        wback: bool,
+        size: usize,
+    ) -> Result<(), ExecError> {
+        let (address, offset) = self.offset(rn, imm, add, index);
+        self.write(address, size, self.r(rt))?;
+        if wback {
+            self.set(rn, offset);
+        }
+        Ok(())
+    }
+
+    /// Returns the address of a register offset access.
+    fn register_address(&self, rn: &Register, rm: &Register, shift: &Option<ImmShift>) -> u32 {
+        let offset = match shift {
+            Some(shift) => self.r(rm) << shift.shift_n,
+            None => self.r(rm),
+        };
+        self.r(rn).wrapping_add(offset)
+    }
+
+    /// Loads `size` bytes using a register offset.
+    fn load_register(
+        &mut self,
+        rt: &Register,
+        address: u32,
+        size: usize,
+        signed: bool,
+    ) -> Result<(), ExecError> {
+        let data = self.read(address, size)?;
+        let data = self.extend(data, size, signed);
+        self.load(rt, data)
+    }
+
+    /// Loads `size` bytes from a PC relative address.
+    fn load_literal(
+        &mut self,
+        rt: &Register,
+        operation: &Operation,
+        size: usize,
+        signed: bool,
+    ) -> Result<(), ExecError> {
+        let address = operation
+            .literal_address(self.address)
+            .ok_or(ExecError::Unpredictable)?;
+        self.load_register(rt, address, size, signed)
+    }
+
+    fn extend(&self, data: u32, size: usize, signed: bool) -> u32 {
+        match (size, signed) {
+            (1, true) => data as i8 as u32,
+            (2, true) => data as i16 as u32,
+            _ => data,
+        }
+    }
+
+    /// Loads the registers from consecutive words starting at `address`.
+    fn load_multiple(&mut self, address: u32, registers: &RegisterList) -> Result<(), ExecError> {
+        let mut values = Vec::with_capacity(registers.registers.len());
+        for (idx, _) in registers.registers.iter().enumerate() {
+            values.push(self.read_aligned(address.wrapping_add(4 * idx as u32), 4)?);
+        }
+        for (register, value) in registers.registers.iter().zip(values) {
+            self.load(register, value)?;
+        }
+        Ok(())
+    }
+
+    /// Stores the registers to consecutive words starting at `address`.
+    fn store_multiple(&mut self, address: u32, registers: &RegisterList) -> Result<(), ExecError> {
+        for (idx, register) in registers.registers.iter().enumerate() {
+            let value = self.r(register);
+            self.write_aligned(address.wrapping_add(4 * idx as u32), 4, value)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` to `rn` unless the register was loaded.
+    fn write_back(&mut self, rn: &Register, w: bool, registers: &RegisterList, value: u32) {
+        if w && !registers.registers.contains(rn) {
+            self.set(rn, value);
+        }
+    }
+
+    /// Writes a 64 bit result to a register pair.
+    fn set_long(&mut self, rdlo: &Register, rdhi: &Register, value: u64) {
+        self.set(rdlo, value as u32);
+        self.set(rdhi, (value >> 32) as u32);
+    }
+
+    fn long(&self, rdlo: &Register, rdhi: &Register) -> u64 {
+        (self.r(rdhi) as u64) << 32 | self.r(rdlo) as u64
+    }
+
+    /// Applies a parallel addition or subtraction.
+    fn parallel(
+        &mut self,
+        rd: &Option<Register>,
+        rn: &Register,
+        rm: &Register,
+        lanes: &Lanes,
+        signed: bool,
+        mode: Mode,
+    ) {
+        let (result, ge) = parallel(self.r(rn), self.r(rm), lanes, signed, mode);
+        if mode == Mode::Modular {
+            self.state.apsr.ge = ge;
+        }
+        self.set(&rd.unwrap_or(*rn), result);
+    }
+
+    /// Extends the rotated byte or halfword of `rm` and adds it to `rn`.
+    #[allow(clippy::too_many_arguments)]
+    fn extend_add(
+        &mut self,
+        rd: &Register,
+        rn: Option<&Register>,
+        rm: &Register,
+        rotation: &Option<u32>,
+        width: u32,
+        signed: bool,
+        dual: bool,
+    ) {
+        let rotated = self.r(rm).rotate_right(rotation.unwrap_or(0));
+        let base = rn.map_or(0, |rn| self.r(rn));
+        let extend = |value: u32| match signed {
+            true => sign_extend(bits(value, 0, width), width) as u32,
+            false => bits(value, 0, width),
+        };
+        let result = match dual {
+            true => {
+                let low = (base as u16).wrapping_add(extend(rotated) as u16);
+                let high = ((base >> 16) as u16).wrapping_add(extend(rotated >> 16) as u16);
+                (high as u32) << 16 | low as u32
+            }
+            false => base.wrapping_add(extend(rotated)),
+        };
+        self.set(rd, result);
+    }
+
+    /// `SignedSatQ(SInt(rm) +/- SignedSatQ(2 * SInt(rn)))`.
+    fn doubling(&mut self, rd: &Register, rm: &Register, rn: &Register, lane: Lane) {
+        let (doubled, doubled_saturated) = signed_sat_q(2 * self.r(rn) as i32 as i64, 32);
+        let m = self.r(rm) as i32 as i64;
+        let value = match lane {
+            Lane::Add => m + doubled,
+            Lane::Sub => m - doubled,
+        };
+        let (result, saturated) = signed_sat_q(value, 32);
+        self.saturated(doubled_saturated || saturated);
+        self.set(rd, result as u32)
+    }
+
+    /// Accumulates the signed 64 bit product of `rn` and `rm` in to the top
+    /// word of `ra`.
+    fn most_significant(
+        &self,
+        rn: &Register,
+        rm: &Register,
+        ra: Option<&Register>,
+        lane: Lane,
+    ) -> i64 {
+        let product = self.r(rn) as i32 as i64 * self.r(rm) as i32 as i64;
+        let accumulator = ra.map_or(0, |ra| (self.r(ra) as i64) << 32);
+        match lane {
+            Lane::Add => accumulator.wrapping_add(product),
+            Lane::Sub => accumulator.wrapping_sub(product),
+        }
+    }
+
+    /// Swaps in the other stack pointer if `SPSEL` changed.
+    fn write_control(&mut self, value: u8) {
+        if (self.state.control ^ value) & 0b10 != 0 {
+            std::mem::swap(&mut self.state.registers[13], &mut self.state.banked_sp);
+        }
+        self.state.control = value & 0b11;
+    }
+
+    fn mrs(&mut self, sysm: SpecialRegister) -> Result<u32, ExecError> {
+        use SpecialRegister as S;
+        let privileged = self.state.privileged();
+        Ok(match sysm {
+            sysm if sysm.includes_apsr() => self.state.apsr.bits(),
+            // The IPSR and EPSR read as zero in thread mode.
+            S::Ipsr | S::Epsr | S::Iepsr => 0,
+            S::Msp if privileged => self.state.msp(),
+            S::Psp if privileged => self.state.psp(),
+            S::Msp | S::Psp => 0,
+            S::Primask => self.state.primask as u32,
+            S::Basepri | S::BasepriMax => self.state.basepri as u32,
+            S::Faultmask => self.state.faultmask as u32,
+            S::Control => self.state.control as u32,
+            _ => return Err(ExecError::Unpredictable),
+        })
+    }
+
+    fn msr(&mut self, sysm: SpecialRegister, mask: ApsrMask, value: u32) -> Result<(), ExecError> {
+        use SpecialRegister as S;
+        if sysm.is_xpsr() {
+            if sysm.includes_apsr() {
+                let apsr = &mut self.state.apsr;
+                if mask.nzcvq {
+                    apsr.n = value >> 31 & 1 == 1;
+                    apsr.z = value >> 30 & 1 == 1;
+                    apsr.c = value >> 29 & 1 == 1;
+                    apsr.v = value >> 28 & 1 == 1;
+                    apsr.q = value >> 27 & 1 == 1;
+                }
+                if mask.g {
+                    apsr.ge = (value >> 16) as u8 & 0b1111;
+                }
+            }
+            return Ok(());
+        }
+        if sysm.is_v8m() {
+            return Err(ExecError::Unpredictable);
+        }
+        // Unprivileged writes are ignored.
+        if !self.state.privileged() {
+            return Ok(());
+        }
+        match sysm {
+            S::Msp | S::Psp => {
+                let main = self.state.control & 0b10 == 0;
+                match (sysm == S::Msp) == main {
+                    true => self.state.registers[13] = value & !0b11,
+                    false => self.state.banked_sp = value & !0b11,
+                }
+            }
+            S::Primask => self.state.primask = value & 1 == 1,
+            S::Basepri => self.state.basepri = value as u8,
+            S::BasepriMax => {
+                let value = value as u8;
+                if value != 0 && (value < self.state.basepri || self.state.basepri == 0) {
+                    self.state.basepri = value;
+                }
+            }
+            S::Faultmask => self.state.faultmask = value & 1 == 1,
+            S::Control => self.write_control(value as u8),
+            _ => return Err(ExecError::Unpredictable),
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn execute(&mut self, operation: &Operation) -> Result<(), ExecError> {
+        use Operation as O;
+        let carry = self.state.apsr.c;
+        let s = operation.s_suffix(self.state.in_it_block());
+        match operation {
+            // ============================ Data processing ============================
+            O::AdcImmediate(el) => {
+                let x = self.r(&el.rn);
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, el.imm, carry)
+            }
+            O::AdcRegister(el) => {
+                let (x, (y, _)) = (self.r(&el.rn), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, y, carry)
+            }
+            O::AddImmediate(el) => {
+                let x = self.r(&el.rn);
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, el.imm, false)
+            }
+            O::AddRegister(el) => {
+                let (x, (y, _)) = (self.r(&el.rn), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, y, false)
+            }
+            O::AddSPImmediate(el) => {
+                let x = self.r(&Register::SP);
+                self.arithmetic(&el.rd.unwrap_or(Register::SP), s, x, el.imm, false)
+            }
+            O::AddSPRegister(el) => {
+                let (x, (y, _)) = (self.r(&Register::SP), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(Register::SP), s, x, y, false)
+            }
+            O::Adr(el) => {
+                let address = operation.literal_address(self.address);
+                self.set(&el.rd, address.unwrap_or_default())
+            }
+            O::AndImmediate(el) => {
+                let result = self.r(&el.rn) & el.imm;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, el.carry)
+            }
+            O::AndRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                let result = self.r(&el.rn) & y;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, Some(c))
+            }
+            O::AsrImmediate(el) => {
+                let (result, c) = shift_c(self.r(&el.rm), &Shift::Asr, el.imm, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::AsrRegister(el) => {
+                let amount = self.r(&el.rm) & 0xff;
+                let (result, c) = shift_c(self.r(&el.rn), &Shift::Asr, amount, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::Bfc(el) => {
+                let mask = bits(u32::MAX, 0, el.msb + 1 - el.lsb.min(el.msb + 1)) << el.lsb;
+                let result = self.r(&el.rd) & !mask;
+                self.set(&el.rd, result)
+            }
+            O::Bfi(el) => {
+                let width = (el.msb + 1).saturating_sub(el.lsb);
+                let mask = bits(u32::MAX, 0, width) << el.lsb;
+                let result = (self.r(&el.rd) & !mask) | ((self.r(&el.rn) << el.lsb) & mask);
+                self.set(&el.rd, result)
+            }
+            O::BicImmediate(el) => {
+                let result = self.r(&el.rn) & !el.imm;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, el.carry)
+            }
+            O::BicRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                let result = self.r(&el.rn) & !y;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, Some(c))
+            }
+            O::Clz(el) => {
+                let result = self.r(&el.rm).leading_zeros();
+                self.set(&el.rd, result)
+            }
+            O::CmnImmediate(el) => self.compare(self.r(&el.rn), el.imm, false),
+            O::CmnRegister(el) => {
+                let (y, _) = self.shifted(&el.rm, &el.shift);
+                self.compare(self.r(&el.rn), y, false)
+            }
+            O::CmpImmediate(el) => self.compare(self.r(&el.rn), !el.imm, true),
+            O::CmpRegister(el) => {
+                let (y, _) = self.shifted(&el.rm, &el.shift);
+                self.compare(self.r(&el.rn), !y, true)
+            }
+            O::EorImmediate(el) => {
+                let result = self.r(&el.rn) ^ el.imm;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, el.carry)
+            }
+            O::EorRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                let result = self.r(&el.rn) ^ y;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, Some(c))
+            }
+            O::LslImmediate(el) => {
+                let (result, c) = shift_c(self.r(&el.rm), &Shift::Lsl, el.imm as u32, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::LslRegister(el) => {
+                let amount = self.r(&el.rm) & 0xff;
+                let (result, c) = shift_c(self.r(&el.rn), &Shift::Lsl, amount, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::LsrImmediate(el) => {
+                let (result, c) = shift_c(self.r(&el.rm), &Shift::Lsr, el.imm as u32, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::LsrRegister(el) => {
+                let amount = self.r(&el.rm) & 0xff;
+                let (result, c) = shift_c(self.r(&el.rn), &Shift::Lsr, amount, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::MovImmediate(el) => self.logical(&el.rd, s, el.imm, el.carry),
+            O::MovRegister(el) => {
+                let result = self.r(&el.rm);
+                self.logical(&el.rd, s, result, None)
+            }
+            O::Movt(el) => {
+                let result = (self.r(&el.rd) & 0xffff) | (el.imm as u32) << 16;
+                self.set(&el.rd, result)
+            }
+            O::MvnImmediate(el) => self.logical(&el.rd, s, !el.imm, el.carry),
+            O::MvnRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                self.logical(&el.rd, s, !y, Some(c))
+            }
+            O::OrnImmediate(el) => {
+                let result = self.r(&el.rn) | !el.imm;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, el.carry)
+            }
+            O::OrnRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                let result = self.r(&el.rn) | !y;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, Some(c))
+            }
+            O::OrrImmediate(el) => {
+                let result = self.r(&el.rn) | el.imm;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, el.carry)
+            }
+            O::OrrRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                let result = self.r(&el.rn) | y;
+                self.logical(&el.rd.unwrap_or(el.rn), s, result, Some(c))
+            }
+            O::Pkh(el) => {
+                let (y, _) = self.shifted(&el.rm, &el.shift);
+                let n = self.r(&el.rn);
+                let result = match el.tb {
+                    true => (n & 0xffff_0000) | (y & 0xffff),
+                    false => (y & 0xffff_0000) | (n & 0xffff),
+                };
+                self.set(&el.rd.unwrap_or(el.rn), result)
+            }
+            O::Rbit(el) => {
+                let result = self.r(&el.rm).reverse_bits();
+                self.set(&el.rd, result)
+            }
+            O::Rev(el) => {
+                let result = self.r(&el.rm).swap_bytes();
+                self.set(&el.rd, result)
+            }
+            O::Rev16(el) => {
+                let m = self.r(&el.rm);
+                let result = ((m >> 8) & 0x00ff_00ff) | ((m << 8) & 0xff00_ff00);
+                self.set(&el.rd, result)
+            }
+            O::Revsh(el) => {
+                let result = (self.r(&el.rm) as u16).swap_bytes() as i16 as u32;
+                self.set(&el.rd, result)
+            }
+            O::RorImmediate(el) => {
+                let (result, c) = shift_c(self.r(&el.rm), &Shift::Ror, el.imm, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::RorRegister(el) => {
+                let amount = self.r(&el.rm) & 0xff;
+                let (result, c) = shift_c(self.r(&el.rn), &Shift::Ror, amount, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::Rrx(el) => {
+                let (result, c) = shift_c(self.r(&el.rm), &Shift::Rrx, 1, carry);
+                self.logical(&el.rd, s, result, Some(c))
+            }
+            O::RsbImmediate(el) => {
+                let x = !self.r(&el.rn);
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, el.imm, true)
+            }
+            O::RsbRegister(el) => {
+                let (x, (y, _)) = (!self.r(&el.rn), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, y, true)
+            }
+            O::SbcImmediate(el) => {
+                let x = self.r(&el.rn);
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, !el.imm, carry)
+            }
+            O::SbcRegister(el) => {
+                let (x, (y, _)) = (self.r(&el.rn), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, !y, carry)
+            }
+            O::Sbfx(el) => {
+                let value = bits(self.r(&el.rn), el.lsb, el.width);
+                self.set(&el.rd, sign_extend(value, el.width.clamp(1, 32)) as u32)
+            }
+            O::SubImmediate(el) => {
+                let x = self.r(&el.rn);
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, !el.imm, true)
+            }
+            O::SubRegister(el) => {
+                let (x, (y, _)) = (self.r(&el.rn), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(el.rn), s, x, !y, true)
+            }
+            O::SubSpMinusImmediate(el) => {
+                let x = self.r(&Register::SP);
+                self.arithmetic(&el.rd.unwrap_or(Register::SP), s, x, !el.imm, true)
+            }
+            O::SubSpMinusRegister(el) => {
+                let (x, (y, _)) = (self.r(&Register::SP), self.shifted(&el.rm, &el.shift));
+                self.arithmetic(&el.rd.unwrap_or(Register::SP), s, x, !y, true)
+            }
+            O::TeqImmediate(el) => {
+                let result = self.r(&el.rn) ^ el.imm;
+                self.flags(result, el.carry, None)
+            }
+            O::TeqRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                self.flags(self.r(&el.rn) ^ y, Some(c), None)
+            }
+            O::TstImmediate(el) => {
+                let result = self.r(&el.rn) & el.imm;
+                self.flags(result, el.carry, None)
+            }
+            O::TstRegister(el) => {
+                let (y, c) = self.shifted(&el.rm, &el.shift);
+                self.flags(self.r(&el.rn) & y, Some(c), None)
+            }
+            O::Ubfx(el) => {
+                let result = bits(self.r(&el.rn), el.lsb, el.width);
+                self.set(&el.rd, result)
+            }
+
+            // ============================== Saturation ===============================
+            O::Ssat(el) => {
+                let (value, _) = self.shifted(&el.rn, &el.shift);
+                let (result, saturated) = signed_sat_q(value as i32 as i64, el.imm);
+                self.saturated(saturated);
+                self.set(&el.rd, result as u32)
+            }
+            O::Ssat16(el) => {
+                let n = self.r(&el.rn);
+                let (low, low_saturated) = signed_sat_q(half(n, false), el.imm);
+                let (high, high_saturated) = signed_sat_q(half(n, true), el.imm);
+                self.saturated(low_saturated || high_saturated);
+                self.set(&el.rd, (high as u32) << 16 | (low as u32 & 0xffff))
+            }
+            O::Usat(el) => {
+                let (value, _) = self.shifted(&el.rn, &el.shift);
+                let (result, saturated) = unsigned_sat_q(value as i32 as i64, el.imm);
+                self.saturated(saturated);
+                self.set(&el.rd, result as u32)
+            }
+            O::Usat16(el) => {
+                let n = self.r(&el.rn);
+                let (low, low_saturated) = unsigned_sat_q(half(n, false), el.imm);
+                let (high, high_saturated) = unsigned_sat_q(half(n, true), el.imm);
+                self.saturated(low_saturated || high_saturated);
+                self.set(&el.rd, (high as u32) << 16 | low as u32)
+            }
+            O::Qadd(el) => {
+                let sum = self.r(&el.rm) as i32 as i64 + self.r(&el.rn) as i32 as i64;
+                let (result, saturated) = signed_sat_q(sum, 32);
+                self.saturated(saturated);
+                self.set(&el.rd.unwrap_or(el.rm), result as u32)
+            }
+            O::Qsub(el) => {
+                let diff = self.r(&el.rm) as i32 as i64 - self.r(&el.rn) as i32 as i64;
+                let (result, saturated) = signed_sat_q(diff, 32);
+                self.saturated(saturated);
+                self.set(&el.rd.unwrap_or(el.rm), result as u32)
+            }
+            O::Qdadd(el) => self.doubling(&el.rd.unwrap_or(el.rm), &el.rm, &el.rn, Lane::Add),
+            O::Qdsub(el) => self.doubling(&el.rd.unwrap_or(el.rm), &el.rm, &el.rn, Lane::Sub),
+
+            // =========================== Parallel add/sub ============================
+            O::Sadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, true, Modular),
+            O::Sadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, true, Modular),
+            O::Sasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, true, Modular),
+            O::Ssax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, true, Modular),
+            O::Ssub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, true, Modular),
+            O::Ssub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, true, Modular),
+            O::Qadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, true, Saturating),
+            O::Qadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, true, Saturating),
+            O::Qasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, true, Saturating),
+            O::Qsax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, true, Saturating),
+            O::Qsub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, true, Saturating),
+            O::Qsub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, true, Saturating),
+            O::Shadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, true, Halving),
+            O::Shadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, true, Halving),
+            O::Shasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, true, Halving),
+            O::Shsax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, true, Halving),
+            O::Shsub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, true, Halving),
+            O::Shsub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, true, Halving),
+            O::Uadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, false, Modular),
+            O::Uadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, false, Modular),
+            O::Uasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, false, Modular),
+            O::Usax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, false, Modular),
+            O::Usub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, false, Modular),
+            O::Usub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, false, Modular),
+            O::Uqadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, false, Saturating),
+            O::Uqadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, false, Saturating),
+            O::Uqasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, false, Saturating),
+            O::Uqsax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, false, Saturating),
+            O::Uqsub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, false, Saturating),
+            O::Uqsub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, false, Saturating),
+            O::Uhadd16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD16, false, Halving),
+            O::Uhadd8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ADD8, false, Halving),
+            O::Uhasx(el) => self.parallel(&el.rd, &el.rn, &el.rm, &ASX, false, Halving),
+            O::Uhsax(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SAX, false, Halving),
+            O::Uhsub16(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB16, false, Halving),
+            O::Uhsub8(el) => self.parallel(&el.rd, &el.rn, &el.rm, &SUB8, false, Halving),
+            O::Sel(el) => {
+                let (n, m, ge) = (self.r(&el.rn), self.r(&el.rm), self.state.apsr.ge);
+                let result = (0..4).fold(0, |acc, lane| {
+                    let source = if ge >> lane & 1 == 1 { n } else { m };
+                    acc | (source & (0xff << (8 * lane)))
+                });
+                self.set(&el.rd.unwrap_or(el.rn), result)
+            }
+            O::Usad8(el) => {
+                let result = sum_of_differences(self.r(&el.rn), self.r(&el.rm));
+                self.set(&el.rd.unwrap_or(el.rn), result)
+            }
+            O::Usada8(el) => {
+                let result = sum_of_differences(self.r(&el.rn), self.r(&el.rm));
+                let result = result.wrapping_add(self.r(&el.ra));
+                self.set(&el.rd, result)
+            }
+
+            // ============================ Extend and add =============================
+            O::Sxtab(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                8,
+                true,
+                false,
+            ),
+            O::Sxtab16(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                8,
+                true,
+                true,
+            ),
+            O::Sxtah(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                16,
+                true,
+                false,
+            ),
+            O::Sxtb(el) => self.extend_add(&el.rd, None, &el.rm, &el.rotation, 8, true, false),
+            O::Sxtb16(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rm),
+                None,
+                &el.rm,
+                &el.rotation,
+                8,
+                true,
+                true,
+            ),
+            O::Sxth(el) => self.extend_add(&el.rd, None, &el.rm, &el.rotation, 16, true, false),
+            O::Uxtab(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                8,
+                false,
+                false,
+            ),
+            O::Uxtab16(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                8,
+                false,
+                true,
+            ),
+            O::Uxtah(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rn),
+                Some(&el.rn),
+                &el.rm,
+                &el.rotation,
+                16,
+                false,
+                false,
+            ),
+            O::Uxtb(el) => self.extend_add(&el.rd, None, &el.rm, &el.rotation, 8, false, false),
+            O::Uxtb16(el) => self.extend_add(
+                &el.rd.unwrap_or(el.rm),
+                None,
+                &el.rm,
+                &el.rotation,
+                8,
+                false,
+                true,
+            ),
+            O::Uxth(el) => self.extend_add(&el.rd, None, &el.rm, &el.rotation, 16, false, false),
+
+            // ============================ Multiplication =============================
+            O::Mla(el) => {
+                let result = self
+                    .r(&el.rn)
+                    .wrapping_mul(self.r(&el.rm))
+                    .wrapping_add(self.r(&el.ra));
+                self.set(&el.rd, result)
+            }
+            O::Mls(el) => {
+                let result = self
+                    .r(&el.ra)
+                    .wrapping_sub(self.r(&el.rn).wrapping_mul(self.r(&el.rm)));
+                self.set(&el.rd, result)
+            }
+            O::Mul(el) => {
+                let result = self.r(&el.rn).wrapping_mul(self.r(&el.rm));
+                let rd = el.rd.unwrap_or(el.rn);
+                self.logical(&rd, s, result, None)
+            }
+            O::Sdiv(el) => {
+                let (n, m) = (self.r(&el.rn) as i32, self.r(&el.rm) as i32);
+                let result = match m {
+                    0 => 0,
+                    m => n.wrapping_div(m),
+                };
+                self.set(&el.rd.unwrap_or(el.rn), result as u32)
+            }
+            O::Udiv(el) => {
+                let result = self.r(&el.rn).checked_div(self.r(&el.rm)).unwrap_or(0);
+                self.set(&el.rd.unwrap_or(el.rn), result)
+            }
+            O::Smla(el) => {
+                let product = half(self.r(&el.rn), el.n_high) * half(self.r(&el.rm), el.m_high);
+                let result = product + self.r(&el.ra) as i32 as i64;
+                self.saturated(result != result as i32 as i64);
+                self.set(&el.rd, result as u32)
+            }
+            O::Smlad(el) => {
+                let product = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.x), Lane::Add);
+                let result = product + self.r(&el.ra) as i32 as i64;
+                self.saturated(result != result as i32 as i64);
+                self.set(&el.rd, result as u32)
+            }
+            O::Smlsd(el) => {
+                let product = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.m_swap), Lane::Sub);
+                let result = product + self.r(&el.ra) as i32 as i64;
+                self.saturated(result != result as i32 as i64);
+                self.set(&el.rd, result as u32)
+            }
+            O::Smlal(el) => {
+                let product = self.r(&el.rn) as i32 as i64 * self.r(&el.rm) as i32 as i64;
+                let result = (product as u64).wrapping_add(self.long(&el.rdlo, &el.rdhi));
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::SmlalSelective(el) => {
+                let product = half(self.r(&el.rn), el.n_high) * half(self.r(&el.rm), el.m_high);
+                let result = (product as u64).wrapping_add(self.long(&el.rdlo, &el.rdhi));
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::Smlald(el) => {
+                let product = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.x), Lane::Add);
+                let result = (product as u64).wrapping_add(self.long(&el.rdlo, &el.rdhi));
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::Smlsld(el) => {
+                let product = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.m_swap), Lane::Sub);
+                let result = (product as u64).wrapping_add(self.long(&el.rdlo, &el.rdhi));
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::Smlaw(el) => {
+                let product = self.r(&el.rn) as i32 as i64 * half(self.r(&el.rm), el.m_high);
+                let result = (product >> 16) + self.r(&el.ra) as i32 as i64;
+                self.saturated(result != result as i32 as i64);
+                self.set(&el.rd, result as u32)
+            }
+            O::Smmla(el) => {
+                let result = self.most_significant(&el.rn, &el.rm, Some(&el.ra), Lane::Add);
+                self.set(&el.rd, round(result, flag(&el.round)))
+            }
+            O::Smmls(el) => {
+                let result = self.most_significant(&el.rn, &el.rm, Some(&el.ra), Lane::Sub);
+                self.set(&el.rd, round(result, flag(&el.round)))
+            }
+            O::Smmul(el) => {
+                let result = self.most_significant(&el.rn, &el.rm, None, Lane::Add);
+                self.set(&el.rd, round(result, flag(&el.round)))
+            }
+            O::Smuad(el) => {
+                let result = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.m_swap), Lane::Add);
+                self.saturated(result != result as i32 as i64);
+                self.set(&el.rd, result as u32)
+            }
+            O::Smusd(el) => {
+                let result = dual(self.r(&el.rn), self.r(&el.rm), flag(&el.m_swap), Lane::Sub);
+                self.set(&el.rd.unwrap_or(el.rn), result as u32)
+            }
+            O::Smul(el) => {
+                let product = half(self.r(&el.rn), el.n_high) * half(self.r(&el.rm), el.m_high);
+                self.set(&el.rd.unwrap_or(el.rn), product as u32)
+            }
+            O::Smull(el) => {
+                let product = self.r(&el.rn) as i32 as i64 * self.r(&el.rm) as i32 as i64;
+                self.set_long(&el.rdlo, &el.rdhi, product as u64)
+            }
+            O::Smulw(el) => {
+                let product = self.r(&el.rn) as i32 as i64 * half(self.r(&el.rm), el.m_high);
+                self.set(&el.rd.unwrap_or(el.rn), (product >> 16) as u32)
+            }
+            O::Umaal(el) => {
+                let result = self.r(&el.rn) as u64 * self.r(&el.rm) as u64
+                    + self.r(&el.rdhi) as u64
+                    + self.r(&el.rdlo) as u64;
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::Umlal(el) => {
+                let product = self.r(&el.rn) as u64 * self.r(&el.rm) as u64;
+                let result = product.wrapping_add(self.long(&el.rdlo, &el.rdhi));
+                self.set_long(&el.rdlo, &el.rdhi, result)
+            }
+            O::Umull(el) => {
+                let product = self.r(&el.rn) as u64 * self.r(&el.rm) as u64;
+                self.set_long(&el.rdlo, &el.rdhi, product)
+            }
+
+            // =============================== Branches ================================
+            O::B(el) => {
+                if self.state.condition_passed(&el.condition) {
+                    let target = operation.branch_target(self.address).unwrap_or_default();
+                    self.branch(target);
+                }
+            }
+            O::Bl(_) => {
+                self.state.registers[14] = self.next | 1;
+                let target = operation.branch_target(self.address).unwrap_or_default();
+                self.branch(target);
+            }
+            O::Blx(el) => {
+                let target = self.r(&el.rm);
+                self.bx(target)?;
+                self.state.registers[14] = self.address.wrapping_add(2) | 1;
+            }
+            O::Bx(el) => self.bx(self.r(&el.rm))?,
+            O::Cbz(el) => {
+                if (self.r(&el.rn) == 0) != el.non.unwrap_or(false) {
+                    let target = operation.branch_target(self.address).unwrap_or_default();
+                    self.branch(target);
+                }
+            }
+            O::Tb(el) => {
+                let (address, size) = match el.is_tbh.unwrap_or(false) {
+                    true => (self.r(&el.rn).wrapping_add(self.r(&el.rm) << 1), 2),
+                    false => (self.r(&el.rn).wrapping_add(self.r(&el.rm)), 1),
+                };
+                let offset = self.read(address, size)?;
+                self.branch(pc(self.address).wrapping_add(offset * 2));
+            }
+            O::It(el) => {
+                let conditions = &el.conds.conditions;
+                let first = conditions.first().cloned().unwrap_or(Condition::None);
+                let firstcond: u8 = first.clone().into();
+                let mut mask = 1 << (4 - conditions.len().clamp(1, 4));
+                for (idx, condition) in conditions.iter().enumerate().skip(1) {
+                    let bit = match *condition == first {
+                        true => firstcond & 1,
+                        false => !firstcond & 1,
+                    };
+                    mask |= bit << (4 - idx);
+                }
+                self.state.itstate = (firstcond << 4) | (mask & 0b1111);
+            }
+
+            // ============================ Loads and stores ============================
+            O::Ldm(el) => {
+                let base = self.r(&el.rn);
+                self.load_multiple(base, &el.registers)?;
+                let end = base.wrapping_add(4 * el.registers.registers.len() as u32);
+                self.write_back(&el.rn, flag(&el.w), &el.registers, end)
+            }
+            O::Ldmdb(el) => {
+                let start = self
+                    .r(&el.rn)
+                    .wrapping_sub(4 * el.registers.registers.len() as u32);
+                self.load_multiple(start, &el.registers)?;
+                self.write_back(&el.rn, flag(&el.w), &el.registers, start)
+            }
+            O::Pop(el) => {
+                let base = self.r(&Register::SP);
+                self.load_multiple(base, &el.registers)?;
+                let end = base.wrapping_add(4 * el.registers.registers.len() as u32);
+                self.write_back(&Register::SP, true, &el.registers, end)
+            }
+            O::Push(el) => {
+                let start = self
+                    .r(&Register::SP)
+                    .wrapping_sub(4 * el.registers.registers.len() as u32);
+                self.store_multiple(start, &el.registers)?;
+                self.set(&Register::SP, start)
+            }
+            O::Stm(el) => {
+                let base = self.r(&el.rn);
+                self.store_multiple(base, &el.registers)?;
+                if flag(&el.w) {
+                    let end = base.wrapping_add(4 * el.registers.registers.len() as u32);
+                    self.set(&el.rn, end)
+                }
+            }
+            O::Stmdb(el) => {
+                let start = self
+                    .r(&el.rn)
+                    .wrapping_sub(4 * el.registers.registers.len() as u32);
+                self.store_multiple(start, &el.registers)?;
+                if flag(&el.w) {
+                    self.set(&el.rn, start)
+                }
+            }
+            O::LdrImmediate(el) => {
+                let wback = flag(&el.w);
+                self.load_immediate(&el.rt, &el.rn, el.imm, el.add, el.index, wback, 4, false)?
+            }
+            O::LdrbImmediate(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                el.add.unwrap_or(true),
+                el.index,
+                flag(&el.w),
+                1,
+                false,
+            )?,
+            O::LdrhImmediate(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm,
+                el.add.unwrap_or(true),
+                el.index.unwrap_or(true),
+                flag(&el.w),
+                2,
+                false,
+            )?,
+            O::LdrsbImmediate(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                el.add,
+                el.index,
+                el.wback,
+                1,
+                true,
+            )?,
+            O::LdrshImmediate(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                el.add,
+                el.index,
+                el.wback,
+                2,
+                true,
+            )?,
+            O::Ldrt(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                true,
+                true,
+                false,
+                4,
+                false,
+            )?,
+            O::Ldrbt(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                true,
+                true,
+                false,
+                1,
+                false,
+            )?,
+            O::Ldrht(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                true,
+                true,
+                false,
+                2,
+                false,
+            )?,
+            O::Ldrsbt(el) => {
+                self.load_immediate(&el.rt, &el.rn, el.imm, true, true, false, 1, true)?
+            }
+            O::Ldrsht(el) => self.load_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                true,
+                true,
+                false,
+                2,
+                true,
+            )?,
+            O::LdrLiteral(el) => self.load_literal(&el.rt, operation, 4, false)?,
+            O::LdrbLiteral(el) => self.load_literal(&el.rt, operation, 1, false)?,
+            O::LdrhLiteral(el) => self.load_literal(&el.rt, operation, 2, false)?,
+            O::LdrsbLiteral(el) => self.load_literal(&el.rt, operation, 1, true)?,
+            O::LdrshLiteral(el) => self.load_literal(&el.rt, operation, 2, true)?,
+            O::LdrRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.load_register(&el.rt, address, 4, false)?
+            }
+            O::LdrbRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.load_register(&el.rt, address, 1, false)?
+            }
+            O::LdrhRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.load_register(&el.rt, address, 2, false)?
+            }
+            O::LdrsbRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.load_register(&el.rt, address, 1, true)?
+            }
+            O::LdrshRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.load_register(&el.rt, address, 2, true)?
+            }
+            O::LdrdImmediate(el) => {
+                let (add, index) = (el.add.unwrap_or(true), el.index.unwrap_or(true));
+                let (address, offset) = self.offset(&el.rn, el.imm, add, index);
+                let first = self.read_aligned(address, 4)?;
+                let second = self.read_aligned(address.wrapping_add(4), 4)?;
+                if flag(&el.w) {
+                    self.set(&el.rn, offset);
+                }
+                self.set(&el.rt, first);
+                self.set(&el.rt2, second)
+            }
+            O::LdrdLiteral(el) => {
+                let address = operation.literal_address(self.address).unwrap_or_default();
+                let first = self.read_aligned(address, 4)?;
+                let second = self.read_aligned(address.wrapping_add(4), 4)?;
+                self.set(&el.rt, first);
+                self.set(&el.rt2, second)
+            }
+            O::StrImmediate(el) => self.store_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm,
+                el.add,
+                el.index.unwrap_or(true),
+                flag(&el.w),
+                4,
+            )?,
+            O::StrbImmediate(el) => self.store_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm,
+                el.add,
+                el.index.unwrap_or(true),
+                flag(&el.w),
+                1,
+            )?,
+            O::StrhImmediate(el) => self.store_immediate(
+                &el.rt,
+                &el.rn,
+                el.imm.unwrap_or(0),
+                el.add,
+                el.index,
+                el.w,
+                2,
+            )?,
+            O::Strt(el) => {
+                self.store_immediate(&el.rt, &el.rn, el.imm.unwrap_or(0), true, true, false, 4)?
+            }
+            O::Strbt(el) => {
+                self.store_immediate(&el.rt, &el.rn, el.imm.unwrap_or(0), true, true, false, 1)?
+            }
+            O::Strht(el) => {
+                self.store_immediate(&el.rt, &el.rn, el.imm.unwrap_or(0), true, true, false, 2)?
+            }
+            O::StrRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.write(address, 4, self.r(&el.rt))?
+            }
+            O::StrbRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.write(address, 1, self.r(&el.rt))?
+            }
+            O::StrhRegister(el) => {
+                let address = self.register_address(&el.rn, &el.rm, &el.shift);
+                self.write(address, 2, self.r(&el.rt))?
+            }
+            O::StrdImmediate(el) => {
+                let index = el.index.unwrap_or(true);
+                let (address, offset) = self.offset(&el.rn, el.imm.unwrap_or(0), el.add, index);
+                self.write_aligned(address, 4, self.r(&el.rt))?;
+                self.write_aligned(address.wrapping_add(4), 4, self.r(&el.rt2))?;
+                if flag(&el.w) {
+                    self.set(&el.rn, offset);
+                }
+            }
+            O::Ldrex(el) => {
+                let address = self.r(&el.rn).wrapping_add(el.imm);
+                let value = self.read_aligned(address, 4)?;
+                self.state.exclusive = Some(address);
+                self.set(&el.rt, value)
+            }
+            O::Ldrexb(el) => {
+                let address = self.r(&el.rn);
+                let value = self.read(address, 1)?;
+                self.state.exclusive = Some(address);
+                self.set(&el.rt, value)
+            }
+            O::Ldrexh(el) => {
+                let address = self.r(&el.rn);
+                let value = self.read_aligned(address, 2)?;
+                self.state.exclusive = Some(address);
+                self.set(&el.rt, value)
+            }
+            O::Strex(el) => {
+                let address = self.r(&el.rn).wrapping_add(el.imm.unwrap_or(0));
+                self.store_exclusive(&el.rd, &el.rt, address, 4)?
+            }
+            O::Strexb(el) => {
+                let address = self.r(&el.rn);
+                self.store_exclusive(&el.rd, &el.rt, address, 1)?
+            }
+            O::Strexh(el) => {
+                let address = self.r(&el.rn);
+                self.store_exclusive(&el.rd, &el.rt, address, 2)?
+            }
+            O::Clrex(_) => self.state.exclusive = None,
+
+            // ========================== System and hints =============================
+            O::Mrs(el) => {
+                let value = self.mrs(el.sysm)?;
+                self.set(&el.rd, value)
+            }
+            O::Msr(el) => self.msr(el.sysm, el.mask, self.r(&el.rn))?,
+            O::Cps(el) => {
+                if self.state.privileged() {
+                    if el.affect_pri {
+                        self.state.primask = el.disable;
+                    }
+                    if el.affect_fault {
+                        self.state.faultmask = el.disable;
+                    }
+                }
+            }
+            O::Svc(el) => return Err(Exception::SupervisorCall(el.imm).into()),
+            O::Bkpt(el) => return Err(Exception::Breakpoint(el.imm).into()),
+            O::Udf(_) | O::Uqsad8(_) => return Err(UsageFault::Undefined.into()),
+            O::Nop(_)
+            | O::Yield(_)
+            | O::Wfe(_)
+            | O::Wfi(_)
+            | O::Sev(_)
+            | O::Dbg(_)
+            | O::Dmb(_)
+            | O::Dsb(_)
+            | O::Isb(_)
+            | O::PldImmediate(_)
+            | O::PldLiteral(_)
+            | O::PldRegister(_)
+            | O::PliImmediate(_)
+            | O::PliRegister(_) => {}
+
+            // ========================== Coprocessor and FPU ==========================
+            O::Cdp(_)
+            | O::LdcImmediate(_)
+            | O::LdcLiteral(_)
+            | O::Mcr(_)
+            | O::Mcrr(_)
+            | O::Mrc(_)
+            | O::Mrrc(_)
+            | O::Stc(_)
+            | O::VabsF32(_)
+            | O::VabsF64(_)
+            | O::VaddF32(_)
+            | O::VaddF64(_)
+            | O::VcmpF32(_)
+            | O::VcmpF64(_)
+            | O::VcmpZeroF32(_)
+            | O::VcmpZeroF64(_)
+            | O::VcvtF32F16(_)
+            | O::VcvtF16F32(_)
+            | O::VcvtF64F16(_)
+            | O::VcvtF16F64(_)
+            | O::VcvtF64F32(_)
+            | O::VcvtF32F64(_)
+            | O::VcvtFromIntF32(_)
+            | O::VcvtFromIntF64(_)
+            | O::VcvtToIntF32(_)
+            | O::VcvtToIntF64(_)
+            | O::VcvtFixedF32(_)
+            | O::VcvtFixedF64(_)
+            | O::VdivF32(_)
+            | O::VdivF64(_)
+            | O::VfmaF32(_)
+            | O::VfmaF64(_)
+            | O::VfmsF32(_)
+            | O::VfmsF64(_)
+            | O::VfnmaF32(_)
+            | O::VfnmaF64(_)
+            | O::VfnmsF32(_)
+            | O::VfnmsF64(_)
+            | O::VldmF32(_)
+            | O::VldmF64(_)
+            | O::VldrF32(_)
+            | O::VldrF64(_)
+            | O::VmaxnmF32(_)
+            | O::VmaxnmF64(_)
+            | O::VminnmF32(_)
+            | O::VminnmF64(_)
+            | O::VmlaF32(_)
+            | O::VmlaF64(_)
+            | O::VmlsF32(_)
+            | O::VmlsF64(_)
+            | O::VmovImmediateF32(_)
+            | O::VmovImmediateF64(_)
+            | O::VmovRegisterF32(_)
+            | O::VmovRegisterF64(_)
+            | O::VmovCoreF32(_)
+            | O::VmovCore2F32(_)
+            | O::VmovCoreF64(_)
+            | O::VmovCoreScalar(_)
+            | O::Vmrs(_)
+            | O::Vmsr(_)
+            | O::VmulF32(_)
+            | O::VmulF64(_)
+            | O::VnegF32(_)
+            | O::VnegF64(_)
+            | O::VnmlaF32(_)
+            | O::VnmlaF64(_)
+            | O::VnmlsF32(_)
+            | O::VnmlsF64(_)
+            | O::VnmulF32(_)
+            | O::VnmulF64(_)
+            | O::VpopF32(_)
+            | O::VpopF64(_)
+            | O::VpushF32(_)
+            | O::VpushF64(_)
+            | O::VrintF32(_)
+            | O::VrintF64(_)
+            | O::VrintxF32(_)
+            | O::VrintxF64(_)
+            | O::VselF32(_)
+            | O::VselF64(_)
+            | O::VsqrtF32(_)
+            | O::VsqrtF64(_)
+            | O::VstmF32(_)
+            | O::VstmF64(_)
+            | O::VstrF32(_)
+            | O::VstrF64(_)
+            | O::VsubF32(_)
+            | O::VsubF64(_) => return Err(UsageFault::NoCoprocessor.into()),
+        }
+        Ok(())
+    }
+
+    /// Stores `rt` if the local monitor is in the exclusive access state,
+    /// writes 0 to `rd` on success and 1 otherwise.
+    fn store_exclusive(
+        &mut self,
+        rd: &Register,
+        rt: &Register,
+        address: u32,
+        size: usize,
+    ) -> Result<(), ExecError> {
+        if !address.is_multiple_of(size as u32) {
+            return Err(UsageFault::Unaligned(address).into());
+        }
+        let passed = self.state.exclusive.take() == Some(address);
+        if passed {
+            self.write(address, size, self.r(rt))?;
+        }
+        self.set(rd, !passed as u32);
+        Ok(())
+    }
+}
+
+/// Sums or subtracts the products of the signed halfwords of `n` and `m`,
+/// the halfwords of `m` are swapped first if `swap` is set.
+fn dual(n: u32, m: u32, swap: bool, lane: Lane) -> i64 {
+    let m = match swap {
+        true => m.rotate_right(16),
+        false => m,
+    };
+    let low = half(n, false) * half(m, false);
+    let high = half(n, true) * half(m, true);
+    match lane {
+        Lane::Add => low + high,
+        Lane::Sub => low - high,
+    }
+}
+
+/// Returns the top word of `value`, rounded if `round` is set.
+fn round(value: i64, round: bool) -> u32 {
+    match round {
+        true => (value.wrapping_add(0x8000_0000) >> 32) as u32,
+        false => (value >> 32) as u32,
+    }
+}
+
+fn flag(value: &Option<bool>) -> bool {
+    value.unwrap_or(false)
+}
+
+/// Sums the absolute differences of the bytes in `n` and `m`.
+fn sum_of_differences(n: u32, m: u32) -> u32 {
+    (0..4)
+        .map(|lane| ((n >> (8 * lane)) as u8).abs_diff((m >> (8 * lane)) as u8) as u32)
+        .sum()
+}
@@ -3,11 +3,11 @@
 //! Main macros is the [`instruction`] macro.
 //! This provides the ability to create a new instruction
 //! in a short and readable way.
-use crate::{arch::ArchError, ParseError};
+use crate::{arch::ArchError, ParseError, ParseErrorKind};
 
 impl From<ArchError> for ParseError {
     fn from(value: ArchError) -> Self {
-        Self::ArchError(value)
+        ParseErrorKind::ArchError(value).into()
     }
 }
 
@@ -66,7 +66,7 @@ macro_rules! instruction {
                     Self: Sized {
                     let word: $size = match iter.peek::<1>(){
                         Some(buff) => Ok(buff),
-                        None => Err(ParseError::Invalid16Bit(stringify!($id))),
+                        None => Err($crate::ParseErrorKind::IncompleteProgram),
                     }?;
                     $(
                         let $field_id:$type = instruction!($size;word $(as $representation)?; $start -> $end $($expr)?);
@@ -122,6 +122,10 @@ macro_rules! instruction {
                 )+
             }
         }
+
+        impl $crate::asm::Table for $table {
+            const NAME: &'static str = stringify!($table);
+        }
         $(
 
             $(
@@ -150,7 +154,7 @@ macro_rules! instruction {
                         // Consume a word from the buffer
                         let word:$size = match iter.peek::<1>(){
                             Some(buff) => Ok(buff),
-                            None => Err(ParseError::Invalid16Bit(stringify!($id))),
+                            None => Err($crate::ParseErrorKind::IncompleteProgram),
                         }?;
                         $(
                             let $field_id:$type = instruction!($size; word $(as $representation)?; $start -> $end $($expr)?);
@@ -205,6 +209,33 @@ macro_rules! combine {
     };
 }
 
+#[cfg(test)]
+/// Decodes the fixtures of the test modules.
+pub(crate) mod fixtures {
+    use crate::{prelude::*, profile::Profile};
+
+    /// Decodes the first operation in `bin`, panicking if it is malformed.
+    pub(crate) fn parse(bin: &[u8]) -> Operation {
+        let mut stream = PeekableBuffer::from(bin.iter().cloned());
+        Operation::parse(&mut stream).expect("Parser broken").1
+    }
+
+    /// Decodes the first operation in `bin` that is defined in `profile`.
+    pub(crate) fn parse_for(
+        bin: &[u8],
+        profile: impl Into<Profile>,
+    ) -> Result<(usize, Operation), ParseError> {
+        let mut stream = PeekableBuffer::from(bin.iter().cloned());
+        Operation::parse_for(&mut stream, profile)
+    }
+
+    /// Decodes all of `bin` as a program.
+    pub(crate) fn parse_asm(bin: &[u8]) -> Result<ASM, ParseError> {
+        let mut stream = PeekableBuffer::from(bin.iter().cloned());
+        ASM::parse(&mut stream)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -0,0 +1,297 @@
+//! Tracks the IT block that an [`Operation`] is decoded in.
+//!
+//! An `it` instruction makes the following one to four instructions
+//! conditional, which changes both whether or not they execute and how
+//! [`SetFlags::InITBlock`](crate::arch::SetFlags::InITBlock) resolves. The
+//! [`ItState`] machine follows the decoded operations and annotates each one
+//! with a [`Conditional`].
+//!
+//! ```
+//! use disarmv7::{it_block::Conditional, prelude::*};
+//!
+//! // cmp r0, #0 ; ite eq ; moveq r1, #1 ; movne r1, #2 ; adds r1, #1
+//! let bin = [0x00, 0x28, 0x0c, 0xbf, 0x01, 0x21, 0x02, 0x21, 0x01, 0x31];
+//! let mut stream = PeekableBuffer::from(bin.into_iter());
+//! let asm = ASM::parse(&mut stream).unwrap();
+//!
+//! let conditions: Vec<Condition> = asm
+//!     .annotated()
+//!     .map(|(_, _, annotation)| annotation.condition.clone())
+//!     .collect();
+//! assert_eq!(
+//!     conditions,
+//!     vec![
+//!         Condition::None,
+//!         Condition::None,
+//!         Condition::Eq,
+//!         Condition::Ne,
+//!         Condition::None
+//!     ]
+//! );
+//!
+//! // movs r1, #1 has no `s` suffix inside of the IT block.
+//! let (_, _, annotation) = asm.annotated().nth(2).unwrap();
+//! assert!(!annotation.s_suffix);
+//! let (_, _, annotation) = asm.annotated().nth(4).unwrap();
+//! assert!(annotation.s_suffix);
+//! ```
+
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{
+    arch::{set_flags::LocalUnwrap, Condition},
+    operation::Operation,
+};
+
+/// The IT block state that an [`Operation`] executes in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional {
+    /// The condition that must pass for the operation to execute, branches
+    /// outside of IT blocks carry their own condition.
+    pub condition: Condition,
+
+    /// True if the operation is inside of an IT block.
+    pub in_it_block: bool,
+
+    /// True if the operation is the last one in an IT block.
+    pub last_in_it_block: bool,
+
+    /// True if the operation carries the `s` suffix, see
+    /// [`Operation::s_suffix`].
+    pub s_suffix: bool,
+}
+
+/// Enumerates the ways in which an operation can violate the IT block that
+/// it is placed in, all of which are UNPREDICTABLE.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItViolation {
+    /// An operation that writes to the PC is not the last one in the block.
+    BranchNotLast,
+
+    /// A conditional branch encoding was used inside of the block.
+    ConditionalBranch,
+
+    /// An `it` instruction was placed inside of another IT block.
+    NestedIt,
+
+    /// A `cbz` or `cbnz` was placed inside of the block.
+    CbzInItBlock,
+
+    /// A `cps` was placed inside of the block.
+    CpsInItBlock,
+}
+
+impl Display for ItViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BranchNotLast => write!(f, "branch is not last in IT block"),
+            Self::ConditionalBranch => write!(f, "conditional branch in IT block"),
+            Self::NestedIt => write!(f, "nested IT block"),
+            Self::CbzInItBlock => write!(f, "cbz in IT block"),
+            Self::CpsInItBlock => write!(f, "cps in IT block"),
+        }
+    }
+}
+
+impl std::error::Error for ItViolation {}
+
+/// Tracks the conditions of the current IT block.
+///
+/// Each decoded operation is first [`checked`](ItState::check) and then
+/// [`advanced`](ItState::advance) past.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItState {
+    conditions: VecDeque<Condition>,
+}
+
+impl ItState {
+    /// Creates a new state outside of any IT block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the next operation is inside of an IT block.
+    pub fn in_it_block(&self) -> bool {
+        !self.conditions.is_empty()
+    }
+
+    /// Returns true if the next operation is the last one in an IT block.
+    pub fn last_in_it_block(&self) -> bool {
+        self.conditions.len() == 1
+    }
+
+    /// Checks that `operation` may be placed at the current position.
+    pub fn check(&self, operation: &Operation) -> Result<(), ItViolation> {
+        if !self.in_it_block() {
+            return Ok(());
+        }
+        match operation {
+            Operation::It(_) => return Err(ItViolation::NestedIt),
+            Operation::Cbz(_) => return Err(ItViolation::CbzInItBlock),
+            Operation::Cps(_) => return Err(ItViolation::CpsInItBlock),
+            Operation::B(el) if el.condition != Condition::None => {
+                return Err(ItViolation::ConditionalBranch)
+            }
+            _ => {}
+        }
+        if operation.may_change_pc() && !self.last_in_it_block() {
+            return Err(ItViolation::BranchNotLast);
+        }
+        Ok(())
+    }
+
+    /// Advances the state past `operation` and returns the state that it
+    /// executes in.
+    ///
+    /// This does not [`check`](ItState::check) the operation, a nested `it`
+    /// replaces the current block.
+    pub fn advance(&mut self, operation: &Operation) -> Conditional {
+        let in_it_block = self.in_it_block();
+        let last_in_it_block = self.last_in_it_block();
+        let condition = match (self.conditions.pop_front(), operation) {
+            (Some(condition), _) => condition,
+            (None, Operation::B(el)) => el.condition.clone(),
+            (None, _) => Condition::None,
+        };
+        if let Operation::It(el) = operation {
+            self.conditions = el.conds.conditions.iter().cloned().collect();
+        }
+        Conditional {
+            condition,
+            in_it_block,
+            last_in_it_block,
+            s_suffix: operation.s_suffix(in_it_block),
+        }
+    }
+}
+
+impl Operation {
+    /// Returns true if the operation carries the `s` suffix, i.e. the `S`
+    /// bit of its encoding as resolved for `in_it_block`.
+    ///
+    /// Only the optional suffix is reported, `cmp`, `cmn`, `tst` and `teq`
+    /// always update the flags but return false. Use [`Operation::writes`]
+    /// for the flags that an operation updates.
+    pub fn s_suffix(&self, in_it_block: bool) -> bool {
+        use Operation as O;
+        match self {
+            O::AdcRegister(el) => el.s.local_unwrap(in_it_block),
+            O::AddImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::AddRegister(el) => el.s.local_unwrap(in_it_block),
+            O::AndRegister(el) => el.s.local_unwrap(in_it_block),
+            O::AsrImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::AsrRegister(el) => el.s.local_unwrap(in_it_block),
+            O::BicRegister(el) => el.s.local_unwrap(in_it_block),
+            O::EorRegister(el) => el.s.local_unwrap(in_it_block),
+            O::LslImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::LslRegister(el) => el.s.local_unwrap(in_it_block),
+            O::LsrImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::LsrRegister(el) => el.s.local_unwrap(in_it_block),
+            O::MovImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::Mul(el) => el.s.local_unwrap(in_it_block),
+            O::MvnRegister(el) => el.s.local_unwrap(in_it_block),
+            O::OrrRegister(el) => el.s.local_unwrap(in_it_block),
+            O::RorRegister(el) => el.s.local_unwrap(in_it_block),
+            O::RsbImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::SbcRegister(el) => el.s.local_unwrap(in_it_block),
+            O::SubImmediate(el) => el.s.local_unwrap(in_it_block),
+            O::SubRegister(el) => el.s.local_unwrap(in_it_block),
+            O::AdcImmediate(el) => el.s.unwrap_or(false),
+            O::AddSPImmediate(el) => el.s.unwrap_or(false),
+            O::AddSPRegister(el) => el.s.unwrap_or(false),
+            O::AndImmediate(el) => el.s.unwrap_or(false),
+            O::BicImmediate(el) => el.s.unwrap_or(false),
+            O::EorImmediate(el) => el.s.unwrap_or(false),
+            O::MovRegister(el) => el.s.unwrap_or(false),
+            O::MvnImmediate(el) => el.s.unwrap_or(false),
+            O::OrnImmediate(el) => el.s.unwrap_or(false),
+            O::OrnRegister(el) => el.s.unwrap_or(false),
+            O::OrrImmediate(el) => el.s.unwrap_or(false),
+            O::RorImmediate(el) => el.s.unwrap_or(false),
+            O::Rrx(el) => el.s.unwrap_or(false),
+            O::RsbRegister(el) => el.s.unwrap_or(false),
+            O::SbcImmediate(el) => el.s.unwrap_or(false),
+            O::SubSpMinusImmediate(el) => el.s.unwrap_or(false),
+            O::SubSpMinusRegister(el) => el.s.unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{helpers::fixtures::parse_asm, operation::Flags, prelude::*};
+
+    fn violation(bin: &[u8]) -> ItViolation {
+        let error = parse_asm(bin).expect_err("Accepted an invalid IT block");
+        let ParseErrorKind::PartiallyParsed(inner, _) = error.kind else {
+            panic!("Expected a partially parsed program")
+        };
+        match inner.kind {
+            ParseErrorKind::ItBlock(violation) => violation,
+            kind => panic!("Expected an IT block violation, got {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn test_s_suffix() {
+        // cmp r0, #0 ; adds r1, #1
+        let asm = parse_asm(&[0x00, 0x28, 0x01, 0x31]).unwrap();
+        let (_, cmp, annotation) = asm.annotated().next().unwrap();
+        assert!(!annotation.s_suffix);
+        assert_eq!(cmp.writes().flags, Flags::NZCV);
+        let (_, adds, annotation) = asm.annotated().nth(1).unwrap();
+        assert!(annotation.s_suffix);
+        assert_eq!(adds.writes().flags, Flags::NZCV);
+    }
+
+    #[test]
+    fn test_annotations() {
+        // itt ne ; addne r0, r0, r1 ; bxne lr
+        let asm = parse_asm(&[0x1c, 0xbf, 0x40, 0x18, 0x70, 0x47]).unwrap();
+        let annotations: Vec<&Conditional> = asm.annotated().map(|el| el.2).collect();
+        assert!(!annotations[0].in_it_block);
+        assert_eq!(annotations[1].condition, Condition::Ne);
+        assert!(annotations[1].in_it_block && !annotations[1].last_in_it_block);
+        assert!(!annotations[1].s_suffix);
+        assert_eq!(annotations[2].condition, Condition::Ne);
+        assert!(annotations[2].last_in_it_block);
+    }
+
+    #[test]
+    fn test_conditional_branch_outside() {
+        // beq #4
+        let asm = parse_asm(&[0x02, 0xd0]).unwrap();
+        assert_eq!(asm.annotated().next().unwrap().2.condition, Condition::Eq);
+    }
+
+    #[test]
+    fn test_violations() {
+        // itt eq ; bxeq lr ; moveq r0, r1
+        assert_eq!(
+            violation(&[0x04, 0xbf, 0x70, 0x47, 0x08, 0x46]),
+            ItViolation::BranchNotLast
+        );
+        // it eq ; it eq
+        assert_eq!(violation(&[0x08, 0xbf, 0x08, 0xbf]), ItViolation::NestedIt);
+        // it eq ; cbz r0, #4
+        assert_eq!(
+            violation(&[0x08, 0xbf, 0x10, 0xb1]),
+            ItViolation::CbzInItBlock
+        );
+        // it eq ; beq #4
+        assert_eq!(
+            violation(&[0x08, 0xbf, 0x02, 0xd0]),
+            ItViolation::ConditionalBranch
+        );
+    }
+
+    #[test]
+    fn test_branch_last() {
+        // it eq ; beq.n #4 (T2 encoding)
+        let asm = parse_asm(&[0x08, 0xbf, 0x02, 0xe0]).unwrap();
+        let (_, _, annotation) = asm.annotated().nth(1).unwrap();
+        assert_eq!(annotation.condition, Condition::Eq);
+    }
+}
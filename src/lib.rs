@@ -104,17 +104,31 @@
 #![deny(missing_docs)]
 #![deny(rustdoc::all)]
 
+pub mod analysis;
 pub mod arch;
 mod asm;
+#[cfg(any(test, feature = "bench"))]
+pub mod bench;
 pub mod buffer;
+pub mod cycles;
+pub mod decoder;
+#[cfg(feature = "elf")]
+pub mod elf;
+pub mod encode;
+pub mod exec;
 mod helpers;
+pub mod it_block;
 pub mod operation;
+pub mod profile;
 
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
-use arch::ArchError;
-use asm::b16::B16;
+use arch::{ArchError, Register};
+use asm::{b16::B16, Table};
+use encode::Encoded;
+use it_block::{Conditional, ItState, ItViolation};
 use operation::Operation;
+use profile::{Architecture, Extensions, Profile};
 
 use crate::asm::b32::B32;
 
@@ -126,6 +140,18 @@ use crate::asm::b32::B32;
 #[allow(dead_code)]
 pub struct ASM {
     statements: Vec<(usize, operation::Operation)>,
+    conditions: Vec<Conditional>,
+}
+
+impl ASM {
+    /// Returns every statement together with the IT block state that it
+    /// executes in, see [`it_block`].
+    pub fn annotated(&self) -> impl Iterator<Item = (usize, &Operation, &Conditional)> {
+        self.statements
+            .iter()
+            .zip(&self.conditions)
+            .map(|((size, operation), conditional)| (*size, operation, conditional))
+    }
 }
 
 /// Denotes that the element can be peeked `N` elements into the future.
@@ -160,9 +186,17 @@ pub trait Stream: Consume<u32> + Consume<u16> + Consume<u8> + Debug {
     {
         match self.peek::<1>() {
             Some(word) => Ok(word),
-            None => Err(ParseError::IncompleteProgram),
+            None => Err(ParseErrorKind::IncompleteProgram.into()),
         }
     }
+
+    /// Returns the number of bytes that have been consumed from the stream.
+    ///
+    /// Streams that do not track their position return `None`, the errors
+    /// that are parsed from them carry no offset.
+    fn offset(&self) -> Option<usize> {
+        None
+    }
 }
 /// Denotes that the type can be constructed from a [`Stream`].
 pub trait Parse {
@@ -178,30 +212,46 @@ pub trait Parse {
         Self: Sized;
 }
 
+/// Denotes that the type can be converted back in to machine code.
+///
+/// This is the inverse of [`Parse`], i.e. parsing the encoded value yields
+/// an equivalent value.
+pub trait Encode {
+    /// What the encoder produces.
+    type Target;
+
+    /// Encodes the value using the shortest legal encoding.
+    fn encode(&self) -> Result<Self::Target, EncodeError> {
+        self.encode_with_size(None)
+    }
+
+    /// Encodes the value using an encoding of `size` bits.
+    ///
+    /// If no size is requested this picks the shortest legal encoding.
+    fn encode_with_size(&self, size: Option<usize>) -> Result<Self::Target, EncodeError>;
+}
+
 pub(crate) trait ToOperation {
-    /// Translates the encoded value in to a [`Operation`] instruction
-    fn encoding_specific_operations(self) -> crate::operation::Operation;
+    /// Translates the encoded value in to a [`Operation`] instruction, a field
+    /// that does not fit the operand is reported rather than panicking.
+    fn encoding_specific_operations(self) -> Result<crate::operation::Operation, ParseError>;
 }
 
-#[derive(Debug)]
-/// Enumerates the errors that might occur during parsing [`ASM`].
-pub enum ParseError {
+#[derive(Debug, Clone, PartialEq)]
+/// Enumerates the reasons that parsing might fail, see [`ParseError`].
+pub enum ParseErrorKind {
     /// Thrown when the buffer is not long enough.
     /// The current instruction was not valid
     IncompleteProgram,
 
-    /// Thrown when there is no matching 16 bit instruction
-    ///
-    /// Occurred while parsing the block in question
-    Invalid16Bit(&'static str),
+    /// Thrown when the encoding is UNDEFINED in the architecture.
+    Undefined,
 
-    /// Thrown when there is no matching 32 bit instruction
-    ///
-    /// Occurred while parsing the block in question
-    Invalid32Bit(&'static str),
+    /// Thrown when the encoding is UNPREDICTABLE in the architecture.
+    Unpredictable,
 
-    /// Thrown when there is no matching
-    Incomplete32Bit,
+    /// Thrown when the encoding is valid but not implemented in this crate.
+    Unimplemented,
 
     /// Thrown when a field in an identifier is incorrect
     InvalidField(String),
@@ -209,28 +259,198 @@ pub enum ParseError {
     /// Thrown when a target register does not exist.
     InvalidRegister(u8),
 
-    /// Thrown when an unpredictable instruction is used
-    Unpredictable,
-
-    /// Thrown when an undefined instruction is used
-    Undefined,
-
-    /// Thrown when a non covered case is reached
-    IncompleteParser,
-
     /// Thrown when an invalid condition is requested
     InvalidCondition,
 
     /// Thrown when the parsing fails part way through parsing
-    PartiallyParsed(Box<Self>, Vec<Operation>),
+    PartiallyParsed(Box<ParseError>, Vec<Operation>),
 
     /// Sub-crate [`arch`] threw an error
     ArchError(ArchError),
 
+    /// Thrown when an operation is not allowed at its position in an IT
+    /// block.
+    ItBlock(ItViolation),
+
+    /// Thrown when the encoding does not exist in the [`Architecture`] that
+    /// is decoded for, see [`profile`].
+    NotInArchitecture(Architecture),
+
+    /// Thrown when the encoding requires [`Extensions`] that the core does
+    /// not implement, see [`profile`].
+    MissingExtensions(Extensions),
+
     /// Thrown when internal logic is faulty, this should never occur
     InternalError(&'static str),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// Describes why parsing failed and where.
+///
+/// ```
+/// use disarmv7::prelude::*;
+///
+/// // ldrh pc, [r0] is an unallocated memory hint
+/// let bin = [0xb0, 0xf8, 0x00, 0xf0];
+/// let mut stream = PeekableBuffer::from(bin.into_iter());
+/// let error = Operation::parse(&mut stream).unwrap_err();
+///
+/// assert_eq!(error.kind, ParseErrorKind::Unimplemented);
+/// assert_eq!(error.offset, Some(0));
+/// assert_eq!(error.bits, Some(Encoded::Wide(0xf8b0_f000)));
+/// assert_eq!(error.path, vec!["B32", "A5_19"]);
+/// assert_eq!(
+///     error.to_string(),
+///     "encoding not implemented, 0xf8b0f000 at offset 0 (B32 -> A5_19)"
+/// );
+/// ```
+pub struct ParseError {
+    /// The reason that parsing failed.
+    pub kind: ParseErrorKind,
+
+    /// The offset in bytes from the start of the [`Stream`] to the
+    /// instruction that could not be parsed.
+    pub offset: Option<usize>,
+
+    /// The encoding that could not be parsed.
+    pub bits: Option<Encoded>,
+
+    /// The decode tables that were consulted, outermost first.
+    pub path: Vec<&'static str>,
+}
+
+impl ParseError {
+    /// Creates a new error without any information about where it occurred.
+    pub fn new(kind: ParseErrorKind) -> Self {
+        Self {
+            kind,
+            offset: None,
+            bits: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Records that the error occurred within `table`.
+    pub(crate) fn within(mut self, table: &'static str) -> Self {
+        self.path.insert(0, table);
+        self
+    }
+
+    /// Records the offset and encoding of the instruction, unless they are
+    /// already known.
+    pub(crate) fn at(mut self, offset: Option<usize>, bits: Encoded) -> Self {
+        self.offset = self.offset.or(offset);
+        self.bits.get_or_insert(bits);
+        self
+    }
+}
+
+impl From<ParseErrorKind> for ParseError {
+    fn from(value: ParseErrorKind) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IncompleteProgram => write!(f, "incomplete program"),
+            Self::Undefined => write!(f, "UNDEFINED encoding"),
+            Self::Unpredictable => write!(f, "UNPREDICTABLE encoding"),
+            Self::Unimplemented => write!(f, "encoding not implemented"),
+            Self::InvalidField(field) => write!(f, "invalid field, {field}"),
+            Self::InvalidRegister(register) => write!(f, "invalid register {register}"),
+            Self::InvalidCondition => write!(f, "invalid condition"),
+            Self::PartiallyParsed(error, parsed) => {
+                write!(f, "{error} after {} operations", parsed.len())
+            }
+            Self::ArchError(error) => write!(f, "{error}"),
+            Self::ItBlock(violation) => write!(f, "{violation}"),
+            Self::NotInArchitecture(architecture) => {
+                write!(f, "encoding does not exist in {architecture}")
+            }
+            Self::MissingExtensions(extensions) => {
+                write!(f, "encoding requires the {extensions} extension")
+            }
+            Self::InternalError(error) => write!(f, "internal error, {error}"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let ParseErrorKind::PartiallyParsed(..) = self.kind {
+            return write!(f, "{}", self.kind);
+        }
+        write!(f, "{}", self.kind)?;
+        match self.bits {
+            Some(Encoded::Narrow(halfword)) => write!(f, ", {halfword:#06x}")?,
+            Some(Encoded::Wide(word)) => write!(f, ", {word:#010x}")?,
+            None => {}
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {offset}")?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " ({})", self.path.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::PartiallyParsed(error, _) => Some(error.as_ref()),
+            ParseErrorKind::ArchError(error) => Some(error),
+            ParseErrorKind::ItBlock(violation) => Some(violation),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Enumerates the errors that might occur during [`encoding`](Encode).
+pub enum EncodeError {
+    /// Thrown when the requested size is neither 16 nor 32 bits.
+    InvalidSize(usize),
+
+    /// Thrown when the operation has no encoding of the requested size.
+    NoEncoding(usize),
+
+    /// Thrown when a register can not be represented in the encoding.
+    RegisterOutOfRange(Register),
+
+    /// Thrown when an immediate can not be represented in the encoding.
+    ImmediateOutOfRange(u32),
+
+    /// Thrown when an immediate can not be represented as a modified
+    /// immediate constant.
+    NotExpandable(u32),
+
+    /// Thrown when the combination of operands has no encoding.
+    InvalidOperands(&'static str),
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSize(size) => write!(f, "invalid encoding size {size}, expected 16 or 32"),
+            Self::NoEncoding(size) => write!(f, "no {size}-bit encoding"),
+            Self::RegisterOutOfRange(register) => {
+                write!(f, "register {register} can not be encoded")
+            }
+            Self::ImmediateOutOfRange(imm) => write!(f, "immediate {imm:#x} can not be encoded"),
+            Self::NotExpandable(imm) => {
+                write!(f, "immediate {imm:#x} is not a modified immediate constant")
+            }
+            Self::InvalidOperands(reason) => write!(f, "invalid operands, {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
 impl Parse for ASM {
     type Target = Self;
 
@@ -238,19 +458,53 @@ impl Parse for ASM {
     where
         Self: Sized,
     {
+        Self::parse_for(iter, Profile::default())
+    }
+}
+
+impl ASM {
+    /// Parses the stream, rejecting the encodings that are not defined in the
+    /// `profile`, see [`profile`](mod@profile).
+    pub fn parse_for<T: Stream>(
+        iter: &mut T,
+        profile: impl Into<Profile>,
+    ) -> Result<ASM, ParseError> {
+        let profile = profile.into();
         let mut stmts = Vec::new();
+        let mut conditions = Vec::new();
+        let mut state = ItState::new();
         while let Some(_halfword) = iter.peek::<1>() as Option<u16> {
-            match Operation::parse(iter) {
-                Ok(el) => stmts.push(el),
+            let offset = iter.offset();
+            let parsed =
+                Operation::parse_for(iter, profile).and_then(|el| match state.check(&el.1) {
+                    Ok(()) => Ok(el),
+                    Err(violation) => Err(ParseError {
+                        offset,
+                        ..ParseErrorKind::ItBlock(violation).into()
+                    }),
+                });
+            match parsed {
+                Ok(el) => {
+                    conditions.push(state.advance(&el.1));
+                    stmts.push(el);
+                }
                 Err(e) => {
-                    return Err(ParseError::PartiallyParsed(
-                        Box::new(e),
-                        stmts.into_iter().map(|el| el.1).collect(),
-                    ))
+                    return Err(ParseError {
+                        offset: e.offset,
+                        bits: e.bits,
+                        path: e.path.clone(),
+                        kind: ParseErrorKind::PartiallyParsed(
+                            Box::new(e),
+                            stmts.into_iter().map(|el| el.1).collect(),
+                        ),
+                    })
                 }
             };
         }
-        Ok(stmts.into())
+        Ok(Self {
+            statements: stmts,
+            conditions,
+        })
     }
 }
 
@@ -261,22 +515,56 @@ impl Parse for operation::Operation {
     where
         Self: Sized,
     {
+        Self::parse_for(iter, Profile::default())
+    }
+}
+
+impl operation::Operation {
+    /// Parses a single operation, rejecting it without consuming it if the
+    /// encoding is not defined in the `profile`, see [`profile`](mod@profile).
+    pub fn parse_for<T: Stream>(
+        iter: &mut T,
+        profile: impl Into<Profile>,
+    ) -> Result<(usize, operation::Operation), ParseError> {
+        let profile = profile.into();
+        let offset = iter.offset();
         let halfword: Option<u16> = iter.peek::<1>();
-        if halfword.is_none() {
-            return Err(ParseError::IncompleteProgram);
-        }
-        let halfword = halfword.unwrap();
+        let halfword = match halfword {
+            Some(halfword) => halfword,
+            None => {
+                return Err(ParseError {
+                    offset,
+                    ..ParseErrorKind::IncompleteProgram.into()
+                })
+            }
+        };
 
-        Ok(match halfword >> 11 {
-            0b11101..=0b11111 => B32::parse(iter)?,
-            _ => B16::parse(iter)?,
-        })
+        match halfword >> 11 {
+            0b11101..=0b11111 => asm::lookup::wide(iter, profile)
+                .map_err(|e| e.within(B32::NAME))
+                .map_err(|e| {
+                    let word: Option<u32> = iter.peek::<1>();
+                    e.at(
+                        offset,
+                        word.map_or(Encoded::Narrow(halfword), Encoded::Wide),
+                    )
+                }),
+            _ => asm::lookup::narrow(iter, profile)
+                .map_err(|e| e.within(B16::NAME))
+                .map_err(|e| e.at(offset, Encoded::Narrow(halfword))),
+        }
     }
 }
 
+/// Annotates the statements without checking for IT block violations.
 impl From<Vec<(usize, Operation)>> for ASM {
     fn from(value: Vec<(usize, operation::Operation)>) -> Self {
-        Self { statements: value }
+        let mut state = ItState::new();
+        let conditions = value.iter().map(|el| state.advance(&el.1)).collect();
+        Self {
+            statements: value,
+            conditions,
+        }
     }
 }
 
@@ -288,19 +576,105 @@ impl From<ASM> for Vec<(usize, Operation)> {
 
 /// Re-exports the needed types to use this crate.
 pub mod prelude {
-    pub use super::{Parse, Peek, Stream, ASM};
+    pub use super::{Encode, Parse, Peek, Stream, ASM};
     pub use crate::{
         arch::{
             self,
             set_flags::SetFlags,
             wrapper_types::*,
+            ApsrMask,
+            BarrierOption,
             Condition,
+            DebugHint,
+            F32Register,
+            F64Register,
             ImmShift,
             Register,
             RegisterList,
+            RoundingMode,
             Shift,
+            SpecialRegister,
         },
-        buffer::PeekableBuffer,
+        buffer::{PeekableBuffer, SliceStream},
+        decoder::{Decoder, MappedDecoder},
+        encode::Encoded,
         operation::{self, Operation},
+        ParseError,
+        ParseErrorKind,
     };
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{decoder::linear_sweep, prelude::*};
+
+    #[test]
+    fn test_error_kinds() {
+        // add pc, pc
+        let mut stream = PeekableBuffer::from([0x00, 0x45].into_iter());
+        let error = Operation::parse(&mut stream).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::Unpredictable);
+        assert_eq!(error.bits, Some(Encoded::Narrow(0x4500)));
+        assert_eq!(error.path, vec!["B16", "A5_4"]);
+
+        // A hint with op1 != 0
+        let mut stream = PeekableBuffer::from([0xaf, 0xf3, 0x00, 0x81].into_iter());
+        let error = Operation::parse(&mut stream).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::Undefined);
+        assert_eq!(error.path, vec!["B32", "A5_13", "A5_14"]);
+
+        // Only the first half of a 32 bit encoding
+        let mut stream = PeekableBuffer::from([0xaf, 0xf3].into_iter());
+        let error = Operation::parse(&mut stream).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::IncompleteProgram);
+        assert_eq!(error.bits, Some(Encoded::Narrow(0xf3af)));
+    }
+
+    #[test]
+    fn test_error_offset() {
+        // bx lr ; A hint with op1 != 0
+        let bin = [0x70, 0x47, 0xaf, 0xf3, 0x00, 0x81];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let error = ASM::parse(&mut stream).unwrap_err();
+
+        assert_eq!(error.offset, Some(2));
+        assert_eq!(error.bits, Some(Encoded::Wide(0xf3af_8100)));
+        assert_eq!(error.path, vec!["B32", "A5_13", "A5_14"]);
+        let ParseErrorKind::PartiallyParsed(inner, parsed) = &error.kind else {
+            panic!("Expected a partially parsed program")
+        };
+        assert_eq!(inner.kind, ParseErrorKind::Undefined);
+        assert_eq!(parsed.len(), 1);
+
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(
+            source.to_string(),
+            "UNDEFINED encoding, 0xf3af8100 at offset 2 (B32 -> A5_13 -> A5_14)"
+        );
+    }
+
+    #[test]
+    fn test_fuzz_corpus() {
+        // Replays the checked in fuzzing corpora as raw images, a panic fails
+        // the test.
+        let corpora = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus");
+        let mut replayed = 0;
+        for target in std::fs::read_dir(corpora).expect("Could not read the corpora") {
+            let target = target.unwrap().path();
+            for entry in std::fs::read_dir(&target).unwrap() {
+                let image = std::fs::read(entry.unwrap().path()).unwrap();
+                let from_slice = ASM::parse(&mut SliceStream::new(&image));
+                let from_buffer = ASM::parse(&mut PeekableBuffer::from(image.iter().cloned()));
+                assert_eq!(format!("{from_slice:?}"), format!("{from_buffer:?}"));
+
+                let records = linear_sweep(&mut SliceStream::new(&image), 0);
+                assert_eq!(
+                    records,
+                    linear_sweep(&mut PeekableBuffer::from(image.iter().cloned()), 0)
+                );
+                replayed += 1;
+            }
+        }
+        assert!(replayed > 0);
+    }
+}
@@ -1,14 +1,26 @@
 //! Creates the [`Operation`] enum.
 #![allow(missing_docs)]
 
+mod access;
+mod class;
+mod display;
+mod target;
+
+pub use access::{Flags, RegisterAccess, RegisterSet};
 use builder_derive::{Builder, Consumer};
+pub use class::InstructionClass;
+pub use display::Ual;
+pub use target::{aligned_pc, pc};
 
 use crate::arch::{
+    barrier::BarrierOption,
     condition::{Condition, ITCondition},
     coproc::CoProcessor,
-    register::{Register, RegisterList},
+    hint::DebugHint,
+    register::{F32Register, F64Register, Register, RegisterList},
+    rounding::RoundingMode,
     shift::ImmShift,
-    wrapper_types::*,
+    special_register::{ApsrMask, SpecialRegister},
     SetFlags,
 };
 
@@ -129,11 +141,11 @@ operation!(
 
     // ==================================== D ====================================
 
-    Dbg <option:u8>
+    Dbg <option: DebugHint>
 
-    Dmb {option: u8}
+    Dmb {option: BarrierOption}
 
-    Dsb {option: u8}
+    Dsb {option: BarrierOption}
 
     // ==================================== D ====================================
 
@@ -143,7 +155,10 @@ operation!(
 
     // ==================================== I ====================================
 
-    Isb {option: Imm4}
+    // Armv7-M only defines `sy` for isb, the reserved options are kept as
+    // `BarrierOption::Reserved` rather than rejected since the processor
+    // executes them as `sy`.
+    Isb {option: BarrierOption}
 
     It <conds: ITCondition>/* , <mask: Imm4> */
 
@@ -234,9 +249,9 @@ operation!(
     Mrrc <coproc: CoProcessor>, <opc1: u8>, <rt:Register>, <rt2: Register>, <crm: u8>
     Mrc  <coproc: CoProcessor>, <opc1: u8>, {opc2: u8}, <rt:Register>, <crm: u8>, <crn: u8>
 
-    Mrs <rd: Register>, <sysm: u8>
+    Mrs <rd: Register>, <sysm: SpecialRegister>
 
-    Msr <rn: Register>, <mask:Imm2>, <sysm:u8>
+    Msr <rn: Register>, <mask: ApsrMask>, <sysm: SpecialRegister>
 
     Mul {s: SetFlags}, {rd: Register}, <rn: Register>, <rm: Register>
 
@@ -464,9 +479,116 @@ operation!(
 
 
     // ==================================== V ====================================
-    //
-    // I will be omitting all of the floating point instructions for now.
-    // TODO! Add in floats
+
+    VabsF32 <sd: F32Register>, <sm: F32Register>
+    VabsF64 <dd: F64Register>, <dm: F64Register>
+
+    VaddF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VaddF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VcmpF32 {e: bool}, <sd: F32Register>, <sm: F32Register>
+    VcmpF64 {e: bool}, <dd: F64Register>, <dm: F64Register>
+    VcmpZeroF32 {e: bool}, <sd: F32Register>
+    VcmpZeroF64 {e: bool}, <dd: F64Register>
+
+    VcvtF32F16 <top: bool>, <sd: F32Register>, <sm: F32Register>
+    VcvtF16F32 <top: bool>, <sd: F32Register>, <sm: F32Register>
+    VcvtF64F16 <top: bool>, <dd: F64Register>, <sm: F32Register>
+    VcvtF16F64 <top: bool>, <sd: F32Register>, <dm: F64Register>
+
+    VcvtF64F32 <dd: F64Register>, <sm: F32Register>
+    VcvtF32F64 <sd: F32Register>, <dm: F64Register>
+
+    VcvtFromIntF32 <signed: bool>, <sd: F32Register>, <sm: F32Register>
+    VcvtFromIntF64 <signed: bool>, <dd: F64Register>, <sm: F32Register>
+    VcvtToIntF32 <signed: bool>, {r: RoundingMode}, <sd: F32Register>, <sm: F32Register>
+    VcvtToIntF64 <signed: bool>, {r: RoundingMode}, <sd: F32Register>, <dm: F64Register>
+
+    VcvtFixedF32 <to_fixed: bool>, <signed: bool>, <size: u8>, <sd: F32Register>, <fbits: u8>
+    VcvtFixedF64 <to_fixed: bool>, <signed: bool>, <size: u8>, <dd: F64Register>, <fbits: u8>
+
+    VdivF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VdivF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VfmaF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VfmaF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VfmsF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VfmsF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VfnmaF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VfnmaF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VfnmsF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VfnmsF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VldmF32 <add: bool>, <w: bool>, <rn: Register>, <registers: Vec<F32Register>>
+    VldmF64 <add: bool>, <w: bool>, <rn: Register>, <registers: Vec<F64Register>>
+
+    VldrF32 <add: bool>, <rn: Register>, <sd: F32Register>, <imm: u32>
+    VldrF64 <add: bool>, <rn: Register>, <dd: F64Register>, <imm: u32>
+
+    VmaxnmF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VmaxnmF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VminnmF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VminnmF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VmlaF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VmlaF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VmlsF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VmlsF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    // The immediate is the IEEE 754 bit pattern of the constant.
+    VmovImmediateF32 <sd: F32Register>, <imm: u32>
+    VmovImmediateF64 <dd: F64Register>, <imm: u64>
+    VmovRegisterF32 <sd: F32Register>, <sm: F32Register>
+    VmovRegisterF64 <dd: F64Register>, <dm: F64Register>
+
+    VmovCoreF32 <to_core: bool>, <rt: Register>, <sn: F32Register>
+    VmovCore2F32 <to_core: bool>, <rt: Register>, <rt2: Register>, <sm: F32Register>, <sm1: F32Register>
+    VmovCoreF64 <to_core: bool>, <rt: Register>, <rt2: Register>, <dm: F64Register>
+    VmovCoreScalar <to_core: bool>, <rt: Register>, <dd: F64Register>, <index: u8>
+
+    // Transfers to or from the FPSCR, using PC as rt denotes APSR_nzcv.
+    Vmrs <rt: Register>
+    Vmsr <rt: Register>
+
+    VmulF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VmulF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VnegF32 <sd: F32Register>, <sm: F32Register>
+    VnegF64 <dd: F64Register>, <dm: F64Register>
+
+    VnmlaF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VnmlaF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VnmlsF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VnmlsF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+    VnmulF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VnmulF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VpopF32 <registers: Vec<F32Register>>
+    VpopF64 <registers: Vec<F64Register>>
+    VpushF32 <registers: Vec<F32Register>>
+    VpushF64 <registers: Vec<F64Register>>
+
+    // No rounding mode means that the FPSCR rounding mode is used.
+    VrintF32 {r: RoundingMode}, <sd: F32Register>, <sm: F32Register>
+    VrintF64 {r: RoundingMode}, <dd: F64Register>, <dm: F64Register>
+    VrintxF32 <sd: F32Register>, <sm: F32Register>
+    VrintxF64 <dd: F64Register>, <dm: F64Register>
+
+    VselF32 <cond: Condition>, <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VselF64 <cond: Condition>, <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
+
+    VsqrtF32 <sd: F32Register>, <sm: F32Register>
+    VsqrtF64 <dd: F64Register>, <dm: F64Register>
+
+    VstmF32 <add: bool>, <w: bool>, <rn: Register>, <registers: Vec<F32Register>>
+    VstmF64 <add: bool>, <w: bool>, <rn: Register>, <registers: Vec<F64Register>>
+
+    VstrF32 <add: bool>, <rn: Register>, <sd: F32Register>, <imm: u32>
+    VstrF64 <add: bool>, <rn: Register>, <dd: F64Register>, <imm: u32>
+
+    VsubF32 <sd: F32Register>, <sn: F32Register>, <sm: F32Register>
+    VsubF64 <dd: F64Register>, <dn: F64Register>, <dm: F64Register>
 
 
     // ==================================== W ====================================
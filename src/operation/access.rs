@@ -0,0 +1,1196 @@
+//! Lists the registers and condition flags that an [`Operation`] reads and
+//! writes.
+//!
+//! Implicit operands are included, e.g. the SP for `push` and `pop`, the LR
+//! for `bl` and `blx`, the PC for branches and PC relative operands and the
+//! base register of loads and stores that write back. Only the core
+//! registers are tracked, floating point registers are not.
+
+use std::{
+    fmt::Display,
+    ops::{BitOr, BitOrAssign},
+};
+
+use super::Operation;
+use crate::arch::{Condition, Register};
+
+/// A set of core registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RegisterSet {
+    bits: u16,
+}
+
+impl RegisterSet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Adds `register` to the set.
+    pub fn insert(&mut self, register: Register) {
+        self.bits |= 1 << register as u8;
+    }
+
+    /// Removes `register` from the set.
+    pub fn remove(&mut self, register: Register) {
+        self.bits &= !(1 << register as u8);
+    }
+
+    /// Returns true if `register` is in the set.
+    pub const fn contains(&self, register: Register) -> bool {
+        self.bits & (1 << register as u8) != 0
+    }
+
+    /// Returns the number of registers in the set.
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns true if the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Iterates over the registers in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Register> + '_ {
+        (0..16u8)
+            .filter(|idx| self.bits & (1 << idx) != 0)
+            .filter_map(|idx| Register::try_from(idx).ok())
+    }
+}
+
+impl<const N: usize> From<[Register; N]> for RegisterSet {
+    fn from(value: [Register; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl FromIterator<Register> for RegisterSet {
+    fn from_iter<T: IntoIterator<Item = Register>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Register> for RegisterSet {
+    fn extend<T: IntoIterator<Item = Register>>(&mut self, iter: T) {
+        for register in iter {
+            self.insert(register);
+        }
+    }
+}
+
+impl BitOr for RegisterSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self {
+            bits: self.bits | rhs.bits,
+        }
+    }
+}
+
+impl BitOrAssign for RegisterSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.bits |= rhs.bits;
+    }
+}
+
+/// Renders the set as a register list, i.e. `{r0, sp}`.
+impl Display for RegisterSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let registers: Vec<String> = self.iter().map(|el| el.to_string()).collect();
+        write!(f, "{{{}}}", registers.join(", "))
+    }
+}
+
+/// The APSR flags that an operation reads or writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Flags {
+    /// The negative flag.
+    pub n: bool,
+    /// The zero flag.
+    pub z: bool,
+    /// The carry flag.
+    pub c: bool,
+    /// The overflow flag.
+    pub v: bool,
+    /// The sticky saturation flag.
+    pub q: bool,
+    /// The four greater than or equal flags.
+    pub ge: bool,
+}
+
+impl Flags {
+    /// All of the flags in the APSR.
+    pub const ALL: Self = Self::new(true, true, true, true, true, true);
+    /// The C flag, read by `adc`, `sbc` and `rrx`.
+    pub const C: Self = Self::new(false, false, true, false, false, false);
+    /// The GE flags, written by parallel additions and subtractions.
+    pub const GE: Self = Self::new(false, false, false, false, false, true);
+    /// No flags.
+    pub const NONE: Self = Self::new(false, false, false, false, false, false);
+    /// The N and Z flags, written by multiplications and `movs`.
+    pub const NZ: Self = Self::new(true, true, false, false, false, false);
+    /// The N, Z and C flags, written by logical operations and shifts.
+    pub const NZC: Self = Self::new(true, true, true, false, false, false);
+    /// The N, Z, C and V flags, written by arithmetic operations.
+    pub const NZCV: Self = Self::new(true, true, true, true, false, false);
+    /// The Q flag, written by saturating operations.
+    pub const Q: Self = Self::new(false, false, false, false, true, false);
+
+    const fn new(n: bool, z: bool, c: bool, v: bool, q: bool, ge: bool) -> Self {
+        Self { n, z, c, v, q, ge }
+    }
+
+    /// Returns the flags that `condition` tests.
+    pub const fn read_by(condition: &Condition) -> Self {
+        let (n, z, c, v) = match condition {
+            Condition::Eq | Condition::Ne => (false, true, false, false),
+            Condition::Cs | Condition::Cc => (false, false, true, false),
+            Condition::Mi | Condition::Pl => (true, false, false, false),
+            Condition::Vs | Condition::Vc => (false, false, false, true),
+            Condition::Hi | Condition::Ls => (false, true, true, false),
+            Condition::Ge | Condition::Lt => (true, false, false, true),
+            Condition::Gt | Condition::Le => (true, true, false, true),
+            Condition::None => (false, false, false, false),
+        };
+        Self::new(n, z, c, v, false, false)
+    }
+
+    /// Returns true if no flags are set.
+    pub const fn is_empty(&self) -> bool {
+        !(self.n || self.z || self.c || self.v || self.q || self.ge)
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.n || rhs.n,
+            self.z || rhs.z,
+            self.c || rhs.c,
+            self.v || rhs.v,
+            self.q || rhs.q,
+            self.ge || rhs.ge,
+        )
+    }
+}
+
+/// The registers and flags that an operation accesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RegisterAccess {
+    /// The accessed core registers.
+    pub registers: RegisterSet,
+    /// The accessed APSR flags.
+    pub flags: Flags,
+}
+
+/// Creates a set from the registers.
+fn set<I: IntoIterator<Item = Register>>(registers: I) -> RegisterSet {
+    registers.into_iter().collect()
+}
+
+/// Returns the base register if the operation writes back to it.
+fn wback(w: Option<bool>, rn: Register) -> RegisterSet {
+    set(w.unwrap_or(false).then_some(rn))
+}
+
+/// Returns the registers in a register list.
+fn list(registers: &crate::arch::RegisterList) -> RegisterSet {
+    set(registers.registers.iter().copied())
+}
+
+impl Operation {
+    /// Returns the registers and flags that the operation reads.
+    ///
+    /// The flags tested by the condition of an enclosing IT block are not
+    /// included, see [`Conditional`](crate::it_block::Conditional).
+    ///
+    /// ```
+    /// use disarmv7::{
+    ///     operation::{Flags, RegisterSet},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let push: Operation = operation::Push::builder()
+    ///     .set_registers(RegisterList {
+    ///         registers: vec![Register::R4, Register::LR],
+    ///     })
+    ///     .complete()
+    ///     .into();
+    ///
+    /// let reads = push.reads();
+    /// assert_eq!(
+    ///     reads.registers,
+    ///     RegisterSet::from([Register::R4, Register::SP, Register::LR])
+    /// );
+    /// assert_eq!(push.writes().registers, RegisterSet::from([Register::SP]));
+    /// assert_eq!(reads.flags, Flags::NONE);
+    /// ```
+    pub fn reads(&self) -> RegisterAccess {
+        let (registers, flags, _, _) = self.access(false);
+        RegisterAccess { registers, flags }
+    }
+
+    /// Returns the registers and flags that the operation writes when it is
+    /// placed outside of an IT block.
+    pub fn writes(&self) -> RegisterAccess {
+        self.writes_in_it_block(false)
+    }
+
+    /// Returns the registers and flags that the operation writes, where
+    /// `in_it_block` resolves the flag setting behaviour, see
+    /// [`Operation::s_suffix`].
+    pub fn writes_in_it_block(&self, in_it_block: bool) -> RegisterAccess {
+        let (_, _, registers, flags) = self.access(in_it_block);
+        RegisterAccess { registers, flags }
+    }
+
+    /// Returns the read registers, read flags, written registers and written
+    /// flags.
+    fn access(&self, in_it_block: bool) -> (RegisterSet, Flags, RegisterSet, Flags) {
+        use Flags as F;
+        use Operation as O;
+        use Register::{LR, PC, SP};
+        let none = RegisterSet::new();
+        let s_suffix = self.s_suffix(in_it_block);
+        let s = |flags: Flags| match s_suffix {
+            true => flags,
+            false => F::NONE,
+        };
+
+        match self {
+            O::AdcImmediate(el) => (
+                set([el.rn]),
+                F::C,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::AdcRegister(el) => (
+                set([el.rn, el.rm]),
+                F::C,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::AddImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::AddRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::AddSPImmediate(el) => (set([SP]), F::NONE, set([el.rd.unwrap_or(SP)]), s(F::NZCV)),
+            O::AddSPRegister(el) => (
+                set([SP, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(SP)]),
+                s(F::NZCV),
+            ),
+            O::Adr(el) => (set([PC]), F::NONE, set([el.rd]), F::NONE),
+            O::AndImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::AndRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::AsrImmediate(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::AsrRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::B(el) => (set([PC]), F::read_by(&el.condition), set([PC]), F::NONE),
+            O::Bfc(el) => (set([el.rd]), F::NONE, set([el.rd]), F::NONE),
+            O::Bfi(el) => (set([el.rd, el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::BicImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::BicRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::Bl(_) => (set([PC]), F::NONE, set([LR, PC]), F::NONE),
+            O::Blx(el) => (set([el.rm, PC]), F::NONE, set([LR, PC]), F::NONE),
+            O::Bx(el) => (set([el.rm]), F::NONE, set([PC]), F::NONE),
+            O::Cbz(el) => (set([el.rn, PC]), F::NONE, set([PC]), F::NONE),
+            O::Clz(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::CmnImmediate(el) => (set([el.rn]), F::NONE, none, F::NZCV),
+            O::CmnRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NZCV),
+            O::CmpImmediate(el) => (set([el.rn]), F::NONE, none, F::NZCV),
+            O::CmpRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NZCV),
+            O::EorImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::EorRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::Ldm(el) => (
+                set([el.rn]),
+                F::NONE,
+                list(&el.registers) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::Ldmdb(el) => (
+                set([el.rn]),
+                F::NONE,
+                list(&el.registers) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt]) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrLiteral(el) => (set([PC]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rt]) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrbImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt]) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrbLiteral(el) => (set([PC]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrbRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrbt(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrdImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt, el.rt2]) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrdLiteral(el) => (set([PC]), F::NONE, set([el.rt, el.rt2]), F::NONE),
+            O::Ldrex(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrexb(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrexh(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrhImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt]) | wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::LdrhLiteral(el) => (set([PC]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrhRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrht(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrsbImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt]) | wback(Some(el.wback), el.rn),
+                F::NONE,
+            ),
+            O::LdrsbLiteral(el) => (set([PC]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrsbRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrsbt(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrshImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rt]) | wback(Some(el.wback), el.rn),
+                F::NONE,
+            ),
+            O::LdrshLiteral(el) => (set([PC]), F::NONE, set([el.rt]), F::NONE),
+            O::LdrshRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrsht(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::Ldrt(el) => (set([el.rn]), F::NONE, set([el.rt]), F::NONE),
+            O::LdcImmediate(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::LdcLiteral(_) => (set([PC]), F::NONE, none, F::NONE),
+            O::LslImmediate(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::LslRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::LsrImmediate(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::LsrRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::Mcrr(el) => (set([el.rt, el.rt2]), F::NONE, none, F::NONE),
+            O::Mcr(el) => (set([el.rt]), F::NONE, none, F::NONE),
+            O::Mla(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::NONE),
+            O::Mls(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::NONE),
+            O::MovImmediate(el) => (none, F::NONE, set([el.rd]), s(F::NZC)),
+            O::MovRegister(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZ)),
+            O::Movt(el) => (set([el.rd]), F::NONE, set([el.rd]), F::NONE),
+            O::Mrrc(el) => (none, F::NONE, set([el.rt, el.rt2]), F::NONE),
+            // Using the PC as the destination transfers to APSR_nzcv.
+            O::Mrc(el) if el.rt == PC => (none, F::NONE, none, F::NZCV),
+            O::Mrc(el) => (none, F::NONE, set([el.rt]), F::NONE),
+            O::Mrs(el) => {
+                let flags = match el.sysm.includes_apsr() {
+                    true => F::ALL,
+                    false => F::NONE,
+                };
+                (none, flags, set([el.rd]), F::NONE)
+            }
+            O::Msr(el) => {
+                let flags = match el.sysm.includes_apsr() {
+                    true => match (el.mask.nzcvq, el.mask.g) {
+                        (true, true) => F::ALL,
+                        (true, false) => F::NZCV | F::Q,
+                        (false, true) => F::GE,
+                        (false, false) => F::NONE,
+                    },
+                    false => F::NONE,
+                };
+                (set([el.rn]), F::NONE, none, flags)
+            }
+            O::Mul(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZ),
+            ),
+            O::MvnImmediate(el) => (none, F::NONE, set([el.rd]), s(F::NZC)),
+            O::MvnRegister(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::OrnImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::OrnRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::OrrImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::OrrRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZC),
+            ),
+            O::Pkh(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::PldImmediate(el) => (set([el.rn]), F::NONE, none, F::NONE),
+            O::PldLiteral(_) => (set([PC]), F::NONE, none, F::NONE),
+            O::PldRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NONE),
+            O::PliImmediate(el) => (set([el.rn.unwrap_or(PC)]), F::NONE, none, F::NONE),
+            O::PliRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NONE),
+            O::Pop(el) => (set([SP]), F::NONE, set([SP]) | list(&el.registers), F::NONE),
+            O::Push(el) => (set([SP]) | list(&el.registers), F::NONE, set([SP]), F::NONE),
+            O::Qadd(el) => (
+                set([el.rm, el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::Q,
+            ),
+            O::Qadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Qadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Qasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Qdadd(el) => (
+                set([el.rm, el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::Q,
+            ),
+            O::Qdsub(el) => (
+                set([el.rm, el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::Q,
+            ),
+            O::Qsax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Qsub(el) => (
+                set([el.rm, el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::Q,
+            ),
+            O::Qsub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Qsub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Rbit(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Rev(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Rev16(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Revsh(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::RorImmediate(el) => (set([el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::RorRegister(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), s(F::NZC)),
+            O::Rrx(el) => (set([el.rm]), F::C, set([el.rd]), s(F::NZC)),
+            O::RsbImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::RsbRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::Sadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Sadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Sasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::SbcImmediate(el) => (
+                set([el.rn]),
+                F::C,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::SbcRegister(el) => (
+                set([el.rn, el.rm]),
+                F::C,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::Sbfx(el) => (set([el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::Sdiv(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Sel(el) => (
+                set([el.rn, el.rm]),
+                F::GE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shsax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shsub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Shsub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Smla(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::Q),
+            O::Smlad(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::Q),
+            O::Smlal(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::SmlalSelective(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Smlald(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Smlaw(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::Q),
+            O::Smlsd(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::Q),
+            O::Smlsld(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Smmla(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::NONE),
+            O::Smmls(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::NONE),
+            O::Smmul(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Smuad(el) => (set([el.rn, el.rm]), F::NONE, set([el.rd]), F::Q),
+            O::Smul(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Smull(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Smulw(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Smusd(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Ssat(el) => (set([el.rn]), F::NONE, set([el.rd]), F::Q),
+            O::Ssat16(el) => (set([el.rn]), F::NONE, set([el.rd]), F::Q),
+            O::Ssax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Ssub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Ssub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Stm(el) => (
+                set([el.rn]) | list(&el.registers),
+                F::NONE,
+                wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::Stmdb(el) => (
+                set([el.rn]) | list(&el.registers),
+                F::NONE,
+                wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::StrImmediate(el) => (set([el.rt, el.rn]), F::NONE, wback(el.w, el.rn), F::NONE),
+            O::StrRegister(el) => (set([el.rt, el.rn, el.rm]), F::NONE, none, F::NONE),
+            O::StrbImmediate(el) => (set([el.rt, el.rn]), F::NONE, wback(el.w, el.rn), F::NONE),
+            O::StrbRegister(el) => (set([el.rt, el.rn, el.rm]), F::NONE, none, F::NONE),
+            O::Strbt(el) => (set([el.rt, el.rn]), F::NONE, none, F::NONE),
+            O::StrdImmediate(el) => (
+                set([el.rt, el.rt2, el.rn]),
+                F::NONE,
+                wback(el.w, el.rn),
+                F::NONE,
+            ),
+            O::Strex(el) => (set([el.rt, el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::Strexb(el) => (set([el.rt, el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::Strexh(el) => (set([el.rt, el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::StrhImmediate(el) => (
+                set([el.rt, el.rn]),
+                F::NONE,
+                wback(Some(el.w), el.rn),
+                F::NONE,
+            ),
+            O::StrhRegister(el) => (set([el.rt, el.rn, el.rm]), F::NONE, none, F::NONE),
+            O::Strht(el) => (set([el.rt, el.rn]), F::NONE, none, F::NONE),
+            O::Strt(el) => (set([el.rt, el.rn]), F::NONE, none, F::NONE),
+            O::SubImmediate(el) => (
+                set([el.rn]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::SubRegister(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                s(F::NZCV),
+            ),
+            O::Stc(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::SubSpMinusImmediate(el) => {
+                (set([SP]), F::NONE, set([el.rd.unwrap_or(SP)]), s(F::NZCV))
+            }
+            O::SubSpMinusRegister(el) => (
+                set([SP, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(SP)]),
+                s(F::NZCV),
+            ),
+            O::Sxtab(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Sxtab16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Sxtah(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Sxtb(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Sxtb16(el) => (
+                set([el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::NONE,
+            ),
+            O::Sxth(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Tb(el) => (set([el.rn, el.rm]), F::NONE, set([PC]), F::NONE),
+            O::TeqImmediate(el) => (set([el.rn]), F::NONE, none, F::NZC),
+            O::TeqRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NZC),
+            O::TstImmediate(el) => (set([el.rn]), F::NONE, none, F::NZC),
+            O::TstRegister(el) => (set([el.rn, el.rm]), F::NONE, none, F::NZC),
+            O::Uadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Uadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Uasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Ubfx(el) => (set([el.rn]), F::NONE, set([el.rd]), F::NONE),
+            O::Udiv(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhsax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhsub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uhsub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Umaal(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Umlal(el) => (
+                set([el.rdlo, el.rdhi, el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Umull(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rdlo, el.rdhi]),
+                F::NONE,
+            ),
+            O::Uqadd16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqadd8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqasx(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqsax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqsub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqsub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uqsad8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Usada8(el) => (set([el.rn, el.rm, el.ra]), F::NONE, set([el.rd]), F::NONE),
+            O::Usad8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Usat(el) => (set([el.rn]), F::NONE, set([el.rd]), F::Q),
+            O::Usat16(el) => (set([el.rn]), F::NONE, set([el.rd]), F::Q),
+            O::Usax(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Usub16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Usub8(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::GE,
+            ),
+            O::Uxtab(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uxtab16(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uxtah(el) => (
+                set([el.rn, el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rn)]),
+                F::NONE,
+            ),
+            O::Uxtb(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::Uxtb16(el) => (
+                set([el.rm]),
+                F::NONE,
+                set([el.rd.unwrap_or(el.rm)]),
+                F::NONE,
+            ),
+            O::Uxth(el) => (set([el.rm]), F::NONE, set([el.rd]), F::NONE),
+            O::VldmF32(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::VldmF64(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::VldrF32(el) => (set([el.rn]), F::NONE, none, F::NONE),
+            O::VldrF64(el) => (set([el.rn]), F::NONE, none, F::NONE),
+            O::VmovCoreF32(el) if el.to_core => (none, F::NONE, set([el.rt]), F::NONE),
+            O::VmovCoreF32(el) => (set([el.rt]), F::NONE, none, F::NONE),
+            O::VmovCore2F32(el) if el.to_core => (none, F::NONE, set([el.rt, el.rt2]), F::NONE),
+            O::VmovCore2F32(el) => (set([el.rt, el.rt2]), F::NONE, none, F::NONE),
+            O::VmovCoreF64(el) if el.to_core => (none, F::NONE, set([el.rt, el.rt2]), F::NONE),
+            O::VmovCoreF64(el) => (set([el.rt, el.rt2]), F::NONE, none, F::NONE),
+            O::VmovCoreScalar(el) if el.to_core => (none, F::NONE, set([el.rt]), F::NONE),
+            O::VmovCoreScalar(el) => (set([el.rt]), F::NONE, none, F::NONE),
+            // Using the PC as the destination transfers to APSR_nzcv.
+            O::Vmrs(el) if el.rt == PC => (none, F::NONE, none, F::NZCV),
+            O::Vmrs(el) => (none, F::NONE, set([el.rt]), F::NONE),
+            O::Vmsr(el) => (set([el.rt]), F::NONE, none, F::NONE),
+            O::VpopF32(_) | O::VpopF64(_) | O::VpushF32(_) | O::VpushF64(_) => {
+                (set([SP]), F::NONE, set([SP]), F::NONE)
+            }
+            O::VselF32(el) => (none, F::read_by(&el.cond), none, F::NONE),
+            O::VselF64(el) => (none, F::read_by(&el.cond), none, F::NONE),
+            O::VstmF32(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::VstmF64(el) => (set([el.rn]), F::NONE, wback(Some(el.w), el.rn), F::NONE),
+            O::VstrF32(el) => (set([el.rn]), F::NONE, none, F::NONE),
+            O::VstrF64(el) => (set([el.rn]), F::NONE, none, F::NONE),
+            O::Bkpt(_)
+            | O::Cdp(_)
+            | O::Clrex(_)
+            | O::Cps(_)
+            | O::Dbg(_)
+            | O::Dmb(_)
+            | O::Dsb(_)
+            | O::Isb(_)
+            | O::It(_)
+            | O::Nop(_)
+            | O::Sev(_)
+            | O::Svc(_)
+            | O::Udf(_)
+            | O::Wfe(_)
+            | O::Wfi(_)
+            | O::Yield(_) => (none, F::NONE, none, F::NONE),
+            // Operations that only access floating point registers.
+            O::VabsF32(_)
+            | O::VabsF64(_)
+            | O::VaddF32(_)
+            | O::VaddF64(_)
+            | O::VcmpF32(_)
+            | O::VcmpF64(_)
+            | O::VcmpZeroF32(_)
+            | O::VcmpZeroF64(_)
+            | O::VcvtF32F16(_)
+            | O::VcvtF16F32(_)
+            | O::VcvtF64F16(_)
+            | O::VcvtF16F64(_)
+            | O::VcvtF64F32(_)
+            | O::VcvtF32F64(_)
+            | O::VcvtFromIntF32(_)
+            | O::VcvtFromIntF64(_)
+            | O::VcvtToIntF32(_)
+            | O::VcvtToIntF64(_)
+            | O::VcvtFixedF32(_)
+            | O::VcvtFixedF64(_)
+            | O::VdivF32(_)
+            | O::VdivF64(_)
+            | O::VfmaF32(_)
+            | O::VfmaF64(_)
+            | O::VfmsF32(_)
+            | O::VfmsF64(_)
+            | O::VfnmaF32(_)
+            | O::VfnmaF64(_)
+            | O::VfnmsF32(_)
+            | O::VfnmsF64(_)
+            | O::VmaxnmF32(_)
+            | O::VmaxnmF64(_)
+            | O::VminnmF32(_)
+            | O::VminnmF64(_)
+            | O::VmlaF32(_)
+            | O::VmlaF64(_)
+            | O::VmlsF32(_)
+            | O::VmlsF64(_)
+            | O::VmovImmediateF32(_)
+            | O::VmovImmediateF64(_)
+            | O::VmovRegisterF32(_)
+            | O::VmovRegisterF64(_)
+            | O::VmulF32(_)
+            | O::VmulF64(_)
+            | O::VnegF32(_)
+            | O::VnegF64(_)
+            | O::VnmlaF32(_)
+            | O::VnmlaF64(_)
+            | O::VnmlsF32(_)
+            | O::VnmlsF64(_)
+            | O::VnmulF32(_)
+            | O::VnmulF64(_)
+            | O::VrintF32(_)
+            | O::VrintF64(_)
+            | O::VrintxF32(_)
+            | O::VrintxF64(_)
+            | O::VsqrtF32(_)
+            | O::VsqrtF64(_)
+            | O::VsubF32(_)
+            | O::VsubF64(_) => (none, F::NONE, none, F::NONE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::helpers::fixtures::parse;
+
+    #[test]
+    fn test_set() {
+        let mut set = RegisterSet::from([Register::R0, Register::PC]);
+        set.insert(Register::R0);
+        set.insert(Register::SP);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_string(), "{r0, sp, pc}");
+        set.remove(Register::PC);
+        assert!(!set.contains(Register::PC));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![
+            Register::R0,
+            Register::SP
+        ]);
+    }
+
+    #[test]
+    fn test_implicit() {
+        use Register::*;
+        // bl #0
+        let bl = parse(&[0x00, 0xf0, 0x00, 0xf8]);
+        assert_eq!(bl.reads().registers, RegisterSet::from([PC]));
+        assert_eq!(bl.writes().registers, RegisterSet::from([LR, PC]));
+
+        // pop {r4, pc}
+        let pop = parse(&[0x10, 0xbd]);
+        assert_eq!(pop.reads().registers, RegisterSet::from([SP]));
+        assert_eq!(pop.writes().registers, RegisterSet::from([R4, SP, PC]));
+
+        // ldr r0, [r1, #4]!
+        let ldr = parse(&[0x51, 0xf8, 0x04, 0x0f]);
+        assert_eq!(ldr.reads().registers, RegisterSet::from([R1]));
+        assert_eq!(ldr.writes().registers, RegisterSet::from([R0, R1]));
+
+        // ldr r0, [r1, #4]
+        let ldr = parse(&[0x48, 0x68]);
+        assert_eq!(ldr.writes().registers, RegisterSet::from([R0]));
+
+        // umlal r0, r1, r2, r3
+        let umlal = parse(&[0xe2, 0xfb, 0x03, 0x01]);
+        assert_eq!(umlal.reads().registers, RegisterSet::from([R0, R1, R2, R3]));
+        assert_eq!(umlal.writes().registers, RegisterSet::from([R0, R1]));
+    }
+
+    #[test]
+    fn test_flags() {
+        // adds r0, r0, r1
+        let adds = parse(&[0x40, 0x18]);
+        assert_eq!(adds.writes().flags, Flags::NZCV);
+        assert_eq!(adds.writes_in_it_block(true).flags, Flags::NONE);
+
+        // adc.w r0, r0, r1
+        let adc = parse(&[0x40, 0xeb, 0x01, 0x00]);
+        assert_eq!(adc.reads().flags, Flags::C);
+        assert!(adc.writes().flags.is_empty());
+
+        // bhi #0
+        let bhi = parse(&[0x00, 0xd8]);
+        assert_eq!(bhi.reads().flags, Flags {
+            c: true,
+            z: true,
+            ..Flags::NONE
+        });
+
+        // sadd16 r0, r1, r2 ; sel r0, r1, r2 ; qadd r0, r1, r2
+        assert_eq!(parse(&[0x91, 0xfa, 0x02, 0xf0]).writes().flags, Flags::GE);
+        assert_eq!(parse(&[0xa1, 0xfa, 0x82, 0xf0]).reads().flags, Flags::GE);
+        assert_eq!(parse(&[0x82, 0xfa, 0x81, 0xf0]).writes().flags, Flags::Q);
+
+        // vmrs APSR_nzcv, fpscr
+        let vmrs = parse(&[0xf1, 0xee, 0x10, 0xfa]);
+        assert!(vmrs.writes().registers.is_empty());
+        assert_eq!(vmrs.writes().flags, Flags::NZCV);
+    }
+
+    #[test]
+    fn test_defaults() {
+        use Register::*;
+        // qadd r0, r1, r2, rd defaults to rm.
+        let qadd = parse(&[0x82, 0xfa, 0x81, 0xf0]);
+        assert_eq!(qadd.writes().registers, RegisterSet::from([R0]));
+        assert_eq!(qadd.reads().registers, RegisterSet::from([R1, R2]));
+
+        // add sp, #8
+        let add = parse(&[0x02, 0xb0]);
+        assert_eq!(add.reads().registers, RegisterSet::from([SP]));
+        assert_eq!(add.writes().registers, RegisterSet::from([SP]));
+    }
+}
@@ -0,0 +1,792 @@
+//! Sorts the [`Operation`]s in to semantic categories.
+
+use std::ops::{BitOr, BitOrAssign};
+
+use super::Operation;
+use crate::{
+    arch::{Condition, Register},
+    profile::Extensions,
+};
+
+/// A set of semantic categories, an operation may belong to several of them,
+/// e.g. `pop {pc}` is a [`LOAD`](Self::LOAD), a [`BRANCH`](Self::BRANCH) and
+/// a [`RETURN`](Self::RETURN).
+///
+/// ```
+/// use disarmv7::{operation::InstructionClass, prelude::*};
+///
+/// let bx: Operation = operation::Bx::builder()
+///     .set_rm(Register::LR)
+///     .complete()
+///     .into();
+///
+/// assert!(bx.class().contains(InstructionClass::BRANCH | InstructionClass::RETURN));
+/// assert!(bx.is_return() && !bx.is_call());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InstructionClass {
+    bits: u16,
+}
+
+impl InstructionClass {
+    /// Orders memory accesses or instruction fetches.
+    pub const BARRIER: Self = Self::bit(8);
+    /// Writes to the PC.
+    pub const BRANCH: Self = Self::bit(0);
+    /// Branches to a subroutine and writes the return address to the LR.
+    pub const CALL: Self = Self::bit(1);
+    /// Branches only if a condition holds, i.e. `b<c>`, `cbz` and `cbnz`.
+    pub const CONDITIONAL: Self = Self::bit(3);
+    /// Accesses a generic coprocessor.
+    pub const COPROCESSOR: Self = Self::bit(11);
+    /// Part of the DSP extension.
+    pub const DSP: Self = Self::bit(10);
+    /// Takes part in an exclusive access, i.e. `ldrex`, `strex` and `clrex`.
+    pub const EXCLUSIVE: Self = Self::bit(7);
+    /// Part of the floating point extension.
+    pub const FLOATING_POINT: Self = Self::bit(12);
+    /// Branches to a target that is not encoded in the instruction.
+    pub const INDIRECT: Self = Self::bit(4);
+    /// Reads from memory.
+    pub const LOAD: Self = Self::bit(5);
+    /// Belongs to no category.
+    pub const NONE: Self = Self { bits: 0 };
+    /// Returns from a subroutine, i.e. `bx lr`, `pop {.., pc}` or loads the
+    /// PC from the stack.
+    pub const RETURN: Self = Self::bit(2);
+    /// Writes to memory.
+    pub const STORE: Self = Self::bit(6);
+    /// Accesses system state, raises an exception or is a hint to the
+    /// processor.
+    pub const SYSTEM: Self = Self::bit(9);
+
+    const fn bit(idx: u8) -> Self {
+        Self { bits: 1 << idx }
+    }
+
+    /// Returns true if all of the categories in `other` are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Returns true if any of the categories in `other` are set.
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    /// Returns true if no category is set.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+}
+
+impl BitOr for InstructionClass {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self {
+            bits: self.bits | rhs.bits,
+        }
+    }
+}
+
+impl BitOrAssign for InstructionClass {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.bits |= rhs.bits;
+    }
+}
+
+/// Returns `class` if `condition` holds.
+const fn when(condition: bool, class: InstructionClass) -> InstructionClass {
+    match condition {
+        true => class,
+        false => InstructionClass::NONE,
+    }
+}
+
+impl Operation {
+    /// Returns the categories that the operation belongs to.
+    pub fn class(&self) -> InstructionClass {
+        use InstructionClass as C;
+        let branch = self.may_change_pc();
+        when(branch, C::BRANCH)
+            | when(branch && self.branch_target(0).is_none(), C::INDIRECT)
+            | when(self.is_dsp_extension(), C::DSP)
+            | when(self.is_floating_point_extension(), C::FLOATING_POINT)
+            | self.categories()
+    }
+
+    /// Returns the categories that follow from the variant, every variant is
+    /// listed such that a new one has to be classified before it compiles.
+    fn categories(&self) -> InstructionClass {
+        use InstructionClass as C;
+        let pops_pc = |rn: &Register, registers: &[Register]| {
+            *rn == Register::SP && registers.contains(&Register::PC)
+        };
+        match self {
+            Self::Dmb(_) | Self::Dsb(_) | Self::Isb(_) => C::BARRIER,
+            Self::Bkpt(_)
+            | Self::Cps(_)
+            | Self::Dbg(_)
+            | Self::Mrs(_)
+            | Self::Msr(_)
+            | Self::Sev(_)
+            | Self::Svc(_)
+            | Self::Udf(_)
+            | Self::Wfe(_)
+            | Self::Wfi(_)
+            | Self::Yield(_) => C::SYSTEM,
+            Self::Cdp(_) | Self::Mcr(_) | Self::Mcrr(_) | Self::Mrc(_) | Self::Mrrc(_) => {
+                C::COPROCESSOR
+            }
+            Self::LdcImmediate(_) | Self::LdcLiteral(_) => C::COPROCESSOR | C::LOAD,
+            Self::Stc(_) => C::COPROCESSOR | C::STORE,
+            Self::Bl(_) | Self::Blx(_) => C::CALL,
+            Self::Cbz(_) => C::CONDITIONAL,
+            Self::Ldrex(_) | Self::Ldrexb(_) | Self::Ldrexh(_) => C::LOAD | C::EXCLUSIVE,
+            Self::Strex(_) | Self::Strexb(_) | Self::Strexh(_) => C::STORE | C::EXCLUSIVE,
+            Self::Clrex(_) => C::EXCLUSIVE,
+            Self::Bx(el) => when(el.rm == Register::LR, C::RETURN),
+            Self::B(el) => when(el.condition != Condition::None, C::CONDITIONAL),
+            Self::Pop(el) => {
+                C::LOAD | when(el.registers.registers.contains(&Register::PC), C::RETURN)
+            }
+            Self::Ldm(el) => C::LOAD | when(pops_pc(&el.rn, &el.registers.registers), C::RETURN),
+            Self::LdrImmediate(el) => C::LOAD | when(pops_pc(&el.rn, &[el.rt]), C::RETURN),
+            // Table branches load the offset from memory.
+            Self::Tb(_)
+            | Self::Ldmdb(_)
+            | Self::LdrLiteral(_)
+            | Self::LdrRegister(_)
+            | Self::LdrbImmediate(_)
+            | Self::LdrbLiteral(_)
+            | Self::LdrbRegister(_)
+            | Self::Ldrbt(_)
+            | Self::LdrdImmediate(_)
+            | Self::LdrdLiteral(_)
+            | Self::LdrhImmediate(_)
+            | Self::LdrhLiteral(_)
+            | Self::LdrhRegister(_)
+            | Self::Ldrht(_)
+            | Self::LdrsbImmediate(_)
+            | Self::LdrsbLiteral(_)
+            | Self::LdrsbRegister(_)
+            | Self::Ldrsbt(_)
+            | Self::LdrshImmediate(_)
+            | Self::LdrshLiteral(_)
+            | Self::LdrshRegister(_)
+            | Self::Ldrsht(_)
+            | Self::Ldrt(_)
+            | Self::VldmF32(_)
+            | Self::VldmF64(_)
+            | Self::VldrF32(_)
+            | Self::VldrF64(_)
+            | Self::VpopF32(_)
+            | Self::VpopF64(_) => C::LOAD,
+            Self::Stm(_)
+            | Self::Stmdb(_)
+            | Self::StrImmediate(_)
+            | Self::StrRegister(_)
+            | Self::StrbImmediate(_)
+            | Self::StrbRegister(_)
+            | Self::Strbt(_)
+            | Self::StrdImmediate(_)
+            | Self::StrhImmediate(_)
+            | Self::StrhRegister(_)
+            | Self::Strht(_)
+            | Self::Strt(_)
+            | Self::Push(_)
+            | Self::VstmF32(_)
+            | Self::VstmF64(_)
+            | Self::VstrF32(_)
+            | Self::VstrF64(_)
+            | Self::VpushF32(_)
+            | Self::VpushF64(_) => C::STORE,
+            Self::AdcImmediate(_)
+            | Self::AdcRegister(_)
+            | Self::AddImmediate(_)
+            | Self::AddRegister(_)
+            | Self::AddSPImmediate(_)
+            | Self::AddSPRegister(_)
+            | Self::Adr(_)
+            | Self::AndImmediate(_)
+            | Self::AndRegister(_)
+            | Self::AsrImmediate(_)
+            | Self::AsrRegister(_)
+            | Self::Bfc(_)
+            | Self::Bfi(_)
+            | Self::BicImmediate(_)
+            | Self::BicRegister(_)
+            | Self::Clz(_)
+            | Self::CmnImmediate(_)
+            | Self::CmnRegister(_)
+            | Self::CmpImmediate(_)
+            | Self::CmpRegister(_)
+            | Self::EorImmediate(_)
+            | Self::EorRegister(_)
+            | Self::It(_)
+            | Self::LslImmediate(_)
+            | Self::LslRegister(_)
+            | Self::LsrImmediate(_)
+            | Self::LsrRegister(_)
+            | Self::Mla(_)
+            | Self::Mls(_)
+            | Self::MovImmediate(_)
+            | Self::MovRegister(_)
+            | Self::Movt(_)
+            | Self::Mul(_)
+            | Self::MvnImmediate(_)
+            | Self::MvnRegister(_)
+            | Self::Nop(_)
+            | Self::OrnImmediate(_)
+            | Self::OrnRegister(_)
+            | Self::OrrImmediate(_)
+            | Self::OrrRegister(_)
+            | Self::Pkh(_)
+            | Self::PldImmediate(_)
+            | Self::PldLiteral(_)
+            | Self::PldRegister(_)
+            | Self::PliImmediate(_)
+            | Self::PliRegister(_)
+            | Self::Qadd(_)
+            | Self::Qadd16(_)
+            | Self::Qadd8(_)
+            | Self::Qasx(_)
+            | Self::Qdadd(_)
+            | Self::Qdsub(_)
+            | Self::Qsax(_)
+            | Self::Qsub(_)
+            | Self::Qsub16(_)
+            | Self::Qsub8(_)
+            | Self::Rbit(_)
+            | Self::Rev(_)
+            | Self::Rev16(_)
+            | Self::Revsh(_)
+            | Self::RorImmediate(_)
+            | Self::RorRegister(_)
+            | Self::Rrx(_)
+            | Self::RsbImmediate(_)
+            | Self::RsbRegister(_)
+            | Self::Sadd16(_)
+            | Self::Sadd8(_)
+            | Self::Sasx(_)
+            | Self::SbcImmediate(_)
+            | Self::SbcRegister(_)
+            | Self::Sbfx(_)
+            | Self::Sdiv(_)
+            | Self::Sel(_)
+            | Self::Shadd16(_)
+            | Self::Shadd8(_)
+            | Self::Shasx(_)
+            | Self::Shsax(_)
+            | Self::Shsub16(_)
+            | Self::Shsub8(_)
+            | Self::Smla(_)
+            | Self::Smlad(_)
+            | Self::Smlal(_)
+            | Self::SmlalSelective(_)
+            | Self::Smlald(_)
+            | Self::Smlaw(_)
+            | Self::Smlsd(_)
+            | Self::Smlsld(_)
+            | Self::Smmla(_)
+            | Self::Smmls(_)
+            | Self::Smmul(_)
+            | Self::Smuad(_)
+            | Self::Smul(_)
+            | Self::Smull(_)
+            | Self::Smulw(_)
+            | Self::Smusd(_)
+            | Self::Ssat(_)
+            | Self::Ssat16(_)
+            | Self::Ssax(_)
+            | Self::Ssub16(_)
+            | Self::Ssub8(_)
+            | Self::SubImmediate(_)
+            | Self::SubRegister(_)
+            | Self::SubSpMinusImmediate(_)
+            | Self::SubSpMinusRegister(_)
+            | Self::Sxtab(_)
+            | Self::Sxtab16(_)
+            | Self::Sxtah(_)
+            | Self::Sxtb(_)
+            | Self::Sxtb16(_)
+            | Self::Sxth(_)
+            | Self::TeqImmediate(_)
+            | Self::TeqRegister(_)
+            | Self::TstImmediate(_)
+            | Self::TstRegister(_)
+            | Self::Uadd16(_)
+            | Self::Uadd8(_)
+            | Self::Uasx(_)
+            | Self::Ubfx(_)
+            | Self::Udiv(_)
+            | Self::Uhadd16(_)
+            | Self::Uhadd8(_)
+            | Self::Uhasx(_)
+            | Self::Uhsax(_)
+            | Self::Uhsub16(_)
+            | Self::Uhsub8(_)
+            | Self::Umaal(_)
+            | Self::Umlal(_)
+            | Self::Umull(_)
+            | Self::Uqadd16(_)
+            | Self::Uqadd8(_)
+            | Self::Uqasx(_)
+            | Self::Uqsax(_)
+            | Self::Uqsub16(_)
+            | Self::Uqsub8(_)
+            | Self::Uqsad8(_)
+            | Self::Usada8(_)
+            | Self::Usad8(_)
+            | Self::Usat(_)
+            | Self::Usat16(_)
+            | Self::Usax(_)
+            | Self::Usub16(_)
+            | Self::Usub8(_)
+            | Self::Uxtab(_)
+            | Self::Uxtab16(_)
+            | Self::Uxtah(_)
+            | Self::Uxtb(_)
+            | Self::Uxtb16(_)
+            | Self::Uxth(_)
+            | Self::VabsF32(_)
+            | Self::VabsF64(_)
+            | Self::VaddF32(_)
+            | Self::VaddF64(_)
+            | Self::VcmpF32(_)
+            | Self::VcmpF64(_)
+            | Self::VcmpZeroF32(_)
+            | Self::VcmpZeroF64(_)
+            | Self::VcvtF32F16(_)
+            | Self::VcvtF16F32(_)
+            | Self::VcvtF64F16(_)
+            | Self::VcvtF16F64(_)
+            | Self::VcvtF64F32(_)
+            | Self::VcvtF32F64(_)
+            | Self::VcvtFromIntF32(_)
+            | Self::VcvtFromIntF64(_)
+            | Self::VcvtToIntF32(_)
+            | Self::VcvtToIntF64(_)
+            | Self::VcvtFixedF32(_)
+            | Self::VcvtFixedF64(_)
+            | Self::VdivF32(_)
+            | Self::VdivF64(_)
+            | Self::VfmaF32(_)
+            | Self::VfmaF64(_)
+            | Self::VfmsF32(_)
+            | Self::VfmsF64(_)
+            | Self::VfnmaF32(_)
+            | Self::VfnmaF64(_)
+            | Self::VfnmsF32(_)
+            | Self::VfnmsF64(_)
+            | Self::VmaxnmF32(_)
+            | Self::VmaxnmF64(_)
+            | Self::VminnmF32(_)
+            | Self::VminnmF64(_)
+            | Self::VmlaF32(_)
+            | Self::VmlaF64(_)
+            | Self::VmlsF32(_)
+            | Self::VmlsF64(_)
+            | Self::VmovImmediateF32(_)
+            | Self::VmovImmediateF64(_)
+            | Self::VmovRegisterF32(_)
+            | Self::VmovRegisterF64(_)
+            | Self::VmovCoreF32(_)
+            | Self::VmovCore2F32(_)
+            | Self::VmovCoreF64(_)
+            | Self::VmovCoreScalar(_)
+            | Self::Vmrs(_)
+            | Self::Vmsr(_)
+            | Self::VmulF32(_)
+            | Self::VmulF64(_)
+            | Self::VnegF32(_)
+            | Self::VnegF64(_)
+            | Self::VnmlaF32(_)
+            | Self::VnmlaF64(_)
+            | Self::VnmlsF32(_)
+            | Self::VnmlsF64(_)
+            | Self::VnmulF32(_)
+            | Self::VnmulF64(_)
+            | Self::VrintF32(_)
+            | Self::VrintF64(_)
+            | Self::VrintxF32(_)
+            | Self::VrintxF64(_)
+            | Self::VselF32(_)
+            | Self::VselF64(_)
+            | Self::VsqrtF32(_)
+            | Self::VsqrtF64(_)
+            | Self::VsubF32(_)
+            | Self::VsubF64(_) => C::NONE,
+        }
+    }
+
+    /// Returns true if the operation is part of the DSP extension.
+    fn is_dsp_extension(&self) -> bool {
+        matches!(
+            self,
+            Self::Pkh(_)
+                | Self::Qadd(_)
+                | Self::Qadd16(_)
+                | Self::Qadd8(_)
+                | Self::Qasx(_)
+                | Self::Qdadd(_)
+                | Self::Qdsub(_)
+                | Self::Qsax(_)
+                | Self::Qsub(_)
+                | Self::Qsub16(_)
+                | Self::Qsub8(_)
+                | Self::Sadd16(_)
+                | Self::Sadd8(_)
+                | Self::Sasx(_)
+                | Self::Sel(_)
+                | Self::Shadd16(_)
+                | Self::Shadd8(_)
+                | Self::Shasx(_)
+                | Self::Shsax(_)
+                | Self::Shsub16(_)
+                | Self::Shsub8(_)
+                | Self::Smla(_)
+                | Self::Smlad(_)
+                | Self::SmlalSelective(_)
+                | Self::Smlald(_)
+                | Self::Smlaw(_)
+                | Self::Smlsd(_)
+                | Self::Smlsld(_)
+                | Self::Smmla(_)
+                | Self::Smmls(_)
+                | Self::Smmul(_)
+                | Self::Smuad(_)
+                | Self::Smul(_)
+                | Self::Smulw(_)
+                | Self::Smusd(_)
+                | Self::Ssat16(_)
+                | Self::Ssax(_)
+                | Self::Ssub16(_)
+                | Self::Ssub8(_)
+                | Self::Sxtab(_)
+                | Self::Sxtab16(_)
+                | Self::Sxtah(_)
+                | Self::Sxtb16(_)
+                | Self::Uadd16(_)
+                | Self::Uadd8(_)
+                | Self::Uasx(_)
+                | Self::Uhadd16(_)
+                | Self::Uhadd8(_)
+                | Self::Uhasx(_)
+                | Self::Uhsax(_)
+                | Self::Uhsub16(_)
+                | Self::Uhsub8(_)
+                | Self::Umaal(_)
+                | Self::Uqadd16(_)
+                | Self::Uqadd8(_)
+                | Self::Uqasx(_)
+                | Self::Uqsad8(_)
+                | Self::Uqsax(_)
+                | Self::Uqsub16(_)
+                | Self::Uqsub8(_)
+                | Self::Usad8(_)
+                | Self::Usada8(_)
+                | Self::Usat16(_)
+                | Self::Usax(_)
+                | Self::Usub16(_)
+                | Self::Usub8(_)
+                | Self::Uxtab(_)
+                | Self::Uxtab16(_)
+                | Self::Uxtah(_)
+                | Self::Uxtb16(_)
+        )
+    }
+
+    /// Returns true if the operation is part of the floating point
+    /// extension.
+    fn is_floating_point_extension(&self) -> bool {
+        matches!(
+            self,
+            Self::VabsF32(_)
+                | Self::VabsF64(_)
+                | Self::VaddF32(_)
+                | Self::VaddF64(_)
+                | Self::VcmpF32(_)
+                | Self::VcmpF64(_)
+                | Self::VcmpZeroF32(_)
+                | Self::VcmpZeroF64(_)
+                | Self::VcvtF32F16(_)
+                | Self::VcvtF16F32(_)
+                | Self::VcvtF64F16(_)
+                | Self::VcvtF16F64(_)
+                | Self::VcvtF64F32(_)
+                | Self::VcvtF32F64(_)
+                | Self::VcvtFromIntF32(_)
+                | Self::VcvtFromIntF64(_)
+                | Self::VcvtToIntF32(_)
+                | Self::VcvtToIntF64(_)
+                | Self::VcvtFixedF32(_)
+                | Self::VcvtFixedF64(_)
+                | Self::VdivF32(_)
+                | Self::VdivF64(_)
+                | Self::VfmaF32(_)
+                | Self::VfmaF64(_)
+                | Self::VfmsF32(_)
+                | Self::VfmsF64(_)
+                | Self::VfnmaF32(_)
+                | Self::VfnmaF64(_)
+                | Self::VfnmsF32(_)
+                | Self::VfnmsF64(_)
+                | Self::VldmF32(_)
+                | Self::VldmF64(_)
+                | Self::VldrF32(_)
+                | Self::VldrF64(_)
+                | Self::VmaxnmF32(_)
+                | Self::VmaxnmF64(_)
+                | Self::VminnmF32(_)
+                | Self::VminnmF64(_)
+                | Self::VmlaF32(_)
+                | Self::VmlaF64(_)
+                | Self::VmlsF32(_)
+                | Self::VmlsF64(_)
+                | Self::VmovImmediateF32(_)
+                | Self::VmovImmediateF64(_)
+                | Self::VmovRegisterF32(_)
+                | Self::VmovRegisterF64(_)
+                | Self::VmovCoreF32(_)
+                | Self::VmovCore2F32(_)
+                | Self::VmovCoreF64(_)
+                | Self::VmovCoreScalar(_)
+                | Self::Vmrs(_)
+                | Self::Vmsr(_)
+                | Self::VmulF32(_)
+                | Self::VmulF64(_)
+                | Self::VnegF32(_)
+                | Self::VnegF64(_)
+                | Self::VnmlaF32(_)
+                | Self::VnmlaF64(_)
+                | Self::VnmlsF32(_)
+                | Self::VnmlsF64(_)
+                | Self::VnmulF32(_)
+                | Self::VnmulF64(_)
+                | Self::VpopF32(_)
+                | Self::VpopF64(_)
+                | Self::VpushF32(_)
+                | Self::VpushF64(_)
+                | Self::VrintF32(_)
+                | Self::VrintF64(_)
+                | Self::VrintxF32(_)
+                | Self::VrintxF64(_)
+                | Self::VselF32(_)
+                | Self::VselF64(_)
+                | Self::VsqrtF32(_)
+                | Self::VsqrtF64(_)
+                | Self::VstmF32(_)
+                | Self::VstmF64(_)
+                | Self::VstrF32(_)
+                | Self::VstrF64(_)
+                | Self::VsubF32(_)
+                | Self::VsubF64(_)
+        )
+    }
+
+    /// Returns true if the operation computes on double precision values.
+    ///
+    /// Loads, stores and moves of doubleword registers are also available in
+    /// single precision implementations.
+    fn is_double_precision(&self) -> bool {
+        matches!(
+            self,
+            Self::VabsF64(_)
+                | Self::VaddF64(_)
+                | Self::VcmpF64(_)
+                | Self::VcmpZeroF64(_)
+                | Self::VcvtF64F16(_)
+                | Self::VcvtF16F64(_)
+                | Self::VcvtF64F32(_)
+                | Self::VcvtF32F64(_)
+                | Self::VcvtFromIntF64(_)
+                | Self::VcvtToIntF64(_)
+                | Self::VcvtFixedF64(_)
+                | Self::VdivF64(_)
+                | Self::VfmaF64(_)
+                | Self::VfmsF64(_)
+                | Self::VfnmaF64(_)
+                | Self::VfnmsF64(_)
+                | Self::VmaxnmF64(_)
+                | Self::VminnmF64(_)
+                | Self::VmlaF64(_)
+                | Self::VmlsF64(_)
+                | Self::VmovImmediateF64(_)
+                | Self::VmovRegisterF64(_)
+                | Self::VmulF64(_)
+                | Self::VnegF64(_)
+                | Self::VnmlaF64(_)
+                | Self::VnmlsF64(_)
+                | Self::VnmulF64(_)
+                | Self::VrintF64(_)
+                | Self::VrintxF64(_)
+                | Self::VselF64(_)
+                | Self::VsqrtF64(_)
+                | Self::VsubF64(_)
+        )
+    }
+
+    /// Returns the optional extensions that must be implemented for the
+    /// operation to be defined, see [`profile`](crate::profile).
+    ///
+    /// Double precision operations require both
+    /// [`FP_SINGLE`](Extensions::FP_SINGLE) and
+    /// [`FP_DOUBLE`](Extensions::FP_DOUBLE).
+    pub fn required_extensions(&self) -> Extensions {
+        let mut extensions = Extensions::NONE;
+        if self.is_dsp_extension() {
+            extensions |= Extensions::DSP;
+        }
+        if self.is_floating_point_extension() {
+            extensions |= Extensions::FP_SINGLE;
+        }
+        if self.is_double_precision() {
+            extensions |= Extensions::FP_DOUBLE;
+        }
+        extensions
+    }
+
+    /// Returns true if the operation may write to the PC, either as a branch
+    /// or by using the PC as a destination register.
+    ///
+    /// Exceptions raised by the operation, e.g. by `svc`, are not included.
+    pub fn may_change_pc(&self) -> bool {
+        self.writes().registers.contains(Register::PC)
+    }
+
+    /// Returns true if the operation writes to the PC, see
+    /// [`InstructionClass::BRANCH`].
+    pub fn is_branch(&self) -> bool {
+        self.may_change_pc()
+    }
+
+    /// Returns true if the operation calls a subroutine, see
+    /// [`InstructionClass::CALL`].
+    pub fn is_call(&self) -> bool {
+        self.class().contains(InstructionClass::CALL)
+    }
+
+    /// Returns true if the operation returns from a subroutine, see
+    /// [`InstructionClass::RETURN`].
+    pub fn is_return(&self) -> bool {
+        self.class().contains(InstructionClass::RETURN)
+    }
+
+    /// Returns true if the operation reads from memory.
+    pub fn is_load(&self) -> bool {
+        self.class().contains(InstructionClass::LOAD)
+    }
+
+    /// Returns true if the operation writes to memory.
+    pub fn is_store(&self) -> bool {
+        self.class().contains(InstructionClass::STORE)
+    }
+
+    /// Returns true if the operation reads from or writes to memory.
+    pub fn is_memory_access(&self) -> bool {
+        self.class()
+            .intersects(InstructionClass::LOAD | InstructionClass::STORE)
+    }
+
+    /// Returns true if the operation takes part in an exclusive access.
+    pub fn is_exclusive(&self) -> bool {
+        self.class().contains(InstructionClass::EXCLUSIVE)
+    }
+
+    /// Returns true if the operation is a memory or instruction barrier.
+    pub fn is_barrier(&self) -> bool {
+        self.class().contains(InstructionClass::BARRIER)
+    }
+
+    /// Returns true if the operation is a system instruction, see
+    /// [`InstructionClass::SYSTEM`].
+    pub fn is_system(&self) -> bool {
+        self.class().contains(InstructionClass::SYSTEM)
+    }
+
+    /// Returns true if the operation is part of the DSP extension.
+    pub fn is_dsp(&self) -> bool {
+        self.class().contains(InstructionClass::DSP)
+    }
+
+    /// Returns true if the operation accesses a generic coprocessor.
+    pub fn is_coprocessor(&self) -> bool {
+        self.class().contains(InstructionClass::COPROCESSOR)
+    }
+
+    /// Returns true if the operation is part of the floating point
+    /// extension.
+    pub fn is_floating_point(&self) -> bool {
+        self.class().contains(InstructionClass::FLOATING_POINT)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::helpers::fixtures::parse;
+
+    #[test]
+    fn test_returns() {
+        // bx lr ; pop {r4, pc} ; ldr pc, [sp], #4 ; ldmia.w sp!, {r4, pc}
+        for bin in [
+            &[0x70, 0x47][..],
+            &[0x10, 0xbd],
+            &[0x5d, 0xf8, 0x04, 0xfb],
+            &[0xbd, 0xe8, 0x10, 0x80],
+        ] {
+            let op = parse(bin);
+            assert!(op.is_return(), "{op:?} is a return");
+            assert!(op.may_change_pc());
+            assert!(op.class().contains(InstructionClass::INDIRECT));
+        }
+        // bx r0 ; ldr pc, [r0] ; pop {r4}
+        for bin in [&[0x00, 0x47][..], &[0xd0, 0xf8, 0x00, 0xf0], &[0x10, 0xbc]] {
+            assert!(!parse(bin).is_return());
+        }
+    }
+
+    #[test]
+    fn test_branches() {
+        use InstructionClass as C;
+        // beq #0
+        let beq = parse(&[0x00, 0xd0]);
+        assert_eq!(beq.class(), C::BRANCH | C::CONDITIONAL);
+        // bl #0
+        let bl = parse(&[0x00, 0xf0, 0x00, 0xf8]);
+        assert_eq!(bl.class(), C::BRANCH | C::CALL);
+        // blx r3
+        let blx = parse(&[0x98, 0x47]);
+        assert_eq!(blx.class(), C::BRANCH | C::CALL | C::INDIRECT);
+        // mov pc, r0
+        assert!(parse(&[0x87, 0x46]).may_change_pc());
+        // tbb [pc, r0]
+        let tbb = parse(&[0xdf, 0xe8, 0x00, 0xf0]);
+        assert_eq!(tbb.class(), C::BRANCH | C::INDIRECT | C::LOAD);
+        // adds r0, r0, r1
+        assert!(parse(&[0x40, 0x18]).class().is_empty());
+    }
+
+    #[test]
+    fn test_categories() {
+        // ldrex r0, [r1]
+        let ldrex = parse(&[0x51, 0xe8, 0x00, 0x0f]);
+        assert!(ldrex.is_exclusive() && ldrex.is_load() && !ldrex.is_store());
+        // push {r4, lr}
+        assert!(parse(&[0x10, 0xb5]).is_memory_access());
+        // dmb sy
+        assert!(parse(&[0xbf, 0xf3, 0x5f, 0x8f]).is_barrier());
+        // wfi ; svc #0
+        assert!(parse(&[0x30, 0xbf]).is_system());
+        assert!(parse(&[0x00, 0xdf]).is_system());
+        // sadd16 r0, r1, r2
+        assert!(parse(&[0x91, 0xfa, 0x02, 0xf0]).is_dsp());
+        // mcr p15, #0, r0, c1, c0, #0
+        assert!(parse(&[0x01, 0xee, 0x10, 0x0f]).is_coprocessor());
+        // vldr s0, [r0]
+        let vldr = parse(&[0x90, 0xed, 0x00, 0x0a]);
+        assert!(vldr.is_floating_point() && vldr.is_load());
+    }
+}
@@ -0,0 +1,1397 @@
+//! Renders [`Operation`]s as ARM Unified Assembler Language (UAL) text.
+//!
+//! The plain [`Display`] implementation on [`Operation`] renders the
+//! instruction as if it was decoded outside of an IT block and without any
+//! width qualifiers. For encoding specific output use [`Operation::ual`]
+//! which allows the caller to provide the encoding size and IT block state.
+
+use std::fmt::{Display, Formatter, Result};
+
+use super::Operation;
+use crate::arch::{BarrierOption, Condition, ImmShift, Register, RoundingMode};
+
+/// Renders an [`Operation`] as UAL assembly.
+///
+/// Constructed via [`Operation::ual`].
+#[derive(Debug, Clone)]
+pub struct Ual<'a> {
+    operation: &'a Operation,
+    size: Option<usize>,
+    in_it_block: bool,
+    condition: Condition,
+    explicit_width: bool,
+}
+
+/// The rendered pieces of an instruction.
+struct Parts {
+    mnemonic: String,
+    s: bool,
+    condition: Option<Condition>,
+    operands: String,
+}
+
+impl Operation {
+    /// Returns a UAL renderer for this operation.
+    ///
+    /// ```
+    /// use disarmv7::prelude::*;
+    ///
+    /// let op: Operation = operation::B::builder()
+    ///     .set_condition(Condition::Eq)
+    ///     .set_imm(4)
+    ///     .complete()
+    ///     .into();
+    /// assert_eq!(format!("{}", op.ual().size(32)), "beq.w #4");
+    /// ```
+    pub fn ual(&self) -> Ual<'_> {
+        Ual {
+            operation: self,
+            size: None,
+            in_it_block: false,
+            condition: Condition::None,
+            explicit_width: false,
+        }
+    }
+
+    /// Returns true if the mnemonic has both a 16 and a 32 bit encoding.
+    fn has_narrow_form(&self) -> bool {
+        matches!(
+            self,
+            Self::AdcRegister(_)
+                | Self::AddImmediate(_)
+                | Self::AddRegister(_)
+                | Self::AddSPImmediate(_)
+                | Self::AddSPRegister(_)
+                | Self::Adr(_)
+                | Self::AndRegister(_)
+                | Self::AsrImmediate(_)
+                | Self::AsrRegister(_)
+                | Self::B(_)
+                | Self::BicRegister(_)
+                | Self::CmnRegister(_)
+                | Self::CmpImmediate(_)
+                | Self::CmpRegister(_)
+                | Self::EorRegister(_)
+                | Self::Ldm(_)
+                | Self::LdrImmediate(_)
+                | Self::LdrLiteral(_)
+                | Self::LdrRegister(_)
+                | Self::LdrbImmediate(_)
+                | Self::LdrbRegister(_)
+                | Self::LdrhImmediate(_)
+                | Self::LdrhRegister(_)
+                | Self::LdrsbRegister(_)
+                | Self::LdrshRegister(_)
+                | Self::LslImmediate(_)
+                | Self::LslRegister(_)
+                | Self::LsrImmediate(_)
+                | Self::LsrRegister(_)
+                | Self::MovImmediate(_)
+                | Self::MovRegister(_)
+                | Self::Mul(_)
+                | Self::MvnRegister(_)
+                | Self::Nop(_)
+                | Self::OrrRegister(_)
+                | Self::Pop(_)
+                | Self::Push(_)
+                | Self::Rev(_)
+                | Self::Rev16(_)
+                | Self::Revsh(_)
+                | Self::RorRegister(_)
+                | Self::RsbImmediate(_)
+                | Self::SbcRegister(_)
+                | Self::Sev(_)
+                | Self::Stm(_)
+                | Self::StrImmediate(_)
+                | Self::StrRegister(_)
+                | Self::StrbImmediate(_)
+                | Self::StrbRegister(_)
+                | Self::StrhImmediate(_)
+                | Self::StrhRegister(_)
+                | Self::SubImmediate(_)
+                | Self::SubRegister(_)
+                | Self::SubSpMinusImmediate(_)
+                | Self::Sxtb(_)
+                | Self::Sxth(_)
+                | Self::TstRegister(_)
+                | Self::Udf(_)
+                | Self::Uxtb(_)
+                | Self::Uxth(_)
+                | Self::Wfe(_)
+                | Self::Wfi(_)
+                | Self::Yield(_)
+        )
+    }
+}
+
+impl<'a> Ual<'a> {
+    /// Sets the size in bits of the encoding that the operation was decoded
+    /// from.
+    ///
+    /// 32 bit encodings of mnemonics that also have a 16 bit encoding are
+    /// rendered with a `.w` qualifier.
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets wether or not the operation is executed inside of an IT block.
+    ///
+    /// This changes the flag setting behaviour of some 16 bit encodings, see
+    /// [`SetFlags::InITBlock`](crate::arch::SetFlags::InITBlock).
+    pub fn in_it_block(mut self, in_it_block: bool) -> Self {
+        self.in_it_block = in_it_block;
+        self
+    }
+
+    /// Sets the condition that the operation executes under in an IT block,
+    /// which is rendered as a suffix of the mnemonic.
+    pub fn condition(mut self, condition: Condition) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    /// Always render the width qualifier, `.n` for 16 bit encodings and `.w`
+    /// for 32 bit encodings.
+    ///
+    /// Has no effect unless the [`size`](Ual::size) is known.
+    pub fn explicit_width(mut self, explicit_width: bool) -> Self {
+        self.explicit_width = explicit_width;
+        self
+    }
+
+    fn qualifier(&self) -> &'static str {
+        match (self.size, self.explicit_width) {
+            (Some(32), true) => ".w",
+            (Some(32), false) if self.operation.has_narrow_form() => ".w",
+            (Some(16), true) => ".n",
+            _ => "",
+        }
+    }
+
+    fn branch_target(&self, imm: u32) -> String {
+        format!("#{}", imm as i32)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn parts(&self) -> Parts {
+        use Operation as O;
+        let parts = |mnemonic: &str, s: bool, operands: String| Parts {
+            mnemonic: mnemonic.to_string(),
+            s,
+            condition: None,
+            operands,
+        };
+        let plain = |mnemonic: &str, operands: String| parts(mnemonic, false, operands);
+        let s = self.operation.s_suffix(self.in_it_block);
+        match self.operation {
+            O::AdcImmediate(el) => parts("adc", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::AdcRegister(el) => parts("adc", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::AddImmediate(el) => parts("add", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::AddRegister(el) => parts("add", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::AddSPImmediate(el) => parts("add", s, imm_op(&el.rd, &Register::SP, el.imm)),
+            O::AddSPRegister(el) => {
+                parts("add", s, reg_op(&el.rd, &Register::SP, &el.rm, &el.shift))
+            }
+            O::Adr(el) => plain("adr", format!("{}, {}", el.rd, signed(el.add, el.imm))),
+            O::AndImmediate(el) => parts("and", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::AndRegister(el) => parts("and", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::AsrImmediate(el) => parts("asr", s, format!("{}, {}, #{}", el.rd, el.rm, el.imm)),
+            O::AsrRegister(el) => parts("asr", s, format!("{}, {}, {}", el.rd, el.rn, el.rm)),
+            O::B(el) => Parts {
+                condition: Some(el.condition.clone()),
+                ..plain("b", self.branch_target(el.imm))
+            },
+            O::Bfc(el) => plain(
+                "bfc",
+                format!("{}, #{}, #{}", el.rd, el.lsb, width(el.lsb, el.msb)),
+            ),
+            O::Bfi(el) => plain(
+                "bfi",
+                format!(
+                    "{}, {}, #{}, #{}",
+                    el.rd,
+                    el.rn,
+                    el.lsb,
+                    width(el.lsb, el.msb)
+                ),
+            ),
+            O::BicImmediate(el) => parts("bic", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::BicRegister(el) => parts("bic", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Bkpt(el) => plain("bkpt", format!("#{}", el.imm)),
+            O::Bl(el) => plain("bl", self.branch_target(el.imm)),
+            O::Blx(el) => plain("blx", el.rm.to_string()),
+            O::Bx(el) => plain("bx", el.rm.to_string()),
+            O::Cbz(el) => plain(
+                match el.non {
+                    Some(true) => "cbnz",
+                    _ => "cbz",
+                },
+                format!("{}, {}", el.rn, self.branch_target(el.imm)),
+            ),
+            O::Cdp(el) => plain(
+                "cdp",
+                format!(
+                    "{}, #{}, c{}, c{}, c{}, #{}",
+                    el.coproc, el.opc1, el.crd, el.crn, el.crm, el.opc2
+                ),
+            ),
+            O::Clrex(_) => plain("clrex", String::new()),
+            O::Clz(el) => plain("clz", format!("{}, {}", el.rd, el.rm)),
+            O::CmnImmediate(el) => plain("cmn", format!("{}, #{}", el.rn, el.imm)),
+            O::CmnRegister(el) => plain("cmn", reg_op(&None, &el.rn, &el.rm, &el.shift)),
+            O::CmpImmediate(el) => plain("cmp", format!("{}, #{}", el.rn, el.imm)),
+            O::CmpRegister(el) => plain("cmp", reg_op(&None, &el.rn, &el.rm, &el.shift)),
+            O::Cps(el) => {
+                let mut operands = String::new();
+                if el.affect_pri {
+                    operands.push('i');
+                }
+                if el.affect_fault {
+                    operands.push('f');
+                }
+                plain(if el.enable { "cpsie" } else { "cpsid" }, operands)
+            }
+            O::Dbg(el) => plain("dbg", el.option.to_string()),
+            O::Dmb(el) => plain("dmb", el.option.unwrap_or(BarrierOption::Sy).to_string()),
+            O::Dsb(el) => plain("dsb", el.option.unwrap_or(BarrierOption::Sy).to_string()),
+            O::EorImmediate(el) => parts("eor", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::EorRegister(el) => parts("eor", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Isb(el) => plain("isb", el.option.unwrap_or(BarrierOption::Sy).to_string()),
+            O::It(el) => {
+                let first = el
+                    .conds
+                    .conditions
+                    .first()
+                    .cloned()
+                    .unwrap_or(Condition::None);
+                let mut mnemonic = "it".to_string();
+                for cond in el.conds.conditions.iter().skip(1) {
+                    mnemonic.push(if *cond == first { 't' } else { 'e' });
+                }
+                let operands = match first {
+                    Condition::None => "al".to_string(),
+                    cond => cond.to_string(),
+                };
+                plain(&mnemonic, operands)
+            }
+            O::Ldm(el) => plain("ldm", reg_list_op(&el.rn, flag(&el.w), &el.registers)),
+            O::Ldmdb(el) => plain("ldmdb", reg_list_op(&el.rn, flag(&el.w), &el.registers)),
+            O::LdrImmediate(el) => plain(
+                "ldr",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(&el.rn, el.imm, el.add, el.index, flag(&el.w))
+                ),
+            ),
+            O::LdrLiteral(el) => plain("ldr", format!("{}, {}", el.rt, literal(el.add, el.imm))),
+            O::LdrRegister(el) => plain(
+                "ldr",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::LdrbImmediate(el) => plain(
+                "ldrb",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(
+                        &el.rn,
+                        el.imm.unwrap_or(0),
+                        el.add.unwrap_or(true),
+                        el.index,
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::LdrbLiteral(el) => plain(
+                "ldrb",
+                format!("{}, {}", el.rt, literal(el.add.unwrap_or(true), el.imm)),
+            ),
+            O::LdrbRegister(el) => plain(
+                "ldrb",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Ldrbt(el) => plain(
+                "ldrbt",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::LdrdImmediate(el) => plain(
+                "ldrd",
+                format!(
+                    "{}, {}, {}",
+                    el.rt,
+                    el.rt2,
+                    address(
+                        &el.rn,
+                        el.imm,
+                        el.add.unwrap_or(true),
+                        el.index.unwrap_or(true),
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::LdrdLiteral(el) => plain(
+                "ldrd",
+                format!(
+                    "{}, {}, {}",
+                    el.rt,
+                    el.rt2,
+                    literal(el.add.unwrap_or(true), el.imm)
+                ),
+            ),
+            O::Ldrex(el) => plain(
+                "ldrex",
+                format!("{}, {}", el.rt, offset_address(&el.rn, Some(el.imm))),
+            ),
+            O::Ldrexb(el) => plain("ldrexb", format!("{}, [{}]", el.rt, el.rn)),
+            O::Ldrexh(el) => plain("ldrexh", format!("{}, [{}]", el.rt, el.rn)),
+            O::LdrhImmediate(el) => plain(
+                "ldrh",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(
+                        &el.rn,
+                        el.imm,
+                        el.add.unwrap_or(true),
+                        el.index.unwrap_or(true),
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::LdrhLiteral(el) => plain(
+                "ldrh",
+                format!("{}, {}", el.rt, literal(el.add.unwrap_or(true), el.imm)),
+            ),
+            O::LdrhRegister(el) => plain(
+                "ldrh",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Ldrht(el) => plain(
+                "ldrht",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::LdrsbImmediate(el) => plain(
+                "ldrsb",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(&el.rn, el.imm.unwrap_or(0), el.add, el.index, el.wback)
+                ),
+            ),
+            O::LdrsbLiteral(el) => {
+                plain("ldrsb", format!("{}, {}", el.rt, literal(el.add, el.imm)))
+            }
+            O::LdrsbRegister(el) => plain(
+                "ldrsb",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Ldrsbt(el) => plain(
+                "ldrsbt",
+                format!("{}, {}", el.rt, offset_address(&el.rn, Some(el.imm))),
+            ),
+            O::LdrshImmediate(el) => plain(
+                "ldrsh",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(&el.rn, el.imm.unwrap_or(0), el.add, el.index, el.wback)
+                ),
+            ),
+            O::LdrshLiteral(el) => {
+                plain("ldrsh", format!("{}, {}", el.rt, literal(el.add, el.imm)))
+            }
+            O::LdrshRegister(el) => plain(
+                "ldrsh",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Ldrsht(el) => plain(
+                "ldrsht",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::Ldrt(el) => plain(
+                "ldrt",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::LdcImmediate(el) => plain(
+                "ldc",
+                format!(
+                    "{}, c{}, {}",
+                    el.coproc,
+                    el.crd,
+                    address(&el.rn, el.imm.unwrap_or(0), el.add, el.index, el.w)
+                ),
+            ),
+            O::LdcLiteral(el) => plain(
+                "ldc",
+                format!("{}, c{}, {}", el.coproc, el.crd, literal(el.add, el.imm)),
+            ),
+            O::LslImmediate(el) => parts("lsl", s, format!("{}, {}, #{}", el.rd, el.rm, el.imm)),
+            O::LslRegister(el) => parts("lsl", s, format!("{}, {}, {}", el.rd, el.rn, el.rm)),
+            O::LsrImmediate(el) => parts("lsr", s, format!("{}, {}, #{}", el.rd, el.rm, el.imm)),
+            O::LsrRegister(el) => parts("lsr", s, format!("{}, {}, {}", el.rd, el.rn, el.rm)),
+            O::Mcrr(el) => plain(
+                "mcrr",
+                format!(
+                    "{}, #{}, {}, {}, c{}",
+                    el.coproc, el.opc1, el.rt, el.rt2, el.crm
+                ),
+            ),
+            O::Mcr(el) => plain(
+                "mcr",
+                coproc_transfer(
+                    &el.coproc.to_string(),
+                    el.opc1,
+                    &el.rt,
+                    el.crn,
+                    el.crm,
+                    el.opc2,
+                ),
+            ),
+            O::Mla(el) => plain("mla", format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra)),
+            O::Mls(el) => plain("mls", format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra)),
+            O::MovImmediate(el) => parts("mov", s, format!("{}, #{}", el.rd, el.imm)),
+            O::MovRegister(el) => parts("mov", s, format!("{}, {}", el.rd, el.rm)),
+            O::Movt(el) => plain("movt", format!("{}, #{}", el.rd, el.imm)),
+            O::Mrrc(el) => plain(
+                "mrrc",
+                format!(
+                    "{}, #{}, {}, {}, c{}",
+                    el.coproc, el.opc1, el.rt, el.rt2, el.crm
+                ),
+            ),
+            O::Mrc(el) => plain(
+                "mrc",
+                coproc_transfer(
+                    &el.coproc.to_string(),
+                    el.opc1,
+                    &el.rt,
+                    el.crn,
+                    el.crm,
+                    el.opc2,
+                ),
+            ),
+            O::Mrs(el) => plain("mrs", format!("{}, {}", el.rd, el.sysm)),
+            O::Msr(el) => {
+                let suffix = match el.sysm.is_xpsr() {
+                    true => el.mask.to_string(),
+                    false => String::new(),
+                };
+                plain("msr", format!("{}{suffix}, {}", el.sysm, el.rn))
+            }
+            O::Mul(el) => parts("mul", s, three(&el.rd, &el.rn, &el.rm)),
+            O::MvnImmediate(el) => parts("mvn", s, format!("{}, #{}", el.rd, el.imm)),
+            O::MvnRegister(el) => parts(
+                "mvn",
+                s,
+                shifted(format!("{}, {}", el.rd, el.rm), &el.shift),
+            ),
+            O::Nop(_) => plain("nop", String::new()),
+            O::OrnImmediate(el) => parts("orn", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::OrnRegister(el) => parts("orn", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::OrrImmediate(el) => parts("orr", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::OrrRegister(el) => parts("orr", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Pkh(el) => plain(
+                if el.tb { "pkhtb" } else { "pkhbt" },
+                reg_op(&el.rd, &el.rn, &el.rm, &el.shift),
+            ),
+            O::PldImmediate(el) => plain(
+                "pld",
+                address(&el.rn, el.imm, el.add.unwrap_or(true), true, false),
+            ),
+            O::PldLiteral(el) => plain("pld", literal(el.add.unwrap_or(true), el.imm)),
+            O::PldRegister(el) => plain("pld", reg_address(&el.rn, &el.rm, &el.shift)),
+            O::PliImmediate(el) => plain("pli", match el.rn {
+                Some(rn) => address(&rn, el.imm, el.add.unwrap_or(true), true, false),
+                None => literal(el.add.unwrap_or(true), el.imm),
+            }),
+            O::PliRegister(el) => plain("pli", reg_address(&el.rn, &el.rm, &el.shift)),
+            O::Pop(el) => plain("pop", el.registers.to_string()),
+            O::Push(el) => plain("push", el.registers.to_string()),
+            O::Qadd(el) => plain("qadd", three(&el.rd, &el.rm, &el.rn)),
+            O::Qadd16(el) => plain("qadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Qadd8(el) => plain("qadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Qasx(el) => plain("qasx", three(&el.rd, &el.rn, &el.rm)),
+            O::Qdadd(el) => plain("qdadd", three(&el.rd, &el.rm, &el.rn)),
+            O::Qdsub(el) => plain("qdsub", three(&el.rd, &el.rm, &el.rn)),
+            O::Qsax(el) => plain("qsax", three(&el.rd, &el.rn, &el.rm)),
+            O::Qsub(el) => plain("qsub", three(&el.rd, &el.rm, &el.rn)),
+            O::Qsub16(el) => plain("qsub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Qsub8(el) => plain("qsub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Rbit(el) => plain("rbit", format!("{}, {}", el.rd, el.rm)),
+            O::Rev(el) => plain("rev", format!("{}, {}", el.rd, el.rm)),
+            O::Rev16(el) => plain("rev16", format!("{}, {}", el.rd, el.rm)),
+            O::Revsh(el) => plain("revsh", format!("{}, {}", el.rd, el.rm)),
+            O::RorImmediate(el) => parts("ror", s, format!("{}, {}, #{}", el.rd, el.rm, el.imm)),
+            O::RorRegister(el) => parts("ror", s, format!("{}, {}, {}", el.rd, el.rn, el.rm)),
+            O::Rrx(el) => parts("rrx", s, format!("{}, {}", el.rd, el.rm)),
+            O::RsbImmediate(el) => parts("rsb", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::RsbRegister(el) => parts("rsb", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Sadd16(el) => plain("sadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Sadd8(el) => plain("sadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Sasx(el) => plain("sasx", three(&el.rd, &el.rn, &el.rm)),
+            O::SbcImmediate(el) => parts("sbc", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::SbcRegister(el) => parts("sbc", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Sbfx(el) => plain(
+                "sbfx",
+                format!("{}, {}, #{}, #{}", el.rd, el.rn, el.lsb, el.width),
+            ),
+            O::Sdiv(el) => plain("sdiv", three(&el.rd, &el.rn, &el.rm)),
+            O::Sel(el) => plain("sel", three(&el.rd, &el.rn, &el.rm)),
+            O::Sev(_) => plain("sev", String::new()),
+            O::Svc(el) => plain("svc", format!("#{}", el.imm)),
+            O::Shadd16(el) => plain("shadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Shadd8(el) => plain("shadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Shasx(el) => plain("shasx", three(&el.rd, &el.rn, &el.rm)),
+            O::Shsax(el) => plain("shsax", three(&el.rd, &el.rn, &el.rm)),
+            O::Shsub16(el) => plain("shsub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Shsub8(el) => plain("shsub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Smla(el) => plain(
+                &format!("smla{}{}", half(el.n_high), half(el.m_high)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smlad(el) => plain(
+                &format!("smlad{}", exchange(&el.x)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smlal(el) => plain("smlal", long(&el.rdlo, &el.rdhi, &el.rn, &el.rm)),
+            O::SmlalSelective(el) => plain(
+                &format!("smlal{}{}", half(el.n_high), half(el.m_high)),
+                long(&el.rdlo, &el.rdhi, &el.rn, &el.rm),
+            ),
+            O::Smlald(el) => plain(
+                &format!("smlald{}", exchange(&el.x)),
+                long(&el.rdlo, &el.rdhi, &el.rn, &el.rm),
+            ),
+            O::Smlaw(el) => plain(
+                &format!("smlaw{}", half(el.m_high)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smlsd(el) => plain(
+                &format!("smlsd{}", exchange(&el.m_swap)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smlsld(el) => plain(
+                &format!("smlsld{}", exchange(&el.m_swap)),
+                long(&el.rdlo, &el.rdhi, &el.rn, &el.rm),
+            ),
+            O::Smmla(el) => plain(
+                &format!("smmla{}", round(&el.round)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smmls(el) => plain(
+                &format!("smmls{}", round(&el.round)),
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Smmul(el) => plain(
+                &format!("smmul{}", round(&el.round)),
+                format!("{}, {}, {}", el.rd, el.rn, el.rm),
+            ),
+            O::Smuad(el) => plain(
+                &format!("smuad{}", exchange(&el.m_swap)),
+                format!("{}, {}, {}", el.rd, el.rn, el.rm),
+            ),
+            O::Smul(el) => plain(
+                &format!("smul{}{}", half(el.n_high), half(el.m_high)),
+                three(&el.rd, &el.rn, &el.rm),
+            ),
+            O::Smull(el) => plain("smull", long(&el.rdlo, &el.rdhi, &el.rn, &el.rm)),
+            O::Smulw(el) => plain(
+                &format!("smulw{}", half(el.m_high)),
+                three(&el.rd, &el.rn, &el.rm),
+            ),
+            O::Smusd(el) => plain(
+                &format!("smusd{}", exchange(&el.m_swap)),
+                three(&el.rd, &el.rn, &el.rm),
+            ),
+            O::Ssat(el) => plain(
+                "ssat",
+                shifted(format!("{}, #{}, {}", el.rd, el.imm, el.rn), &el.shift),
+            ),
+            O::Ssat16(el) => plain("ssat16", format!("{}, #{}, {}", el.rd, el.imm, el.rn)),
+            O::Ssax(el) => plain("ssax", three(&el.rd, &el.rn, &el.rm)),
+            O::Ssub16(el) => plain("ssub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Ssub8(el) => plain("ssub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Stm(el) => plain("stm", reg_list_op(&el.rn, flag(&el.w), &el.registers)),
+            O::Stmdb(el) => plain("stmdb", reg_list_op(&el.rn, flag(&el.w), &el.registers)),
+            O::StrImmediate(el) => plain(
+                "str",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(
+                        &el.rn,
+                        el.imm,
+                        el.add,
+                        el.index.unwrap_or(true),
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::StrRegister(el) => plain(
+                "str",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::StrbImmediate(el) => plain(
+                "strb",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(
+                        &el.rn,
+                        el.imm,
+                        el.add,
+                        el.index.unwrap_or(true),
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::StrbRegister(el) => plain(
+                "strb",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Strbt(el) => plain(
+                "strbt",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::StrdImmediate(el) => plain(
+                "strd",
+                format!(
+                    "{}, {}, {}",
+                    el.rt,
+                    el.rt2,
+                    address(
+                        &el.rn,
+                        el.imm.unwrap_or(0),
+                        el.add,
+                        el.index.unwrap_or(true),
+                        flag(&el.w)
+                    )
+                ),
+            ),
+            O::Strex(el) => plain(
+                "strex",
+                format!("{}, {}, {}", el.rd, el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::Strexb(el) => plain("strexb", format!("{}, {}, [{}]", el.rd, el.rt, el.rn)),
+            O::Strexh(el) => plain("strexh", format!("{}, {}, [{}]", el.rd, el.rt, el.rn)),
+            O::StrhImmediate(el) => plain(
+                "strh",
+                format!(
+                    "{}, {}",
+                    el.rt,
+                    address(&el.rn, el.imm.unwrap_or(0), el.add, el.index, el.w)
+                ),
+            ),
+            O::StrhRegister(el) => plain(
+                "strh",
+                format!("{}, {}", el.rt, reg_address(&el.rn, &el.rm, &el.shift)),
+            ),
+            O::Strht(el) => plain(
+                "strht",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::Strt(el) => plain(
+                "strt",
+                format!("{}, {}", el.rt, offset_address(&el.rn, el.imm)),
+            ),
+            O::SubImmediate(el) => parts("sub", s, imm_op(&el.rd, &el.rn, el.imm)),
+            O::SubRegister(el) => parts("sub", s, reg_op(&el.rd, &el.rn, &el.rm, &el.shift)),
+            O::Stc(el) => plain(
+                "stc",
+                format!(
+                    "{}, c{}, {}",
+                    el.coproc,
+                    el.crd,
+                    address(&el.rn, el.imm.unwrap_or(0), el.add, el.index, el.w)
+                ),
+            ),
+            O::SubSpMinusImmediate(el) => parts("sub", s, imm_op(&el.rd, &Register::SP, el.imm)),
+            O::SubSpMinusRegister(el) => {
+                parts("sub", s, reg_op(&el.rd, &Register::SP, &el.rm, &el.shift))
+            }
+            O::Sxtab(el) => plain(
+                "sxtab",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Sxtab16(el) => plain(
+                "sxtab16",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Sxtah(el) => plain(
+                "sxtah",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Sxtb(el) => plain(
+                "sxtb",
+                rotated(format!("{}, {}", el.rd, el.rm), &el.rotation),
+            ),
+            O::Sxtb16(el) => plain("sxtb16", rotated(two(&el.rd, &el.rm), &el.rotation)),
+            O::Sxth(el) => plain(
+                "sxth",
+                rotated(format!("{}, {}", el.rd, el.rm), &el.rotation),
+            ),
+            O::Tb(el) => match el.is_tbh {
+                Some(true) => plain("tbh", format!("[{}, {}, lsl #1]", el.rn, el.rm)),
+                _ => plain("tbb", format!("[{}, {}]", el.rn, el.rm)),
+            },
+            O::TeqImmediate(el) => plain("teq", format!("{}, #{}", el.rn, el.imm)),
+            O::TeqRegister(el) => plain("teq", reg_op(&None, &el.rn, &el.rm, &el.shift)),
+            O::TstImmediate(el) => plain("tst", format!("{}, #{}", el.rn, el.imm)),
+            O::TstRegister(el) => plain("tst", reg_op(&None, &el.rn, &el.rm, &el.shift)),
+            O::Uadd16(el) => plain("uadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Uadd8(el) => plain("uadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Uasx(el) => plain("uasx", three(&el.rd, &el.rn, &el.rm)),
+            O::Ubfx(el) => plain(
+                "ubfx",
+                format!("{}, {}, #{}, #{}", el.rd, el.rn, el.lsb, el.width),
+            ),
+            O::Udf(el) => plain("udf", format!("#{}", el.imm)),
+            O::Udiv(el) => plain("udiv", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhadd16(el) => plain("uhadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhadd8(el) => plain("uhadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhasx(el) => plain("uhasx", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhsax(el) => plain("uhsax", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhsub16(el) => plain("uhsub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Uhsub8(el) => plain("uhsub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Umaal(el) => plain("umaal", long(&el.rdlo, &el.rdhi, &el.rn, &el.rm)),
+            O::Umlal(el) => plain("umlal", long(&el.rdlo, &el.rdhi, &el.rn, &el.rm)),
+            O::Umull(el) => plain("umull", long(&el.rdlo, &el.rdhi, &el.rn, &el.rm)),
+            O::Uqadd16(el) => plain("uqadd16", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqadd8(el) => plain("uqadd8", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqasx(el) => plain("uqasx", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqsax(el) => plain("uqsax", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqsub16(el) => plain("uqsub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqsub8(el) => plain("uqsub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Uqsad8(el) => plain("uqsad8", three(&el.rd, &el.rn, &el.rm)),
+            O::Usada8(el) => plain(
+                "usada8",
+                format!("{}, {}, {}, {}", el.rd, el.rn, el.rm, el.ra),
+            ),
+            O::Usad8(el) => plain("usad8", three(&el.rd, &el.rn, &el.rm)),
+            O::Usat(el) => plain(
+                "usat",
+                shifted(format!("{}, #{}, {}", el.rd, el.imm, el.rn), &el.shift),
+            ),
+            O::Usat16(el) => plain("usat16", format!("{}, #{}, {}", el.rd, el.imm, el.rn)),
+            O::Usax(el) => plain("usax", three(&el.rd, &el.rn, &el.rm)),
+            O::Usub16(el) => plain("usub16", three(&el.rd, &el.rn, &el.rm)),
+            O::Usub8(el) => plain("usub8", three(&el.rd, &el.rn, &el.rm)),
+            O::Uxtab(el) => plain(
+                "uxtab",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Uxtab16(el) => plain(
+                "uxtab16",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Uxtah(el) => plain(
+                "uxtah",
+                rotated(three(&el.rd, &el.rn, &el.rm), &el.rotation),
+            ),
+            O::Uxtb(el) => plain(
+                "uxtb",
+                rotated(format!("{}, {}", el.rd, el.rm), &el.rotation),
+            ),
+            O::Uxtb16(el) => plain("uxtb16", rotated(two(&el.rd, &el.rm), &el.rotation)),
+            O::Uxth(el) => plain(
+                "uxth",
+                rotated(format!("{}, {}", el.rd, el.rm), &el.rotation),
+            ),
+            O::VabsF32(el) => plain("vabs.f32", format!("{}, {}", el.sd, el.sm)),
+            O::VabsF64(el) => plain("vabs.f64", format!("{}, {}", el.dd, el.dm)),
+            O::VaddF32(el) => plain("vadd.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VaddF64(el) => plain("vadd.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VcmpF32(el) => plain(
+                &format!("vcmp{}.f32", exception(&el.e)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VcmpF64(el) => plain(
+                &format!("vcmp{}.f64", exception(&el.e)),
+                format!("{}, {}", el.dd, el.dm),
+            ),
+            O::VcmpZeroF32(el) => plain(
+                &format!("vcmp{}.f32", exception(&el.e)),
+                format!("{}, #0.0", el.sd),
+            ),
+            O::VcmpZeroF64(el) => plain(
+                &format!("vcmp{}.f64", exception(&el.e)),
+                format!("{}, #0.0", el.dd),
+            ),
+            O::VcvtF32F16(el) => plain(
+                &format!("vcvt{}.f32.f16", half(el.top)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VcvtF16F32(el) => plain(
+                &format!("vcvt{}.f16.f32", half(el.top)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VcvtF64F16(el) => plain(
+                &format!("vcvt{}.f64.f16", half(el.top)),
+                format!("{}, {}", el.dd, el.sm),
+            ),
+            O::VcvtF16F64(el) => plain(
+                &format!("vcvt{}.f16.f64", half(el.top)),
+                format!("{}, {}", el.sd, el.dm),
+            ),
+            O::VcvtF64F32(el) => plain("vcvt.f64.f32", format!("{}, {}", el.dd, el.sm)),
+            O::VcvtF32F64(el) => plain("vcvt.f32.f64", format!("{}, {}", el.sd, el.dm)),
+            O::VcvtFromIntF32(el) => plain(
+                &format!("vcvt.f32.{}32", sign(el.signed)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VcvtFromIntF64(el) => plain(
+                &format!("vcvt.f64.{}32", sign(el.signed)),
+                format!("{}, {}", el.dd, el.sm),
+            ),
+            O::VcvtToIntF32(el) => plain(
+                &format!("{}.{}32.f32", to_int(&el.r), sign(el.signed)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VcvtToIntF64(el) => plain(
+                &format!("{}.{}32.f64", to_int(&el.r), sign(el.signed)),
+                format!("{}, {}", el.sd, el.dm),
+            ),
+            O::VcvtFixedF32(el) => plain(
+                &fixed("f32", el.to_fixed, el.signed, el.size),
+                format!("{}, {}, #{}", el.sd, el.sd, el.fbits),
+            ),
+            O::VcvtFixedF64(el) => plain(
+                &fixed("f64", el.to_fixed, el.signed, el.size),
+                format!("{}, {}, #{}", el.dd, el.dd, el.fbits),
+            ),
+            O::VdivF32(el) => plain("vdiv.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VdivF64(el) => plain("vdiv.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VfmaF32(el) => plain("vfma.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VfmaF64(el) => plain("vfma.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VfmsF32(el) => plain("vfms.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VfmsF64(el) => plain("vfms.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VfnmaF32(el) => plain("vfnma.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VfnmaF64(el) => plain("vfnma.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VfnmsF32(el) => plain("vfnms.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VfnmsF64(el) => plain("vfnms.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VldmF32(el) => plain(
+                multiple("vldm", el.add),
+                fp_list_op(&el.rn, el.w, &el.registers),
+            ),
+            O::VldmF64(el) => plain(
+                multiple("vldm", el.add),
+                fp_list_op(&el.rn, el.w, &el.registers),
+            ),
+            O::VldrF32(el) => plain(
+                "vldr",
+                format!(
+                    "{}, {}",
+                    el.sd,
+                    address(&el.rn, el.imm, el.add, true, false)
+                ),
+            ),
+            O::VldrF64(el) => plain(
+                "vldr",
+                format!(
+                    "{}, {}",
+                    el.dd,
+                    address(&el.rn, el.imm, el.add, true, false)
+                ),
+            ),
+            O::VmaxnmF32(el) => plain("vmaxnm.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VmaxnmF64(el) => plain("vmaxnm.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VminnmF32(el) => plain("vminnm.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VminnmF64(el) => plain("vminnm.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VmlaF32(el) => plain("vmla.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VmlaF64(el) => plain("vmla.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VmlsF32(el) => plain("vmls.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VmlsF64(el) => plain("vmls.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VmovImmediateF32(el) => plain(
+                "vmov.f32",
+                format!("{}, #{:?}", el.sd, f32::from_bits(el.imm)),
+            ),
+            O::VmovImmediateF64(el) => plain(
+                "vmov.f64",
+                format!("{}, #{:?}", el.dd, f64::from_bits(el.imm)),
+            ),
+            O::VmovRegisterF32(el) => plain("vmov.f32", format!("{}, {}", el.sd, el.sm)),
+            O::VmovRegisterF64(el) => plain("vmov.f64", format!("{}, {}", el.dd, el.dm)),
+            O::VmovCoreF32(el) => plain("vmov", match el.to_core {
+                true => format!("{}, {}", el.rt, el.sn),
+                false => format!("{}, {}", el.sn, el.rt),
+            }),
+            O::VmovCore2F32(el) => plain("vmov", match el.to_core {
+                true => format!("{}, {}, {}, {}", el.rt, el.rt2, el.sm, el.sm1),
+                false => format!("{}, {}, {}, {}", el.sm, el.sm1, el.rt, el.rt2),
+            }),
+            O::VmovCoreF64(el) => plain("vmov", match el.to_core {
+                true => format!("{}, {}, {}", el.rt, el.rt2, el.dm),
+                false => format!("{}, {}, {}", el.dm, el.rt, el.rt2),
+            }),
+            O::VmovCoreScalar(el) => plain("vmov.32", match el.to_core {
+                true => format!("{}, {}[{}]", el.rt, el.dd, el.index),
+                false => format!("{}[{}], {}", el.dd, el.index, el.rt),
+            }),
+            O::Vmrs(el) => plain("vmrs", match el.rt {
+                Register::PC => "apsr_nzcv, fpscr".to_string(),
+                rt => format!("{rt}, fpscr"),
+            }),
+            O::Vmsr(el) => plain("vmsr", format!("fpscr, {}", el.rt)),
+            O::VmulF32(el) => plain("vmul.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VmulF64(el) => plain("vmul.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VnegF32(el) => plain("vneg.f32", format!("{}, {}", el.sd, el.sm)),
+            O::VnegF64(el) => plain("vneg.f64", format!("{}, {}", el.dd, el.dm)),
+            O::VnmlaF32(el) => plain("vnmla.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VnmlaF64(el) => plain("vnmla.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VnmlsF32(el) => plain("vnmls.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VnmlsF64(el) => plain("vnmls.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VnmulF32(el) => plain("vnmul.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VnmulF64(el) => plain("vnmul.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::VpopF32(el) => plain("vpop", fp_list(&el.registers)),
+            O::VpopF64(el) => plain("vpop", fp_list(&el.registers)),
+            O::VpushF32(el) => plain("vpush", fp_list(&el.registers)),
+            O::VpushF64(el) => plain("vpush", fp_list(&el.registers)),
+            O::VrintF32(el) => plain(
+                &format!("vrint{}.f32", rounding(&el.r)),
+                format!("{}, {}", el.sd, el.sm),
+            ),
+            O::VrintF64(el) => plain(
+                &format!("vrint{}.f64", rounding(&el.r)),
+                format!("{}, {}", el.dd, el.dm),
+            ),
+            O::VrintxF32(el) => plain("vrintx.f32", format!("{}, {}", el.sd, el.sm)),
+            O::VrintxF64(el) => plain("vrintx.f64", format!("{}, {}", el.dd, el.dm)),
+            O::VselF32(el) => plain(
+                &format!("vsel{}.f32", el.cond),
+                fp_three(&el.sd, &el.sn, &el.sm),
+            ),
+            O::VselF64(el) => plain(
+                &format!("vsel{}.f64", el.cond),
+                fp_three(&el.dd, &el.dn, &el.dm),
+            ),
+            O::VsqrtF32(el) => plain("vsqrt.f32", format!("{}, {}", el.sd, el.sm)),
+            O::VsqrtF64(el) => plain("vsqrt.f64", format!("{}, {}", el.dd, el.dm)),
+            O::VstmF32(el) => plain(
+                multiple("vstm", el.add),
+                fp_list_op(&el.rn, el.w, &el.registers),
+            ),
+            O::VstmF64(el) => plain(
+                multiple("vstm", el.add),
+                fp_list_op(&el.rn, el.w, &el.registers),
+            ),
+            O::VstrF32(el) => plain(
+                "vstr",
+                format!(
+                    "{}, {}",
+                    el.sd,
+                    address(&el.rn, el.imm, el.add, true, false)
+                ),
+            ),
+            O::VstrF64(el) => plain(
+                "vstr",
+                format!(
+                    "{}, {}",
+                    el.dd,
+                    address(&el.rn, el.imm, el.add, true, false)
+                ),
+            ),
+            O::VsubF32(el) => plain("vsub.f32", fp_three(&el.sd, &el.sn, &el.sm)),
+            O::VsubF64(el) => plain("vsub.f64", fp_three(&el.dd, &el.dn, &el.dm)),
+            O::Wfe(_) => plain("wfe", String::new()),
+            O::Wfi(_) => plain("wfi", String::new()),
+            O::Yield(_) => plain("yield", String::new()),
+        }
+    }
+}
+
+impl Display for Ual<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Parts {
+            mnemonic,
+            s,
+            condition,
+            operands,
+        } = self.parts();
+        write!(f, "{mnemonic}")?;
+        if s {
+            write!(f, "s")?;
+        }
+        match condition {
+            Some(condition) if condition != Condition::None => write!(f, "{condition}")?,
+            _ => write!(f, "{}", self.condition)?,
+        }
+        write!(f, "{}", self.qualifier())?;
+        if !operands.is_empty() {
+            write!(f, " {operands}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.ual())
+    }
+}
+
+fn flag(value: &Option<bool>) -> bool {
+    value.unwrap_or(false)
+}
+
+/// `<Rd>, <Rm>` where `Rd` defaults to `Rm`.
+fn two(rd: &Option<Register>, rm: &Register) -> String {
+    format!("{}, {rm}", rd.unwrap_or(*rm))
+}
+
+/// `<Rd>, <Rn>, <Rm>` where `Rd` defaults to `Rn`.
+fn three(rd: &Option<Register>, rn: &Register, rm: &Register) -> String {
+    format!("{}, {rn}, {rm}", rd.unwrap_or(*rn))
+}
+
+/// `<RdLo>, <RdHi>, <Rn>, <Rm>`.
+fn long(rdlo: &Register, rdhi: &Register, rn: &Register, rm: &Register) -> String {
+    format!("{rdlo}, {rdhi}, {rn}, {rm}")
+}
+
+/// `{<Rd>,} <Rn>, #<imm>`.
+fn imm_op(rd: &Option<Register>, rn: &Register, imm: u32) -> String {
+    match rd {
+        Some(rd) => format!("{rd}, {rn}, #{imm}"),
+        None => format!("{rn}, #{imm}"),
+    }
+}
+
+/// `{<Rd>,} <Rn>, <Rm> {, <shift>}`.
+fn reg_op(rd: &Option<Register>, rn: &Register, rm: &Register, shift: &Option<ImmShift>) -> String {
+    let base = match rd {
+        Some(rd) => format!("{rd}, {rn}, {rm}"),
+        None => format!("{rn}, {rm}"),
+    };
+    shifted(base, shift)
+}
+
+/// `<Rn>{!}, <registers>`.
+fn reg_list_op(rn: &Register, wback: bool, registers: &crate::arch::RegisterList) -> String {
+    format!("{rn}{}, {registers}", if wback { "!" } else { "" })
+}
+
+/// Appends the shift to the operands, omitting the no-op `lsl #0`.
+fn shifted(base: String, shift: &Option<ImmShift>) -> String {
+    match shift {
+        Some(ImmShift {
+            shift_t: crate::arch::Shift::Lsl,
+            shift_n: 0,
+        })
+        | None => base,
+        Some(shift) => format!("{base}, {shift}"),
+    }
+}
+
+/// Appends the rotation to the operands, omitting the no-op `ror #0`.
+fn rotated(base: String, rotation: &Option<u32>) -> String {
+    match rotation {
+        Some(0) | None => base,
+        Some(rotation) => format!("{base}, ror #{rotation}"),
+    }
+}
+
+fn signed(add: bool, imm: u32) -> String {
+    format!("#{}{imm}", if add { "" } else { "-" })
+}
+
+/// Renders the immediate addressing modes.
+///
+/// - offset: `[<Rn>{, #+/-<imm>}]`
+/// - pre-indexed: `[<Rn>, #+/-<imm>]!`
+/// - post-indexed: `[<Rn>], #+/-<imm>`
+fn address(rn: &Register, imm: u32, add: bool, index: bool, wback: bool) -> String {
+    match (index, wback) {
+        (true, false) if imm == 0 && add => format!("[{rn}]"),
+        (true, false) => format!("[{rn}, {}]", signed(add, imm)),
+        (true, true) => format!("[{rn}, {}]!", signed(add, imm)),
+        (false, _) => format!("[{rn}], {}", signed(add, imm)),
+    }
+}
+
+/// `[<Rn>{, #<imm>}]`.
+fn offset_address(rn: &Register, imm: Option<u32>) -> String {
+    address(rn, imm.unwrap_or(0), true, true, false)
+}
+
+/// `[pc, #+/-<imm>]`.
+fn literal(add: bool, imm: u32) -> String {
+    format!("[pc, {}]", signed(add, imm))
+}
+
+/// `[<Rn>, <Rm>{, lsl #<imm>}]`.
+fn reg_address(rn: &Register, rm: &Register, shift: &Option<ImmShift>) -> String {
+    format!("[{}]", shifted(format!("{rn}, {rm}"), shift))
+}
+
+/// `<coproc>, #<opc1>, <Rt>, <CRn>, <CRm>{, #<opc2>}`.
+fn coproc_transfer(
+    coproc: &str,
+    opc1: u8,
+    rt: &Register,
+    crn: u8,
+    crm: u8,
+    opc2: Option<u8>,
+) -> String {
+    let base = format!("{coproc}, #{opc1}, {rt}, c{crn}, c{crm}");
+    match opc2 {
+        Some(opc2) if opc2 != 0 => format!("{base}, #{opc2}"),
+        _ => base,
+    }
+}
+
+fn width(lsb: u32, msb: u32) -> u32 {
+    (msb + 1).saturating_sub(lsb)
+}
+
+fn half(high: bool) -> &'static str {
+    if high {
+        "t"
+    } else {
+        "b"
+    }
+}
+
+fn exchange(x: &Option<bool>) -> &'static str {
+    if flag(x) {
+        "x"
+    } else {
+        ""
+    }
+}
+
+fn round(r: &Option<bool>) -> &'static str {
+    if flag(r) {
+        "r"
+    } else {
+        ""
+    }
+}
+
+/// `<Vd>, <Vn>, <Vm>` for floating point registers.
+fn fp_three<T: Display>(d: &T, n: &T, m: &T) -> String {
+    format!("{d}, {n}, {m}")
+}
+
+/// `{<registers>}` for floating point registers.
+fn fp_list<T: Display>(registers: &[T]) -> String {
+    let registers: Vec<String> = registers.iter().map(|reg| reg.to_string()).collect();
+    format!("{{{}}}", registers.join(", "))
+}
+
+/// `<Rn>{!}, <registers>` for floating point registers.
+fn fp_list_op<T: Display>(rn: &Register, wback: bool, registers: &[T]) -> String {
+    format!(
+        "{rn}{}, {}",
+        if wback { "!" } else { "" },
+        fp_list(registers)
+    )
+}
+
+/// Selects the increment after or decrement before mnemonic.
+fn multiple(mnemonic: &str, add: bool) -> &str {
+    match (mnemonic, add) {
+        (mnemonic, true) => mnemonic,
+        ("vldm", false) => "vldmdb",
+        (_, false) => "vstmdb",
+    }
+}
+
+fn sign(signed: bool) -> &'static str {
+    if signed {
+        "s"
+    } else {
+        "u"
+    }
+}
+
+fn exception(e: &Option<bool>) -> &'static str {
+    if flag(e) {
+        "e"
+    } else {
+        ""
+    }
+}
+
+/// The rounding mode suffix, no rounding mode denotes the `FPSCR` rounding
+/// mode.
+fn rounding(r: &Option<RoundingMode>) -> String {
+    match r {
+        Some(r) => r.to_string(),
+        None => "r".to_string(),
+    }
+}
+
+/// `vcvt` rounds towards zero while `vcvtr` uses the `FPSCR` rounding mode.
+fn to_int(r: &Option<RoundingMode>) -> String {
+    match r {
+        Some(RoundingMode::Zero) => "vcvt".to_string(),
+        r => format!("vcvt{}", rounding(r)),
+    }
+}
+
+/// `vcvt.<dt>.<fixed>` or `vcvt.<fixed>.<dt>`.
+fn fixed(dt: &str, to_fixed: bool, signed: bool, size: u8) -> String {
+    let fixed = format!("{}{size}", sign(signed));
+    match to_fixed {
+        true => format!("vcvt.{fixed}.{dt}"),
+        false => format!("vcvt.{dt}.{fixed}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    fn decode(bin: &[u8]) -> (usize, Operation) {
+        let mut stream = PeekableBuffer::from(bin.iter().cloned().rev());
+        Operation::parse(&mut stream).expect("Parser broken")
+    }
+
+    #[test]
+    fn test_display_flags_and_qualifiers() {
+        // lsls r3, r0
+        let (size, instr) = decode(&[0b01000000u8, 0b10000011u8]);
+        assert_eq!(format!("{instr}"), "lsls r3, r3, r0");
+        assert_eq!(format!("{}", instr.ual().size(size)), "lsls r3, r3, r0");
+        assert_eq!(
+            format!("{}", instr.ual().size(size).explicit_width(true)),
+            "lsls.n r3, r3, r0"
+        );
+        assert_eq!(
+            format!("{}", instr.ual().size(size).in_it_block(true)),
+            "lsl r3, r3, r0"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                instr
+                    .ual()
+                    .size(size)
+                    .in_it_block(true)
+                    .condition(Condition::Ne)
+                    .explicit_width(true)
+            ),
+            "lslne.n r3, r3, r0"
+        );
+    }
+
+    #[test]
+    fn test_display_branch() {
+        let mut bin = vec![];
+        bin.extend([0b11110100u8, 0b11001100u8].into_iter().rev());
+        bin.extend([0b10101000u8, 0b00000011u8].into_iter().rev());
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let (size, instr) = Operation::parse(&mut stream).expect("Parser broken");
+        assert_eq!(format!("{}", instr.ual().size(size)), "bcc.w #-212986");
+    }
+
+    #[test]
+    fn test_display_shift() {
+        let target: Operation = operation::AddRegister::builder()
+            .set_s(Some(SetFlags::Literal(true)))
+            .set_rd(Some(Register::R0))
+            .set_rn(Register::R1)
+            .set_rm(Register::R2)
+            .set_shift(Some(ImmShift::from((Shift::Lsl, 2))))
+            .complete()
+            .into();
+        assert_eq!(format!("{target}"), "adds r0, r1, r2, lsl #2");
+    }
+
+    #[test]
+    fn test_display_register_list() {
+        let target: Operation = operation::Push::builder()
+            .set_registers(RegisterList::try_from(0b0100_0000_1111_0000u16).unwrap())
+            .complete()
+            .into();
+        assert_eq!(format!("{target}"), "push {r4, r5, r6, r7, lr}");
+    }
+
+    #[test]
+    fn test_display_addressing_modes() {
+        let offset: Operation = operation::LdrImmediate::builder()
+            .set_w(Some(false))
+            .set_add(true)
+            .set_index(true)
+            .set_rt(Register::R0)
+            .set_rn(Register::R1)
+            .set_imm(4)
+            .complete()
+            .into();
+        assert_eq!(format!("{offset}"), "ldr r0, [r1, #4]");
+
+        let pre: Operation = operation::LdrImmediate::builder()
+            .set_w(Some(true))
+            .set_add(false)
+            .set_index(true)
+            .set_rt(Register::R0)
+            .set_rn(Register::R1)
+            .set_imm(4)
+            .complete()
+            .into();
+        assert_eq!(format!("{pre}"), "ldr r0, [r1, #-4]!");
+
+        let post: Operation = operation::LdrImmediate::builder()
+            .set_w(Some(true))
+            .set_add(true)
+            .set_index(false)
+            .set_rt(Register::R0)
+            .set_rn(Register::SP)
+            .set_imm(8)
+            .complete()
+            .into();
+        assert_eq!(format!("{post}"), "ldr r0, [sp], #8");
+
+        let register: Operation = operation::StrRegister::builder()
+            .set_rt(Register::R0)
+            .set_rn(Register::R1)
+            .set_rm(Register::R2)
+            .set_shift(Some(ImmShift::from((Shift::Lsl, 2))))
+            .complete()
+            .into();
+        assert_eq!(format!("{register}"), "str r0, [r1, r2, lsl #2]");
+    }
+
+    #[test]
+    fn test_display_it() {
+        let target: Operation = operation::It::builder()
+            .set_conds(arch::ITCondition::from((Condition::Eq, 0b1010)))
+            .complete()
+            .into();
+        assert_eq!(format!("{target}"), "itet eq");
+    }
+
+    #[test]
+    fn test_display_floating_point() {
+        let vadd: Operation = operation::VaddF32::builder()
+            .set_sd(F32Register::S0)
+            .set_sn(F32Register::S1)
+            .set_sm(F32Register::S2)
+            .complete()
+            .into();
+        assert_eq!(format!("{vadd}"), "vadd.f32 s0, s1, s2");
+
+        let vpush: Operation = operation::VpushF64::builder()
+            .set_registers(vec![F64Register::D8, F64Register::D9])
+            .complete()
+            .into();
+        assert_eq!(format!("{vpush}"), "vpush {d8, d9}");
+
+        let vcvt: Operation = operation::VcvtToIntF64::builder()
+            .set_signed(false)
+            .set_r(Some(RoundingMode::TiesEven))
+            .set_sd(F32Register::S0)
+            .set_dm(F64Register::D1)
+            .complete()
+            .into();
+        assert_eq!(format!("{vcvt}"), "vcvtn.u32.f64 s0, d1");
+
+        let vmrs: Operation = operation::Vmrs::builder()
+            .set_rt(Register::PC)
+            .complete()
+            .into();
+        assert_eq!(format!("{vmrs}"), "vmrs apsr_nzcv, fpscr");
+    }
+}
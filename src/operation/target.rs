@@ -0,0 +1,144 @@
+//! Resolves the PC relative operands of an [`Operation`] to absolute
+//! addresses.
+//!
+//! In Thumb state the PC reads as the address of the current instruction
+//! plus 4, regardless of the size of the encoding. Branches use this value
+//! directly while literal loads and `adr` use `Align(PC, 4)`.
+
+use super::Operation;
+use crate::arch::Register;
+
+/// Returns the value that the PC reads as for the instruction at `address`.
+pub const fn pc(address: u32) -> u32 {
+    address.wrapping_add(4)
+}
+
+/// Returns `Align(PC, 4)` for the instruction at `address`.
+pub const fn aligned_pc(address: u32) -> u32 {
+    pc(address) & !0b11
+}
+
+/// Adds or subtracts `imm` from `base`.
+fn offset(base: u32, add: bool, imm: u32) -> u32 {
+    match add {
+        true => base.wrapping_add(imm),
+        false => base.wrapping_sub(imm),
+    }
+}
+
+impl Operation {
+    /// Returns the absolute target of a PC relative branch placed at
+    /// `address`.
+    ///
+    /// Branches to registers, such as `bx lr`, have no statically known
+    /// target and yield `None`.
+    pub fn branch_target(&self, address: u32) -> Option<u32> {
+        match self {
+            // The immediates are sign extended in to the u32.
+            Self::B(el) => Some(pc(address).wrapping_add(el.imm)),
+            Self::Bl(el) => Some(pc(address).wrapping_add(el.imm)),
+            Self::Cbz(el) => Some(pc(address).wrapping_add(el.imm)),
+            _ => None,
+        }
+    }
+
+    /// Returns the absolute address computed from the PC by a literal load,
+    /// literal preload or `adr` placed at `address`.
+    ///
+    /// For `adr` this is the value written to the destination register, for
+    /// all other operations it is the accessed memory address.
+    pub fn literal_address(&self, address: u32) -> Option<u32> {
+        let base = aligned_pc(address);
+        match self {
+            Self::Adr(el) => Some(offset(base, el.add, el.imm)),
+            Self::LdrLiteral(el) => Some(offset(base, el.add, el.imm)),
+            Self::LdrbLiteral(el) => Some(offset(base, el.add.unwrap_or(true), el.imm)),
+            Self::LdrhLiteral(el) => Some(offset(base, el.add.unwrap_or(true), el.imm)),
+            Self::LdrsbLiteral(el) => Some(offset(base, el.add, el.imm)),
+            Self::LdrshLiteral(el) => Some(offset(base, el.add, el.imm)),
+            Self::LdrdLiteral(el) => Some(offset(base, el.add.unwrap_or(true), el.imm)),
+            Self::LdcLiteral(el) => Some(offset(base, el.add, el.imm)),
+            Self::PldLiteral(el) => Some(offset(base, el.add.unwrap_or(true), el.imm)),
+            Self::PliImmediate(el) if el.rn.is_none() => {
+                Some(offset(base, el.add.unwrap_or(true), el.imm))
+            }
+            Self::VldrF32(el) if el.rn == Register::PC => Some(offset(base, el.add, el.imm)),
+            Self::VldrF64(el) if el.rn == Register::PC => Some(offset(base, el.add, el.imm)),
+            _ => None,
+        }
+    }
+
+    /// Returns the absolute address of the PC relative operand of the
+    /// operation placed at `address`, if any.
+    ///
+    /// This is the [`branch_target`](Self::branch_target) for branches and
+    /// the [`literal_address`](Self::literal_address) for everything else.
+    ///
+    /// ```
+    /// use disarmv7::prelude::*;
+    ///
+    /// let ldr: Operation = operation::LdrLiteral::builder()
+    ///     .set_add(true)
+    ///     .set_rt(Register::R0)
+    ///     .set_imm(8)
+    ///     .complete()
+    ///     .into();
+    /// assert_eq!(ldr.pc_relative_target(0x0800_0102), Some(0x0800_010C));
+    /// ```
+    pub fn pc_relative_target(&self, address: u32) -> Option<u32> {
+        self.branch_target(address)
+            .or_else(|| self.literal_address(address))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_branch_target() {
+        let b: Operation = operation::B::builder()
+            .set_condition(Condition::None)
+            .set_imm((-8i32) as u32)
+            .complete()
+            .into();
+        assert_eq!(b.branch_target(0x100), Some(0xFC));
+        assert_eq!(b.literal_address(0x100), None);
+
+        let cbz: Operation = operation::Cbz::builder()
+            .set_non(Some(false))
+            .set_rn(Register::R0)
+            .set_imm(0x3C)
+            .complete()
+            .into();
+        assert_eq!(cbz.branch_target(0x102), Some(0x142));
+
+        let bx: Operation = operation::Bx::builder()
+            .set_rm(Register::LR)
+            .complete()
+            .into();
+        assert_eq!(bx.pc_relative_target(0x100), None);
+    }
+
+    #[test]
+    fn test_literal_alignment() {
+        let adr: Operation = operation::Adr::builder()
+            .set_rd(Register::R1)
+            .set_add(false)
+            .set_imm(4)
+            .complete()
+            .into();
+        // Align(0x102 + 4, 4) - 4
+        assert_eq!(adr.literal_address(0x102), Some(0x100));
+        assert_eq!(adr.literal_address(0x100), Some(0x100));
+
+        let vldr: Operation = operation::VldrF64::builder()
+            .set_add(true)
+            .set_rn(Register::PC)
+            .set_dd(F64Register::D0)
+            .set_imm(16)
+            .complete()
+            .into();
+        assert_eq!(vldr.pc_relative_target(0x1006), Some(0x1018));
+    }
+}
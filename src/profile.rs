@@ -0,0 +1,423 @@
+//! Restricts decoding to the instructions of a given core.
+//!
+//! By default every encoding that this crate knows about is decoded. A
+//! [`Profile`] describes the [`Architecture`] of a core and the optional
+//! [`Extensions`] that it implements, e.g. the Cortex-M3 is Armv7-M without
+//! any extensions while the Cortex-M4F is Armv7-M with the DSP and single
+//! precision floating point extensions. Encodings that the core treats as
+//! UNDEFINED are reported as
+//! [`NotInArchitecture`](crate::ParseErrorKind::NotInArchitecture) or
+//! [`MissingExtensions`](crate::ParseErrorKind::MissingExtensions) without
+//! consuming them.
+//!
+//! ```
+//! use disarmv7::{
+//!     prelude::*,
+//!     profile::{Architecture, Extensions, Profile},
+//! };
+//!
+//! // cbz r0, #0
+//! let bin = [0x00, 0xb1];
+//! let mut stream = PeekableBuffer::from(bin.into_iter());
+//! let error = Operation::parse_for(&mut stream, Architecture::V6M).unwrap_err();
+//!
+//! assert_eq!(
+//!     error.kind,
+//!     ParseErrorKind::NotInArchitecture(Architecture::V6M)
+//! );
+//! assert!(Operation::parse_for(&mut stream, Architecture::V7M).is_ok());
+//!
+//! // sadd16 r0, r1, r2
+//! let bin = [0x91, 0xfa, 0x02, 0xf0];
+//! let mut stream = PeekableBuffer::from(bin.into_iter());
+//! let error = Operation::parse_for(&mut stream, Architecture::V7M).unwrap_err();
+//!
+//! assert_eq!(error.kind, ParseErrorKind::MissingExtensions(Extensions::DSP));
+//! let cortex_m4 = Profile::new(Architecture::V7M, Extensions::DSP);
+//! assert!(Operation::parse_for(&mut stream, cortex_m4).is_ok());
+//! ```
+
+use std::{
+    fmt::Display,
+    ops::{BitOr, BitOrAssign},
+};
+
+use crate::{arch::SpecialRegister, operation::Operation, ParseErrorKind};
+
+/// Enumerates the architecture profiles that decoding can be restricted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Architecture {
+    /// Armv6-M, as implemented by the Cortex-M0 and Cortex-M0+.
+    ///
+    /// Only `bl`, `msr`, `mrs`, `dmb`, `dsb`, `isb` and `udf` have 32-bit
+    /// encodings, and `cbz`, `cbnz` and `it` do not exist.
+    V6M,
+
+    /// Armv7-M, as implemented by the Cortex-M3 and, together with the DSP
+    /// extension, as Armv7E-M by the Cortex-M4 and Cortex-M7.
+    V7M,
+}
+
+impl Architecture {
+    /// Returns true if the `size` bit encoding of `operation` exists in the
+    /// architecture, regardless of the extensions that it requires.
+    pub fn supports(&self, size: usize, operation: &Operation) -> bool {
+        match self {
+            Self::V6M => match (size, operation) {
+                (32, Operation::Bl(_))
+                | (32, Operation::Dmb(_))
+                | (32, Operation::Dsb(_))
+                | (32, Operation::Isb(_))
+                | (32, Operation::Udf(_)) => true,
+                (32, Operation::Mrs(el)) => Self::v6m_special_register(el.sysm),
+                (32, Operation::Msr(el)) => Self::v6m_special_register(el.sysm),
+                (32, _) => false,
+                (_, Operation::Cbz(_)) | (_, Operation::It(_)) => false,
+                // Armv6-M only has PRIMASK.
+                (_, Operation::Cps(el)) => !el.affect_fault,
+                _ => true,
+            },
+            Self::V7M => true,
+        }
+    }
+
+    /// Returns true if the special register exists in Armv6-M.
+    fn v6m_special_register(sysm: SpecialRegister) -> bool {
+        use SpecialRegister as S;
+        matches!(
+            sysm,
+            S::Apsr
+                | S::Iapsr
+                | S::Eapsr
+                | S::Xpsr
+                | S::Ipsr
+                | S::Epsr
+                | S::Iepsr
+                | S::Msp
+                | S::Psp
+                | S::Primask
+                | S::Control
+        )
+    }
+}
+
+impl Display for Architecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V6M => write!(f, "Armv6-M"),
+            Self::V7M => write!(f, "Armv7-M"),
+        }
+    }
+}
+
+/// A set of optional architecture extensions, see
+/// [`Operation::required_extensions`].
+///
+/// This crate does not decode any of the instructions that are added by the
+/// [`MVE`](Self::MVE) or [`SECURITY`](Self::SECURITY) extensions, they are
+/// included to describe a core completely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Extensions {
+    bits: u8,
+}
+
+impl Extensions {
+    /// Every extension.
+    pub const ALL: Self = Self { bits: 0b11111 };
+    /// The DSP extension, which turns Armv7-M in to Armv7E-M.
+    pub const DSP: Self = Self::bit(0);
+    /// The double precision floating point extension, requires
+    /// [`FP_SINGLE`](Self::FP_SINGLE).
+    pub const FP_DOUBLE: Self = Self::bit(2);
+    /// The single precision floating point extension.
+    pub const FP_SINGLE: Self = Self::bit(1);
+    /// The M-profile vector extension.
+    pub const MVE: Self = Self::bit(3);
+    const NAMES: [(Self, &'static str); 5] = [
+        (Self::DSP, "DSP"),
+        (Self::FP_SINGLE, "FP single"),
+        (Self::FP_DOUBLE, "FP double"),
+        (Self::MVE, "MVE"),
+        (Self::SECURITY, "security"),
+    ];
+    /// No extensions.
+    pub const NONE: Self = Self { bits: 0 };
+    /// The security extension.
+    pub const SECURITY: Self = Self::bit(4);
+
+    const fn bit(idx: u8) -> Self {
+        Self { bits: 1 << idx }
+    }
+
+    /// Returns true if all of the extensions in `other` are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Returns true if any of the extensions in `other` are set.
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    /// Returns true if no extension is set.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns the extensions that are set in `self` but not in `other`.
+    pub const fn difference(&self, other: Self) -> Self {
+        Self {
+            bits: self.bits & !other.bits,
+        }
+    }
+}
+
+impl BitOr for Extensions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self {
+            bits: self.bits | rhs.bits,
+        }
+    }
+}
+
+impl BitOrAssign for Extensions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.bits |= rhs.bits;
+    }
+}
+
+impl Display for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = Self::NAMES
+            .iter()
+            .filter(|(extension, _)| self.contains(*extension))
+            .map(|(_, name)| *name)
+            .collect();
+        match names.is_empty() {
+            true => write!(f, "none"),
+            false => write!(f, "{}", names.join(", ")),
+        }
+    }
+}
+
+/// The architecture and extensions of the core that is decoded for.
+///
+/// The default profile accepts every encoding that this crate decodes, while
+/// a bare [`Architecture`] converts in to a profile without any extensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Profile {
+    /// The architecture that the core implements.
+    pub architecture: Architecture,
+
+    /// The optional extensions that the core implements.
+    pub extensions: Extensions,
+}
+
+impl Profile {
+    /// Creates a new profile for a core implementing `architecture` and
+    /// `extensions`.
+    pub const fn new(architecture: Architecture, extensions: Extensions) -> Self {
+        Self {
+            architecture,
+            extensions,
+        }
+    }
+
+    /// Checks that the `size` bit encoding of `operation` is defined on the
+    /// core.
+    pub fn check(&self, size: usize, operation: &Operation) -> Result<(), ParseErrorKind> {
+        if !self.architecture.supports(size, operation) {
+            return Err(ParseErrorKind::NotInArchitecture(self.architecture));
+        }
+        if self.extensions == Extensions::ALL {
+            return Ok(());
+        }
+        let missing = operation.required_extensions().difference(self.extensions);
+        if !missing.is_empty() {
+            return Err(ParseErrorKind::MissingExtensions(missing));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the `size` bit encoding of `operation` is defined on
+    /// the core.
+    pub fn supports(&self, size: usize, operation: &Operation) -> bool {
+        self.check(size, operation).is_ok()
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::new(Architecture::V7M, Extensions::ALL)
+    }
+}
+
+impl From<Architecture> for Profile {
+    fn from(value: Architecture) -> Self {
+        Self::new(value, Extensions::NONE)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        decoder::Record,
+        helpers::fixtures::parse_for,
+        prelude::*,
+        profile::{Architecture, Extensions, Profile},
+    };
+
+    #[test]
+    fn test_v6m_wide() {
+        let accepted: [&[u8]; 7] = [
+            // bl #0
+            &[0x00, 0xf0, 0x00, 0xf8],
+            // msr primask, r0
+            &[0x80, 0xf3, 0x10, 0x88],
+            // mrs r0, ipsr
+            &[0xef, 0xf3, 0x05, 0x80],
+            // dmb sy
+            &[0xbf, 0xf3, 0x5f, 0x8f],
+            // dsb sy
+            &[0xbf, 0xf3, 0x4f, 0x8f],
+            // isb sy
+            &[0xbf, 0xf3, 0x6f, 0x8f],
+            // udf.w #0
+            &[0xf0, 0xf7, 0x00, 0xa0],
+        ];
+        for bin in accepted {
+            assert_eq!(parse_for(bin, Architecture::V6M).unwrap().0, 32);
+        }
+
+        let rejected: [&[u8]; 3] = [
+            // add.w r0, r1, r2
+            &[0x01, 0xeb, 0x02, 0x00],
+            // msr basepri, r0
+            &[0x80, 0xf3, 0x11, 0x88],
+            // b.w #0
+            &[0x00, 0xf0, 0x00, 0xb8],
+        ];
+        for bin in rejected {
+            let error = parse_for(bin, Architecture::V6M).unwrap_err();
+            assert_eq!(
+                error.kind,
+                ParseErrorKind::NotInArchitecture(Architecture::V6M)
+            );
+            assert_eq!(error.offset, Some(0));
+            assert_eq!(error.path, vec!["B32"]);
+            assert!(parse_for(bin, Profile::default()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_v6m_narrow() {
+        let rejected: [&[u8]; 3] = [
+            // cbnz r0, #0
+            &[0x00, 0xb9],
+            // it eq
+            &[0x08, 0xbf],
+            // cpsid f
+            &[0x71, 0xb6],
+        ];
+        for bin in rejected {
+            let error = parse_for(bin, Architecture::V6M).unwrap_err();
+            assert_eq!(
+                error.kind,
+                ParseErrorKind::NotInArchitecture(Architecture::V6M)
+            );
+            assert_eq!(
+                error.bits,
+                Some(Encoded::Narrow(bin[0] as u16 | (bin[1] as u16) << 8))
+            );
+        }
+
+        // sxth r0, r1 ; cpsid i ; movs r0, #1
+        let bin = [0x08, 0xb2, 0x72, 0xb6, 0x01, 0x20];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        let asm = ASM::parse_for(&mut stream, Architecture::V6M).unwrap();
+        assert_eq!(asm.annotated().count(), 3);
+    }
+
+    #[test]
+    fn test_extensions() {
+        let cases: [(&[u8], Extensions); 10] = [
+            // sadd16 r0, r1, r2
+            (&[0x91, 0xfa, 0x02, 0xf0], Extensions::DSP),
+            // smlad r0, r1, r2, r3
+            (&[0x21, 0xfb, 0x02, 0x30], Extensions::DSP),
+            // qadd r0, r1, r2
+            (&[0x82, 0xfa, 0x81, 0xf0], Extensions::DSP),
+            // mul r0, r1, r2
+            (&[0x01, 0xfb, 0x02, 0xf0], Extensions::NONE),
+            // clz r0, r1
+            (&[0xb1, 0xfa, 0x81, 0xf0], Extensions::NONE),
+            // smull r0, r1, r2, r3
+            (&[0x82, 0xfb, 0x03, 0x01], Extensions::NONE),
+            // sdiv r0, r1, r2
+            (&[0x91, 0xfb, 0xf2, 0xf0], Extensions::NONE),
+            // vadd.f32 s0, s1, s2
+            (&[0x30, 0xee, 0x81, 0x0a], Extensions::FP_SINGLE),
+            // vadd.f64 d0, d1, d2
+            (
+                &[0x31, 0xee, 0x02, 0x0b],
+                Extensions::FP_SINGLE | Extensions::FP_DOUBLE,
+            ),
+            // vldr d0, [r0]
+            (&[0x90, 0xed, 0x00, 0x0b], Extensions::FP_SINGLE),
+        ];
+        for (bin, required) in cases {
+            let (_, operation) = parse_for(bin, Profile::default()).unwrap();
+            assert_eq!(operation.required_extensions(), required);
+
+            let result = parse_for(bin, Architecture::V7M);
+            match required.is_empty() {
+                true => assert!(result.is_ok()),
+                false => {
+                    assert_eq!(
+                        result.unwrap_err().kind,
+                        ParseErrorKind::MissingExtensions(required)
+                    )
+                }
+            }
+        }
+
+        // A Cortex-M4F lacks double precision.
+        let cortex_m4f = Profile::new(Architecture::V7M, Extensions::DSP | Extensions::FP_SINGLE);
+        assert!(parse_for(&[0x91, 0xfa, 0x02, 0xf0], cortex_m4f).is_ok());
+        assert!(parse_for(&[0x90, 0xed, 0x00, 0x0b], cortex_m4f).is_ok());
+        let error = parse_for(&[0x31, 0xee, 0x02, 0x0b], cortex_m4f).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::MissingExtensions(Extensions::FP_DOUBLE)
+        );
+        assert_eq!(
+            error.to_string(),
+            "encoding requires the FP double extension, 0xee310b02 at offset 0 (B32)"
+        );
+        assert_eq!((Extensions::DSP | Extensions::MVE).to_string(), "DSP, MVE");
+    }
+
+    #[test]
+    fn test_not_consumed() {
+        // add.w r0, r1, r2 ; bx lr
+        let bin = [0x01, 0xeb, 0x02, 0x00, 0x70, 0x47];
+        let mut stream = PeekableBuffer::from(bin.into_iter());
+        assert!(Operation::parse_for(&mut stream, Architecture::V6M).is_err());
+        assert_eq!(stream.offset(), Some(0));
+
+        let records: Vec<Record> = MappedDecoder::new(&mut stream, 0x100, Vec::new())
+            .profile(Architecture::V6M)
+            .recovering()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(matches!(records[0], Record::Undecodable {
+            address: 0x100,
+            size: 32,
+            bits: 0xeb01_0002,
+            ..
+        }));
+        assert_eq!(records[1].address(), 0x104);
+    }
+}